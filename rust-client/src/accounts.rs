@@ -0,0 +1,234 @@
+use rand::RngCore;
+use std::sync::Arc;
+
+use miden_client::{
+    account::{
+        component::{BasicFungibleFaucet, BasicWallet},
+        Account, AccountBuilder, AccountId, AccountStorage, AccountStorageMode, AccountType,
+    },
+    asset::{FungibleAsset, TokenSymbol},
+    auth::{AuthFalcon512Rpo, AuthSecretKey},
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteType},
+    store::AccountRecordData,
+    transaction::TransactionRequestBuilder,
+    Client, ClientError, Felt,
+};
+use miden_protocol::account::auth::PublicKeyCommitment;
+
+use crate::{
+    address_book::AddressBook,
+    client::network_id,
+    output::{emit, Event},
+    say, session,
+    wait::{has_note, submit_and_wait, wait_for},
+};
+
+/// Creates a public basic wallet, tracks it and stores its key.
+pub async fn create_basic_account(
+    client: &mut Client<FilesystemKeyStore>,
+    keystore: &Arc<FilesystemKeyStore>,
+) -> Result<Account, ClientError> {
+    create_basic_account_with_key(client, keystore, &AuthSecretKey::new_falcon512_rpo()).await
+}
+
+/// Like [`create_basic_account`], with a key the caller keeps a copy of.
+pub async fn create_basic_account_with_key(
+    client: &mut Client<FilesystemKeyStore>,
+    keystore: &Arc<FilesystemKeyStore>,
+    key_pair: &AuthSecretKey,
+) -> Result<Account, ClientError> {
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let account = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
+        .with_component(BasicWallet)
+        .build()?;
+
+    client.add_account(&account, false).await?;
+    add_key(keystore, &account, key_pair)?;
+    record_auth(&account, Some(key_pair.public_key().to_commitment()));
+
+    Ok(account)
+}
+
+/// Creates a public `MID` fungible faucet, tracks it and stores its key.
+pub async fn create_basic_faucet(
+    client: &mut Client<FilesystemKeyStore>,
+    keystore: &Arc<FilesystemKeyStore>,
+) -> Result<Account, ClientError> {
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let key_pair = AuthSecretKey::new_falcon512_rpo();
+    let symbol = TokenSymbol::new("MID").expect("MID is a valid token symbol");
+    let decimals = 8;
+    let max_supply = Felt::new(1_000_000);
+
+    let account = AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
+        .with_component(
+            BasicFungibleFaucet::new(symbol, decimals, max_supply)
+                .expect("the faucet parameters are within their limits"),
+        )
+        .build()?;
+
+    client.add_account(&account, false).await?;
+    add_key(keystore, &account, &key_pair)?;
+    record_auth(&account, Some(key_pair.public_key().to_commitment()));
+
+    Ok(account)
+}
+
+/// Stores the key of a newly tracked account, failing like a keystore that
+/// cannot be opened.
+fn add_key(
+    keystore: &FilesystemKeyStore,
+    account: &Account,
+    key_pair: &AuthSecretKey,
+) -> Result<(), ClientError> {
+    keystore.add_key(key_pair).map_err(|err| {
+        ClientError::ClientInitializationError(format!(
+            "cannot store the key of account {}: {err}",
+            account.id().to_bech32(network_id())
+        ))
+    })
+}
+
+/// Tracks a contract built with `NoAuth` and marks it as keyless in the address book.
+pub async fn add_no_auth_account(
+    client: &mut Client<FilesystemKeyStore>,
+    account: &Account,
+) -> Result<(), ClientError> {
+    client.add_account(account, false).await?;
+    record_auth(account, None);
+    Ok(())
+}
+
+/// Records a newly tracked account in the address book and reports it under `--json`.
+///
+/// `key` is the commitment the account signs with, `None` for a `NoAuth`
+/// contract. The address book is a convenience for tooling, so failing to
+/// update it only warns.
+pub(crate) fn record_auth(account: &Account, key: Option<PublicKeyCommitment>) {
+    emit(Event::account_created(account));
+    session::record_account();
+    let result = AddressBook::open_default().and_then(|mut book| match key {
+        Some(key) => book.record_keys(account.id(), key),
+        None => book.record_no_auth(account.id()),
+    });
+    if let Err(err) = result {
+        eprintln!("Could not update the address book: {err}");
+    }
+}
+
+/// Returns the latest full state of a tracked account.
+///
+/// An account the store does not track, or only holds partially, fails with
+/// [`ClientError::AccountDataNotFound`].
+pub async fn get_full_account(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+) -> Result<Account, ClientError> {
+    let account_record = client
+        .get_account(account_id)
+        .await?
+        .ok_or(ClientError::AccountDataNotFound(account_id))?;
+    match account_record.account_data() {
+        AccountRecordData::Full(account) => Ok(account.clone()),
+        AccountRecordData::Partial(_) => Err(ClientError::AccountDataNotFound(account_id)),
+    }
+}
+
+/// Returns the balance of `account_id` in `faucet_id`'s token and reports it
+/// under `--json`.
+pub async fn balance(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    faucet_id: AccountId,
+) -> Result<u64, ClientError> {
+    let amount = get_full_account(client, account_id)
+        .await?
+        .vault()
+        .get_balance(faucet_id)
+        .unwrap_or(0);
+    emit(Event::balance(account_id, faucet_id, amount));
+    Ok(amount)
+}
+
+/// Has `account_id` consume a payout note and returns its balance in
+/// `faucet_id`'s token afterwards.
+pub async fn collect(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    payout: Note,
+    faucet_id: AccountId,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payout, None)])
+        .build()?;
+    submit_and_wait(client, account_id, consume_request).await?;
+    Ok(balance(client, account_id, faucet_id).await?)
+}
+
+/// Tokens a fungible faucet has minted and not burned yet.
+///
+/// The protocol keeps the total issuance in the faucet's reserved sysdata slot,
+/// as `[0, 0, 0, issuance]`; minting raises it and burning lowers it.
+pub fn token_issuance(faucet: &Account) -> Result<u64, Box<dyn std::error::Error>> {
+    let sysdata = faucet
+        .storage()
+        .get_item(AccountStorage::faucet_sysdata_slot())?;
+    Ok(sysdata[3].as_int())
+}
+
+/// Mints from `faucet_id` until `account_id` holds at least `target` of its token.
+///
+/// An account that is already funded is left alone, so repeated runs can reuse
+/// their accounts instead of deploying and funding new ones. Returns the amount
+/// minted, which is zero when no top-up was needed.
+pub async fn top_up(
+    client: &mut Client<FilesystemKeyStore>,
+    faucet_id: AccountId,
+    account_id: AccountId,
+    target: u64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let balance = get_full_account(client, account_id)
+        .await?
+        .vault()
+        .get_balance(faucet_id)
+        .unwrap_or(0);
+    if balance >= target {
+        return Ok(0);
+    }
+
+    let amount = target - balance;
+    say!(
+        "Topping up {} with {amount} tokens (balance {balance}, target {target})",
+        account_id.to_bech32(network_id())
+    );
+    let mint_request = TransactionRequestBuilder::new().build_mint_fungible_asset(
+        FungibleAsset::new(faucet_id, amount)?,
+        account_id,
+        NoteType::Public,
+        client.rng(),
+    )?;
+    let minted = mint_request
+        .expected_output_own_notes()
+        .pop()
+        .expect("a mint request creates one note");
+    submit_and_wait(client, faucet_id, mint_request).await?;
+
+    // Other notes may be waiting for the account, so only this mint is consumed
+    wait_for(client, has_note(minted.id())).await?;
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(minted, None)])
+        .build()?;
+    submit_and_wait(client, account_id, consume_request).await?;
+    Ok(amount)
+}
@@ -0,0 +1,100 @@
+//! Sharing one client, or anything else that needs `&mut`, across tokio tasks.
+//!
+//! A `Client` takes `&mut self` for everything that touches its store, so it
+//! cannot be shared by reference. Opening one client per task on the same
+//! SQLite file compiles, but each builds transactions from the account state it
+//! read, so two tasks acting for the same account conflict. An [`Actor`] owns the state instead. Tasks hold cheap
+//! [`ActorHandle`]s and send it jobs over a channel, which the actor runs one
+//! at a time:
+//!
+//! ```ignore
+//! let (actor, handle) = Actor::new(client);
+//! let worker = handle.clone();
+//! tokio::spawn(async move {
+//!     worker.call(|client| Box::pin(async move { client.sync_state().await })).await
+//! });
+//! drop(handle);
+//! let client = actor.run().await; // returns once every handle is dropped
+//! ```
+//!
+//! The actor runs on the task that awaits [`Actor::run`], so the state and the
+//! futures of its jobs never have to be `Send`; only the jobs and their
+//! results cross tasks.
+
+use std::{fmt, future::Future, pin::Pin};
+
+use tokio::sync::{mpsc, oneshot};
+
+/// Jobs that may queue up before [`ActorHandle::call`] waits for room.
+pub const QUEUE_LEN: usize = 32;
+
+/// Future returned by a job, borrowing the actor's state.
+pub type JobFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+type Job<S> = Box<dyn for<'a> FnOnce(&'a mut S) -> JobFuture<'a, ()> + Send>;
+
+/// Owns `S` and runs the jobs sent through its handles in order.
+pub struct Actor<S> {
+    state: S,
+    jobs: mpsc::Receiver<Job<S>>,
+}
+
+impl<S> Actor<S> {
+    /// Takes ownership of `state` and returns the actor with a first handle.
+    pub fn new(state: S) -> (Self, ActorHandle<S>) {
+        let (sender, jobs) = mpsc::channel(QUEUE_LEN);
+        (Self { state, jobs }, ActorHandle { sender })
+    }
+
+    /// Runs jobs until every handle is dropped, then gives the state back.
+    pub async fn run(mut self) -> S {
+        while let Some(job) = self.jobs.recv().await {
+            job(&mut self.state).await;
+        }
+        self.state
+    }
+}
+
+/// Sends jobs to an [`Actor`]. Clone it for every task that needs the state.
+pub struct ActorHandle<S> {
+    sender: mpsc::Sender<Job<S>>,
+}
+
+impl<S> Clone for ActorHandle<S> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<S: 'static> ActorHandle<S> {
+    /// Runs `job` on the actor's state once the jobs queued before it are done.
+    pub async fn call<T, F>(&self, job: F) -> Result<T, ActorStopped>
+    where
+        T: Send + 'static,
+        F: for<'a> FnOnce(&'a mut S) -> JobFuture<'a, T> + Send + 'static,
+    {
+        let (reply, response) = oneshot::channel();
+        let job: Job<S> = Box::new(move |state| {
+            Box::pin(async move {
+                // The caller may have given up waiting, which is fine
+                let _ = reply.send(job(state).await);
+            })
+        });
+        self.sender.send(job).await.map_err(|_| ActorStopped)?;
+        response.await.map_err(|_| ActorStopped)
+    }
+}
+
+/// The actor stopped before it could answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActorStopped;
+
+impl fmt::Display for ActorStopped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the actor owning the state has stopped")
+    }
+}
+
+impl std::error::Error for ActorStopped {}
@@ -0,0 +1,251 @@
+//! Records which tracked accounts sign with a key from the keystore.
+//!
+//! Contracts built with `NoAuth` never request a signature, so the keystore has
+//! nothing for them. The address book makes that explicit, letting tooling fail
+//! with a clear message instead of searching the keystore for a key that was
+//! never created. For accounts that do sign it keeps the public key commitment,
+//! so a missing key is caught before a transaction is executed rather than deep
+//! inside the executor.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use miden_client::{
+    account::AccountId, keystore::FilesystemKeyStore, transaction::TransactionRequest, Client,
+    ClientError, Word,
+};
+use miden_protocol::account::auth::PublicKeyCommitment;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{network_id, parse_account_id, registry_dir},
+    error::ConfigError,
+};
+
+/// File name of the address book, kept in [`crate::client::registry_dir`].
+pub const ADDRESS_BOOK_FILE: &str = "address_book.toml";
+
+/// Keystores opened by clients of this process, see [`check_signer`].
+static KEYSTORES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// How an account authenticates its transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccountAuth {
+    /// Signs with a secret key held in the keystore.
+    Keys,
+    /// Built with `NoAuth`, no key exists.
+    NoAuth,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AddressBookEntry {
+    account_id: String,
+    auth: AccountAuth,
+    /// Hex of the public key commitment of a [`AccountAuth::Keys`] account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AddressBookFile {
+    #[serde(default)]
+    account: Vec<AddressBookEntry>,
+}
+
+/// Accounts known to the tutorials together with their authentication scheme.
+#[derive(Debug)]
+pub struct AddressBook {
+    path: PathBuf,
+    file: AddressBookFile,
+}
+
+impl AddressBook {
+    /// Loads the address book, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref().to_path_buf();
+        let file = match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => AddressBookFile::default(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, file })
+    }
+
+    /// Loads the address book from [`ADDRESS_BOOK_FILE`] in the registry directory.
+    pub fn open_default() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load(registry_dir().join(ADDRESS_BOOK_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records an account signing with `key` and writes the address book back to disk.
+    pub fn record_keys(
+        &mut self,
+        account_id: AccountId,
+        key: PublicKeyCommitment,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let key: Word = key.into();
+        self.record(account_id, AccountAuth::Keys, Some(key.to_hex()))
+    }
+
+    /// Records a `NoAuth` contract and writes the address book back to disk.
+    pub fn record_no_auth(
+        &mut self,
+        account_id: AccountId,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.record(account_id, AccountAuth::NoAuth, None)
+    }
+
+    fn record(
+        &mut self,
+        account_id: AccountId,
+        auth: AccountAuth,
+        key: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let entry = AddressBookEntry {
+            account_id: account_id.to_bech32(network_id()),
+            auth,
+            key,
+        };
+        match self
+            .file
+            .account
+            .iter_mut()
+            .find(|entry| parse_account_id(&entry.account_id) == Ok(account_id))
+        {
+            Some(existing) => *existing = entry,
+            None => self.file.account.push(entry),
+        }
+        fs::write(&self.path, toml::to_string(&self.file)?)?;
+        Ok(())
+    }
+
+    fn entry(&self, account_id: AccountId) -> Option<&AddressBookEntry> {
+        // Entries are matched by ID, so a book written for another network still applies
+        self.file
+            .account
+            .iter()
+            .find(|entry| parse_account_id(&entry.account_id) == Ok(account_id))
+    }
+
+    /// Returns how the account authenticates, if it was recorded.
+    pub fn auth(&self, account_id: AccountId) -> Option<AccountAuth> {
+        self.entry(account_id).map(|entry| entry.auth)
+    }
+
+    /// Returns the key the account signs with, failing for `NoAuth` contracts.
+    ///
+    /// Accounts missing from the address book, which may predate it, and keyed
+    /// accounts recorded without their key give `None`.
+    pub fn require_keys(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<PublicKeyCommitment>, ConfigError> {
+        let Some(entry) = self.entry(account_id) else {
+            return Ok(None);
+        };
+        match (entry.auth, &entry.key) {
+            (AccountAuth::NoAuth, _) => Err(ConfigError::new(format!(
+                "account {} is a NoAuth contract and has no signing key; \
+                 its transactions need no signature",
+                account_id.to_bech32(network_id())
+            ))),
+            (AccountAuth::Keys, None) => Ok(None),
+            (AccountAuth::Keys, Some(key)) => Word::try_from(key.as_str())
+                .map(|key| Some(key.into()))
+                .map_err(|err| {
+                    ConfigError::new(format!(
+                        "invalid key of {} in {}: {err}",
+                        entry.account_id,
+                        self.path.display()
+                    ))
+                }),
+        }
+    }
+}
+
+/// Remembers a keystore opened by a client, for [`check_signer`].
+pub(crate) fn register_keystore(path: &Path) {
+    let mut keystores = KEYSTORES.lock().unwrap_or_else(|err| err.into_inner());
+    if !keystores.iter().any(|known| known == path) {
+        keystores.push(path.to_path_buf());
+    }
+}
+
+/// Fails before execution when `request` cannot be authenticated for `account_id`.
+///
+/// An account the client does not track has nothing to sign with, and a
+/// `NoAuth` contract has no auth procedure to take the auth argument of an
+/// authenticated request, so both are refused with a clear message. A keyed
+/// account needs its key in one of the keystores opened by this process.
+/// Accounts the address book does not know the auth of, such as a multisig
+/// whose cosigners hold the keys, are let through. The address book is a
+/// convenience, so one that cannot be read only skips the checks that need it.
+///
+/// Fails as a client initialization error, which exits as
+/// [`crate::error::TutorialError::Config`], so it fits next to the client's
+/// own errors.
+pub async fn check_signer(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    request: &TransactionRequest,
+) -> Result<(), ClientError> {
+    if client.get_account(account_id).await?.is_none() {
+        return Err(ClientError::ClientInitializationError(format!(
+            "account {} is not tracked by this client, so nothing can sign for it; \
+             create or import it first",
+            account_id.to_bech32(network_id())
+        )));
+    }
+    let Ok(book) = AddressBook::open_default() else {
+        return Ok(());
+    };
+    let key = match book.auth(account_id) {
+        Some(AccountAuth::Keys) => book
+            .require_keys(account_id)
+            .map_err(|err| ClientError::ClientInitializationError(err.to_string()))?,
+        Some(AccountAuth::NoAuth) if request.auth_arg().is_some() => {
+            return Err(ClientError::ClientInitializationError(format!(
+                "account {} is a NoAuth contract and cannot authenticate a request; \
+                 drop the auth argument",
+                account_id.to_bech32(network_id())
+            )));
+        }
+        Some(AccountAuth::NoAuth) | None => None,
+    };
+    let Some(key) = key else {
+        return Ok(());
+    };
+
+    let keystores = KEYSTORES.lock().unwrap_or_else(|err| err.into_inner());
+    if keystores.is_empty() {
+        return Ok(());
+    }
+    let found = keystores.iter().any(|path| {
+        FilesystemKeyStore::new(path.clone())
+            .ok()
+            .and_then(|keystore| keystore.get_key(key).ok().flatten())
+            .is_some()
+    });
+    if found {
+        return Ok(());
+    }
+    let key: Word = key.into();
+    Err(ClientError::ClientInitializationError(format!(
+        "account {} signs with key {}, which is in none of the keystores of this run ({}); \
+         the key was removed or the account belongs to another keystore",
+        account_id.to_bech32(network_id()),
+        key.to_hex(),
+        keystores
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
+}
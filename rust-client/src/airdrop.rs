@@ -0,0 +1,111 @@
+//! Reads the recipients of an airdrop from a CSV file.
+//!
+//! Each line holds a bech32 address and, optionally, the amount it receives:
+//!
+//! ```text
+//! address,amount
+//! mtst1qy...,25
+//! mtst1qz...
+//! ```
+//!
+//! The `address,amount` header is optional, blank lines and lines starting with
+//! `#` are skipped, and a line without an amount gets the default one.
+
+use std::{fmt, fs, path::Path};
+
+use miden_client::account::AccountId;
+
+use crate::{client::parse_account_id, error::ConfigError};
+
+/// One recipient of the airdrop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recipient {
+    pub account_id: AccountId,
+    pub amount: u64,
+}
+
+/// A line of the recipients file that cannot be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRow {
+    /// Line number in the file, starting at 1.
+    pub line: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for InvalidRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for InvalidRow {}
+
+/// Parses the recipients in `csv`, giving `default_amount` to lines without one.
+///
+/// An address listed twice is rejected rather than paid twice, since it is far
+/// more likely a mistake in the file than an intended double share.
+pub fn parse_recipients(csv: &str, default_amount: u64) -> Result<Vec<Recipient>, InvalidRow> {
+    let mut recipients: Vec<Recipient> = Vec::new();
+    for (index, raw) in csv.lines().enumerate() {
+        let line = index + 1;
+        let invalid = |reason: String| InvalidRow { line, reason };
+
+        let row = raw.trim();
+        if row.is_empty() || row.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+        if recipients.is_empty() && fields[0].eq_ignore_ascii_case("address") {
+            continue;
+        }
+
+        let (address, amount) = match fields.as_slice() {
+            [address] => (*address, default_amount),
+            [address, amount] => {
+                let amount = amount
+                    .parse()
+                    .map_err(|_| invalid(format!("invalid amount '{amount}'")))?;
+                (*address, amount)
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "expected 'address[,amount]', found {} fields",
+                    fields.len()
+                )))
+            }
+        };
+        if amount == 0 {
+            return Err(invalid("amount must be positive".into()));
+        }
+        let account_id = parse_account_id(address)
+            .map_err(|err| invalid(format!("invalid address '{address}': {err}")))?;
+        if recipients.iter().any(|r| r.account_id == account_id) {
+            return Err(invalid(format!("{address} is listed twice")));
+        }
+        recipients.push(Recipient { account_id, amount });
+    }
+    Ok(recipients)
+}
+
+/// Reads and parses the recipients file at `path`.
+///
+/// A line that cannot be used fails as a [`ConfigError`].
+pub fn load_recipients(
+    path: impl AsRef<Path>,
+    default_amount: u64,
+) -> Result<Vec<Recipient>, Box<dyn std::error::Error>> {
+    let csv = fs::read_to_string(path)?;
+    parse_recipients(&csv, default_amount).map_err(|err| ConfigError::new(err).into())
+}
+
+/// Tokens the whole airdrop sends, or `None` if the sum overflows.
+pub fn total_amount(recipients: &[Recipient]) -> Option<u64> {
+    recipients
+        .iter()
+        .try_fold(0_u64, |total, r| total.checked_add(r.amount))
+}
+
+/// Transactions needed to pay `recipients` with at most `batch_size` notes each.
+pub fn batch_count(recipients: usize, batch_size: usize) -> usize {
+    recipients.div_ceil(batch_size)
+}
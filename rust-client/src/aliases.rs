@@ -0,0 +1,159 @@
+//! Short names for accounts, kept between binaries and runs.
+//!
+//! A binary that creates an account worth referring to later saves it under a
+//! name with [`save_alias`], and other binaries take `alice` or `recipient`
+//! where they would take a bech32 ID, through [`resolve_alias`]. Like the
+//! [`crate::deployments`] registry, aliases are kept per network:
+//!
+//! ```toml
+//! [devnet]
+//! alice = "mdev1..."
+//! faucet = "mdev1..."
+//! ```
+//!
+//! Names of contracts in the deployments registry, such as `counter`, resolve
+//! too, so deployed contracts need no second entry here.
+
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use miden_client::account::AccountId;
+
+use crate::{
+    client::{network, parse_account_id, registry_dir},
+    deployments::Deployments,
+    network::Network,
+};
+
+/// File name of the aliases, kept in [`crate::client::registry_dir`].
+pub const ALIASES_FILE: &str = "aliases.toml";
+
+/// Environment variable pointing at another aliases file.
+pub const ALIASES_ENV: &str = "MIDEN_TUTORIAL_ALIASES";
+
+/// Account IDs by network and alias.
+#[derive(Debug)]
+pub struct Aliases {
+    path: PathBuf,
+    networks: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Aliases {
+    /// Loads the aliases, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref().to_path_buf();
+        let networks = match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, networks })
+    }
+
+    /// Loads the aliases from [`ALIASES_ENV`], or from [`ALIASES_FILE`] in the
+    /// registry directory if it is unset.
+    pub fn open_default() -> Result<Self, Box<dyn std::error::Error>> {
+        match env::var(ALIASES_ENV) {
+            Ok(path) => Self::load(path),
+            Err(_) => Self::load(registry_dir().join(ALIASES_FILE)),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Points `name` at `account_id` on `network`, replacing what it named
+    /// before, and writes the aliases back to disk.
+    ///
+    /// Names are case-insensitive and may not look like a bech32 ID.
+    pub fn save(
+        &mut self,
+        network: Network,
+        name: &str,
+        account_id: AccountId,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let name = normalize(name)?;
+        self.networks
+            .entry(network.name().to_string())
+            .or_default()
+            .insert(name, account_id.to_bech32(network.network_id()));
+        fs::write(&self.path, toml::to_string(&self.networks)?)?;
+        Ok(())
+    }
+
+    /// Returns the account `name` stands for on `network`.
+    pub fn get(&self, network: Network, name: &str) -> Result<Option<AccountId>, String> {
+        self.networks
+            .get(network.name())
+            .and_then(|aliases| aliases.get(&name.to_lowercase()))
+            .map(|id| parse_account_id(id))
+            .transpose()
+    }
+
+    /// Every alias of `network` with its account, in name order.
+    pub fn entries(&self, network: Network) -> Result<Vec<(String, AccountId)>, String> {
+        self.networks
+            .get(network.name())
+            .into_iter()
+            .flatten()
+            .map(|(name, id)| Ok((name.clone(), parse_account_id(id)?)))
+            .collect()
+    }
+}
+
+/// Lowercases an alias and rejects names that could be mistaken for an ID.
+fn normalize(name: &str) -> Result<String, String> {
+    let name = name.to_lowercase();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(format!(
+            "alias `{name}` may only use letters, digits, `_` and `-`"
+        ));
+    }
+    if parse_account_id(&name).is_ok() {
+        return Err(format!("alias `{name}` is an account ID"));
+    }
+    Ok(name)
+}
+
+/// Saves `name` for `account_id` on the current network in the default aliases.
+pub fn save_alias(name: &str, account_id: AccountId) -> Result<(), Box<dyn std::error::Error>> {
+    Aliases::open_default()?.save(network(), name, account_id)
+}
+
+/// Returns the account `name` stands for on the current network, looking in
+/// the aliases first and the deployments registry second.
+pub fn lookup_alias(name: &str) -> Result<Option<AccountId>, Box<dyn std::error::Error>> {
+    if let Some(id) = Aliases::open_default()?.get(network(), name)? {
+        return Ok(Some(id));
+    }
+    Ok(Deployments::open_default()?.get(network(), &name.to_lowercase())?)
+}
+
+/// Turns a bech32 ID or an alias, as given on the command line, into an ID.
+///
+/// An unknown alias fails as a not-found error naming the aliases file.
+pub fn resolve_alias(name_or_id: &str) -> Result<AccountId, Box<dyn std::error::Error>> {
+    if let Ok(id) = parse_account_id(name_or_id) {
+        return Ok(id);
+    }
+    match lookup_alias(name_or_id)? {
+        Some(id) => Ok(id),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "`{name_or_id}` is neither an account ID nor an alias saved for {} in {}",
+                network().name(),
+                Aliases::open_default()?.path().display()
+            ),
+        )
+        .into()),
+    }
+}
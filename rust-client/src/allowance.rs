@@ -0,0 +1,310 @@
+//! SDK-style wrapper around the approved-spender pattern in
+//! `masm/accounts/allowance_book.masm` and `masm/accounts/allowance_vault.masm`.
+//!
+//! Like an ERC-20 allowance, the owner approves a spender for an amount and the
+//! spender pulls up to that amount without the owner taking part. The book maps
+//! each spender to the total the owner approved, and only takes approvals from
+//! the owner. The vault holds the owner's tokens and records what each spender
+//! took. A spender pulls with a spend note, and the vault reads the spender's
+//! allowance from the book with a foreign procedure invocation before paying.
+
+use miden_client::{
+    account::{AccountId, StorageMap, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    rpc::domain::account::{AccountStorageRequirements, StorageMapKey},
+    transaction::{ForeignAccount, OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const OWNER_SLOT: &str = "miden::tutorials::allowance::owner";
+pub const ALLOWANCES_SLOT: &str = "miden::tutorials::allowance::allowances";
+pub const BOOK_SLOT: &str = "miden::tutorials::allowance_vault::book";
+pub const GET_ALLOWANCE_SLOT: &str = "miden::tutorials::allowance_vault::get_allowance";
+pub const TOKEN_SLOT: &str = "miden::tutorials::allowance_vault::token";
+pub const SPENT_SLOT: &str = "miden::tutorials::allowance_vault::spent";
+
+/// Library path the book procedures are exported under.
+pub const BOOK_LIBRARY_PATH: &str = "external_contract::allowance_book";
+
+/// Library path the vault procedures are exported under.
+pub const VAULT_LIBRARY_PATH: &str = "external_contract::allowance_vault";
+
+const BOOK_FILE: &str = "masm/accounts/allowance_book.masm";
+const BOOK_CODE: &str = include_str!("../../masm/accounts/allowance_book.masm");
+const VAULT_FILE: &str = "masm/accounts/allowance_vault.masm";
+const VAULT_CODE: &str = include_str!("../../masm/accounts/allowance_vault.masm");
+const APPROVE_NOTE_FILE: &str = "masm/notes/allowance_approve_note.masm";
+const APPROVE_NOTE_CODE: &str = include_str!("../../masm/notes/allowance_approve_note.masm");
+const DEPOSIT_NOTE_FILE: &str = "masm/notes/allowance_deposit_note.masm";
+const DEPOSIT_NOTE_CODE: &str = include_str!("../../masm/notes/allowance_deposit_note.masm");
+const SPEND_NOTE_FILE: &str = "masm/notes/allowance_spend_note.masm";
+const SPEND_NOTE_CODE: &str = include_str!("../../masm/notes/allowance_spend_note.masm");
+
+/// A deployed allowance book and the vault it guards.
+pub struct Allowance {
+    pub book_id: AccountId,
+    pub vault_id: AccountId,
+    pub owner: AccountId,
+    /// Faucet of the token the vault holds.
+    pub faucet_id: AccountId,
+    book_library: Library,
+    vault_library: Library,
+}
+
+impl Allowance {
+    /// Builds the book of `owner` and a vault for the token of `faucet_id`, and
+    /// starts tracking both.
+    ///
+    /// The vault stores the book's ID and the MAST root of its `get_allowance`,
+    /// so a spend can only be checked against this book. Both contracts are
+    /// deployed on-chain with the first note they consume.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        owner: AccountId,
+        faucet_id: AccountId,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let book_deployer =
+            ContractDeployer::new(MasmFile::embedded(BOOK_FILE, BOOK_CODE), BOOK_LIBRARY_PATH)
+                .with_slots([
+                    StorageSlot::with_value(StorageSlotName::new(OWNER_SLOT)?, id_word(owner)),
+                    StorageSlot::with_map(
+                        StorageSlotName::new(ALLOWANCES_SLOT)?,
+                        StorageMap::with_entries([])?,
+                    ),
+                ]);
+        let book_library = book_deployer.library()?;
+        let book = book_deployer.deploy(client).await?;
+
+        let get_allowance = book_library
+            .get_procedure_root_by_path(&format!("{BOOK_LIBRARY_PATH}::get_allowance"))
+            .expect("get_allowance export not found");
+        let vault_deployer = ContractDeployer::new(
+            MasmFile::embedded(VAULT_FILE, VAULT_CODE),
+            VAULT_LIBRARY_PATH,
+        )
+        .with_slots([
+            StorageSlot::with_value(StorageSlotName::new(BOOK_SLOT)?, id_word(book.id())),
+            StorageSlot::with_value(StorageSlotName::new(GET_ALLOWANCE_SLOT)?, get_allowance),
+            StorageSlot::with_value(StorageSlotName::new(TOKEN_SLOT)?, id_word(faucet_id)),
+            StorageSlot::with_map(
+                StorageSlotName::new(SPENT_SLOT)?,
+                StorageMap::with_entries([])?,
+            ),
+        ]);
+        let vault_library = vault_deployer.library()?;
+        let vault = vault_deployer.deploy(client).await?;
+
+        Ok(Self {
+            book_id: book.id(),
+            vault_id: vault.id(),
+            owner,
+            faucet_id,
+            book_library,
+            vault_library,
+        })
+    }
+
+    /// Sends a note from `sender` setting the total `spender` may take to
+    /// `allowance`, and has the book consume it. The book only accepts it from the owner.
+    pub async fn approve(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        spender: AccountId,
+        allowance: u64,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = note_script(
+            client,
+            &self.book_library,
+            MasmFile::embedded(APPROVE_NOTE_FILE, APPROVE_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(sender, script)
+            .inputs([
+                spender.prefix().as_felt(),
+                spender.suffix(),
+                Felt::new(allowance),
+            ])
+            .target(self.book_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, sender, &note).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .build()?;
+        Ok(submit_and_wait(client, self.book_id, consume_request).await?)
+    }
+
+    /// Moves `amount` tokens of the owner into the vault.
+    pub async fn deposit(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        amount: u64,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = note_script(
+            client,
+            &self.vault_library,
+            MasmFile::embedded(DEPOSIT_NOTE_FILE, DEPOSIT_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(self.owner, script)
+            .asset(FungibleAsset::new(self.faucet_id, amount)?)
+            .target(self.vault_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, self.owner, &note).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .build()?;
+        Ok(submit_and_wait(client, self.vault_id, consume_request).await?)
+    }
+
+    /// Has `spender` pull `amount` tokens from the vault and returns the P2ID
+    /// note paying them.
+    ///
+    /// The vault reads the spender's allowance from the book, so the
+    /// transaction carries the book as a foreign account, with a proof of the
+    /// spender's map entry.
+    pub async fn spend(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        spender: AccountId,
+        amount: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let payout = create_p2id_note(
+            self.vault_id,
+            spender,
+            vec![FungibleAsset::new(self.faucet_id, amount)?.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        inputs.push(Felt::new(amount));
+        let script = note_script(
+            client,
+            &self.vault_library,
+            MasmFile::embedded(SPEND_NOTE_FILE, SPEND_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(spender, script)
+            .inputs(inputs)
+            .target(self.vault_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, spender, &note).await?;
+
+        let keys: [StorageMapKey; 1] = [id_word(spender).into()];
+        let storage_requirements = AccountStorageRequirements::new([(
+            StorageSlotName::new(ALLOWANCES_SLOT)?,
+            keys.iter(),
+        )]);
+        let book = ForeignAccount::public(self.book_id, storage_requirements)?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .foreign_accounts([book])
+            .expected_output_recipients(vec![payout.recipient().clone()])
+            .build()?;
+        let report = submit_and_wait(client, self.vault_id, consume_request).await?;
+        Ok((report, payout))
+    }
+
+    /// Returns the total the owner approved for `spender`, as of the last sync.
+    pub async fn allowance(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        spender: AccountId,
+    ) -> Result<u64, ClientError> {
+        self.map_entry(client, self.book_id, ALLOWANCES_SLOT, spender)
+            .await
+    }
+
+    /// Returns what `spender` has taken from the vault, as of the last sync.
+    pub async fn spent(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        spender: AccountId,
+    ) -> Result<u64, ClientError> {
+        self.map_entry(client, self.vault_id, SPENT_SLOT, spender)
+            .await
+    }
+
+    /// Returns what `spender` can still take, as of the last sync.
+    ///
+    /// The owner can lower an allowance below what was already spent, which
+    /// leaves nothing to take rather than a negative amount.
+    pub async fn remaining(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        spender: AccountId,
+    ) -> Result<u64, ClientError> {
+        let allowance = self.allowance(client, spender).await?;
+        let spent = self.spent(client, spender).await?;
+        Ok(allowance.saturating_sub(spent))
+    }
+
+    /// Reads the entry of `spender` in a map stored as `[value, 0, 0, 0]`.
+    async fn map_entry(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        account_id: AccountId,
+        slot: &str,
+        spender: AccountId,
+    ) -> Result<u64, ClientError> {
+        let account = get_full_account(client, account_id).await?;
+        let slot = StorageSlotName::new(slot).expect("valid slot name");
+        let entry = account
+            .storage()
+            .get_map_item(&slot, id_word(spender))
+            .expect("allowance contract is missing its map slot");
+        // The value sits on top of the stack, which is the last element of the word
+        Ok(entry[3].as_int())
+    }
+
+    async fn send(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        note: &Note,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        Ok(submit_and_wait(client, sender, note_request).await?)
+    }
+}
+
+fn note_script(
+    client: &mut Client<FilesystemKeyStore>,
+    library: &Library,
+    file: MasmFile,
+) -> Result<NoteScript, ClientError> {
+    let builder = client
+        .code_builder()
+        .with_dynamically_linked_library(library)?;
+    // The scripts are embedded, so a failure here is a bug in the contracts themselves
+    let script = file
+        .check(builder.compile_note_script(&file.source))
+        .expect("allowance note script does not compile");
+    Ok(script)
+}
+
+/// An account ID as `[prefix, suffix, 0, 0]` on the stack, the layout of the
+/// ID slots and of the map keys.
+fn id_word(account_id: AccountId) -> Word {
+    Word::from([
+        Felt::new(0),
+        Felt::new(0),
+        account_id.suffix(),
+        account_id.prefix().as_felt(),
+    ])
+}
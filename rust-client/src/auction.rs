@@ -0,0 +1,254 @@
+//! SDK-style wrapper around the auction contract in `masm/accounts/auction.masm`.
+//!
+//! The auction is a network account: bidders never execute a transaction
+//! against it, they send bid notes that the network transaction builder
+//! consumes. A bid has to beat the highest one, which the contract keeps in a
+//! storage map, and the bid it replaces is refunded right away. Once the end
+//! block is reached the seller settles with a note of their own and receives
+//! the winning bid.
+
+use miden_client::{
+    account::{AccountId, AccountStorageMode, StorageMap, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::{Asset, FungibleAsset},
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const SELLER_SLOT: &str = "miden::tutorials::auction::seller";
+pub const RESERVE_SLOT: &str = "miden::tutorials::auction::reserve";
+pub const TERMS_SLOT: &str = "miden::tutorials::auction::terms";
+pub const BIDS_SLOT: &str = "miden::tutorials::auction::bids";
+
+/// Library path the auction procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::auction_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/auction.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/auction.masm");
+const START_SCRIPT_FILE: &str = "masm/scripts/auction_start_script.masm";
+const START_SCRIPT_CODE: &str = include_str!("../../masm/scripts/auction_start_script.masm");
+const BID_NOTE_FILE: &str = "masm/notes/auction_bid_note.masm";
+const BID_NOTE_CODE: &str = include_str!("../../masm/notes/auction_bid_note.masm");
+const SETTLE_NOTE_FILE: &str = "masm/notes/auction_settle_note.masm";
+const SETTLE_NOTE_CODE: &str = include_str!("../../masm/notes/auction_settle_note.masm");
+
+/// Map key of the highest bid, `[1, 0, 0, 0]` on the stack.
+const HIGHEST_KEY: [u64; 4] = [0, 0, 0, 1];
+
+/// A running auction.
+pub struct Auction {
+    pub account_id: AccountId,
+    pub seller: AccountId,
+    /// The lowest acceptable bid, which also fixes the token bids are paid in.
+    pub reserve: FungibleAsset,
+    /// First block at which bids are no longer taken and the seller can settle.
+    pub end_block: u32,
+    library: Library,
+}
+
+impl Auction {
+    /// Builds the auction, starts tracking it and opens it until `end_block`.
+    ///
+    /// Opening is the one transaction the client runs against the auction
+    /// itself, and it deploys the account on the network.
+    pub async fn start(
+        client: &mut Client<FilesystemKeyStore>,
+        seller: AccountId,
+        reserve: FungibleAsset,
+        end_block: u32,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let seller_word = Word::from([
+            Felt::new(0),
+            Felt::new(0),
+            seller.suffix(),
+            seller.prefix().as_felt(),
+        ]);
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(SELLER_SLOT)?, seller_word),
+            StorageSlot::with_value(
+                StorageSlotName::new(RESERVE_SLOT)?,
+                Word::from(Asset::from(reserve)),
+            ),
+            StorageSlot::with_value(StorageSlotName::new(TERMS_SLOT)?, Word::default()),
+            StorageSlot::with_map(
+                StorageSlotName::new(BIDS_SLOT)?,
+                StorageMap::with_entries([])?,
+            ),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots)
+        .storage_mode(AccountStorageMode::Network);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        let script_file = MasmFile::embedded(START_SCRIPT_FILE, START_SCRIPT_CODE);
+        let script_file = script_file.with_source(
+            script_file
+                .source
+                .replace("{end_block}", &end_block.to_string()),
+        );
+        let start_script = script_file.check(
+            client
+                .code_builder()
+                .with_dynamically_linked_library(&library)?
+                .compile_tx_script(&script_file.source),
+        )?;
+        let start_request = TransactionRequestBuilder::new()
+            .custom_script(start_script)
+            .build()?;
+        submit_and_wait(client, account.id(), start_request).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            seller,
+            reserve,
+            end_block,
+            library,
+        })
+    }
+
+    /// Sends a bid of `amount` from `bidder` to the network.
+    ///
+    /// Returns the bid note and the P2ID note refunding it once it is outbid.
+    /// The refund only exists after a higher bid, but its ID is known now.
+    pub async fn bid(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        bidder: AccountId,
+        amount: u64,
+    ) -> Result<(Note, Note), Box<dyn std::error::Error>> {
+        let asset = FungibleAsset::new(self.reserve.faucet_id(), amount)?;
+        let refund = create_p2id_note(
+            self.account_id,
+            bidder,
+            vec![asset.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+
+        let mut inputs = refund.recipient().digest().to_vec();
+        inputs.push(refund.metadata().tag().into());
+        let script = self.note_script(client, MasmFile::embedded(BID_NOTE_FILE, BID_NOTE_CODE))?;
+        let note = NoteBuilder::new(bidder, script)
+            .inputs(inputs)
+            .asset(asset)
+            .network_target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, bidder, &note).await?;
+        Ok((note, refund))
+    }
+
+    /// Sends the seller's settle note to the network.
+    ///
+    /// Returns the settle note and the P2ID note paying the highest bid to the
+    /// seller, if anyone bid. Call it after the end block, when the highest bid
+    /// as of the last sync can no longer change.
+    pub async fn settle(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<(Note, Option<Note>), Box<dyn std::error::Error>> {
+        let highest = self.highest_bid(client).await?;
+        let assets = match highest {
+            Some((_, amount)) => vec![FungibleAsset::new(self.reserve.faucet_id(), amount)?.into()],
+            None => vec![],
+        };
+        let payout = create_p2id_note(
+            self.account_id,
+            self.seller,
+            assets,
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(SETTLE_NOTE_FILE, SETTLE_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(self.seller, script)
+            .inputs(inputs)
+            .network_target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, self.seller, &note).await?;
+        Ok((note, highest.map(|_| payout)))
+    }
+
+    /// Returns the highest bidder and bid, as of the last sync.
+    pub async fn highest_bid(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Option<(AccountId, u64)>, ClientError> {
+        let auction = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(BIDS_SLOT).expect("valid slot name");
+        let highest = auction
+            .storage()
+            .get_map_item(&slot, Word::from(HIGHEST_KEY.map(Felt::new)))
+            .expect("auction account is missing its bids slot");
+        // Stored as [amount, bidder_prefix, bidder_suffix, refund_tag] on the
+        // stack, so the amount is the last element of the word
+        if highest[3].as_int() == 0 {
+            return Ok(None);
+        }
+        let bidder = AccountId::try_from([highest[2], highest[1]])
+            .expect("the auction stores valid bidder IDs");
+        Ok(Some((bidder, highest[3].as_int())))
+    }
+
+    /// Returns whether the seller has settled, as of the last sync.
+    pub async fn is_settled(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<bool, ClientError> {
+        let auction = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(TERMS_SLOT).expect("valid slot name");
+        let terms = auction
+            .storage()
+            .get_item(&slot)
+            .expect("auction account is missing its terms slot");
+        // The flag sits second from the top of the stack, below the end block
+        Ok(terms[2].as_int() == 1)
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the auction itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("auction note script does not compile");
+        Ok(script)
+    }
+
+    async fn send(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        note: &Note,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        Ok(submit_and_wait(client, sender, note_request).await?)
+    }
+}
@@ -0,0 +1,203 @@
+//! SDK-style wrapper around the bank contract in `masm/accounts/bank.masm`.
+//!
+//! The bank pools the tokens of one faucet and keeps an internal balance per
+//! user. Users deposit by sending it a note carrying the tokens and withdraw by
+//! sending a request note, which the bank answers with a P2ID note back to them.
+
+use miden_client::{
+    account::{AccountId, StorageMap, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const BALANCES_SLOT: &str = "miden::tutorials::bank::balances";
+pub const FAUCET_SLOT: &str = "miden::tutorials::bank::faucet";
+
+/// Library path the bank procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::bank_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/bank.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/bank.masm");
+const DEPOSIT_NOTE_FILE: &str = "masm/notes/bank_deposit_note.masm";
+const DEPOSIT_NOTE_CODE: &str = include_str!("../../masm/notes/bank_deposit_note.masm");
+const WITHDRAW_NOTE_FILE: &str = "masm/notes/bank_withdraw_note.masm";
+const WITHDRAW_NOTE_CODE: &str = include_str!("../../masm/notes/bank_withdraw_note.masm");
+
+/// A deployed bank contract holding the tokens of a single faucet.
+pub struct Bank {
+    pub account_id: AccountId,
+    pub faucet_id: AccountId,
+    library: Library,
+}
+
+impl Bank {
+    /// Builds a new bank for `faucet_id` and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the first deposit it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        faucet_id: AccountId,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let faucet = Word::from([
+            Felt::new(0),
+            Felt::new(0),
+            faucet_id.suffix(),
+            faucet_id.prefix().as_felt(),
+        ]);
+        let slots = vec![
+            StorageSlot::with_map(
+                StorageSlotName::new(BALANCES_SLOT)?,
+                StorageMap::with_entries([])?,
+            ),
+            StorageSlot::with_value(StorageSlotName::new(FAUCET_SLOT)?, faucet),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            faucet_id,
+            library,
+        })
+    }
+
+    /// Moves `amount` tokens from `user` into the bank and credits them to the user.
+    pub async fn deposit(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        amount: u64,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let asset = FungibleAsset::new(self.faucet_id, amount).expect("invalid deposit amount");
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(DEPOSIT_NOTE_FILE, DEPOSIT_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(user, script)
+            .asset(asset)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        self.send_and_consume(client, user, note, vec![]).await
+    }
+
+    /// Debits `amount` tokens from `user` and returns the P2ID note paying them out.
+    ///
+    /// When the report shows the withdrawal committed, the user can consume the
+    /// payout like any other P2ID note.
+    pub async fn withdraw(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        amount: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let asset = FungibleAsset::new(self.faucet_id, amount).expect("invalid withdraw amount");
+        let payout = create_p2id_note(
+            self.account_id,
+            user,
+            vec![asset.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.extend([payout.metadata().tag().into(), Felt::new(amount)]);
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(WITHDRAW_NOTE_FILE, WITHDRAW_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(user, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, user, note, vec![payout.clone()])
+            .await?;
+        Ok((report, payout))
+    }
+
+    /// Returns the internal balance of `user`, as of the last sync.
+    pub async fn balance_of(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+    ) -> Result<u64, ClientError> {
+        let bank = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(BALANCES_SLOT).expect("valid slot name");
+        let balance = bank
+            .storage()
+            .get_map_item(&slot, user_key(user))
+            .expect("bank account is missing its balances slot");
+        // The balance sits on top of the stack, which is the last element of the word
+        Ok(balance[3].as_int())
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the bank itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("bank note script does not compile");
+        Ok(script)
+    }
+
+    /// Sends `note` from `user` and has the bank consume it right away.
+    async fn send_and_consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        note: Note,
+        expected_outputs: Vec<Note>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, user, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .expected_output_recipients(
+                expected_outputs
+                    .iter()
+                    .map(|note| note.recipient().clone())
+                    .collect(),
+            )
+            .build()?;
+        submit_and_wait(client, self.account_id, consume_request).await
+    }
+}
+
+/// Map key of a user's balance, `[user_prefix, user_suffix, 0, 0]` on the stack.
+fn user_key(user: AccountId) -> Word {
+    Word::from([
+        Felt::new(0),
+        Felt::new(0),
+        user.suffix(),
+        user.prefix().as_felt(),
+    ])
+}
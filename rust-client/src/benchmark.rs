@@ -0,0 +1,226 @@
+//! Phase timings of benchmark transactions, written as a report to compare runs.
+//!
+//! `benchmark` runs each [`Workload`] a number of times and splits every
+//! transaction into the phases the client goes through: executing it locally,
+//! proving it, submitting the proof and waiting until the node commits it. One
+//! [`Sample`] per transaction and one [`Summary`] per workload go into a
+//! [`Report`], written as JSON or CSV so two runs can be diffed or loaded into
+//! a spreadsheet.
+
+use std::{fmt, fs, io, path::Path, time::Duration};
+
+use clap::ValueEnum;
+use serde::{Serialize, Serializer};
+
+/// Kind of transaction a benchmark iteration sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Workload {
+    /// A faucet mints tokens into a P2ID note.
+    Mint,
+    /// A wallet consumes a P2ID note.
+    Consume,
+    /// A transaction script increments the counter contract.
+    CustomScript,
+    /// A transaction script reads the counter through a foreign procedure invocation.
+    FpiRead,
+}
+
+impl Workload {
+    pub const ALL: [Workload; 4] = [
+        Workload::Mint,
+        Workload::Consume,
+        Workload::CustomScript,
+        Workload::FpiRead,
+    ];
+
+    /// Name used on the command line and in reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Workload::Mint => "mint",
+            Workload::Consume => "consume",
+            Workload::CustomScript => "custom-script",
+            Workload::FpiRead => "fpi-read",
+        }
+    }
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Time one transaction spent in each phase, serialized in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PhaseTimes {
+    /// Running the transaction in the local VM.
+    #[serde(rename = "execute_ms", serialize_with = "millis")]
+    pub execute: Duration,
+    /// Generating the proof.
+    #[serde(rename = "prove_ms", serialize_with = "millis")]
+    pub prove: Duration,
+    /// Sending the proof to the node and applying it to the local store.
+    #[serde(rename = "submit_ms", serialize_with = "millis")]
+    pub submit: Duration,
+    /// Waiting for the node to include the transaction in a block.
+    #[serde(rename = "commit_ms", serialize_with = "millis")]
+    pub commit: Duration,
+}
+
+impl PhaseTimes {
+    pub fn total(&self) -> Duration {
+        self.execute + self.prove + self.submit + self.commit
+    }
+
+    /// Mean of each phase over `times`, zero when there are none.
+    pub fn mean(times: &[PhaseTimes]) -> PhaseTimes {
+        if times.is_empty() {
+            return PhaseTimes::default();
+        }
+        let count = times.len() as u32;
+        let sum = |phase: fn(&PhaseTimes) -> Duration| times.iter().map(phase).sum::<Duration>();
+        PhaseTimes {
+            execute: sum(|t| t.execute) / count,
+            prove: sum(|t| t.prove) / count,
+            submit: sum(|t| t.submit) / count,
+            commit: sum(|t| t.commit) / count,
+        }
+    }
+}
+
+fn millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(as_millis(*duration))
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// One timed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Sample {
+    pub workload: Workload,
+    /// Iteration of the workload, starting at 1.
+    pub iteration: usize,
+    /// Transaction ID in hex.
+    pub tx_id: String,
+    /// VM cycles of the execution, the main driver of the proving time.
+    pub cycles: usize,
+    #[serde(flatten)]
+    pub phases: PhaseTimes,
+}
+
+/// Mean phase times of one workload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    pub workload: Workload,
+    pub samples: usize,
+    pub cycles: usize,
+    #[serde(flatten)]
+    pub mean: PhaseTimes,
+}
+
+/// Every sample of a benchmark run and the per-workload means.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Network the transactions were sent to.
+    pub network: String,
+    pub samples: Vec<Sample>,
+}
+
+/// Header of the CSV report, one column per [`Sample`] field.
+pub const CSV_HEADER: &str =
+    "workload,iteration,tx_id,cycles,execute_ms,prove_ms,submit_ms,commit_ms,total_ms";
+
+impl Report {
+    pub fn new(network: impl Into<String>) -> Self {
+        Self {
+            network: network.into(),
+            samples: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, sample: Sample) {
+        self.samples.push(sample);
+    }
+
+    /// Mean phase times and cycles per workload, in the order the workloads first ran.
+    pub fn summaries(&self) -> Vec<Summary> {
+        let mut workloads: Vec<Workload> = Vec::new();
+        for sample in &self.samples {
+            if !workloads.contains(&sample.workload) {
+                workloads.push(sample.workload);
+            }
+        }
+        workloads
+            .into_iter()
+            .map(|workload| {
+                let samples: Vec<&Sample> = self
+                    .samples
+                    .iter()
+                    .filter(|sample| sample.workload == workload)
+                    .collect();
+                let phases: Vec<PhaseTimes> = samples.iter().map(|sample| sample.phases).collect();
+                Summary {
+                    workload,
+                    samples: samples.len(),
+                    cycles: samples.iter().map(|sample| sample.cycles).sum::<usize>()
+                        / samples.len(),
+                    mean: PhaseTimes::mean(&phases),
+                }
+            })
+            .collect()
+    }
+
+    /// The report as pretty JSON, with the samples and the per-workload summaries.
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct JsonReport<'a> {
+            network: &'a str,
+            summaries: Vec<Summary>,
+            samples: &'a [Sample],
+        }
+
+        serde_json::to_string_pretty(&JsonReport {
+            network: &self.network,
+            summaries: self.summaries(),
+            samples: &self.samples,
+        })
+        .expect("reports serialize to JSON")
+    }
+
+    /// The samples as CSV, one line per transaction under [`CSV_HEADER`].
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{CSV_HEADER}\n");
+        for sample in &self.samples {
+            let phases = &sample.phases;
+            csv.push_str(&format!(
+                "{},{},{},{},{:.3},{:.3},{:.3},{:.3},{:.3}\n",
+                sample.workload,
+                sample.iteration,
+                sample.tx_id,
+                sample.cycles,
+                as_millis(phases.execute),
+                as_millis(phases.prove),
+                as_millis(phases.submit),
+                as_millis(phases.commit),
+                as_millis(phases.total()),
+            ));
+        }
+        csv
+    }
+
+    /// Writes the report to `path`, as CSV for a `.csv` extension and as JSON otherwise.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let is_csv = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        let contents = if is_csv {
+            self.to_csv()
+        } else {
+            self.to_json()
+        };
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,226 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::{component::BasicWallet, AccountType, StorageSlot, StorageSlotName},
+    asset::{Asset, FungibleAsset},
+    auth::AuthSecretKey,
+    note::{create_p2id_note, Note, NoteAttachment, NoteTag, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Felt, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    client::{init_client_from_config, network_id},
+    deployer::{ContractAuth, ContractDeployer},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    notes::{consume_all, ConsumeOptions},
+    output::{emit, Event},
+    say,
+    storage::print_account_storage,
+    wait::{submit_and_wait, wait_for_tx},
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the retiring account, its successor and a faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating the retiring account, its successor and a faucet");
+
+    // The retiring account is a regular wallet with an extra component that
+    // holds a forwarding pointer to its successor.
+    let successor_slot_name =
+        StorageSlotName::new("miden::tutorials::retirement::successor").expect("valid slot name");
+    let old_key_pair = AuthSecretKey::new_falcon512_rpo();
+    let retirement_deployer = ContractDeployer::from_path(
+        "../masm/accounts/retirement.masm",
+        "external_contract::retirement",
+    )
+    .with_slot(StorageSlot::with_value(
+        successor_slot_name.clone(),
+        Word::default(),
+    ))
+    .with_component(BasicWallet)
+    .account_type(AccountType::RegularAccountUpdatableCode)
+    .auth(ContractAuth::Falcon512 {
+        key: old_key_pair.clone(),
+        keystore: keystore.clone(),
+    });
+    let retirement_lib = retirement_deployer.library()?;
+    let old_account = retirement_deployer.deploy(&mut client).await?;
+    say!(
+        "Retiring account ID: {}",
+        old_account.id().to_bech32(network_id())
+    );
+    print_account_storage(&old_account);
+
+    let successor_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Successor account ID: {}",
+        successor_account.id().to_bech32(network_id())
+    );
+
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!("Faucet account ID: {}", faucet.id().to_bech32(network_id()));
+
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fund the retiring account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Funding the retiring account");
+
+    let mint_amount = FungibleAsset::new(faucet.id(), 100).unwrap();
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            mint_amount,
+            old_account.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    submit_and_wait(&mut client, faucet.id(), mint_request).await?;
+
+    let consumable_notes = client.get_consumable_notes(Some(old_account.id())).await?;
+    let notes = consumable_notes
+        .iter()
+        .map(|(note, _)| note.clone().try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+    submit_and_wait(&mut client, old_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Sweep every remaining asset to the successor
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Sweeping remaining assets to the successor");
+
+    // Before retiring, consume anything that is still waiting for the account
+    // so that no funds are left behind in unconsumed notes. Notes from faucets
+    // we do not know are left alone.
+    client.sync_state().await?;
+    let options = ConsumeOptions::default().with_known_faucets([faucet.id()]);
+    if let Some(tx_id) = consume_all(&mut client, old_account.id(), &options).await? {
+        wait_for_tx(&mut client, tx_id).await?;
+    }
+
+    let account = get_full_account(&mut client, old_account.id()).await?;
+    let assets: Vec<Asset> = account.vault().assets().collect();
+    say!("Assets to sweep: {:?}", assets);
+
+    let sweep_note = create_p2id_note(
+        old_account.id(),
+        successor_account.id(),
+        assets,
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+    let sweep_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(sweep_note.clone())])
+        .build()
+        .unwrap();
+    say!("Sweeping assets into note {}", sweep_note.id());
+    submit_and_wait(&mut client, old_account.id(), sweep_request).await?;
+
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(vec![sweep_note])?;
+    submit_and_wait(&mut client, successor_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Record a forwarding pointer in storage
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Recording the forwarding pointer to the successor");
+
+    let successor_word: Word = [
+        successor_account.id().prefix().as_felt(),
+        successor_account.id().suffix(),
+        Felt::new(0),
+        Felt::new(0),
+    ]
+    .into();
+    let successor_word_str = successor_word
+        .iter()
+        .map(|f| f.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+
+    let script_template = MasmFile::read("../masm/scripts/retirement_script.masm");
+    let script_file = script_template.with_source(
+        script_template
+            .source
+            .replace("{successor_word}", &successor_word_str),
+    );
+
+    let tx_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&retirement_lib)
+            .unwrap()
+            .compile_tx_script(&script_file.source),
+    )?;
+
+    let pointer_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, old_account.id(), pointer_request).await?;
+
+    let account = get_full_account(&mut client, old_account.id()).await?;
+    let stored_pointer = account.storage().get_item(&successor_slot_name).unwrap();
+    assert_eq!(
+        stored_pointer, successor_word,
+        "forwarding pointer mismatch"
+    );
+    say!("Forwarding pointer stored: {:?}", stored_pointer);
+    emit(Event::storage_value(
+        account.id(),
+        &successor_slot_name,
+        stored_pointer,
+    ));
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Remove the key and stop tracking the retired account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Removing the key and untracking the retired account");
+
+    // Without the secret key nobody using this keystore can authenticate
+    // another transaction for the retired account.
+    keystore
+        .remove_key(old_key_pair.public_key().to_commitment())
+        .unwrap();
+    say!("Removed the retired account's key from the keystore");
+
+    // Stop listening for notes addressed to the retired account. The local
+    // record is kept so the history stays auditable.
+    client
+        .remove_note_tag(NoteTag::with_account_target(old_account.id()))
+        .await?;
+    say!("Stopped tracking notes for the retired account");
+
+    let account = get_full_account(&mut client, successor_account.id()).await?;
+    let balance = account.vault().get_balance(faucet.id()).unwrap();
+    say!("Successor balance: {}", balance);
+    emit(Event::balance(account.id(), faucet.id(), balance));
+
+    // -------------------------------------------------------------------------
+    // STEP 6: What remains on-chain
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] On-chain traces of the retired account");
+    say!("- The account ID and its latest state commitment stay in the account tree.");
+    say!("- Public accounts keep their code and storage, including the forwarding pointer.");
+    say!("- The sweep note and every past transaction remain part of the chain history.");
+    say!("- Notes sent to the retired ID from now on are stuck once its key is gone.");
+
+    Ok(())
+}
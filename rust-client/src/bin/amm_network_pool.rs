@@ -0,0 +1,202 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId, asset::FungibleAsset, keystore::FilesystemKeyStore, note::Note,
+    rpc::GrpcClient, transaction::TransactionRequestBuilder, Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    network_queue::wait_consumed,
+    pool::{quote, Pool, PoolState},
+    say,
+    wait::submit_and_wait,
+};
+
+/// How often a swap note is polled before giving up on it.
+const MAX_POLLS: usize = 40;
+
+/// Has `user` consume a payout note from the pool.
+async fn claim(
+    client: &mut Client<FilesystemKeyStore>,
+    user: AccountId,
+    payout: Note,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payout, None)])
+        .build()?;
+    submit_and_wait(client, user, consume_request).await?;
+    Ok(())
+}
+
+/// Checks that the pool's vault holds exactly its reserves and reports them.
+async fn check_reserves(
+    client: &mut Client<FilesystemKeyStore>,
+    pool: &Pool,
+) -> Result<PoolState, Box<dyn std::error::Error>> {
+    let state = pool.state(client).await?;
+    let vault = get_full_account(client, pool.account_id).await?;
+    assert_eq!(
+        vault.vault().get_balance(pool.token_a).unwrap_or(0),
+        state.reserve_a,
+        "vault and reserve of A differ"
+    );
+    assert_eq!(
+        vault.vault().get_balance(pool.token_b).unwrap_or(0),
+        state.reserve_b,
+        "vault and reserve of B differ"
+    );
+    say!(
+        "Reserves: {} A / {} B, k = {}",
+        state.reserve_a,
+        state.reserve_b,
+        state.k()
+    );
+    Ok(state)
+}
+
+/// Returns the balances of `account_id` in both pool tokens.
+async fn balances(
+    client: &mut Client<FilesystemKeyStore>,
+    pool: &Pool,
+    account_id: AccountId,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let account = get_full_account(client, account_id).await?;
+    Ok((
+        account.vault().get_balance(pool.token_a).unwrap_or(0),
+        account.vault().get_balance(pool.token_b).unwrap_or(0),
+    ))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let setup = ClientSetup::from_cli()?;
+    // Swap notes are followed straight from the node, next to the client's own sync
+    let rpc = GrpcClient::new(&setup.endpoint, setup.timeout_ms);
+    let (mut client, keystore) = setup.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the two tokens, the accounts and the pool
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating tokens A and B, Alice, Bob, Carol and a network pool");
+
+    let token_a = create_basic_faucet(&mut client, &keystore).await?;
+    say!("Token A faucet: {:?}", token_a.id().to_bech32(network_id()));
+    let token_b = create_basic_faucet(&mut client, &keystore).await?;
+    say!("Token B faucet: {:?}", token_b.id().to_bech32(network_id()));
+
+    let mut accounts = Vec::new();
+    for name in ["Alice", "Bob", "Carol"] {
+        let account = create_basic_account(&mut client, &keystore).await?;
+        say!(
+            "{name}'s account ID: {:?}",
+            account.id().to_bech32(network_id())
+        );
+        accounts.push(account.id());
+    }
+    let [alice, bob, carol] = accounts[..] else {
+        unreachable!("three accounts were created");
+    };
+
+    let pool = Pool::deploy_on_network(&mut client, token_a.id(), token_b.id()).await?;
+    say!(
+        "Pool contract ID: {:?}",
+        pool.account_id.to_bech32(network_id())
+    );
+
+    top_up(&mut client, token_a.id(), alice, 10_000).await?;
+    top_up(&mut client, token_b.id(), alice, 20_000).await?;
+    top_up(&mut client, token_a.id(), bob, 1_000).await?;
+    top_up(&mut client, token_b.id(), carol, 2_000).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice seeds the pool
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice deposits 10000 A and 20000 B, setting the price to 2 B per A");
+
+    // The client consumes the seed deposit itself, which deploys the pool on
+    // the network. From here on only the network transaction builder touches it
+    let report = pool
+        .add_liquidity(&mut client, alice, 10_000, 20_000)
+        .await?;
+    assert!(report.is_committed(), "the seed deposit was not committed");
+    let seeded = check_reserves(&mut client, &pool).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Bob swaps A for B through the network
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob swaps 1000 A for B");
+
+    let bob_out = quote(1_000, seeded.reserve_a, seeded.reserve_b);
+    say!("Quoted output: {bob_out} B");
+    let asset_in = FungibleAsset::new(pool.token_a, 1_000)?;
+    let (swap, payout) = pool.send_swap(&mut client, bob, asset_in, bob_out).await?;
+    assert!(
+        wait_consumed(&mut client, &rpc, &swap, MAX_POLLS).await?,
+        "the network did not take Bob's swap"
+    );
+    claim(&mut client, bob, payout).await?;
+
+    let after_bob = check_reserves(&mut client, &pool).await?;
+    assert!(after_bob.k() >= seeded.k(), "Bob's swap decreased k");
+    assert_eq!(balances(&mut client, &pool, bob).await?, (0, bob_out));
+    say!("✅ Bob received {bob_out} B");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Carol swaps B for A at the price Bob left behind
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Carol swaps 2000 B for A");
+
+    let carol_out = quote(2_000, after_bob.reserve_b, after_bob.reserve_a);
+    say!("Quoted output: {carol_out} A");
+    let asset_in = FungibleAsset::new(pool.token_b, 2_000)?;
+    let (swap, payout) = pool
+        .send_swap(&mut client, carol, asset_in, carol_out)
+        .await?;
+    assert!(
+        wait_consumed(&mut client, &rpc, &swap, MAX_POLLS).await?,
+        "the network did not take Carol's swap"
+    );
+    claim(&mut client, carol, payout).await?;
+
+    let after_carol = check_reserves(&mut client, &pool).await?;
+    assert!(after_carol.k() >= after_bob.k(), "Carol's swap decreased k");
+    assert_eq!(balances(&mut client, &pool, carol).await?, (carol_out, 0));
+    say!("✅ Carol received {carol_out} A");
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Every token is accounted for
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Checking that no token was created or lost");
+
+    assert_eq!(
+        after_carol.reserve_a + carol_out,
+        11_000,
+        "A should be in the pool or with Carol"
+    );
+    assert_eq!(
+        after_carol.reserve_b + bob_out,
+        22_000,
+        "B should be in the pool or with Bob"
+    );
+    assert_eq!(
+        after_carol.total_shares, seeded.total_shares,
+        "swaps should not issue shares"
+    );
+    say!(
+        "✅ k went from {} to {}, the pool keeps what rounding leaves",
+        seeded.k(),
+        after_carol.k()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use std::process::ExitCode;
+
+use miden_client::{asset::FungibleAsset, rpc::GrpcClient};
+use rust_client::{
+    accounts::{collect, create_basic_account, create_basic_faucet, top_up},
+    auction::Auction,
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    network_queue::wait_consumed,
+    say,
+    wait::{wait_for_block, WaitOptions},
+};
+
+/// Blocks the auction takes bids for. Every bid waits for the network
+/// transaction builder, so this leaves room for the four bids below.
+const AUCTION_BLOCKS: u32 = 60;
+
+/// How often a network note is polled before giving up on it.
+const MAX_POLLS: usize = 40;
+
+/// Polls spent showing that a rejected bid stays unconsumed.
+const REJECTED_POLLS: usize = 5;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let setup = ClientSetup::from_cli()?;
+    // Network notes are followed straight from the node, next to the client's own sync
+    let rpc = GrpcClient::new(&setup.endpoint, setup.timeout_ms);
+    let (mut client, keystore) = setup.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the seller, three bidders and a faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice the seller, Bob, Carol and Dave the bidders, and a faucet");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    let mut bidders = Vec::new();
+    for name in ["Bob", "Carol", "Dave"] {
+        let account = create_basic_account(&mut client, &keystore).await?;
+        say!(
+            "{name}'s account ID: {:?}",
+            account.id().to_bech32(network_id())
+        );
+        top_up(&mut client, faucet.id(), account.id(), 100).await?;
+        bidders.push(account.id());
+    }
+    let [bob, carol, dave] = bidders[..] else {
+        unreachable!("three bidders were created");
+    };
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice opens the auction on a network account
+    // -------------------------------------------------------------------------
+    let height = client.sync_state().await?.block_num.as_u32();
+    let end_block = height + AUCTION_BLOCKS;
+    say!("\n[STEP 2] Alice opens an auction with a reserve of 10 tokens until block {end_block}");
+
+    let auction = Auction::start(
+        &mut client,
+        alice_account.id(),
+        FungibleAsset::new(faucet.id(), 10)?,
+        end_block,
+    )
+    .await?;
+    say!(
+        "Auction contract ID: {:?}",
+        auction.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Bob opens the bidding, Carol outbids Bob
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob bids 20 tokens, then Carol bids 35");
+
+    // Nobody runs a transaction against the auction: the network transaction
+    // builder consumes each bid note in a block of its own choosing
+    let (bid, bob_refund) = auction.bid(&mut client, bob, 20).await?;
+    assert!(
+        wait_consumed(&mut client, &rpc, &bid, MAX_POLLS).await?,
+        "the network did not take Bob's bid"
+    );
+    say!("Highest bid: {:?}", auction.highest_bid(&mut client).await?);
+
+    let (bid, carol_refund) = auction.bid(&mut client, carol, 35).await?;
+    assert!(
+        wait_consumed(&mut client, &rpc, &bid, MAX_POLLS).await?,
+        "the network did not take Carol's bid"
+    );
+    assert_eq!(auction.highest_bid(&mut client).await?, Some((carol, 35)));
+
+    // Taking Carol's bid refunded Bob's in the same network transaction
+    let balance = collect(&mut client, bob, bob_refund, faucet.id()).await?;
+    say!("✅ Carol leads with 35, Bob was refunded and holds {balance}");
+    assert_eq!(balance, 100, "Bob should be refunded in full");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: A bid that does not beat the highest one
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Dave bids 30, below Carol's 35");
+
+    // The contract rejects the bid, so the builder never consumes the note.
+    // It stays on chain with Dave's tokens until Dave takes them back
+    let (bid, _) = auction.bid(&mut client, dave, 30).await?;
+    let consumed = wait_consumed(&mut client, &rpc, &bid, REJECTED_POLLS).await?;
+    assert!(!consumed, "the auction took a bid below the highest one");
+    assert_eq!(auction.highest_bid(&mut client).await?, Some((carol, 35)));
+
+    let balance = collect(&mut client, dave, bid, faucet.id()).await?;
+    say!("✅ Dave took the rejected bid back and holds {balance}");
+    assert_eq!(balance, 100, "Dave should get the rejected bid back");
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Bob comes back with the winning bid
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Bob bids 50 tokens");
+
+    let (bid, _) = auction.bid(&mut client, bob, 50).await?;
+    assert!(
+        wait_consumed(&mut client, &rpc, &bid, MAX_POLLS).await?,
+        "the network did not take Bob's second bid"
+    );
+    assert_eq!(auction.highest_bid(&mut client).await?, Some((bob, 50)));
+
+    let balance = collect(&mut client, carol, carol_refund, faucet.id()).await?;
+    say!("✅ Bob leads with 50, Carol was refunded and holds {balance}");
+    assert_eq!(balance, 100, "Carol should be refunded in full");
+
+    // -------------------------------------------------------------------------
+    // STEP 6: After the end block Alice settles
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Waiting for block {end_block}, then settling the auction");
+
+    wait_for_block(
+        &mut client,
+        end_block,
+        &WaitOptions::for_blocks(AUCTION_BLOCKS),
+    )
+    .await?;
+
+    let (settle, payout) = auction.settle(&mut client).await?;
+    assert!(
+        wait_consumed(&mut client, &rpc, &settle, MAX_POLLS).await?,
+        "the network did not settle the auction"
+    );
+    assert!(auction.is_settled(&mut client).await?);
+
+    let payout = payout.expect("the auction had bids");
+    let balance = collect(&mut client, alice_account.id(), payout, faucet.id()).await?;
+    say!("✅ Bob won the auction and Alice received {balance} tokens");
+    assert_eq!(balance, 50, "Alice should receive the winning bid");
+
+    Ok(())
+}
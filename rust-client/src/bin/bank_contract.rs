@@ -0,0 +1,141 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteType},
+    transaction::TransactionRequestBuilder,
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    bank::Bank,
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    say,
+    wait::{submit_and_wait, wait_for_notes},
+};
+
+/// Mints `amount` tokens to `account_id` and consumes them.
+async fn fund(
+    client: &mut Client<FilesystemKeyStore>,
+    faucet_id: AccountId,
+    account_id: AccountId,
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let asset = FungibleAsset::new(faucet_id, amount).unwrap();
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(asset, account_id, NoteType::Public, client.rng())
+        .unwrap();
+    submit_and_wait(client, faucet_id, mint_request).await?;
+
+    wait_for_notes(client, account_id, 1).await?;
+    let notes = client
+        .get_consumable_notes(Some(account_id))
+        .await?
+        .into_iter()
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+    submit_and_wait(client, account_id, consume_request).await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts, the faucet and the bank
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts, faucet and bank");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+
+    let bank = Bank::deploy(&mut client, faucet.id()).await.unwrap();
+    say!(
+        "Bank contract ID: {:?}",
+        bank.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fund Alice and Bob
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting tokens for Alice and Bob");
+
+    fund(&mut client, faucet.id(), alice_account.id(), 100).await?;
+    fund(&mut client, faucet.id(), bob_account.id(), 50).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Deposit into the bank
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice deposits 60 tokens, Bob deposits 30");
+
+    bank.deposit(&mut client, alice_account.id(), 60).await?;
+    bank.deposit(&mut client, bob_account.id(), 30).await?;
+
+    let alice_balance = bank.balance_of(&mut client, alice_account.id()).await?;
+    let bob_balance = bank.balance_of(&mut client, bob_account.id()).await?;
+    say!("Alice's bank balance: {}", alice_balance);
+    say!("Bob's bank balance: {}", bob_balance);
+    assert_eq!(alice_balance, 60, "unexpected balance for Alice");
+    assert_eq!(bob_balance, 30, "unexpected balance for Bob");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Withdraw back to Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice withdraws 25 tokens");
+
+    let (report, payout) = bank.withdraw(&mut client, alice_account.id(), 25).await?;
+    assert!(report.is_committed(), "withdrawal was not committed");
+    say!("Payout note: {}", payout.id().to_hex());
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payout, None)])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, alice_account.id(), consume_request).await?;
+
+    let alice_balance = bank.balance_of(&mut client, alice_account.id()).await?;
+    say!("✅ Alice's bank balance is now {}", alice_balance);
+    assert_eq!(alice_balance, 35, "unexpected balance after withdrawal");
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Show that overdrafts are rejected
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Bob tries to withdraw more than was deposited");
+
+    match bank.withdraw(&mut client, bob_account.id(), 100).await {
+        Ok((report, _)) if report.is_committed() => {
+            panic!("bank should reject an overdraft")
+        }
+        Ok(_) => say!("Overdraft discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    Ok(())
+}
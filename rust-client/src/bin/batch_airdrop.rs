@@ -0,0 +1,168 @@
+use clap::Parser;
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use tokio::time::Instant;
+
+use miden_client::{
+    asset::FungibleAsset,
+    note::{create_p2id_note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+};
+use miden_protocol::MAX_OUTPUT_NOTES_PER_TX;
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, top_up},
+    airdrop::{batch_count, load_recipients, total_amount},
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    say,
+    wait::{print_explorer_link, submit_and_wait},
+};
+
+/// Mints tokens and pays them to every address of a CSV file in P2ID notes,
+/// packing as many notes as allowed into each transaction.
+#[derive(Parser)]
+struct Cli {
+    /// CSV of `address[,amount]` lines; without it, fresh wallets are created
+    /// and written to `airdrop_recipients.csv` first
+    #[arg(long)]
+    recipients: Option<PathBuf>,
+    /// Wallets to create when no recipients file is given
+    #[arg(long, default_value_t = 10)]
+    generate: usize,
+    /// Tokens for recipients whose line has no amount
+    #[arg(long, default_value_t = 10)]
+    amount: u64,
+    /// Most notes created by one transaction, the protocol limit by default
+    #[arg(
+        long,
+        default_value_t = MAX_OUTPUT_NOTES_PER_TX as u16,
+        value_parser = clap::value_parser!(u16).range(1..=MAX_OUTPUT_NOTES_PER_TX as i64)
+    )]
+    batch_size: u16,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Read the recipients
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Reading the recipients");
+
+    let path = match cli.recipients {
+        Some(path) => path,
+        None => {
+            say!(
+                "No recipients file given, creating {} wallets",
+                cli.generate
+            );
+            let path = PathBuf::from("airdrop_recipients.csv");
+            let mut csv = String::from("address,amount\n");
+            for _ in 0..cli.generate {
+                let wallet = create_basic_account(&mut client, &keystore).await?;
+                csv.push_str(&format!(
+                    "{},{}\n",
+                    wallet.id().to_bech32(network_id()),
+                    cli.amount
+                ));
+            }
+            fs::write(&path, csv)?;
+            path
+        }
+    };
+    let recipients = load_recipients(&path, cli.amount)?;
+    let total =
+        total_amount(&recipients).ok_or("the airdrop sends more tokens than fit in a u64")?;
+    let batch_size = usize::from(cli.batch_size);
+    let batches = batch_count(recipients.len(), batch_size);
+    say!(
+        "{} recipients in {}, {total} tokens, {batches} transactions of up to {batch_size} notes",
+        recipients.len(),
+        path.display()
+    );
+    if recipients.is_empty() {
+        say!("Nothing to send");
+        return Ok(());
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Mint the whole airdrop into the sender's wallet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting {total} tokens for the airdrop");
+
+    let sender = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Sender's account ID: {:?}",
+        sender.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), sender.id(), total).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Send the notes, one transaction per batch
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Sending {} P2ID notes", recipients.len());
+
+    let start = Instant::now();
+    for (index, batch) in recipients.chunks(batch_size).enumerate() {
+        let batch_start = Instant::now();
+
+        // Every note is built up front, so the wallet creates all of them in
+        // one transaction and pays for one proof instead of one per recipient
+        let mut notes = Vec::with_capacity(batch.len());
+        for recipient in batch {
+            let note = create_p2id_note(
+                sender.id(),
+                recipient.account_id,
+                vec![FungibleAsset::new(faucet.id(), recipient.amount)?.into()],
+                NoteType::Public,
+                NoteAttachment::default(),
+                client.rng(),
+            )?;
+            notes.push(OutputNote::Full(note));
+        }
+        let request = TransactionRequestBuilder::new()
+            .own_output_notes(notes)
+            .build()?;
+        let report = submit_and_wait(&mut client, sender.id(), request).await?;
+        assert!(
+            report.is_committed(),
+            "batch {} was not committed",
+            index + 1
+        );
+
+        say!(
+            "✅ Batch {}/{batches}: {} notes in {:.1?}",
+            index + 1,
+            batch.len(),
+            batch_start.elapsed()
+        );
+        print_explorer_link(&format!("Batch {} transaction", index + 1), report.tx_id);
+    }
+
+    let elapsed = start.elapsed();
+    say!(
+        "\n✅ Airdropped {total} tokens to {} recipients in {batches} transactions and {elapsed:.1?}",
+        recipients.len()
+    );
+    say!(
+        "One transaction per note would have needed {} transactions",
+        recipients.len()
+    );
+
+    Ok(())
+}
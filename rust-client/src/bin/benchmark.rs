@@ -0,0 +1,267 @@
+use clap::Parser;
+use std::{path::PathBuf, process::ExitCode};
+
+use tokio::time::Instant;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::NoteType,
+    transaction::{TransactionId, TransactionRequest, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    benchmark::{PhaseTimes, Report, Sample, Workload},
+    client::{network, network_id, ClientArgs, ClientSetup},
+    contracts::{CountReader, Counter},
+    cycles::CycleCount,
+    error::{report, ExitGuard},
+    middleware::submit_proven_logged,
+    say,
+    wait::{submit_and_wait, wait_for_tx},
+};
+
+/// Sends each workload a number of times, times every phase of its
+/// transactions and writes the samples to a report for comparing runs.
+#[derive(Parser)]
+struct Cli {
+    /// Workload to run, repeat for several [default: all of them]
+    #[arg(long = "workload", value_enum)]
+    workloads: Vec<Workload>,
+    /// Transactions sent per workload
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+    /// Report to write, as CSV for a `.csv` extension and as JSON otherwise
+    #[arg(long, default_value = "bench_report.json")]
+    out: PathBuf,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// Accounts the workloads send their transactions from.
+struct Fixtures {
+    wallet: AccountId,
+    faucet: AccountId,
+    /// The counter and the count reader, when a workload needs them.
+    counter: Option<Counter>,
+    reader: Option<CountReader>,
+}
+
+/// Builds the next transaction of `workload` and the account that sends it.
+///
+/// Anything the transaction depends on, like the note a consume spends, is
+/// sent here and is not part of the timings.
+async fn next_request(
+    client: &mut Client<FilesystemKeyStore>,
+    fixtures: &Fixtures,
+    workload: Workload,
+) -> Result<(AccountId, TransactionRequest), Box<dyn std::error::Error>> {
+    let mint_request = |client: &mut Client<FilesystemKeyStore>| {
+        TransactionRequestBuilder::new().build_mint_fungible_asset(
+            FungibleAsset::new(fixtures.faucet, 10).expect("10 tokens is a valid amount"),
+            fixtures.wallet,
+            NoteType::Public,
+            client.rng(),
+        )
+    };
+    match workload {
+        Workload::Mint => Ok((fixtures.faucet, mint_request(client)?)),
+        Workload::Consume => {
+            let request = mint_request(client)?;
+            let note = request
+                .expected_output_own_notes()
+                .pop()
+                .expect("a mint request creates one note");
+            submit_and_wait(client, fixtures.faucet, request).await?;
+            let consume_request = TransactionRequestBuilder::new()
+                .input_notes([(note, None)])
+                .build()?;
+            Ok((fixtures.wallet, consume_request))
+        }
+        Workload::CustomScript => {
+            let counter = fixtures.counter.as_ref().expect("counter is deployed");
+            let request = TransactionRequestBuilder::new()
+                .custom_script(Counter::increment_script()?)
+                .build()?;
+            Ok((counter.account_id, request))
+        }
+        Workload::FpiRead => {
+            let counter = fixtures.counter.as_ref().expect("counter is deployed");
+            let reader = fixtures.reader.as_ref().expect("reader is deployed");
+            Ok((
+                reader.account_id,
+                reader.copy_count_request(counter.account_id)?,
+            ))
+        }
+    }
+}
+
+/// Executes, proves and submits `request` as `account_id` and waits for it to
+/// be committed, timing each phase on its own.
+async fn run_timed(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    request: TransactionRequest,
+) -> Result<(TransactionId, usize, PhaseTimes), Box<dyn std::error::Error>> {
+    let start = Instant::now();
+    let tx_result = client.execute_transaction(account_id, request).await?;
+    let execute = start.elapsed();
+    let cycles = CycleCount::of(tx_result.executed_transaction()).total;
+
+    let start = Instant::now();
+    let proven_transaction = client.prove_transaction(&tx_result).await?;
+    let prove = start.elapsed();
+    let tx_id = proven_transaction.id();
+
+    let start = Instant::now();
+    let submission_height = submit_proven_logged(client, proven_transaction, &tx_result).await?;
+    client
+        .apply_transaction(&tx_result, submission_height)
+        .await?;
+    let submit = start.elapsed();
+
+    let start = Instant::now();
+    wait_for_tx(client, tx_id).await?;
+    let commit = start.elapsed();
+
+    Ok((
+        tx_id,
+        cycles,
+        PhaseTimes {
+            execute,
+            prove,
+            submit,
+            commit,
+        },
+    ))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let workloads = if cli.workloads.is_empty() {
+        Workload::ALL.to_vec()
+    } else {
+        cli.workloads
+    };
+
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Deploy the accounts the workloads use
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Deploying the benchmark accounts");
+
+    let wallet = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Wallet account ID: {:?}",
+        wallet.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+
+    let mut fixtures = Fixtures {
+        wallet: wallet.id(),
+        faucet: faucet.id(),
+        counter: None,
+        reader: None,
+    };
+    if workloads
+        .iter()
+        .any(|workload| matches!(workload, Workload::CustomScript | Workload::FpiRead))
+    {
+        let counter = Counter::deploy(&mut client).await?;
+        say!(
+            "Counter account ID: {:?}",
+            counter.account_id.to_bech32(network_id())
+        );
+        if workloads.contains(&Workload::FpiRead) {
+            // FPI reads the counter from the chain, and its first increment deploys it
+            counter.increment(&mut client).await?;
+            fixtures.reader = Some(CountReader::deploy(&mut client).await?);
+        }
+        fixtures.counter = Some(counter);
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Run the workloads
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 2] Running {} workloads, {} transactions each",
+        workloads.len(),
+        cli.iterations
+    );
+
+    let mut bench_report = Report::new(network().name());
+    for &workload in &workloads {
+        for iteration in 1..=cli.iterations as usize {
+            let (account_id, request) = next_request(&mut client, &fixtures, workload).await?;
+            let (tx_id, cycles, phases) = run_timed(&mut client, account_id, request).await?;
+            say!(
+                "{workload} #{iteration}: execute {:.2?}, prove {:.2?}, submit {:.2?}, commit {:.2?} ({cycles} cycles)",
+                phases.execute,
+                phases.prove,
+                phases.submit,
+                phases.commit
+            );
+            bench_report.push(Sample {
+                workload,
+                iteration,
+                tx_id: tx_id.to_hex(),
+                cycles,
+                phases,
+            });
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Summarize and write the report
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Mean time per phase\n");
+    say!(
+        "{:<14} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "workload",
+        "cycles",
+        "execute",
+        "prove",
+        "submit",
+        "commit",
+        "total"
+    );
+    for summary in bench_report.summaries() {
+        let mean = summary.mean;
+        say!(
+            "{:<14} {:>8} {:>10.2?} {:>10.2?} {:>10.2?} {:>10.2?} {:>10.2?}",
+            summary.workload.name(),
+            summary.cycles,
+            mean.execute,
+            mean.prove,
+            mean.submit,
+            mean.commit,
+            mean.total()
+        );
+    }
+
+    bench_report.write(&cli.out)?;
+    say!(
+        "\n✅ Wrote {} samples to {}",
+        bench_report.samples.len(),
+        cli.out.display()
+    );
+
+    Ok(())
+}
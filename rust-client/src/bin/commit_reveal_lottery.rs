@@ -0,0 +1,177 @@
+use std::process::ExitCode;
+
+use miden_client::{asset::FungibleAsset, transaction::TransactionRequestBuilder};
+use rust_client::{
+    accounts::{balance, create_basic_account, create_basic_faucet, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    lottery::{Lottery, LotteryState, Secret},
+    say,
+    wait::{submit_and_wait, wait_for_block, WaitOptions},
+};
+
+/// Blocks players have to commit. Every commitment is two transactions, so
+/// this leaves room for the three below.
+const COMMIT_BLOCKS: u32 = 30;
+
+/// Blocks players have to reveal once commitments close.
+const REVEAL_BLOCKS: u32 = 30;
+
+/// Tokens every player stakes.
+const STAKE: u64 = 10;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create three players and a faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice, Bob and Carol, and a faucet");
+
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    let mut players = Vec::new();
+    for name in ["Alice", "Bob", "Carol"] {
+        let account = create_basic_account(&mut client, &keystore).await?;
+        say!(
+            "{name}'s account ID: {:?}",
+            account.id().to_bech32(network_id())
+        );
+        top_up(&mut client, faucet.id(), account.id(), 100).await?;
+        players.push((name, account.id()));
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Deploy the lottery
+    // -------------------------------------------------------------------------
+    let height = client.sync_state().await?.block_num.as_u32();
+    let commit_end = height + COMMIT_BLOCKS;
+    let reveal_end = commit_end + REVEAL_BLOCKS;
+    say!(
+        "\n[STEP 2] Opening a lottery with a stake of {STAKE}: commit until block {commit_end}, reveal until block {reveal_end}"
+    );
+
+    let lottery = Lottery::deploy(
+        &mut client,
+        FungibleAsset::new(faucet.id(), STAKE)?,
+        commit_end,
+        reveal_end,
+    )
+    .await?;
+    say!(
+        "Lottery contract ID: {:?}",
+        lottery.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Every player commits to a secret
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Each player stakes {STAKE} tokens on the hash of a secret");
+
+    // The secrets never leave the players' side until the reveal
+    let mut secrets = Vec::new();
+    for (name, player) in &players {
+        let secret = Secret::random(client.rng());
+        let report = lottery.commit(&mut client, *player, &secret).await?;
+        assert!(
+            report.is_committed(),
+            "{name}'s commitment was not committed"
+        );
+        say!("{name} committed to {}", secret.commitment().to_hex());
+        secrets.push(secret);
+    }
+    let state = lottery.state(&mut client).await?;
+    assert_eq!(state.committed, 3);
+
+    // -------------------------------------------------------------------------
+    // STEP 4: After the commit phase every player reveals
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Waiting for block {commit_end}, then revealing the secrets");
+
+    wait_for_block(
+        &mut client,
+        commit_end,
+        &WaitOptions::for_blocks(COMMIT_BLOCKS),
+    )
+    .await?;
+
+    // A secret that does not hash to the commitment is rejected
+    let (_, alice) = players[0];
+    let forged = Secret {
+        number: secrets[0].number.wrapping_add(1),
+        ..secrets[0]
+    };
+    match lottery.reveal(&mut client, alice, &forged).await {
+        Ok(report) if report.is_committed() => panic!("the lottery took a forged secret"),
+        Ok(_) => say!("Forged reveal discarded as expected"),
+        Err(err) => say!("Forged reveal rejected as expected: {}", err),
+    }
+
+    for ((name, player), secret) in players.iter().zip(&secrets) {
+        let report = lottery.reveal(&mut client, *player, secret).await?;
+        assert!(report.is_committed(), "{name}'s reveal was not committed");
+        say!("{name} revealed {}", secret.number);
+    }
+
+    let state = lottery.state(&mut client).await?;
+    let seed = LotteryState::seed_of(secrets.iter().map(|secret| secret.number));
+    assert_eq!(state.revealed, 3);
+    assert_eq!(
+        state.seed, seed,
+        "the seed should be the sum of the numbers"
+    );
+    let winner_index = state.winner_index().expect("everyone revealed") as usize;
+    let (winner_name, winner) = players[winner_index];
+    assert_eq!(lottery.winner(&mut client).await?, Some(winner));
+    say!("Seed {seed} picks revealer {winner_index}: {winner_name}");
+
+    // -------------------------------------------------------------------------
+    // STEP 5: After the reveal phase the winner claims the pot
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Waiting for block {reveal_end}, then paying out");
+
+    wait_for_block(
+        &mut client,
+        reveal_end,
+        &WaitOptions::for_blocks(REVEAL_BLOCKS),
+    )
+    .await?;
+
+    let (loser_name, loser) = players[(winner_index + 1) % players.len()];
+    match lottery.claim_prize(&mut client, loser).await {
+        Ok((report, _)) if report.is_committed() => panic!("the lottery paid a loser"),
+        Ok(_) => say!("{loser_name}'s claim discarded as expected"),
+        Err(err) => say!("{loser_name}'s claim rejected as expected: {}", err),
+    }
+
+    let (report, payout) = lottery.claim_prize(&mut client, winner).await?;
+    assert!(report.is_committed(), "the payout was not committed");
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payout, None)])
+        .build()?;
+    submit_and_wait(&mut client, winner, consume_request).await?;
+    assert!(lottery.state(&mut client).await?.paid);
+
+    for (name, player) in &players {
+        let held = balance(&mut client, *player, faucet.id()).await?;
+        say!("{name} holds {held}");
+        let expected = if *player == winner {
+            100 + 2 * STAKE
+        } else {
+            100 - STAKE
+        };
+        assert_eq!(held, expected, "{name}'s balance is off");
+    }
+    say!("✅ {winner_name} won the pot of {}", 3 * STAKE);
+
+    Ok(())
+}
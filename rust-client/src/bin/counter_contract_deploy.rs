@@ -1,136 +1,91 @@
-use rand::RngCore;
-use std::{fs, path::Path, sync::Arc};
+use std::process::ExitCode;
 
 use miden_client::{
-    account::{
-        AccountBuilder, AccountComponent, AccountStorageMode, AccountType, StorageSlot,
-        StorageSlotName,
-    },
-    address::NetworkId,
-    assembly::{
-        Assembler, CodeBuilder, DefaultSourceManager, Library, Module, ModuleKind,
-        Path as AssemblyPath,
-    },
-    auth::NoAuth,
-    builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
-    rpc::{Endpoint, GrpcClient},
-    store::AccountRecordData,
-    transaction::{TransactionKernel, TransactionRequestBuilder},
-    ClientError, Word,
+    account::{StorageSlot, StorageSlotName},
+    transaction::TransactionRequestBuilder,
+    Word,
+};
+use rust_client::{
+    accounts::get_full_account,
+    client::{init_client_from_config, network_id},
+    deployer::ContractDeployer,
+    deployments,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    metadata::ContractMetadata,
+    output::{emit, Event},
+    say,
+    storage::print_account_storage,
+    wait::submit_and_wait,
 };
-use miden_client_sqlite_store::ClientBuilderSqliteExt;
-
-fn create_library(
-    assembler: Assembler,
-    library_path: &str,
-    source_code: &str,
-) -> Result<Library, Box<dyn std::error::Error>> {
-    let source_manager = Arc::new(DefaultSourceManager::default());
-    let module = Module::parser(ModuleKind::Library).parse_str(
-        AssemblyPath::new(library_path),
-        source_code,
-        source_manager.clone(),
-    )?;
-    let library = assembler.clone().assemble_library([module])?;
-    Ok(library)
-}
 
 #[tokio::main]
-async fn main() -> Result<(), ClientError> {
-    // Initialize client
-    let endpoint = Endpoint::devnet();
-    let timeout_ms = 10_000;
-    let rpc_client = Arc::new(GrpcClient::new(&endpoint, timeout_ms));
-
-    // Initialize keystore
-    let keystore_path = std::path::PathBuf::from("./keystore");
-    let keystore = Arc::new(FilesystemKeyStore::new(keystore_path).unwrap());
-
-    let store_path = std::path::PathBuf::from("./store.sqlite3");
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
 
-    let mut client = ClientBuilder::new()
-        .rpc(rpc_client)
-        .sqlite_store(store_path)
-        .authenticator(keystore.clone())
-        .in_debug_mode(true.into())
-        .build()
-        .await?;
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, _keystore) = init_client_from_config().await?;
 
     let sync_summary = client.sync_state().await.unwrap();
-    println!("Latest block: {}", sync_summary.block_num);
+    say!("Latest block: {}", sync_summary.block_num);
 
     // -------------------------------------------------------------------------
     // STEP 1: Create a basic counter contract
     // -------------------------------------------------------------------------
-    println!("\n[STEP 1] Creating counter contract.");
-
-    // Load the MASM file for the counter contract
-    let counter_path = Path::new("../masm/accounts/counter.masm");
-    let counter_code = fs::read_to_string(counter_path).unwrap();
+    say!("\n[STEP 1] Creating counter contract.");
+
+    // Human-readable info that explorers and `metadata_reader` can decode
+    let metadata_component = ContractMetadata {
+        name: "Tutorial Counter".to_string(),
+        // No icon yet, an all-zero hash marks it as missing
+        icon_hash: Word::default(),
+        url: "https://github.com/0xMiden/miden-tutorials".to_string(),
+    }
+    .component()
+    .unwrap();
 
-    // Compile the account code into `AccountComponent` with one storage slot
+    // Compile the counter MASM into a component with one storage slot and
+    // deploy it, together with the metadata, as a public `NoAuth` contract
     let counter_slot_name =
         StorageSlotName::new("miden::tutorials::counter").expect("valid slot name");
-    let component_code = CodeBuilder::new()
-        .compile_component_code("external_contract::counter_contract", &counter_code)
-        .unwrap();
-    let counter_component = AccountComponent::new(
-        component_code,
-        vec![StorageSlot::with_value(
-            counter_slot_name.clone(),
-            Word::default(),
-        )],
+    let counter_deployer = ContractDeployer::from_path(
+        "../masm/accounts/counter.masm",
+        "external_contract::counter_contract",
     )
-    .unwrap()
-    .with_supports_all_types();
-
-    // Init seed for the counter contract
-    let mut seed = [0_u8; 32];
-    client.rng().fill_bytes(&mut seed);
-
-    // Build the new `Account` with the component
-    let counter_contract = AccountBuilder::new(seed)
-        .account_type(AccountType::RegularAccountImmutableCode)
-        .storage_mode(AccountStorageMode::Public)
-        .with_component(counter_component.clone())
-        .with_auth_component(NoAuth)
-        .build()
-        .unwrap();
-
-    println!(
+    .with_slot(StorageSlot::with_value(
+        counter_slot_name.clone(),
+        Word::default(),
+    ))
+    .with_component(metadata_component);
+    // Create a library from the counter contract code, for the script below
+    let account_component_lib = counter_deployer.library()?;
+    let counter_contract = counter_deployer.deploy(&mut client).await?;
+
+    say!(
         "counter_contract commitment: {:?}",
         counter_contract.commitment()
     );
-    println!("counter_contract id: {:?}", counter_contract.id());
-    println!("counter_contract storage: {:?}", counter_contract.storage());
-
-    client.add_account(&counter_contract, false).await.unwrap();
+    say!("counter_contract id: {:?}", counter_contract.id());
+    print_account_storage(&counter_contract);
 
     // -------------------------------------------------------------------------
     // STEP 2: Call the Counter Contract with a script
     // -------------------------------------------------------------------------
-    println!("\n[STEP 2] Call Counter Contract With Script");
+    say!("\n[STEP 2] Call Counter Contract With Script");
 
     // Load the MASM script referencing the increment procedure
-    let script_path = Path::new("../masm/scripts/counter_script.masm");
-    let script_code = fs::read_to_string(script_path).unwrap();
-
-    // Create a library from the counter contract code
-    let assembler = TransactionKernel::assembler();
-    let account_component_lib = create_library(
-        assembler.clone(),
-        "external_contract::counter_contract",
-        &counter_code,
-    )
-    .unwrap();
-
-    let tx_script = client
-        .code_builder()
-        .with_dynamically_linked_library(&account_component_lib)
-        .unwrap()
-        .compile_tx_script(&script_code)
-        .unwrap();
+    let script_file = MasmFile::read("../masm/scripts/counter_script.masm");
+
+    let tx_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&account_component_lib)
+            .unwrap()
+            .compile_tx_script(&script_file.source),
+    )?;
 
     // Build a transaction request with the custom script
     let tx_increment_request = TransactionRequestBuilder::new()
@@ -139,39 +94,28 @@ async fn main() -> Result<(), ClientError> {
         .unwrap();
 
     // Execute and submit the transaction
-    let tx_id = client
-        .submit_new_transaction(counter_contract.id(), tx_increment_request)
+    submit_and_wait(&mut client, counter_contract.id(), tx_increment_request)
         .await
         .unwrap();
 
-    println!(
-        "View transaction on MidenScan: https://testnet.midenscan.com/tx/{:?}",
-        tx_id
-    );
-
-    println!(
+    say!(
         "Counter contract id: {:?}",
-        counter_contract.id().to_bech32(NetworkId::Testnet)
+        counter_contract.id().to_bech32(network_id())
     );
 
-    client.sync_state().await.unwrap();
+    // The first transaction deployed the contract, so the tutorials that call it
+    // can now look it up
+    deployments::record(deployments::COUNTER, counter_contract.id())?;
 
     // Retrieve updated contract data to see the incremented counter
-    let account_record = client
-        .get_account(counter_contract.id())
-        .await
-        .unwrap()
-        .expect("counter contract not found");
-    let account = match account_record.account_data() {
-        AccountRecordData::Full(account) => account,
-        AccountRecordData::Partial(_) => {
-            panic!("counter contract is missing full account data")
-        }
-    };
-    println!(
-        "counter contract storage: {:?}",
-        account.storage().get_item(&counter_slot_name)
-    );
+    let account = get_full_account(&mut client, counter_contract.id()).await?;
+    let count = account.storage().get_item(&counter_slot_name)?;
+    say!("counter contract storage: {:?}", count);
+    emit(Event::storage_value(
+        account.id(),
+        &counter_slot_name,
+        count,
+    ));
 
     Ok(())
 }
@@ -0,0 +1,82 @@
+use std::process::ExitCode;
+
+use miden_client::transaction::TransactionRequestBuilder;
+use rust_client::{
+    client::{init_client_from_config, network_id},
+    contracts::Counter,
+    cycles::CycleCount,
+    error::{report, ExitGuard},
+    say,
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, _keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the counter contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating the counter contract");
+
+    let counter = Counter::deploy(&mut client).await?;
+    say!(
+        "Counter contract ID: {:?}",
+        counter.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Execute the increment locally and count its cycles
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Executing the increment locally with cycle accounting");
+
+    // Execution alone yields the measurements, no proof or submission is needed
+    let request = TransactionRequestBuilder::new()
+        .custom_script(Counter::increment_script()?)
+        .build()?;
+    let tx_result = client
+        .execute_transaction(counter.account_id, request)
+        .await?;
+    let count = CycleCount::of(tx_result.executed_transaction());
+    say!("Cycles per phase:");
+    count.print();
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Compare against the documented budget
+    // -------------------------------------------------------------------------
+    let budget = Counter::INCREMENT_BUDGET;
+    say!(
+        "\n[STEP 3] Checking the budget: {} script cycles, {} in total",
+        budget.tx_script,
+        budget.total
+    );
+
+    // Over budget, the binary exits with an error naming the phase that grew
+    budget.check(&count)?;
+    say!(
+        "✅ Within budget: script at {}%, total at {}%",
+        count.tx_script * 100 / budget.tx_script,
+        count.total * 100 / budget.total
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Submit the measured increment
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Submitting the increment");
+
+    let tx_report = counter.increment(&mut client).await?;
+    assert!(tx_report.is_committed(), "increment was not committed");
+    let value = counter.count(&mut client).await?;
+    say!("✅ Counter is now {}", value);
+    assert_eq!(value, 1, "the counter should have been incremented once");
+
+    Ok(())
+}
@@ -1,112 +1,64 @@
+use std::process::ExitCode;
+
 use rand::RngCore;
-use std::sync::Arc;
 use tokio::time::Duration;
 
 use miden_client::{
-    account::{
-        component::{BasicFungibleFaucet, BasicWallet},
-        AccountBuilder, AccountId, AccountStorageMode, AccountType,
-    },
-    address::NetworkId,
-    asset::{FungibleAsset, TokenSymbol},
-    auth::{AuthFalcon512Rpo, AuthSecretKey},
-    builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
+    account::{AccountId, AccountStorageMode, AccountType},
+    asset::FungibleAsset,
     note::{create_p2id_note, NoteAttachment, NoteType},
-    rpc::{Endpoint, GrpcClient},
     transaction::{OutputNote, TransactionRequestBuilder},
-    ClientError, Felt,
 };
-use miden_client_sqlite_store::ClientBuilderSqliteExt;
 use miden_protocol::account::AccountIdVersion;
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    aliases::save_alias,
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    middleware::submit_logged,
+    notes::check_vault_covers,
+    say,
+    wait::{submit_and_wait, wait_for_notes},
+};
 
 #[tokio::main]
-async fn main() -> Result<(), ClientError> {
-    // Initialize client
-    let endpoint = Endpoint::devnet();
-    let timeout_ms = 10_000;
-    let rpc_client = Arc::new(GrpcClient::new(&endpoint, timeout_ms));
-
-    // Initialize keystore
-    let keystore_path = std::path::PathBuf::from("./keystore");
-    let keystore = Arc::new(FilesystemKeyStore::new(keystore_path).unwrap());
-
-    let store_path = std::path::PathBuf::from("./store.sqlite3");
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
 
-    let mut client = ClientBuilder::new()
-        .rpc(rpc_client)
-        .sqlite_store(store_path)
-        .authenticator(keystore.clone())
-        .in_debug_mode(true.into())
-        .build()
-        .await?;
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
 
     let sync_summary = client.sync_state().await.unwrap();
-    println!("Latest block: {}", sync_summary.block_num);
+    say!("Latest block: {}", sync_summary.block_num);
 
     //------------------------------------------------------------
     // STEP 1: Create a basic wallet for Alice
     //------------------------------------------------------------
-    println!("\n[STEP 1] Creating a new account for Alice");
-
-    // Account seed
-    let mut init_seed = [0_u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
-
-    let key_pair = AuthSecretKey::new_falcon512_rpo();
-
-    // Build the account
-    let alice_account = AccountBuilder::new(init_seed)
-        .account_type(AccountType::RegularAccountUpdatableCode)
-        .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
-        .with_component(BasicWallet)
-        .build()
-        .unwrap();
+    say!("\n[STEP 1] Creating a new account for Alice");
 
-    // Add the account to the client
-    client.add_account(&alice_account, false).await?;
+    // Public wallet with Falcon512 auth, see `rust_client::accounts`
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
 
-    // Add the key pair to the keystore
-    keystore.add_key(&key_pair).unwrap();
+    let alice_account_id_bech32 = alice_account.id().to_bech32(network_id());
+    say!("Alice's account ID: {:?}", alice_account_id_bech32);
 
-    let alice_account_id_bech32 = alice_account.id().to_bech32(NetworkId::Testnet);
-    println!("Alice's account ID: {:?}", alice_account_id_bech32);
+    // Later binaries and runs can refer to this wallet as `alice`
+    save_alias("alice", alice_account.id())?;
 
     //------------------------------------------------------------
     // STEP 2: Deploy a fungible faucet
     //------------------------------------------------------------
-    println!("\n[STEP 2] Deploying a new fungible faucet.");
-
-    // Faucet seed
-    let mut init_seed = [0u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
-
-    // Faucet parameters
-    let symbol = TokenSymbol::new("MID").unwrap();
-    let decimals = 8;
-    let max_supply = Felt::new(1_000_000);
-
-    // Generate key pair
-    let key_pair = AuthSecretKey::new_falcon512_rpo();
-
-    // Build the faucet account
-    let faucet_account = AccountBuilder::new(init_seed)
-        .account_type(AccountType::FungibleFaucet)
-        .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
-        .with_component(BasicFungibleFaucet::new(symbol, decimals, max_supply).unwrap())
-        .build()
-        .unwrap();
-
-    // Add the faucet to the client
-    client.add_account(&faucet_account, false).await?;
+    say!("\n[STEP 2] Deploying a new fungible faucet.");
 
-    // Add the key pair to the keystore
-    keystore.add_key(&key_pair).unwrap();
+    // Public `MID` faucet with a max supply of 1,000,000 tokens
+    let faucet_account = create_basic_faucet(&mut client, &keystore).await?;
 
-    let faucet_account_id_bech32 = faucet_account.id().to_bech32(NetworkId::Testnet);
-    println!("Faucet account ID: {:?}", faucet_account_id_bech32);
+    let faucet_account_id_bech32 = faucet_account.id().to_bech32(network_id());
+    say!("Faucet account ID: {:?}", faucet_account_id_bech32);
+    save_alias("faucet", faucet_account.id())?;
 
     // Resync to show newly deployed faucet
     client.sync_state().await?;
@@ -115,7 +67,7 @@ async fn main() -> Result<(), ClientError> {
     //------------------------------------------------------------
     // STEP 3: Mint 5 notes of 100 tokens for Alice
     //------------------------------------------------------------
-    println!("\n[STEP 3] Minting 5 notes of 100 tokens each for Alice.");
+    say!("\n[STEP 3] Minting 5 notes of 100 tokens each for Alice.");
 
     let amount: u64 = 100;
     let fungible_asset = FungibleAsset::new(faucet_account.id(), amount).unwrap();
@@ -130,17 +82,17 @@ async fn main() -> Result<(), ClientError> {
             )
             .unwrap();
 
-        println!("tx request built");
+        say!("tx request built");
 
-        let tx_id = client
-            .submit_new_transaction(faucet_account.id(), transaction_request)
-            .await?;
-        println!(
+        let tx_id = submit_logged(&mut client, faucet_account.id(), transaction_request).await?;
+        say!(
             "Minted note #{} of {} tokens for Alice. TX: {:?}",
-            i, amount, tx_id
+            i,
+            amount,
+            tx_id
         );
     }
-    println!("All 5 notes minted for Alice successfully!");
+    say!("All 5 notes minted for Alice successfully!");
 
     // Re-sync so minted notes become visible
     client.sync_state().await?;
@@ -148,50 +100,30 @@ async fn main() -> Result<(), ClientError> {
     //------------------------------------------------------------
     // STEP 4: Alice consumes all her notes
     //------------------------------------------------------------
-    println!("\n[STEP 4] Alice will now consume all of her notes to consolidate them.");
+    say!("\n[STEP 4] Alice will now consume all of her notes to consolidate them.");
 
     // Consume all minted notes in a single transaction
-    loop {
-        // Resync to get the latest data
-        client.sync_state().await?;
-
-        let consumable_notes = client
-            .get_consumable_notes(Some(alice_account.id()))
-            .await?;
-        let notes = consumable_notes
-            .iter()
-            .map(|(note, _)| note.clone().try_into())
-            .collect::<Result<Vec<_>, _>>()?;
-
-        if notes.len() == 5 {
-            println!("Found 5 consumable notes for Alice. Consuming them now...");
-            let transaction_request =
-                TransactionRequestBuilder::new().build_consume_notes(notes)?;
-
-            let tx_id = client
-                .submit_new_transaction(alice_account.id(), transaction_request)
-                .await?;
-            println!(
-                "All of Alice's notes consumed successfully. TX: {:?}",
-                tx_id
-            );
-            break;
-        } else {
-            println!(
-                "Currently, Alice has {} consumable notes. Waiting...",
-                notes.len()
-            );
-            tokio::time::sleep(Duration::from_secs(3)).await;
-        }
-    }
+    wait_for_notes(&mut client, alice_account.id(), 5).await?;
+    let consumable_notes = client
+        .get_consumable_notes(Some(alice_account.id()))
+        .await?;
+    let notes = consumable_notes
+        .iter()
+        .map(|(note, _)| note.clone().try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    say!("Found 5 consumable notes for Alice. Consuming them now...");
+    let transaction_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+    submit_and_wait(&mut client, alice_account.id(), transaction_request).await?;
+    say!("All of Alice's notes consumed successfully.");
 
     //------------------------------------------------------------
     // STEP 5: Alice sends 5 notes of 50 tokens to 5 users
     //------------------------------------------------------------
-    println!("\n[STEP 5] Alice sends 5 notes of 50 tokens each to 5 different users.");
+    say!("\n[STEP 5] Alice sends 5 notes of 50 tokens each to 5 different users.");
 
     // Send 50 tokens to 4 accounts in one transaction
-    println!("Creating multiple P2ID notes for 4 target accounts in one transaction...");
+    say!("Creating multiple P2ID notes for 4 target accounts in one transaction...");
     let mut p2id_notes = vec![];
 
     // Creating 4 P2ID notes to 4 'dummy' AccountIds
@@ -222,6 +154,10 @@ async fn main() -> Result<(), ClientError> {
         p2id_notes.push(p2id_note);
     }
 
+    // Fail early with a readable error if Alice cannot fund the notes
+    let alice = get_full_account(&mut client, alice_account.id()).await?;
+    check_vault_covers(&alice, &p2id_notes).unwrap_or_else(|err| panic!("{err}"));
+
     // Specifying output notes and creating a tx request to create them
     let output_notes: Vec<OutputNote> = p2id_notes.into_iter().map(OutputNote::Full).collect();
     let transaction_request = TransactionRequestBuilder::new()
@@ -229,13 +165,10 @@ async fn main() -> Result<(), ClientError> {
         .build()
         .unwrap();
 
-    let tx_id = client
-        .submit_new_transaction(alice_account.id(), transaction_request)
-        .await?;
-
-    println!("Submitted a transaction with 4 P2ID notes. TX: {:?}", tx_id);
+    submit_and_wait(&mut client, alice_account.id(), transaction_request).await?;
+    say!("Submitted a transaction with 4 P2ID notes.");
 
-    println!("Submitting one more single P2ID transaction...");
+    say!("Submitting one more single P2ID transaction...");
     let init_seed: [u8; 15] = {
         let mut init_seed = [0_u8; 15];
         client.rng().fill_bytes(&mut init_seed);
@@ -260,21 +193,22 @@ async fn main() -> Result<(), ClientError> {
         client.rng(),
     )?;
 
+    let alice = get_full_account(&mut client, alice_account.id()).await?;
+    check_vault_covers(&alice, std::slice::from_ref(&p2id_note))
+        .unwrap_or_else(|err| panic!("{err}"));
+
     let transaction_request = TransactionRequestBuilder::new()
         .own_output_notes(vec![OutputNote::Full(p2id_note)])
         .build()
         .unwrap();
 
-    let tx_id = client
-        .submit_new_transaction(alice_account.id(), transaction_request)
-        .await?;
-
-    println!("Submitted final P2ID transaction. TX: {:?}", tx_id);
+    submit_and_wait(&mut client, alice_account.id(), transaction_request).await?;
+    say!("Submitted final P2ID transaction.");
 
-    println!("\nAll steps completed successfully!");
-    println!("Alice created a wallet, a faucet was deployed,");
-    println!("5 notes of 100 tokens were minted to Alice, those notes were consumed,");
-    println!("and then Alice sent 5 separate 50-token notes to 5 different users.");
+    say!("\nAll steps completed successfully!");
+    say!("Alice created a wallet, a faucet was deployed,");
+    say!("5 notes of 100 tokens were minted to Alice, those notes were consumed,");
+    say!("and then Alice sent 5 separate 50-token notes to 5 different users.");
 
     Ok(())
 }
@@ -0,0 +1,159 @@
+use std::process::ExitCode;
+
+use rust_client::{
+    accounts::create_basic_account,
+    client::{init_client_from_config, network_id},
+    data_root::{DataRootContract, Dataset},
+    error::{report, ExitGuard},
+    say,
+};
+
+/// Off-chain records the operator commits to, e.g. an allowlist.
+fn records(names: &[&str]) -> Vec<Vec<u8>> {
+    names.iter().map(|name| name.as_bytes().to_vec()).collect()
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the operator, an impostor and the contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating the operator, an impostor and the data root contract");
+
+    let operator = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Operator account ID: {:?}",
+        operator.id().to_bech32(network_id())
+    );
+    let impostor = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Impostor account ID: {:?}",
+        impostor.id().to_bech32(network_id())
+    );
+
+    let contract = DataRootContract::deploy(&mut client, operator.id()).await?;
+    say!(
+        "Data root contract ID: {:?}",
+        contract.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Commit to the dataset
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Operator publishes the root of the dataset");
+
+    // Only the 32-byte root goes on-chain, however large the dataset grows
+    let dataset = Dataset::new(records(&["alice", "bob", "carol", "dave", "erin"]))?;
+    say!(
+        "Dataset of {} entries has root {}",
+        dataset.entries().len(),
+        dataset.root().to_hex()
+    );
+    let report = contract
+        .publish(&mut client, operator.id(), dataset.root(), 1)
+        .await?;
+    assert!(report.is_committed(), "root update was not committed");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: A consumer checks membership against the on-chain root
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Consumer verifies a membership proof client-side");
+
+    client.sync_state().await?;
+    let (root, version) = contract.root(&mut client).await?;
+    say!("On-chain root (version {version}): {}", root.to_hex());
+    assert_eq!(
+        root,
+        dataset.root(),
+        "on-chain root differs from the dataset"
+    );
+
+    // The operator serves the entry and its path off-chain; checking it costs no transaction
+    let carol = dataset.prove(2).expect("entry 2 exists");
+    assert!(carol.verify(root), "carol's proof should hold");
+    say!(
+        "✅ {:?} is in the dataset (path of depth {})",
+        String::from_utf8_lossy(&carol.entry),
+        carol.path.depth()
+    );
+
+    let mut forged = carol.clone();
+    forged.entry = b"mallory".to_vec();
+    assert!(!forged.verify(root), "a forged entry should not verify");
+    say!("✅ A forged entry is rejected");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Only the operator can move the root forward
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Impostor tries to publish a root of their own");
+
+    let fake = Dataset::new(records(&["mallory"]))?;
+    match contract
+        .publish(&mut client, impostor.id(), fake.root(), version + 1)
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("contract accepted an impostor's root"),
+        Ok(_) => say!("Impostor's update discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    say!("\nOperator replays the version it already published");
+    match contract
+        .publish(&mut client, operator.id(), fake.root(), version)
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("contract accepted a replayed version"),
+        Ok(_) => say!("Replayed update discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Update the dataset and publish the next root
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 5] Operator removes carol and publishes version {}",
+        version + 1
+    );
+
+    let updated = Dataset::new(records(&["alice", "bob", "dave", "erin", "frank"]))?;
+    let report = contract
+        .publish(&mut client, operator.id(), updated.root(), version + 1)
+        .await?;
+    assert!(report.is_committed(), "root update was not committed");
+
+    client.sync_state().await?;
+    let (root, version) = contract.root(&mut client).await?;
+    say!("On-chain root (version {version}): {}", root.to_hex());
+    assert_eq!(
+        root,
+        updated.root(),
+        "on-chain root differs from the dataset"
+    );
+
+    // Proofs are only as fresh as the root they were issued for
+    assert!(
+        !carol.verify(root),
+        "a proof against the old root should fail"
+    );
+    say!("✅ Carol's old proof no longer verifies");
+
+    let frank = updated.prove(4).expect("entry 4 exists");
+    assert!(frank.verify(root), "frank's proof should hold");
+    say!(
+        "✅ {:?} is in the updated dataset",
+        String::from_utf8_lossy(&frank.entry)
+    );
+
+    Ok(())
+}
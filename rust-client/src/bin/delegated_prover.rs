@@ -1,117 +1,188 @@
-use rand::RngCore;
-use std::sync::Arc;
+use clap::Parser;
+use std::{process::ExitCode, sync::Arc};
+
+use tokio::time::{timeout, Duration, Instant};
 
 use miden_client::{
-    account::{component::BasicWallet, AccountBuilder, AccountStorageMode, AccountType},
-    auth::{AuthFalcon512Rpo, AuthSecretKey},
-    builder::ClientBuilder,
     keystore::FilesystemKeyStore,
-    rpc::{Endpoint, GrpcClient},
-    store::AccountRecordData,
     transaction::{
-        LocalTransactionProver, ProvingOptions, TransactionProver, TransactionRequestBuilder,
+        LocalTransactionProver, ProvenTransaction, ProvingOptions, TransactionProver,
+        TransactionResult,
     },
-    ClientError,
+    Client, ClientError, RemoteTransactionProver,
+};
+use rust_client::{
+    accounts::get_full_account,
+    client::{network, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    proving,
+    run_mode::exit_if_dry_run_proven,
+    say,
 };
-use miden_client_sqlite_store::ClientBuilderSqliteExt;
 
-#[tokio::main]
-async fn main() -> Result<(), ClientError> {
-    // Initialize client
-    let endpoint = Endpoint::devnet();
-    let timeout_ms = 10_000;
-    let rpc_client = Arc::new(GrpcClient::new(&endpoint, timeout_ms));
-
-    // Initialize keystore
-    let keystore_path = std::path::PathBuf::from("./keystore");
-    let keystore = Arc::new(FilesystemKeyStore::new(keystore_path).unwrap());
-
-    let store_path = std::path::PathBuf::from("./store.sqlite3");
-
-    let mut client = ClientBuilder::new()
-        .rpc(rpc_client)
-        .sqlite_store(store_path)
-        .authenticator(keystore.clone())
-        .in_debug_mode(true.into())
-        .build()
+/// Proves a transaction with a delegated prover, falling back to local proving
+/// when the prover fails or does not answer in time.
+#[derive(Parser)]
+struct Cli {
+    /// Delegated prover endpoint [default: the network's public prover]
+    #[arg(long)]
+    prover_url: Option<String>,
+    /// Prove locally without contacting a delegated prover
+    #[arg(long, conflicts_with = "prover_url")]
+    local: bool,
+    /// Seconds to wait for the delegated prover before proving locally
+    #[arg(long, default_value_t = 60)]
+    prover_timeout: u64,
+    /// Skip proving with every `ProvingOptions` preset before the real proof
+    #[arg(long)]
+    skip_options_comparison: bool,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// Proves `tx_result` on this machine.
+async fn prove_locally(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_result: &TransactionResult,
+) -> Result<ProvenTransaction, ClientError> {
+    say!("Proving transaction with the local prover...");
+    let local_tx_prover: Arc<dyn TransactionProver> =
+        Arc::new(LocalTransactionProver::new(ProvingOptions::default()));
+    let start = Instant::now();
+    let proven_transaction = client
+        .prove_transaction_with(tx_result, local_tx_prover)
         .await?;
+    say!("Local proof generated in {:.2?}", start.elapsed());
+    Ok(proven_transaction)
+}
 
-    let sync_summary = client.sync_state().await.unwrap();
-    println!("Latest block: {}", sync_summary.block_num);
+/// Sends `tx_result` to the prover at `url` and proves it locally if the
+/// prover returns an error or takes longer than `wait`.
+///
+/// The prover only sees the executed transaction, and the proof it returns is
+/// checked by the node like any other, so a faulty prover costs time, not funds.
+async fn prove_remotely(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_result: &TransactionResult,
+    url: &str,
+    wait: Duration,
+) -> Result<ProvenTransaction, ClientError> {
+    say!("Proving transaction with the delegated prover at {url}...");
+    let remote_tx_prover: Arc<dyn TransactionProver> =
+        Arc::new(RemoteTransactionProver::new(url.to_string()));
+    let start = Instant::now();
+    match timeout(
+        wait,
+        client.prove_transaction_with(tx_result, remote_tx_prover),
+    )
+    .await
+    {
+        Ok(Ok(proven_transaction)) => {
+            say!("✅ Delegated proof received in {:.2?}", start.elapsed());
+            return Ok(proven_transaction);
+        }
+        Ok(Err(err)) => say!(
+            "Delegated prover failed after {:.2?}: {}",
+            start.elapsed(),
+            err
+        ),
+        Err(_) => say!("Delegated prover did not answer within {:?}", wait),
+    }
+
+    say!("Falling back to local proving");
+    prove_locally(client, tx_result).await
+}
 
-    // Create Alice's account
-    let mut init_seed = [0_u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
+/// Proves `tx_result` locally with every preset and prints the size, proving
+/// time and security level of each proof.
+///
+/// These proofs are only measured, none of them is submitted.
+async fn compare_proving_options(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_result: &TransactionResult,
+) -> Result<(), ClientError> {
+    let mut metrics = Vec::new();
+    for (preset, options) in proving::presets() {
+        let (_, proof_metrics) = proving::prove_with(client, tx_result, preset, options).await?;
+        metrics.push(proof_metrics);
+    }
+    proving::print_comparison(&metrics);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
 
-    let key_pair = AuthSecretKey::new_falcon512_rpo();
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
 
-    let alice_account = AccountBuilder::new(init_seed)
-        .account_type(AccountType::RegularAccountImmutableCode)
-        .storage_mode(AccountStorageMode::Private)
-        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
-        .with_component(BasicWallet)
-        .build()
-        .unwrap();
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
 
-    client.add_account(&alice_account, false).await?;
-    keystore.add_key(&key_pair).unwrap();
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // Create Alice's account
+    let alice_account = proving::create_nonce_bump_account(&mut client, &keystore).await?;
 
     // -------------------------------------------------------------------------
-    // Setup the local tx prover
+    // Pick the prover
     // -------------------------------------------------------------------------
-    let local_tx_prover = LocalTransactionProver::new(ProvingOptions::default());
-    let tx_prover: Arc<dyn TransactionProver> = Arc::new(local_tx_prover);
+    let prover_url = if cli.local {
+        None
+    } else {
+        cli.prover_url
+            .or_else(|| network().remote_prover_url().map(String::from))
+    };
 
     // We use a dummy transaction request to showcase delegated proving.
     // The only effect of this tx should be increasing Alice's nonce.
-    println!("Alice nonce initial: {:?}", alice_account.nonce());
-    let script_code = "begin push.1 drop end";
-    let tx_script = client
-        .code_builder()
-        .compile_tx_script(script_code)
-        .unwrap();
-
-    let transaction_request = TransactionRequestBuilder::new()
-        .custom_script(tx_script)
-        .build()
-        .unwrap();
+    say!("Alice nonce initial: {:?}", alice_account.nonce());
+    let transaction_request = proving::nonce_bump_request(&mut client)?;
 
     // Step 1: Execute the transaction locally
-    println!("Executing transaction...");
+    say!("Executing transaction...");
     let tx_result = client
         .execute_transaction(alice_account.id(), transaction_request)
         .await?;
 
-    // Step 2: Prove the transaction using the local prover
-    println!("Proving transaction with local prover...");
-    let proven_transaction = client.prove_transaction_with(&tx_result, tx_prover).await?;
+    // Step 2: Compare the proving options on this machine
+    if !cli.skip_options_comparison {
+        say!("Comparing proving options...");
+        compare_proving_options(&mut client, &tx_result).await?;
+    }
+
+    // Step 3: Prove the transaction with the delegated prover, or locally
+    let proven_transaction = match &prover_url {
+        Some(url) => {
+            let wait = Duration::from_secs(cli.prover_timeout);
+            prove_remotely(&mut client, &tx_result, url, wait).await?
+        }
+        None => prove_locally(&mut client, &tx_result).await?,
+    };
 
-    // Step 3: Submit the proven transaction
-    println!("Submitting proven transaction...");
+    // Step 4: Submit the proven transaction
+    exit_if_dry_run_proven(proven_transaction.id());
+    say!("Submitting proven transaction...");
     let submission_height = client
         .submit_proven_transaction(proven_transaction, &tx_result)
         .await?;
 
-    // Step 4: Apply the transaction to local store
+    // Step 5: Apply the transaction to local store
     client
         .apply_transaction(&tx_result, submission_height)
         .await?;
 
-    println!("Transaction submitted successfully using local prover!");
+    say!("Transaction submitted successfully!");
 
     client.sync_state().await.unwrap();
 
-    let account_record = client
-        .get_account(alice_account.id())
-        .await
-        .unwrap()
-        .expect("alice account not found");
-    let account = match account_record.account_data() {
-        AccountRecordData::Full(account) => account,
-        AccountRecordData::Partial(_) => panic!("alice account is missing full account data"),
-    };
+    let account = get_full_account(&mut client, alice_account.id()).await?;
 
-    println!("Alice nonce has increased: {:?}", account.nonce());
+    say!("Alice nonce has increased: {:?}", account.nonce());
 
     Ok(())
 }
@@ -0,0 +1,158 @@
+use std::{process::ExitCode, sync::Arc};
+use tokio::time::sleep;
+
+use miden_client::{
+    account::AccountId,
+    keystore::FilesystemKeyStore,
+    transaction::{
+        LocalTransactionProver, ProvingOptions, TransactionProver, TransactionRequest,
+        TransactionRequestBuilder, TransactionStatus,
+    },
+    Client, ClientError,
+};
+use rust_client::{
+    accounts::{create_basic_account, get_full_account},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    middleware::{submit_proven_logged, SubmissionFailure},
+    say,
+    wait::{submit_and_wait, wait_for_final_status, Poller},
+};
+
+/// Builds a request that only bumps the nonce and expires `delta` blocks after its reference block.
+fn expiring_request(
+    client: &mut Client<FilesystemKeyStore>,
+    delta: u16,
+) -> Result<TransactionRequest, Box<dyn std::error::Error>> {
+    let script_file = MasmFile::embedded(
+        "expiring_tx_script.masm",
+        format!(
+            "use miden::protocol::tx
+begin
+    push.{delta} exec.tx::update_expiration_block_delta
+end"
+        ),
+    );
+    let tx_script =
+        script_file.check(client.code_builder().compile_tx_script(&script_file.source))?;
+    Ok(TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()?)
+}
+
+async fn print_nonce(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+) -> Result<(), ClientError> {
+    let account = get_full_account(client, account_id).await?;
+    say!("Local nonce of the account: {}", account.nonce());
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create Alice's account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice's account");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    // Deploy the account first, so that only the expiring transaction is discarded
+    let deploy_request = expiring_request(&mut client, 100)?;
+    submit_and_wait(&mut client, alice_account.id(), deploy_request).await?;
+    print_nonce(&mut client, alice_account.id()).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Prove a transaction that expires two blocks later
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Proving a transaction that expires after two blocks");
+
+    let request = expiring_request(&mut client, 2)?;
+    let tx_result = client
+        .execute_transaction(alice_account.id(), request)
+        .await?;
+    let tx_prover: Arc<dyn TransactionProver> =
+        Arc::new(LocalTransactionProver::new(ProvingOptions::default()));
+    let proven_transaction = client.prove_transaction_with(&tx_result, tx_prover).await?;
+    let tx_id = proven_transaction.id();
+    let expiration_block = proven_transaction.expiration_block_num();
+    say!(
+        "Transaction {} expires at block {}",
+        tx_id.to_hex(),
+        expiration_block
+    );
+
+    // Track it locally as pending, but hold back the submission
+    let current_height = client.get_sync_height().await?;
+    client.apply_transaction(&tx_result, current_height).await?;
+    say!("Applied locally without submitting it");
+    print_nonce(&mut client, alice_account.id()).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Let it expire and watch the store discard it
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Waiting for the chain to pass the expiration block");
+
+    let mut poller = Poller::default();
+    while client.sync_state().await?.block_num <= expiration_block {
+        let delay = poller.next_delay();
+        say!("Not expired yet. Next check in {:.1?}...", delay);
+        sleep(delay).await;
+    }
+
+    let status = wait_for_final_status(&mut client, tx_id).await?;
+    say!("Final status in the store: {:?}", status);
+    assert!(
+        matches!(status, TransactionStatus::Discarded(_)),
+        "an unsubmitted transaction should be discarded"
+    );
+    // Discarding rolls the local account back to its last committed state
+    print_nonce(&mut client, alice_account.id()).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Submitting the expired proof is too late
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Submitting the expired proof anyway");
+
+    match submit_proven_logged(&mut client, proven_transaction, &tx_result).await {
+        Ok(_) => panic!("the node should reject an expired transaction"),
+        Err(err) => say!(
+            "Rejected as {:?}: {}",
+            SubmissionFailure::classify(&err),
+            err
+        ),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Rebuild the request and submit it in time
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Rebuilding the request against the current state");
+
+    // A proof is bound to its reference block and account state, so the only
+    // way forward is to execute and prove the request again
+    let request = expiring_request(&mut client, 100)?;
+    let report = submit_and_wait(&mut client, alice_account.id(), request).await?;
+    assert!(
+        report.is_committed(),
+        "rebuilt transaction was not committed"
+    );
+    print_nonce(&mut client, alice_account.id()).await?;
+
+    Ok(())
+}
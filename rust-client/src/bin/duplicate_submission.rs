@@ -0,0 +1,116 @@
+use std::{process::ExitCode, sync::Arc};
+
+use miden_client::{
+    asset::FungibleAsset,
+    note::NoteType,
+    transaction::{
+        LocalTransactionProver, ProvingOptions, TransactionProver, TransactionRequestBuilder,
+    },
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    middleware::{submit_proven_logged, SubmissionFailure},
+    say,
+    wait::{print_explorer_link, wait_for_tx},
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create Alice's account and a faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice's account and a faucet");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Execute and prove a mint transaction once
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Executing and proving a mint transaction");
+
+    let mint_amount = FungibleAsset::new(faucet.id(), 100).unwrap();
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            mint_amount,
+            alice_account.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    let tx_result = client
+        .execute_transaction(faucet.id(), mint_request)
+        .await?;
+
+    let tx_prover: Arc<dyn TransactionProver> =
+        Arc::new(LocalTransactionProver::new(ProvingOptions::default()));
+    let proven_transaction = client.prove_transaction_with(&tx_result, tx_prover).await?;
+    let tx_id = proven_transaction.id();
+    say!("Proven transaction: {}", tx_id.to_hex());
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Submit the proof, then submit it again right away
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Submitting the same proof twice");
+
+    let submission_height =
+        submit_proven_logged(&mut client, proven_transaction.clone(), &tx_result).await?;
+    say!("First submission accepted at block {}", submission_height);
+    print_explorer_link("View transaction", tx_id);
+
+    // The node still holds the first copy in its mempool
+    match submit_proven_logged(&mut client, proven_transaction.clone(), &tx_result).await {
+        Ok(_) => say!("The node accepted the duplicate; it will only be applied once"),
+        Err(err) => match SubmissionFailure::classify(&err) {
+            SubmissionFailure::AlreadySubmitted => {
+                say!("✅ Duplicate detected: the first submission is still in flight")
+            }
+            SubmissionFailure::Other => say!("Duplicate rejected with: {}", err),
+        },
+    }
+
+    // Only the first submission is applied to the local store
+    client
+        .apply_transaction(&tx_result, submission_height)
+        .await?;
+    wait_for_tx(&mut client, tx_id).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Submit the proof once more after it was committed
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Submitting the proof again after it was committed");
+
+    // Once committed, the faucet has moved past the state the proof starts from,
+    // so the node reports a stale account state rather than a duplicate
+    match submit_proven_logged(&mut client, proven_transaction, &tx_result).await {
+        Ok(_) => panic!("the node should reject a proof that was already committed"),
+        Err(err) => say!(
+            "Rejected as {:?}: {}",
+            SubmissionFailure::classify(&err),
+            err
+        ),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::{AccountId, AccountType, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteType},
+    transaction::{TransactionRequest, TransactionRequestBuilder},
+    Client, Felt, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, get_full_account},
+    client::{init_client_from_config, network_id},
+    deployer::{ContractAuth, ContractDeployer},
+    error::{report, ExitGuard},
+    masm::{word_to_masm, MasmFile},
+    say,
+    wait::{submit_and_wait, wait_for_block, WaitOptions},
+};
+
+/// Blocks in one release period.
+const PERIOD_BLOCKS: u64 = 10;
+
+/// Tokens released per period.
+const CAP: u64 = 100;
+
+const SCHEDULE_SLOT: &str = "miden::tutorials::emission_faucet::schedule";
+const WINDOW_SLOT: &str = "miden::tutorials::emission_faucet::window";
+
+/// Builds a request minting `amount` tokens to `target`, and the note it creates.
+fn mint_request(
+    client: &mut Client<FilesystemKeyStore>,
+    library: &Library,
+    faucet_id: AccountId,
+    target: AccountId,
+    amount: u64,
+) -> Result<(TransactionRequest, Note), Box<dyn std::error::Error>> {
+    let note = create_p2id_note(
+        faucet_id,
+        target,
+        vec![FungibleAsset::new(faucet_id, amount)?.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+
+    let script_template = MasmFile::read("../masm/scripts/emission_mint_script.masm");
+    let script_code = script_template
+        .source
+        .replace("{recipient}", &word_to_masm(note.recipient().digest()))
+        .replace(
+            "{tag}",
+            &Felt::from(note.metadata().tag()).as_int().to_string(),
+        )
+        .replace("{amount}", &amount.to_string());
+    let script_file = script_template.with_source(script_code);
+    let script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(library)?
+            .compile_tx_script(&script_file.source),
+    )?;
+
+    // The script builds the note, so the client is told which recipient to expect
+    let request = TransactionRequestBuilder::new()
+        .custom_script(script)
+        .expected_output_recipients(vec![note.recipient().clone()])
+        .build()?;
+    Ok((request, note))
+}
+
+/// The faucet's current period and the tokens minted in it, as of the last sync.
+async fn window(
+    client: &mut Client<FilesystemKeyStore>,
+    faucet_id: AccountId,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let account = get_full_account(client, faucet_id).await?;
+    let window = account
+        .storage()
+        .get_item(&StorageSlotName::new(WINDOW_SLOT)?)?;
+    // The period sits on top of the stack, which is the last element of the word
+    Ok((window[3].as_int(), window[2].as_int()))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Deploy a faucet releasing CAP tokens every PERIOD_BLOCKS blocks
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Deploying a faucet releasing {CAP} tokens every {PERIOD_BLOCKS} blocks");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+
+    let schedule = Word::from([
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(CAP),
+        Felt::new(PERIOD_BLOCKS),
+    ]);
+    let deployer = ContractDeployer::from_path(
+        "../masm/accounts/emission_faucet.masm",
+        "external_contract::emission_faucet",
+    )
+    .account_type(AccountType::FungibleFaucet)
+    .auth(ContractAuth::new_falcon512(&keystore))
+    .with_slots([
+        StorageSlot::with_value(StorageSlotName::new(SCHEDULE_SLOT)?, schedule),
+        StorageSlot::with_value(StorageSlotName::new(WINDOW_SLOT)?, Word::default()),
+    ]);
+    let library = deployer.library()?;
+    let faucet = deployer.deploy(&mut client).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Mint part of the period's release
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting 60 tokens for Alice");
+
+    let (request, first_note) =
+        mint_request(&mut client, &library, faucet.id(), alice_account.id(), 60)?;
+    submit_and_wait(&mut client, faucet.id(), request).await?;
+    let (period, minted) = window(&mut client, faucet.id()).await?;
+    say!("✅ Period {period}: {minted} of {CAP} tokens minted");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Going over the cap fails within the same period
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Minting 50 more tokens in the same period");
+
+    // The faucet measures periods by the transaction's reference block, which
+    // is the client's sync height, so the outcome is known before executing
+    let height = client.sync_state().await?.block_num.as_u32() as u64;
+    if height / PERIOD_BLOCKS == period {
+        let (request, _) =
+            mint_request(&mut client, &library, faucet.id(), alice_account.id(), 50)?;
+        match client.execute_transaction(faucet.id(), request).await {
+            Ok(_) => panic!("the faucet should not release more than {CAP} tokens per period"),
+            Err(err) => say!("Rejected as expected at block {height}: {}", err),
+        }
+    } else {
+        say!("Block {height} already starts a new period, skipping the capped attempt");
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: The next period releases a fresh allowance
+    // -------------------------------------------------------------------------
+    let next_period_block = (period + 1) * PERIOD_BLOCKS;
+    say!("\n[STEP 4] Waiting for block {next_period_block}, then minting 50 tokens");
+
+    wait_for_block(
+        &mut client,
+        next_period_block as u32,
+        &WaitOptions::for_blocks(PERIOD_BLOCKS as u32),
+    )
+    .await?;
+    let (request, second_note) =
+        mint_request(&mut client, &library, faucet.id(), alice_account.id(), 50)?;
+    submit_and_wait(&mut client, faucet.id(), request).await?;
+    let (period, minted) = window(&mut client, faucet.id()).await?;
+    say!("✅ Period {period}: {minted} of {CAP} tokens minted");
+    assert_eq!(minted, 50, "a new period starts with nothing minted");
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Alice collects both mints
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Alice consumes both notes");
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(first_note, None), (second_note, None)])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), consume_request).await?;
+
+    let balance = get_full_account(&mut client, alice_account.id())
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    say!("✅ Alice holds {balance} tokens");
+    assert_eq!(balance, 110, "Alice should hold both mints");
+
+    Ok(())
+}
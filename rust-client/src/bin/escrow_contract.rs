@@ -0,0 +1,159 @@
+use std::process::ExitCode;
+
+use miden_client::asset::FungibleAsset;
+use rust_client::{
+    accounts::{collect, create_basic_account, create_basic_faucet, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    escrow::Escrow,
+    say,
+    wait::{wait_for_block, WaitOptions},
+};
+
+/// Blocks the buyer waits before a refund becomes possible in the refund path.
+const REFUND_DELAY: u32 = 10;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the buyer, the seller and the faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice the buyer, Bob the seller and a faucet");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice locks the price of a first deal
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice locks 40 tokens in escrow for Bob");
+
+    let height = client.sync_state().await?.block_num.as_u32();
+    let deal = Escrow::deploy(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        FungibleAsset::new(faucet.id(), 40)?,
+        height + 1_000,
+    )
+    .await?;
+    say!(
+        "Escrow contract ID: {:?}",
+        deal.account_id.to_bech32(network_id())
+    );
+    deal.lock(&mut client).await?;
+    say!(
+        "✅ 40 tokens held until Alice releases them or block {}",
+        deal.deadline
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Nobody but Alice can release, and Bob cannot claim before she does
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob tries to claim early, then to release the funds");
+
+    match deal.claim(&mut client).await {
+        Ok((report, _)) if report.is_committed() => panic!("escrow paid out before a release"),
+        Ok(_) => say!("Early claim discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+    match deal.release(&mut client, bob_account.id()).await {
+        Ok(report) if report.is_committed() => panic!("escrow accepted the seller's release"),
+        Ok(_) => say!("Bob's release discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Alice releases, Bob claims
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice releases the funds and Bob claims them");
+
+    deal.release(&mut client, alice_account.id()).await?;
+    let (released, held) = deal.state(&mut client).await?;
+    say!("Released: {released}, still held: {held}");
+    assert!(
+        released && held,
+        "the release should keep the funds until claimed"
+    );
+
+    let (report, payout) = deal.claim(&mut client).await?;
+    assert!(report.is_committed(), "the claim was not committed");
+    let balance = collect(&mut client, bob_account.id(), payout, faucet.id()).await?;
+    say!("✅ Bob holds {balance} tokens");
+    assert_eq!(balance, 40, "Bob should receive the released price");
+
+    // -------------------------------------------------------------------------
+    // STEP 5: A second deal that Alice never releases
+    // -------------------------------------------------------------------------
+    let height = client.sync_state().await?.block_num.as_u32();
+    let deadline = height + REFUND_DELAY;
+    say!("\n[STEP 5] Alice locks 30 tokens in a second escrow with deadline block {deadline}");
+
+    let deal = Escrow::deploy(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        FungibleAsset::new(faucet.id(), 30)?,
+        deadline,
+    )
+    .await?;
+    deal.lock(&mut client).await?;
+
+    // The deadline is checked against the reference block, which is the
+    // client's sync height, so the outcome is known before executing
+    let height = client.sync_state().await?.block_num.as_u32();
+    if height < deadline {
+        match deal.refund(&mut client).await {
+            Ok((report, _)) if report.is_committed() => {
+                panic!("escrow refunded before the deadline")
+            }
+            Ok(_) => say!("Early refund discarded as expected"),
+            Err(err) => say!("Rejected as expected at block {height}: {}", err),
+        }
+    } else {
+        say!("Block {height} has already reached the deadline, skipping the early refund");
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 6: After the deadline Alice takes the funds back
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Waiting for block {deadline}, then refunding Alice");
+
+    wait_for_block(
+        &mut client,
+        deadline,
+        &WaitOptions::for_blocks(REFUND_DELAY),
+    )
+    .await?;
+    let (report, payout) = deal.refund(&mut client).await?;
+    assert!(report.is_committed(), "the refund was not committed");
+    let balance = collect(&mut client, alice_account.id(), payout, faucet.id()).await?;
+    say!("✅ Alice holds {balance} tokens");
+    assert_eq!(
+        balance, 60,
+        "Alice should only have paid for the released deal"
+    );
+
+    Ok(())
+}
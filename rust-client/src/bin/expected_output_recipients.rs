@@ -0,0 +1,179 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    crypto::FeltRng,
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteRecipient, NoteTag},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, Felt,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumberPolicy, SerialNumbers},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Has Bob execute the consumption of `note` locally, declaring `recipients`.
+///
+/// Nothing is submitted, so the note stays consumable for the next attempt.
+/// Prints how each output note came out, or the error the client raised.
+async fn try_consume(
+    client: &mut Client<FilesystemKeyStore>,
+    bob: AccountId,
+    note: &Note,
+    recipients: Vec<NoteRecipient>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let request = TransactionRequestBuilder::new()
+        .input_notes([(note.clone(), None)])
+        .expected_output_recipients(recipients)
+        .build()?;
+    match client.execute_transaction(bob, request).await {
+        Ok(result) => {
+            for output in result.executed_transaction().output_notes().iter() {
+                match output {
+                    OutputNote::Full(note) => {
+                        say!("Output note {} is known in full", note.id().to_hex())
+                    }
+                    _ => say!(
+                        "Output note {} is known only by its header: the client cannot \
+                         rebuild its script, inputs or serial number",
+                        output.id().to_hex()
+                    ),
+                }
+            }
+        }
+        Err(err) => say!("Execution failed: {}", err),
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Alice sends a note whose script creates another note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Alice sends an iterative output note");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // The script receives the asset and creates a copy of itself holding half
+    // of it, with the serial number incremented, see note_creation_in_masm
+    let note_file = MasmFile::read("../masm/notes/iterative_output_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+    let mut serials = SerialNumbers::new(SerialNumberPolicy::Sequential(client.rng().draw_word()));
+    let tag = NoteTag::new(0);
+    let note_inputs = vec![
+        alice_account.id().prefix().as_felt(),
+        alice_account.id().suffix(),
+        tag.into(),
+        Felt::new(0),
+    ];
+
+    let note = NoteBuilder::new(alice_account.id(), note_script.clone())
+        .inputs(note_inputs.clone())
+        .asset(FungibleAsset::new(faucet.id(), 100)?)
+        .tag(tag)
+        .build(&mut serials, client.rng())?;
+    let send_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note.clone())])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), send_request).await?;
+
+    // The note Bob's consumption will create, rebuilt the way the script builds it
+    let expected = NoteBuilder::new(bob_account.id(), note_script)
+        .inputs(note_inputs)
+        .asset(FungibleAsset::new(faucet.id(), 50)?)
+        .tag(tag)
+        .build(&mut serials, client.rng())?;
+    say!(
+        "The consumption will create a note with recipient {}",
+        expected.recipient().digest().to_hex()
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Omitting the recipient
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Bob consumes the note without declaring any recipient");
+
+    // The script only hands the kernel a recipient digest. Without the
+    // recipient's details the client sees a note it cannot describe, and a
+    // public note without details cannot be published
+    try_consume(&mut client, bob_account.id(), &note, vec![]).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Declaring the wrong recipient
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob declares a recipient with the wrong serial number");
+
+    let wrong = NoteRecipient::new(
+        client.rng().draw_word(),
+        expected.recipient().script().clone(),
+        expected.recipient().inputs().clone(),
+    );
+    say!("Declared recipient {}", wrong.digest().to_hex());
+
+    // The client checks that every declared recipient shows up among the
+    // outputs. A declared recipient that never appears fails the execution
+    try_consume(&mut client, bob_account.id(), &note, vec![wrong]).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Declaring the recipient the script builds
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Bob declares the right recipient and submits");
+
+    try_consume(
+        &mut client,
+        bob_account.id(),
+        &note,
+        vec![expected.recipient().clone()],
+    )
+    .await?;
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(note, None)])
+        .expected_output_recipients(vec![expected.recipient().clone()])
+        .build()?;
+    submit_and_wait(&mut client, bob_account.id(), consume_request).await?;
+
+    // With the details in hand, the client tracks the created note in full
+    let record = client
+        .get_output_note(expected.id())
+        .await?
+        .expect("the declared note is tracked as an output note");
+    say!(
+        "✅ Output note {} tracked, state {:?}",
+        record.id().to_hex(),
+        record.state()
+    );
+
+    Ok(())
+}
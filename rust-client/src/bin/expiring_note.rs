@@ -0,0 +1,162 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    crypto::FeltRng,
+    keystore::FilesystemKeyStore,
+    note::{
+        Note, NoteAssets, NoteInputs, NoteMetadata, NoteRecipient, NoteScript, NoteTag, NoteType,
+    },
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, Felt,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    say,
+    wait::{submit_and_wait, wait_for_block, wait_for_notes, WaitOptions},
+};
+
+/// Creates a note carrying `amount` tokens that expires at `expiry_block`.
+async fn create_expiring_note(
+    client: &mut Client<FilesystemKeyStore>,
+    note_script: NoteScript,
+    sender: AccountId,
+    asset: FungibleAsset,
+    expiry_block: u32,
+) -> Result<Note, Box<dyn std::error::Error>> {
+    let note_inputs = NoteInputs::new(vec![Felt::new(expiry_block as u64)])?;
+    let recipient = NoteRecipient::new(client.rng().draw_word(), note_script, note_inputs);
+    let metadata = NoteMetadata::new(sender, NoteType::Public, NoteTag::new(0));
+    let note = Note::new(NoteAssets::new(vec![asset.into()])?, metadata, recipient);
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note.clone())])
+        .build()
+        .unwrap();
+    say!(
+        "Creating note {} expiring at block {}",
+        note.id().to_hex(),
+        expiry_block
+    );
+    submit_and_wait(client, sender, note_request).await?;
+
+    Ok(note)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    client.sync_state().await?;
+
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 100).unwrap(),
+            alice_account.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    submit_and_wait(&mut client, faucet.id(), mint_request).await?;
+
+    wait_for_notes(&mut client, alice_account.id(), 1).await?;
+    let consumable_notes = client
+        .get_consumable_notes(Some(alice_account.id()))
+        .await?;
+    let notes = consumable_notes
+        .into_iter()
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    submit_and_wait(
+        &mut client,
+        alice_account.id(),
+        TransactionRequestBuilder::new().build_consume_notes(notes)?,
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Create a long-lived and a short-lived note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Creating a long-lived and a short-lived note");
+
+    let note_file = MasmFile::read("../masm/notes/expiring_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+    let current_block = client.get_sync_height().await?.as_u32();
+
+    let long_lived_note = create_expiring_note(
+        &mut client,
+        note_script.clone(),
+        alice_account.id(),
+        FungibleAsset::new(faucet.id(), 30).unwrap(),
+        current_block + 100,
+    )
+    .await?;
+    let short_lived_note = create_expiring_note(
+        &mut client,
+        note_script,
+        alice_account.id(),
+        FungibleAsset::new(faucet.id(), 30).unwrap(),
+        current_block + 5,
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Consume the long-lived note before it expires
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob consumes the long-lived note before its expiry");
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(long_lived_note, None)])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, bob_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Try to consume the short-lived note after it expired
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Waiting for the short-lived note to expire");
+    let expiry_block = current_block + 5;
+    wait_for_block(&mut client, expiry_block, &WaitOptions::for_blocks(5)).await?;
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(short_lived_note, None)])
+        .build()
+        .unwrap();
+    match client
+        .execute_transaction(bob_account.id(), consume_request)
+        .await
+    {
+        Ok(_) => panic!("expired note should not be consumable"),
+        Err(err) => say!("✅ Expired note rejected: {}", err),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::Note,
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError,
+};
+use rust_client::{
+    accounts::{
+        create_basic_account, create_basic_faucet, get_full_account, token_issuance, top_up,
+    },
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Returns the issuance of `faucet_id` and the balance of `holder` in its token.
+async fn supply(
+    client: &mut Client<FilesystemKeyStore>,
+    faucet_id: AccountId,
+    holder: AccountId,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let issuance = token_issuance(&get_full_account(client, faucet_id).await?)?;
+    let balance = get_full_account(client, holder)
+        .await?
+        .vault()
+        .get_balance(faucet_id)
+        .unwrap_or(0);
+    Ok((issuance, balance))
+}
+
+/// Whether `err` is the kernel refusing to run the faucet's `burn` in an account
+/// that is not that faucet, rather than any other failure.
+fn is_burn_unavailable(err: &ClientError) -> bool {
+    let ClientError::TransactionExecutorError(err) = err else {
+        return false;
+    };
+    // The kernel's assertion only survives as text inside the executor's error
+    let message = format!("{err:?}").to_lowercase();
+    ["not part of the account", "non-faucet", "not a faucet"]
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
+/// Executes the consumption of `note` by `account_id` locally and expects the
+/// burn to be unavailable there.
+async fn expect_rejected(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    note: &Note,
+    attempt: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let request = TransactionRequestBuilder::new()
+        .input_notes([(note.clone(), None)])
+        .build()?;
+    match client.execute_transaction(account_id, request).await {
+        Ok(_) => return Err(format!("{attempt} should have been rejected").into()),
+        Err(err) if is_burn_unavailable(&err) => say!("✅ {attempt} rejected: {}", err),
+        Err(err) => return Err(err.into()),
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Mint tokens to Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Minting 100 tokens to Alice");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    let (issuance, balance) = supply(&mut client, faucet.id(), alice_account.id()).await?;
+    say!("Issuance: {issuance}, Alice's balance: {balance}");
+    assert_eq!((issuance, balance), (100, 100));
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice sends 40 tokens back to the faucet in a burn note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice sends 40 tokens to the faucet in a burn note");
+
+    let note_file = MasmFile::read("../masm/notes/burn_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+    let burn_note = NoteBuilder::new(alice_account.id(), note_script)
+        .asset(FungibleAsset::new(faucet.id(), 40)?)
+        .target(faucet.id())
+        .build(&mut SerialNumbers::random(), client.rng())?;
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(burn_note.clone())])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // The tokens left Alice's vault, but they still count as issued until the
+    // faucet consumes the note
+    let (issuance, balance) = supply(&mut client, faucet.id(), alice_account.id()).await?;
+    say!("Issuance: {issuance}, Alice's balance: {balance}");
+    assert_eq!((issuance, balance), (100, 60));
+
+    // -------------------------------------------------------------------------
+    // STEP 3: The faucet consumes the note and burns the tokens
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] The faucet burns the tokens");
+
+    // Only the faucet has the `burn` procedure the note calls
+    expect_rejected(
+        &mut client,
+        alice_account.id(),
+        &burn_note,
+        "Alice consuming the burn note",
+    )
+    .await?;
+
+    let burn_request = TransactionRequestBuilder::new()
+        .input_notes([(burn_note, None)])
+        .build()?;
+    let report = submit_and_wait(&mut client, faucet.id(), burn_request).await?;
+    assert!(report.is_committed(), "the burn was not committed");
+
+    let (issuance, balance) = supply(&mut client, faucet.id(), alice_account.id()).await?;
+    say!("✅ Issuance: {issuance}, Alice's balance: {balance}");
+    assert_eq!((issuance, balance), (60, 60));
+
+    Ok(())
+}
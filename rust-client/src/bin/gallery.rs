@@ -0,0 +1,108 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::process::ExitCode;
+
+use rust_client::{
+    error::report,
+    gallery::{self, Entry, Kind, ENTRIES, MASM_DIR},
+    say,
+};
+
+/// Lists the MASM contracts, notes and scripts and the tutorials showing them.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// One line per MASM file
+    List {
+        /// Only files of this kind
+        #[arg(long, value_enum)]
+        kind: Option<KindArg>,
+    },
+    /// Exports, storage slots, imports and tutorials of one file
+    Show {
+        /// File name without `.masm`, e.g. `counter`
+        name: String,
+    },
+    /// Files using a module, e.g. `output_note` or `miden::protocol::faucet`
+    Uses { module: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KindArg {
+    Account,
+    Note,
+    Script,
+}
+
+impl From<KindArg> for Kind {
+    fn from(kind: KindArg) -> Self {
+        match kind {
+            KindArg::Account => Kind::Account,
+            KindArg::Note => Kind::Note,
+            KindArg::Script => Kind::Script,
+        }
+    }
+}
+
+fn print_line(entry: &Entry) {
+    say!("{:<8} {:<28} {}", entry.kind(), entry.name(), entry.purpose);
+}
+
+fn main() -> ExitCode {
+    report(run())
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    match Cli::parse().command {
+        Command::List { kind } => {
+            let kind = kind.map(Kind::from);
+            for entry in ENTRIES
+                .iter()
+                .filter(|entry| kind.is_none_or(|kind| entry.kind() == kind))
+            {
+                print_line(entry);
+            }
+        }
+        Command::Show { name } => {
+            let entry =
+                gallery::find(&name).ok_or_else(|| format!("no MASM file named '{name}'"))?;
+            let details = entry.details(MASM_DIR)?;
+            say!("{} ({}): {}", entry.name(), entry.kind(), entry.purpose);
+            say!("  file:      masm/{}", entry.path);
+            say!("  exports:   {}", list(&details.exports));
+            say!("  slots:     {}", list(&details.slots));
+            say!("  imports:   {}", list(&details.imports));
+            let tutorials: Vec<String> = entry
+                .tutorials
+                .iter()
+                .map(|tutorial| format!("cargo run --bin {tutorial}"))
+                .collect();
+            say!("  tutorials: {}", list(&tutorials));
+        }
+        Command::Uses { module } => {
+            let mut found = false;
+            for entry in ENTRIES {
+                if entry.details(MASM_DIR)?.uses(&module) {
+                    print_line(entry);
+                    found = true;
+                }
+            }
+            if !found {
+                say!("No MASM file uses '{module}'");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn list(items: &[String]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
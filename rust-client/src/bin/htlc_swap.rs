@@ -0,0 +1,239 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    crypto::FeltRng,
+    keystore::FilesystemKeyStore,
+    note::{
+        Note, NoteAssets, NoteInputs, NoteMetadata, NoteRecipient, NoteScript, NoteTag, NoteType,
+    },
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, Felt, Word,
+};
+use miden_protocol::Hasher;
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    say,
+    wait::{submit_and_wait, wait_for_block, WaitOptions},
+};
+
+/// Blocks the recipient has to claim the lock that goes through. In a
+/// cross-chain swap the initiator's lock must outlive the counterparty's, so
+/// the counterparty still has time to claim after the secret is revealed.
+const CLAIM_WINDOW: u32 = 100;
+
+/// Blocks until the lock nobody claims can be refunded.
+const REFUND_WINDOW: u32 = 5;
+
+/// Locks `asset` from `sender` for `recipient` behind `digest` until `deadline`.
+async fn lock(
+    client: &mut Client<FilesystemKeyStore>,
+    note_script: NoteScript,
+    sender: AccountId,
+    recipient: AccountId,
+    asset: FungibleAsset,
+    digest: Word,
+    deadline: u32,
+) -> Result<Note, Box<dyn std::error::Error>> {
+    let mut inputs = digest.to_vec();
+    inputs.extend([
+        recipient.prefix().as_felt(),
+        recipient.suffix(),
+        Felt::new(deadline as u64),
+    ]);
+    let note_recipient = NoteRecipient::new(
+        client.rng().draw_word(),
+        note_script,
+        NoteInputs::new(inputs)?,
+    );
+    let metadata = NoteMetadata::new(sender, NoteType::Public, NoteTag::new(0));
+    let note = Note::new(
+        NoteAssets::new(vec![asset.into()])?,
+        metadata,
+        note_recipient,
+    );
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note.clone())])
+        .build()?;
+    say!(
+        "Locking {} tokens in note {} until block {}",
+        asset.amount(),
+        note.id().to_hex(),
+        deadline
+    );
+    submit_and_wait(client, sender, note_request).await?;
+
+    Ok(note)
+}
+
+/// Executes the consumption of `note` by `account_id` locally and expects it to fail.
+async fn expect_rejected(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    note: &Note,
+    preimage: Option<Word>,
+    attempt: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let request = TransactionRequestBuilder::new()
+        .input_notes([(note.clone(), preimage)])
+        .build()?;
+    match client.execute_transaction(account_id, request).await {
+        Ok(_) => panic!("{attempt} should have been rejected"),
+        Err(err) => say!("✅ {attempt} rejected: {}", err),
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice picks the secret and locks tokens for Bob behind its hash
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice locks 40 tokens for Bob behind the hash of a secret");
+
+    // Only Alice knows the secret. Bob is given the digest and locks the other
+    // side of the swap on the other chain behind the very same digest.
+    let secret = client.rng().draw_word();
+    let digest = Hasher::hash_elements(secret.as_elements());
+    say!("digest: {}", digest.to_hex());
+
+    let note_file = MasmFile::read("../masm/notes/htlc_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+    let current_block = client.get_sync_height().await?.as_u32();
+
+    let swap_note = lock(
+        &mut client,
+        note_script.clone(),
+        alice_account.id(),
+        bob_account.id(),
+        FungibleAsset::new(faucet.id(), 40)?,
+        digest,
+        current_block + CLAIM_WINDOW,
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Bob claims with the secret before the deadline
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob claims the tokens with the secret");
+
+    let guess = client.rng().draw_word();
+    expect_rejected(
+        &mut client,
+        bob_account.id(),
+        &swap_note,
+        Some(guess),
+        "Bob's claim with a guessed secret",
+    )
+    .await?;
+    expect_rejected(
+        &mut client,
+        alice_account.id(),
+        &swap_note,
+        None,
+        "Alice's refund before the deadline",
+    )
+    .await?;
+
+    // Alice claimed Bob's lock on the other chain, which published the secret
+    // there. Bob reads it from that chain and claims here.
+    let claim_request = TransactionRequestBuilder::new()
+        .input_notes([(swap_note, Some(secret))])
+        .build()?;
+    submit_and_wait(&mut client, bob_account.id(), claim_request).await?;
+    say!("Bob claimed the tokens");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: A lock nobody claims goes back to Alice after the deadline
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice locks 30 more tokens, which Bob never claims");
+
+    // The first secret is public now, so every swap needs a fresh one
+    let unused_secret = client.rng().draw_word();
+    let digest = Hasher::hash_elements(unused_secret.as_elements());
+    let current_block = client.get_sync_height().await?.as_u32();
+    let refund_deadline = current_block + REFUND_WINDOW;
+    let refund_note = lock(
+        &mut client,
+        note_script,
+        alice_account.id(),
+        bob_account.id(),
+        FungibleAsset::new(faucet.id(), 30)?,
+        digest,
+        refund_deadline,
+    )
+    .await?;
+
+    wait_for_block(
+        &mut client,
+        refund_deadline,
+        &WaitOptions::for_blocks(REFUND_WINDOW),
+    )
+    .await?;
+
+    // Past the deadline not even the right secret lets Bob claim
+    expect_rejected(
+        &mut client,
+        bob_account.id(),
+        &refund_note,
+        Some(unused_secret),
+        "Bob's claim after the deadline",
+    )
+    .await?;
+
+    let refund_request = TransactionRequestBuilder::new()
+        .input_notes([(refund_note, None)])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), refund_request).await?;
+    say!("Alice took the tokens back");
+
+    let alice_balance = get_full_account(&mut client, alice_account.id())
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    let bob_balance = get_full_account(&mut client, bob_account.id())
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    say!("Alice holds {alice_balance}, Bob holds {bob_balance}");
+    assert_eq!(alice_balance, 60);
+    assert_eq!(bob_balance, 40);
+
+    Ok(())
+}
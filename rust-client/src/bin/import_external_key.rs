@@ -0,0 +1,101 @@
+use clap::Parser;
+use rand::RngCore;
+use std::{path::PathBuf, process::ExitCode};
+
+use miden_client::{
+    account::{component::BasicWallet, AccountBuilder, AccountStorageMode, AccountType},
+    auth::AuthFalcon512Rpo,
+    transaction::TransactionRequestBuilder,
+};
+use rust_client::{
+    address_book::AddressBook,
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    keys::KeyFile,
+    say,
+    wait::submit_and_wait,
+};
+
+/// Imports a key written by `offline_keygen`, creates a wallet controlled by it and
+/// sends a transaction signed with it.
+#[derive(Parser)]
+struct Cli {
+    /// Key file written by `offline_keygen`
+    key_file: PathBuf,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let path = cli.key_file;
+
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Load and verify the key file
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Loading the key file from {}", path.display());
+
+    let key_file = KeyFile::load(&path)?;
+    let secret_key = key_file.secret_key()?;
+    say!("Public key commitment: {}", key_file.public_key_commitment);
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Import the key before creating the account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Importing the key into the keystore");
+
+    // The key has to be in the keystore before the first transaction needs a signature
+    keystore.add_key(&secret_key).unwrap();
+
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let account = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthFalcon512Rpo::new(
+            secret_key.public_key().to_commitment(),
+        ))
+        .with_component(BasicWallet)
+        .build()
+        .unwrap();
+    client.add_account(&account, false).await?;
+    AddressBook::open_default()?
+        .record_keys(account.id(), secret_key.public_key().to_commitment())?;
+    say!("Account ID: {}", account.id().to_bech32(network_id()));
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Sign a transaction with the imported key
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Sending a transaction signed with the imported key");
+
+    // The script does nothing, but bumping the nonce requires a valid signature
+    let tx_script = client
+        .code_builder()
+        .compile_tx_script("begin push.1 drop end")
+        .unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()
+        .unwrap();
+    let report = submit_and_wait(&mut client, account.id(), tx_request).await?;
+    assert!(
+        report.is_committed(),
+        "signed transaction was not committed"
+    );
+    say!("✅ The imported key authorized the transaction");
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId, asset::FungibleAsset, keystore::FilesystemKeyStore, note::Note,
+    transaction::TransactionRequestBuilder, Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    output::{emit, Event},
+    pool::{quote, Pool, PoolState},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Has `user` consume a payout note from the pool.
+async fn claim(
+    client: &mut Client<FilesystemKeyStore>,
+    user: AccountId,
+    payout: Note,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payout, None)])
+        .build()?;
+    submit_and_wait(client, user, consume_request).await?;
+    Ok(())
+}
+
+/// Checks that the pool's vault holds exactly its reserves and reports them.
+async fn check_reserves(
+    client: &mut Client<FilesystemKeyStore>,
+    pool: &Pool,
+) -> Result<PoolState, Box<dyn std::error::Error>> {
+    let state = pool.state(client).await?;
+    let vault = get_full_account(client, pool.account_id).await?;
+    let balance_a = vault.vault().get_balance(pool.token_a).unwrap_or(0);
+    let balance_b = vault.vault().get_balance(pool.token_b).unwrap_or(0);
+    assert_eq!(balance_a, state.reserve_a, "vault and reserve of A differ");
+    assert_eq!(balance_b, state.reserve_b, "vault and reserve of B differ");
+
+    say!(
+        "Reserves: {} A / {} B, {} shares, k = {}",
+        state.reserve_a,
+        state.reserve_b,
+        state.total_shares,
+        state.k()
+    );
+    emit(Event::balance(pool.account_id, pool.token_a, balance_a));
+    emit(Event::balance(pool.account_id, pool.token_b, balance_b));
+    Ok(state)
+}
+
+/// Returns the balances of `account_id` in both pool tokens.
+async fn balances(
+    client: &mut Client<FilesystemKeyStore>,
+    pool: &Pool,
+    account_id: AccountId,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let account = get_full_account(client, account_id).await?;
+    Ok((
+        account.vault().get_balance(pool.token_a).unwrap_or(0),
+        account.vault().get_balance(pool.token_b).unwrap_or(0),
+    ))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the two tokens, the accounts and the pool
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating tokens A and B, Alice, Bob and the pool");
+
+    let token_a = create_basic_faucet(&mut client, &keystore).await?;
+    say!("Token A faucet: {:?}", token_a.id().to_bech32(network_id()));
+    let token_b = create_basic_faucet(&mut client, &keystore).await?;
+    say!("Token B faucet: {:?}", token_b.id().to_bech32(network_id()));
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    let pool = Pool::deploy(&mut client, token_a.id(), token_b.id()).await?;
+    say!(
+        "Pool contract ID: {:?}",
+        pool.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fund Alice with both tokens and Bob with token A
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting tokens for Alice and Bob");
+
+    top_up(&mut client, token_a.id(), alice_account.id(), 10_000).await?;
+    top_up(&mut client, token_b.id(), alice_account.id(), 20_000).await?;
+    top_up(&mut client, token_a.id(), bob_account.id(), 1_000).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice provides liquidity
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice deposits 10000 A and 20000 B, setting the price to 2 B per A");
+
+    let report = pool
+        .add_liquidity(&mut client, alice_account.id(), 10_000, 20_000)
+        .await?;
+    assert!(report.is_committed(), "deposit was not committed");
+
+    let provided = check_reserves(&mut client, &pool).await?;
+    let alice_shares = pool.shares_of(&mut client, alice_account.id()).await?;
+    say!("✅ Alice holds {} shares", alice_shares);
+    assert_eq!(
+        alice_shares, provided.total_shares,
+        "Alice should own the pool"
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Bob swaps A for B
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Bob swaps 1000 A for B");
+
+    // Quoting from the synced reserves gives the exact payout. Passing it as the
+    // minimum makes the swap fail instead of paying less if the pool moved.
+    let expected_out = quote(1_000, provided.reserve_a, provided.reserve_b);
+    say!("Quoted output: {} B", expected_out);
+
+    let asset_in = FungibleAsset::new(pool.token_a, 1_000)?;
+    let (report, payout) = pool
+        .swap(&mut client, bob_account.id(), asset_in, expected_out)
+        .await?;
+    assert!(report.is_committed(), "swap was not committed");
+    claim(&mut client, bob_account.id(), payout).await?;
+
+    let (_, bob_b) = balances(&mut client, &pool, bob_account.id()).await?;
+    say!("✅ Bob received {} B", bob_b);
+    assert_eq!(bob_b, expected_out, "Bob should receive the quoted output");
+
+    let swapped = check_reserves(&mut client, &pool).await?;
+    assert!(swapped.k() >= provided.k(), "the swap decreased k");
+    assert_eq!(swapped.total_shares, provided.total_shares);
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Alice withdraws all her liquidity
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Alice burns her {} shares", alice_shares);
+
+    let (expected_a, expected_b) = swapped.withdrawal(alice_shares);
+    let (report, payout) = pool
+        .remove_liquidity(&mut client, alice_account.id(), alice_shares)
+        .await?;
+    assert!(report.is_committed(), "withdrawal was not committed");
+    claim(&mut client, alice_account.id(), payout).await?;
+
+    let (alice_a, alice_b) = balances(&mut client, &pool, alice_account.id()).await?;
+    say!("✅ Alice received {} A and {} B", alice_a, alice_b);
+    assert_eq!((alice_a, alice_b), (expected_a, expected_b));
+    // Bob's trade sold Alice's B for his A, and the pool charges no fee
+    assert_eq!(alice_a, 11_000, "Alice should get Bob's A on top of hers");
+    assert_eq!(alice_b + bob_b, 20_000, "no B should be created or lost");
+    emit(Event::balance(alice_account.id(), pool.token_a, alice_a));
+    emit(Event::balance(alice_account.id(), pool.token_b, alice_b));
+
+    let drained = check_reserves(&mut client, &pool).await?;
+    assert_eq!(drained, PoolState::default(), "the pool should be empty");
+
+    Ok(())
+}
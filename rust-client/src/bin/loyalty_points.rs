@@ -0,0 +1,194 @@
+use std::process::ExitCode;
+
+use miden_client::asset::FungibleAsset;
+use rust_client::{
+    accounts::{balance, create_basic_account, create_basic_faucet, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    loyalty::LoyaltyProgram,
+    say,
+    wait::{wait_for_block, WaitOptions},
+};
+
+/// Blocks until the points of the short season expire.
+const SHORT_SEASON: u32 = 10;
+
+/// Blocks until the points of the long season expire, well past the end of the run.
+const LONG_SEASON: u32 = 1_000;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the issuer, the customer, the shop and the program
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice the issuer, Bob the customer, a shop and the loyalty program");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let shop_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Shop's account ID: {:?}",
+        shop_account.id().to_bech32(network_id())
+    );
+
+    let program = LoyaltyProgram::deploy(&mut client, alice_account.id()).await?;
+    say!(
+        "Loyalty program ID: {:?}",
+        program.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Two seasons of points, each from a faucet of its own
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Registering a long and a short season of points");
+
+    // The expiry is keyed by faucet, so the points in an asset always carry
+    // their season with them, even after they have been mixed in a wallet
+    let long_faucet = create_basic_faucet(&mut client, &keystore).await?;
+    let short_faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, long_faucet.id(), alice_account.id(), 100).await?;
+    top_up(&mut client, short_faucet.id(), alice_account.id(), 100).await?;
+
+    let height = client.sync_state().await?.block_num.as_u32();
+    for (faucet_id, season) in [
+        (long_faucet.id(), LONG_SEASON),
+        (short_faucet.id(), SHORT_SEASON),
+    ] {
+        program
+            .register(&mut client, alice_account.id(), faucet_id, height + season)
+            .await?;
+        let expiry = program
+            .expiry(&mut client, faucet_id)
+            .await?
+            .expect("the points were just registered");
+        say!("Points of {faucet_id} expire at block {expiry}");
+    }
+
+    // Only the issuer decides when points expire
+    match program
+        .register(&mut client, bob_account.id(), long_faucet.id(), u32::MAX)
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("the program accepted a customer's expiry"),
+        Ok(_) => say!("Bob's registration discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice issues points of both seasons to Bob
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice issues 50 long-season and 30 short-season points to Bob");
+
+    let long_points = program
+        .issue(
+            &mut client,
+            FungibleAsset::new(long_faucet.id(), 50)?,
+            bob_account.id(),
+        )
+        .await?;
+    let short_points = program
+        .issue(
+            &mut client,
+            FungibleAsset::new(short_faucet.id(), 30)?,
+            bob_account.id(),
+        )
+        .await?;
+
+    // Unexpired points belong to the holder
+    match program
+        .consume(&mut client, alice_account.id(), long_points.clone())
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("the issuer reclaimed unexpired points"),
+        Ok(_) => say!("Alice's early reclaim discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Bob claims the long-season points and spends some at the shop
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Bob claims the long-season points and redeems 20 at the shop");
+
+    program
+        .consume(&mut client, bob_account.id(), long_points)
+        .await?;
+    let redemption = program
+        .redeem(
+            &mut client,
+            bob_account.id(),
+            FungibleAsset::new(long_faucet.id(), 20)?,
+            shop_account.id(),
+        )
+        .await?;
+    let report = program
+        .consume(&mut client, shop_account.id(), redemption)
+        .await?;
+    assert!(report.is_committed(), "the redemption was not committed");
+
+    let bob_points = balance(&mut client, bob_account.id(), long_faucet.id()).await?;
+    let shop_points = balance(&mut client, shop_account.id(), long_faucet.id()).await?;
+    say!("✅ Bob holds {bob_points} points, the shop {shop_points}");
+    assert_eq!(bob_points, 30, "Bob should keep the unredeemed points");
+    assert_eq!(
+        shop_points, 20,
+        "the shop should receive the redeemed points"
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 5: The short season runs out before Bob claims it
+    // -------------------------------------------------------------------------
+    let expiry = program
+        .expiry(&mut client, short_faucet.id())
+        .await?
+        .expect("the short season was registered");
+    say!("\n[STEP 5] Waiting for the short-season points to expire at block {expiry}");
+
+    wait_for_block(&mut client, expiry, &WaitOptions::for_blocks(SHORT_SEASON)).await?;
+
+    match program
+        .consume(&mut client, bob_account.id(), short_points.clone())
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("Bob claimed expired points"),
+        Ok(_) => say!("Bob's late claim discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 6: Alice reclaims the expired points
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Alice reclaims the expired short-season points");
+
+    let report = program
+        .consume(&mut client, alice_account.id(), short_points)
+        .await?;
+    assert!(report.is_committed(), "the reclaim was not committed");
+
+    let alice_points = balance(&mut client, alice_account.id(), short_faucet.id()).await?;
+    say!("✅ Alice holds {alice_points} short-season points again");
+    assert_eq!(
+        alice_points, 100,
+        "Alice should get back every expired point"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use clap::Parser;
+use std::process::ExitCode;
+
+use tokio::time::{Duration, Instant};
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionRequest, TransactionRequestBuilder},
+    Client, ClientError,
+};
+use miden_protocol::MAX_OUTPUT_NOTES_PER_TX;
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, top_up},
+    client::{network_id, ClientArgs, ClientSetup},
+    cycles::{execute_with_cycles, CycleCount},
+    error::{report, ExitGuard},
+    middleware::submit_proven_logged,
+    say,
+    wait::wait_for_tx,
+};
+
+/// Pays Bob in many P2ID notes from one transaction, checks the output note
+/// limit, and compares the cost per note with one transaction per note.
+#[derive(Parser)]
+struct Cli {
+    /// Notes created by the single transaction, and separate transactions sent
+    /// to compare with
+    #[arg(
+        long,
+        default_value_t = 50,
+        value_parser = clap::value_parser!(u16).range(2..=MAX_OUTPUT_NOTES_PER_TX as i64)
+    )]
+    notes: u16,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// Tokens every note carries.
+const AMOUNT: u64 = 1;
+
+/// What proving and executing one transaction cost.
+struct TxCost {
+    cycles: CycleCount,
+    prove: Duration,
+}
+
+/// A request creating `count` P2ID notes of [`AMOUNT`] tokens from `sender` to
+/// `recipient`.
+fn p2id_request(
+    client: &mut Client<FilesystemKeyStore>,
+    sender: AccountId,
+    recipient: AccountId,
+    faucet: AccountId,
+    count: usize,
+) -> Result<TransactionRequest, Box<dyn std::error::Error>> {
+    let mut notes = Vec::with_capacity(count);
+    for _ in 0..count {
+        let note = create_p2id_note(
+            sender,
+            recipient,
+            vec![FungibleAsset::new(faucet, AMOUNT)?.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+        notes.push(OutputNote::Full(note));
+    }
+    Ok(TransactionRequestBuilder::new()
+        .own_output_notes(notes)
+        .build()?)
+}
+
+/// Executes, proves and submits `request`, waits for it and returns its cost.
+async fn send_measured(
+    client: &mut Client<FilesystemKeyStore>,
+    sender: AccountId,
+    request: TransactionRequest,
+    label: &str,
+) -> Result<TxCost, Box<dyn std::error::Error>> {
+    let (tx_result, cycles) = execute_with_cycles(client, sender, request, label).await?;
+    let start = Instant::now();
+    let proven_transaction = client.prove_transaction(&tx_result).await?;
+    let prove = start.elapsed();
+    let tx_id = proven_transaction.id();
+    let submission_height = submit_proven_logged(client, proven_transaction, &tx_result).await?;
+    client
+        .apply_transaction(&tx_result, submission_height)
+        .await?;
+    wait_for_tx(client, tx_id).await?;
+    Ok(TxCost { cycles, prove })
+}
+
+/// Whether the transaction kernel refused `err` for creating more notes than
+/// [`MAX_OUTPUT_NOTES_PER_TX`], rather than for anything else.
+fn is_output_note_limit(err: &ClientError) -> bool {
+    let ClientError::TransactionExecutorError(err) = err else {
+        return false;
+    };
+    // The kernel's assertion only survives as text inside the executor's error
+    let message = format!("{err:?}").to_lowercase();
+    ["number of output notes", "toomanyoutputnotes"]
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let notes = usize::from(cli.notes);
+
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+
+    // Enough for the over-limit attempt too, so it fails on the note count
+    // rather than on Alice's balance
+    let needed = (MAX_OUTPUT_NOTES_PER_TX + 1 + 2 * notes) as u64 * AMOUNT;
+    top_up(&mut client, faucet.id(), alice_account.id(), needed).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Go over the output note limit
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 2] Creating {} notes, one over the limit of {}",
+        MAX_OUTPUT_NOTES_PER_TX + 1,
+        MAX_OUTPUT_NOTES_PER_TX
+    );
+
+    // Executing is enough to hit the limit, so nothing reaches the network
+    let request = p2id_request(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        faucet.id(),
+        MAX_OUTPUT_NOTES_PER_TX + 1,
+    )?;
+    match client
+        .execute_transaction(alice_account.id(), request)
+        .await
+    {
+        Ok(_) => return Err("a transaction over the output note limit was accepted".into()),
+        Err(err) if is_output_note_limit(&err) => say!("✅ Rejected as expected: {err}"),
+        Err(err) => return Err(err.into()),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Pay Bob in many notes from one transaction
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Sending {notes} notes in one transaction");
+
+    let request = p2id_request(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        faucet.id(),
+        notes,
+    )?;
+    let batch = send_measured(
+        &mut client,
+        alice_account.id(),
+        request,
+        &format!("{notes} notes"),
+    )
+    .await?;
+    say!("✅ One transaction created {notes} notes");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Pay Bob the same notes in separate transactions
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Sending {notes} notes in {notes} transactions");
+
+    let mut single = Vec::with_capacity(notes);
+    for index in 0..notes {
+        let request = p2id_request(
+            &mut client,
+            alice_account.id(),
+            bob_account.id(),
+            faucet.id(),
+            1,
+        )?;
+        let label = format!("note {}", index + 1);
+        single.push(send_measured(&mut client, alice_account.id(), request, &label).await?);
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Compare the cost per note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Comparing the cost per note\n");
+
+    let single_cycles = single.iter().map(|cost| cost.cycles.total).sum::<usize>();
+    let single_prove = single.iter().map(|cost| cost.prove).sum::<Duration>();
+    let one_note = &single[0];
+
+    say!(
+        "{:<24} {:>12} {:>12} {:>16}",
+        "",
+        "cycles",
+        "prove time",
+        "cycles per note"
+    );
+    say!(
+        "{:<24} {:>12} {:>12} {:>16}",
+        format!("1 transaction, {notes} notes"),
+        batch.cycles.total,
+        format!("{:.2?}", batch.prove),
+        batch.cycles.total / notes
+    );
+    say!(
+        "{:<24} {:>12} {:>12} {:>16}",
+        format!("{notes} transactions"),
+        single_cycles,
+        format!("{:.2?}", single_prove),
+        single_cycles / notes
+    );
+
+    // Every transaction pays the prologue, epilogue and auth once, so the
+    // marginal note only adds the cycles of building and storing one note
+    let marginal = batch.cycles.total.saturating_sub(one_note.cycles.total) / (notes - 1);
+    say!("\nMarginal cost of one more note: {marginal} cycles");
+    say!(
+        "A transaction of its own costs {} cycles, {:.1}x that",
+        one_note.cycles.total,
+        one_note.cycles.total as f64 / marginal.max(1) as f64
+    );
+    say!(
+        "Proving: {:.2?} for the batch against {:.2?} for the separate transactions",
+        batch.prove,
+        single_prove
+    );
+
+    Ok(())
+}
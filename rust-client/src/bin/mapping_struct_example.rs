@@ -0,0 +1,198 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::{
+        AccountId, AccountStorageMode, AccountType, StorageMap, StorageSlot, StorageSlotName,
+    },
+    transaction::TransactionRequestBuilder,
+    Felt, Word,
+};
+use miden_protocol::account::AccountIdVersion;
+use rand::RngCore;
+use rust_client::{
+    accounts::get_full_account,
+    client::{init_client_from_config, network_id},
+    deployer::ContractDeployer,
+    error::{report, ExitGuard},
+    masm::{word_to_masm, MasmFile},
+    output::{emit, Event},
+    say,
+    storage::print_account_storage,
+    wait::submit_and_wait,
+};
+
+/// A multi-field record stored as a single map value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Record {
+    owner_id: AccountId,
+    amount: u64,
+    deadline: u32,
+}
+
+/// Packs a record into a map value laid out as
+/// `[owner_id_prefix, owner_id_suffix, amount, deadline]`.
+fn encode_record(record: &Record) -> Word {
+    [
+        record.owner_id.prefix().as_felt(),
+        record.owner_id.suffix(),
+        Felt::new(record.amount),
+        Felt::new(record.deadline as u64),
+    ]
+    .into()
+}
+
+/// Unpacks a map value written by [`encode_record`].
+fn decode_record(value: Word) -> Result<Record, Box<dyn std::error::Error>> {
+    let owner_id = AccountId::try_from([value[0], value[1]])?;
+    let deadline = u32::try_from(value[3].as_int())?;
+    Ok(Record {
+        owner_id,
+        amount: value[2].as_int(),
+        deadline,
+    })
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, _keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Deploy a contract storing records in a map
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Deploy a contract storing records in a map");
+
+    let records_slot_name =
+        StorageSlotName::new("miden::tutorials::records").expect("valid slot name");
+    let record_map_deployer = ContractDeployer::from_path(
+        "../masm/accounts/record_map.masm",
+        "miden_by_example::record_map_contract",
+    )
+    .with_slot(StorageSlot::with_map(
+        records_slot_name.clone(),
+        StorageMap::new(),
+    ));
+    let account_component_lib = record_map_deployer.library()?;
+    let record_map_contract = record_map_deployer.deploy(&mut client).await?;
+    say!(
+        "Record map contract id: {}",
+        record_map_contract.id().to_bech32(network_id())
+    );
+    print_account_storage(&record_map_contract);
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Encode a record and write it through the contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Encode a record and write it through the contract");
+
+    let mut owner_seed = [0_u8; 15];
+    client.rng().fill_bytes(&mut owner_seed);
+    let owner_id = AccountId::dummy(
+        owner_seed,
+        AccountIdVersion::Version0,
+        AccountType::RegularAccountUpdatableCode,
+        AccountStorageMode::Public,
+    );
+
+    let record = Record {
+        owner_id,
+        amount: 1_500,
+        deadline: sync_summary.block_num.as_u32() + 1_000,
+    };
+    let key: Word = [Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(1)].into();
+    let encoded = encode_record(&record);
+    say!("Record: {:?}", record);
+    say!("Encoded value: {:?}", encoded);
+
+    let script_template = MasmFile::read("../masm/scripts/record_map_script.masm");
+
+    let script_code = script_template
+        .source
+        .replace("{record}", &word_to_masm(encoded))
+        .replace("{key}", &word_to_masm(key));
+    let script_file = script_template.with_source(script_code);
+    let tx_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&account_component_lib)
+            .unwrap()
+            .compile_tx_script(&script_file.source),
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()
+        .unwrap();
+
+    submit_and_wait(&mut client, record_map_contract.id(), tx_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Show that the contract rejects an invalid record
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Try to write a record with a zero amount");
+
+    let invalid_record = Record {
+        amount: 0,
+        ..record
+    };
+    let invalid_key: Word = [Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(2)].into();
+    let script_code = script_template
+        .source
+        .replace("{record}", &word_to_masm(encode_record(&invalid_record)))
+        .replace("{key}", &word_to_masm(invalid_key));
+    let script_file = script_template.with_source(script_code);
+    let tx_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&account_component_lib)
+            .unwrap()
+            .compile_tx_script(&script_file.source),
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()
+        .unwrap();
+
+    // Executing locally is enough to see the assertion fail
+    match client
+        .execute_transaction(record_map_contract.id(), tx_request)
+        .await
+    {
+        Ok(_) => panic!("record with a zero amount should be rejected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Read the record back and decode it
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Read the record back and decode it");
+
+    client.sync_state().await?;
+
+    let account = get_full_account(&mut client, record_map_contract.id()).await?;
+    let stored = account
+        .storage()
+        .get_map_item(&records_slot_name, key)
+        .unwrap();
+    let decoded = decode_record(stored).unwrap();
+    say!("Stored value: {:?}", stored);
+    emit(Event::map_entry(
+        account.id(),
+        &records_slot_name,
+        key,
+        stored,
+    ));
+    say!("Decoded record: {:?}", decoded);
+    assert_eq!(decoded, record, "decoded record does not match");
+
+    Ok(())
+}
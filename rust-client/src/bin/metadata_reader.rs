@@ -0,0 +1,57 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use miden_client::{account::AccountId, ClientError};
+use rust_client::{
+    accounts::get_full_account,
+    client::{network_id, parse_account_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    metadata::ContractMetadata,
+    say,
+};
+
+/// Imports a public account and prints the metadata stored by the metadata component.
+#[derive(Parser)]
+struct Cli {
+    /// Bech32 ID of the account to read
+    #[arg(value_parser = parse_account_id)]
+    account_id: AccountId,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), ClientError> {
+    let cli = Cli::parse();
+    let account_id = cli.account_id;
+
+    // Initialize client
+    let (mut client, _keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+    client.sync_state().await?;
+
+    if client.get_account(account_id).await?.is_none() {
+        client.import_account_by_id(account_id).await?;
+    }
+    let account = get_full_account(&mut client, account_id).await?;
+
+    match ContractMetadata::from_account(&account) {
+        Ok(metadata) => {
+            say!("account:   {}", account_id.to_bech32(network_id()));
+            say!("name:      {}", metadata.name);
+            say!("icon hash: {}", metadata.icon_hash.to_hex());
+            say!("url:       {}", metadata.url);
+        }
+        Err(err) => say!(
+            "{} does not carry the metadata component: {}",
+            account_id.to_bech32(network_id()),
+            err
+        ),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use std::process::ExitCode;
+
+use tokio::time::{sleep, Duration};
+
+use miden_client::{
+    account::{AccountStorageMode, StorageSlot, StorageSlotName},
+    asset::FungibleAsset,
+    crypto::FeltRng,
+    note::{
+        create_p2id_note, NetworkAccountTarget, Note, NoteAssets, NoteAttachment, NoteError,
+        NoteExecutionHint, NoteInputs, NoteMetadata, NoteRecipient, NoteTag, NoteType,
+    },
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Felt, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    client::{init_client_from_config, network_id},
+    deployer::ContractDeployer,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    output::{emit, Event},
+    say,
+    storage::print_account_storage,
+    wait::{submit_and_wait, wait_for_notes},
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+    client.sync_state().await?;
+
+    let mint_amount = FungibleAsset::new(faucet.id(), 100).unwrap();
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            mint_amount,
+            alice_account.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    submit_and_wait(&mut client, faucet.id(), mint_request).await?;
+
+    wait_for_notes(&mut client, alice_account.id(), 1).await?;
+    let consumable_notes = client
+        .get_consumable_notes(Some(alice_account.id()))
+        .await?;
+    let notes = consumable_notes
+        .into_iter()
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+    submit_and_wait(&mut client, alice_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Deploy the network counter contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Deploying the network counter contract");
+
+    let counter_slot_name =
+        StorageSlotName::new("miden::tutorials::counter").expect("valid slot name");
+    let counter_deployer = ContractDeployer::from_path(
+        "../masm/accounts/counter.masm",
+        "external_contract::counter_contract",
+    )
+    .with_slot(StorageSlot::with_value(
+        counter_slot_name.clone(),
+        Word::default(),
+    ))
+    .storage_mode(AccountStorageMode::Network);
+    let library = counter_deployer.library()?;
+    let counter_contract = counter_deployer.deploy(&mut client).await?;
+    say!(
+        "contract id: {:?}",
+        counter_contract.id().to_bech32(network_id())
+    );
+    print_account_storage(&counter_contract);
+
+    // Incrementing once deploys the contract on-chain
+    let script_file = MasmFile::read("../masm/scripts/counter_script.masm");
+    let tx_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&library)?
+            .compile_tx_script(&script_file.source),
+    )?;
+    let deploy_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()?;
+    submit_and_wait(&mut client, counter_contract.id(), deploy_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Emit a network note and a P2ID note in one transaction
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Emitting a network note and a P2ID note in one transaction");
+
+    // Network note: consumed by the network transaction builder
+    let network_note_file = MasmFile::read("../masm/notes/network_increment_note.masm");
+    let note_script = network_note_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&library)?
+            .compile_note_script(&network_note_file.source),
+    )?;
+    let recipient = NoteRecipient::new(
+        client.rng().draw_word(),
+        note_script,
+        NoteInputs::new(vec![])?,
+    );
+    let attachment = NetworkAccountTarget::new(counter_contract.id(), NoteExecutionHint::Always)
+        .map_err(|e| NoteError::other(e.to_string()))?
+        .into();
+    let metadata = NoteMetadata::new(
+        alice_account.id(),
+        NoteType::Public,
+        NoteTag::with_account_target(counter_contract.id()),
+    )
+    .with_attachment(attachment);
+    let network_note = Note::new(NoteAssets::default(), metadata, recipient);
+
+    // User note: a regular P2ID note consumed by Bob
+    let p2id_note = create_p2id_note(
+        alice_account.id(),
+        bob_account.id(),
+        vec![FungibleAsset::new(faucet.id(), 25).unwrap().into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![
+            OutputNote::Full(network_note.clone()),
+            OutputNote::Full(p2id_note.clone()),
+        ])
+        .build()?;
+    say!("Network note: {}", network_note.id().to_hex());
+    say!("P2ID note:    {}", p2id_note.id().to_hex());
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Track both outcomes
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Tracking both outcomes");
+
+    // The user note needs Bob's client to act
+    wait_for_notes(&mut client, bob_account.id(), 1).await?;
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(vec![p2id_note])?;
+    submit_and_wait(&mut client, bob_account.id(), consume_request).await?;
+    let bob = get_full_account(&mut client, bob_account.id()).await?;
+    let balance = bob.vault().get_balance(faucet.id())?;
+    say!("✅ P2ID outcome: Bob's balance is {}", balance);
+    emit(Event::balance(bob.id(), faucet.id(), balance));
+
+    // The network note is picked up by the network transaction builder on its own
+    let mut counter_value = 0;
+    for _ in 0..10 {
+        client.sync_state().await?;
+        let counter = get_full_account(&mut client, counter_contract.id()).await?;
+        let count: Word = counter.storage().get_item(&counter_slot_name)?;
+        counter_value = count.get(3).map(Felt::as_int).unwrap_or_default();
+        if counter_value >= 2 {
+            break;
+        }
+        say!("Network note not consumed yet (counter = {counter_value}). Waiting...");
+        sleep(Duration::from_secs(6)).await;
+    }
+
+    if counter_value >= 2 {
+        say!("✅ Network outcome: counter incremented to {counter_value}");
+    } else {
+        say!("Network note still pending (last counter value: {counter_value})");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    note::{create_p2id_note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+};
+use rust_client::{
+    accounts::{balance, create_basic_faucet, top_up},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    notes::{consume_all, ConsumeOptions},
+    profiles::{Profile, ProfileManager},
+    say,
+    wait::{submit_and_wait, wait_for_notes, wait_for_tx},
+};
+
+/// Sends `amount` tokens from the profile's wallet to `target` in a P2ID note.
+async fn pay(
+    profile: &mut Profile,
+    faucet_id: AccountId,
+    target: AccountId,
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let wallet = profile.wallet().await?;
+    let note = create_p2id_note(
+        wallet,
+        target,
+        vec![FungibleAsset::new(faucet_id, amount)?.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        profile.client.rng(),
+    )?;
+    let request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note)])
+        .build()?;
+    submit_and_wait(&mut profile.client, wallet, request).await?;
+    say!(
+        "{} sent {amount} tokens to {}",
+        profile.name(),
+        target.to_bech32(network_id())
+    );
+    Ok(())
+}
+
+/// Waits for `expected` notes and consumes the ones carrying the faucet's token.
+async fn collect(
+    profile: &mut Profile,
+    faucet_id: AccountId,
+    expected: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let wallet = profile.wallet().await?;
+    wait_for_notes(&mut profile.client, wallet, expected).await?;
+    let options = ConsumeOptions::default().with_known_faucets([faucet_id]);
+    if let Some(tx_id) = consume_all(&mut profile.client, wallet, &options).await? {
+        wait_for_tx(&mut profile.client, tx_id).await?;
+    }
+    Ok(())
+}
+
+/// Returns the balance of the profile's wallet and reports it.
+async fn wallet_balance(
+    profile: &mut Profile,
+    faucet_id: AccountId,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let wallet = profile.wallet().await?;
+    Ok(balance(&mut profile.client, wallet, faucet_id).await?)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut profiles = ProfileManager::new(ClientSetup::from_cli()?);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Open a profile per persona
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Opening the profiles of Alice, Bob and Carol");
+
+    let mut wallets = Vec::new();
+    for name in ["Alice", "Bob", "Carol"] {
+        say!(
+            "{name}: store {}, keystore {}",
+            profiles.store_path(name).display(),
+            profiles.keystore_path(name).display()
+        );
+        let wallet = profiles.switch(name).await?.wallet().await?;
+        say!("{name}'s wallet: {:?}", wallet.to_bech32(network_id()));
+        wallets.push(wallet);
+    }
+    let [alice_wallet, bob_wallet, carol_wallet] = wallets[..] else {
+        unreachable!("three profiles were opened");
+    };
+    say!(
+        "Open profiles: {}",
+        profiles.names().collect::<Vec<_>>().join(", ")
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice issues a token and pays Bob and Carol
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice mints 100 tokens and pays Bob 40 and Carol 20");
+
+    let alice = profiles.switch("Alice").await?;
+    let faucet = create_basic_faucet(&mut alice.client, &alice.keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+    top_up(&mut alice.client, faucet.id(), alice_wallet, 100).await?;
+    pay(alice, faucet.id(), bob_wallet, 40).await?;
+    pay(alice, faucet.id(), carol_wallet, 20).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Bob collects his payment in his own client and passes some on
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob collects 40 tokens and pays Carol 10");
+
+    let bob = profiles.switch("Bob").await?;
+    collect(bob, faucet.id(), 1).await?;
+    pay(bob, faucet.id(), carol_wallet, 10).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Carol collects both payments
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Carol collects the notes from Alice and Bob");
+
+    let carol = profiles.switch("Carol").await?;
+    collect(carol, faucet.id(), 2).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 5: A profile cannot act for another one
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Carol's client tries to spend from Bob's wallet");
+
+    // Carol's store does not track Bob's wallet and her keystore has no key for it
+    let note = create_p2id_note(
+        bob_wallet,
+        carol_wallet,
+        vec![FungibleAsset::new(faucet.id(), 1)?.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        carol.client.rng(),
+    )?;
+    let request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note)])
+        .build()?;
+    match carol.client.execute_transaction(bob_wallet, request).await {
+        Ok(_) => panic!("Carol's profile should not be able to act for Bob"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 6: Verify balances, each read through its owner's client
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Verifying balances");
+
+    let mut balances = Vec::new();
+    for name in ["Alice", "Bob", "Carol"] {
+        let amount = wallet_balance(profiles.switch(name).await?, faucet.id()).await?;
+        say!("{name} holds {amount} tokens");
+        balances.push(amount);
+    }
+    assert_eq!(balances, [40, 30, 30], "payments did not add up");
+    say!("✅ Every persona kept its own store and keys, and the tokens add up");
+
+    Ok(())
+}
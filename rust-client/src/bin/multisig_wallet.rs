@@ -0,0 +1,239 @@
+use rand::RngCore;
+use std::{error::Error, fs, path::Path, process::ExitCode};
+
+use miden_client::{
+    account::{component::BasicWallet, AccountBuilder, AccountId, AccountStorageMode, AccountType},
+    asset::FungibleAsset,
+    auth::{AuthFalcon512RpoMultisig, AuthFalcon512RpoMultisigConfig, AuthSecretKey},
+    crypto::FeltRng,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionExecutorError, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+use miden_protocol::{account::auth::PublicKeyCommitment, Hasher};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    say,
+    wait::{submit_and_wait, TxReport},
+};
+
+/// Directory holding one keystore per cosigner, next to the client's own keystore.
+const COSIGNER_DIR: &str = "cosigners";
+
+/// Signatures needed out of the three cosigners.
+const THRESHOLD: u32 = 2;
+
+/// A signer holding one key of the multisig in a keystore of their own.
+struct Cosigner {
+    name: &'static str,
+    keystore: FilesystemKeyStore,
+    key: PublicKeyCommitment,
+}
+
+impl Cosigner {
+    fn new(cosigner_dir: &Path, name: &'static str) -> Result<Self, Box<dyn std::error::Error>> {
+        let dir = cosigner_dir.join(name.to_lowercase());
+        fs::create_dir_all(&dir)?;
+        let keystore = FilesystemKeyStore::new(dir)?;
+        let secret_key = AuthSecretKey::new_falcon512_rpo();
+        keystore.add_key(&secret_key)?;
+        Ok(Self {
+            name,
+            keystore,
+            key: secret_key.public_key().to_commitment(),
+        })
+    }
+
+    /// Signs `message` with the key from this cosigner's keystore.
+    ///
+    /// Returns the advice map entry the multisig auth procedure looks the
+    /// signature up under: `hash(public key, message)`.
+    fn sign(&self, message: Word) -> Result<(Word, Vec<Felt>), Box<dyn std::error::Error>> {
+        let secret_key = self
+            .keystore
+            .get_key(self.key)?
+            .ok_or("the key is missing from the cosigner's keystore")?;
+        let signature = secret_key.sign(message);
+        let key: Word = self.key.into();
+        Ok((
+            Hasher::merge(&[key, message]),
+            signature.to_prepared_signature(message),
+        ))
+    }
+}
+
+/// Whether the multisig turned `err` away for lacking signatures, rather than
+/// for the network or anything else about the transaction.
+///
+/// Too few valid signatures end the auth procedure the same way as none at
+/// all, as [`TransactionExecutorError::Unauthorized`]. STEP 2 already shows
+/// that signatures under the right advice map keys are accepted.
+fn is_threshold_failure(err: &(dyn Error + 'static)) -> bool {
+    let mut cause = Some(err);
+    while let Some(current) = cause {
+        if let Some(ClientError::TransactionExecutorError(err)) = current.downcast_ref() {
+            return matches!(err, TransactionExecutorError::Unauthorized(_));
+        }
+        cause = current.source();
+    }
+    false
+}
+
+/// Executes a request against the multisig, signed by `signers`, and submits it.
+///
+/// The first execution stops in the auth procedure and hands back the summary
+/// of the transaction, which is what the cosigners sign. The request is then
+/// rebuilt with their signatures in the advice map. Both runs use the same salt
+/// and the same notes, so the summary they produce is identical.
+async fn submit_signed(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    build: impl Fn() -> TransactionRequestBuilder,
+    signers: &[&Cosigner],
+) -> Result<TxReport, Box<dyn std::error::Error>> {
+    let salt = client.rng().draw_word();
+    let unsigned = build().auth_arg(salt).build()?;
+    let message = match client.execute_transaction(account_id, unsigned).await {
+        Err(ClientError::TransactionExecutorError(TransactionExecutorError::Unauthorized(
+            summary,
+        ))) => summary.to_commitment(),
+        Ok(_) => return Err("the multisig executed a transaction nobody signed".into()),
+        Err(err) => return Err(err.into()),
+    };
+    say!("Transaction summary to sign: {}", message.to_hex());
+
+    let mut signatures = Vec::new();
+    for signer in signers {
+        signatures.push(signer.sign(message)?);
+        say!("{} signed", signer.name);
+    }
+
+    let signed = build()
+        .auth_arg(salt)
+        .extend_advice_map(signatures)
+        .build()?;
+    Ok(submit_and_wait(client, account_id, signed).await?)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let setup = ClientSetup::from_cli()?;
+    // Next to the client's keystore, so `--isolate` and `--memory-store` move them too
+    let cosigner_dir = setup.keystore_path.with_file_name(COSIGNER_DIR);
+    let (mut client, keystore) = setup.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create a wallet that needs 2 of 3 signatures
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating a {THRESHOLD}-of-3 multisig wallet");
+
+    let alice = Cosigner::new(&cosigner_dir, "Alice")?;
+    let bob = Cosigner::new(&cosigner_dir, "Bob")?;
+    let carol = Cosigner::new(&cosigner_dir, "Carol")?;
+
+    // None of the cosigner keys is in the client's keystore, so the client
+    // cannot sign for the wallet on its own
+    let config =
+        AuthFalcon512RpoMultisigConfig::new(vec![alice.key, bob.key, carol.key], THRESHOLD)?;
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+    let multisig = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthFalcon512RpoMultisig::new(config)?)
+        .with_component(BasicWallet)
+        .build()?;
+    client.add_account(&multisig, false).await?;
+    say!(
+        "Multisig account ID: {:?}",
+        multisig.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fund the wallet, which already takes two signatures
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting 100 tokens and receiving them with Alice and Bob's signatures");
+
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    let mint_request = TransactionRequestBuilder::new().build_mint_fungible_asset(
+        FungibleAsset::new(faucet.id(), 100)?,
+        multisig.id(),
+        NoteType::Public,
+        client.rng(),
+    )?;
+    let mint_note = mint_request
+        .expected_output_own_notes()
+        .pop()
+        .expect("a mint request creates one note");
+    submit_and_wait(&mut client, faucet.id(), mint_request).await?;
+
+    // Every change to the wallet bumps its nonce, so receiving needs a quorum too
+    submit_signed(
+        &mut client,
+        multisig.id(),
+        || TransactionRequestBuilder::new().input_notes([(mint_note.clone(), None)]),
+        &[&alice, &bob],
+    )
+    .await?;
+    say!("✅ The wallet received 100 tokens");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: One signature is not enough to spend
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice alone tries to pay 30 tokens to Dave");
+
+    let dave = create_basic_account(&mut client, &keystore).await?;
+    let payment = create_p2id_note(
+        multisig.id(),
+        dave.id(),
+        vec![FungibleAsset::new(faucet.id(), 30)?.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+    let pay = || {
+        TransactionRequestBuilder::new().own_output_notes(vec![OutputNote::Full(payment.clone())])
+    };
+
+    match submit_signed(&mut client, multisig.id(), pay, &[&alice]).await {
+        Ok(report) if report.is_committed() => {
+            return Err("the multisig accepted a single signature".into())
+        }
+        Ok(_) => say!("Alice's payment discarded as expected"),
+        Err(err) if is_threshold_failure(err.as_ref()) => say!("Rejected as expected: {}", err),
+        Err(err) => return Err(err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: With a second signature the payment goes through
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Bob and Carol sign the payment to Dave");
+
+    let report = submit_signed(&mut client, multisig.id(), pay, &[&bob, &carol]).await?;
+    assert!(
+        report.is_committed(),
+        "the signed payment was not committed"
+    );
+
+    let balance = get_full_account(&mut client, multisig.id())
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    say!("✅ The multisig holds {balance} tokens after paying Dave");
+    assert_eq!(balance, 70, "the payment should leave 70 tokens");
+
+    Ok(())
+}
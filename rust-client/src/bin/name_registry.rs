@@ -0,0 +1,114 @@
+use std::process::ExitCode;
+
+use rust_client::{
+    accounts::create_basic_account,
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    name_registry::NameRegistry,
+    say,
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create Alice, Bob and the registry
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice, Bob and the name registry");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    let registry = NameRegistry::deploy(&mut client).await?;
+    say!(
+        "Name registry ID: {:?}",
+        registry.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice and Bob register names
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice registers 'alice', Bob registers 'bob'");
+
+    registry
+        .register(&mut client, alice_account.id(), "alice")
+        .await?;
+    registry
+        .register(&mut client, bob_account.id(), "bob")
+        .await?;
+    for name in ["alice", "bob", "carol"] {
+        match registry.resolve(&mut client, name).await? {
+            Some(owner) => say!("'{name}' resolves to {}", owner.to_bech32(network_id())),
+            None => say!("'{name}' is not registered"),
+        }
+    }
+    assert_eq!(
+        registry.resolve(&mut client, "alice").await?,
+        Some(alice_account.id())
+    );
+    assert_eq!(
+        registry.resolve(&mut client, "bob").await?,
+        Some(bob_account.id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Taken names stay with their owner
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob tries to register 'alice' and to take it with a transfer");
+
+    match registry
+        .register(&mut client, bob_account.id(), "alice")
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("the registry took a name twice"),
+        Ok(_) => say!("Bob's registration discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+    match registry
+        .transfer(&mut client, bob_account.id(), "alice", bob_account.id())
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("the registry let a stranger transfer"),
+        Ok(_) => say!("Bob's transfer discarded as expected"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+    assert_eq!(
+        registry.resolve(&mut client, "alice").await?,
+        Some(alice_account.id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Alice hands 'alice' on to Bob
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice transfers 'alice' to Bob");
+
+    registry
+        .transfer(&mut client, alice_account.id(), "alice", bob_account.id())
+        .await?;
+    let owner = registry.resolve(&mut client, "alice").await?;
+    say!(
+        "✅ 'alice' now resolves to {:?}",
+        owner.map(|owner| owner.to_bech32(network_id()))
+    );
+    assert_eq!(owner, Some(bob_account.id()), "Bob should own 'alice' now");
+
+    Ok(())
+}
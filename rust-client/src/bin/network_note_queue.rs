@@ -0,0 +1,161 @@
+use std::process::ExitCode;
+
+use tokio::time::{sleep, Duration};
+
+use miden_client::{
+    account::{AccountId, AccountStorageMode},
+    crypto::FeltRng,
+    keystore::FilesystemKeyStore,
+    note::{
+        NetworkAccountTarget, Note, NoteAssets, NoteError, NoteExecutionHint, NoteInputs,
+        NoteMetadata, NoteRecipient, NoteScript, NoteTag, NoteType,
+    },
+    rpc::GrpcClient,
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::create_basic_account,
+    client::{network_id, ClientSetup},
+    contracts::Counter,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    network_queue::NoteQueue,
+    say,
+    wait::submit_and_wait,
+};
+
+/// Increment notes sent in one transaction.
+const NOTES: usize = 3;
+
+/// How often the queue is polled before giving up.
+const MAX_POLLS: usize = 40;
+
+/// Builds a public note the network transaction builder consumes against `counter_id`.
+fn increment_note(
+    client: &mut Client<FilesystemKeyStore>,
+    sender: AccountId,
+    counter_id: AccountId,
+    script: NoteScript,
+) -> Result<Note, NoteError> {
+    let recipient = NoteRecipient::new(client.rng().draw_word(), script, NoteInputs::new(vec![])?);
+    let attachment = NetworkAccountTarget::new(counter_id, NoteExecutionHint::Always)
+        .map_err(|e| NoteError::other(e.to_string()))?
+        .into();
+    let metadata = NoteMetadata::new(
+        sender,
+        NoteType::Public,
+        NoteTag::with_account_target(counter_id),
+    )
+    .with_attachment(attachment);
+    Ok(Note::new(NoteAssets::default(), metadata, recipient))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let setup = ClientSetup::from_cli()?;
+    // The queue is read straight from the node, next to the client's own sync
+    let rpc = GrpcClient::new(&setup.endpoint, setup.timeout_ms);
+    let (mut client, keystore) = setup.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create Alice and a network counter
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice and a counter stored on the network");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+
+    let counter_account = Counter::deployer()?
+        .storage_mode(AccountStorageMode::Network)
+        .deploy(&mut client)
+        .await?;
+    let counter = Counter {
+        account_id: counter_account.id(),
+    };
+    // The first increment deploys the contract
+    counter.increment(&mut client).await?;
+    say!(
+        "Network counter ID: {:?}",
+        counter.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice sends several increment notes at once
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice sends {NOTES} increment notes to the counter");
+
+    let note_file = MasmFile::read("../masm/notes/network_increment_note.masm");
+    let note_script = note_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&Counter::library()?)?
+            .compile_note_script(&note_file.source),
+    )?;
+
+    let mut queue = NoteQueue::default();
+    let mut notes = Vec::new();
+    for _ in 0..NOTES {
+        let note = increment_note(
+            &mut client,
+            alice_account.id(),
+            counter.account_id,
+            note_script.clone(),
+        )?;
+        queue.track(&note);
+        notes.push(OutputNote::Full(note));
+    }
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(notes)
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Watch the notes move through the network transaction builder
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Polling the node until every note is consumed");
+
+    // Committing the notes is only half of it: the builder picks them up in
+    // later blocks, one network transaction at a time, which is why a counter
+    // update can trail the note by many seconds
+    for _ in 0..MAX_POLLS {
+        let tip = client.sync_state().await?.block_num;
+        queue.refresh(&rpc).await?;
+        queue.print(tip);
+        if queue.is_drained() {
+            break;
+        }
+        sleep(Duration::from_secs(3)).await;
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Read the counter
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Reading the counter");
+
+    client.sync_state().await?;
+    let count = counter.count(&mut client).await?;
+    if queue.is_drained() {
+        say!("✅ All {NOTES} notes consumed, counter is at {count}");
+        assert_eq!(
+            count,
+            1 + NOTES as u64,
+            "every consumed note increments once"
+        );
+    } else {
+        say!("The builder has not reached every note yet, counter is at {count}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use clap::Parser;
+use miden_client::{
+    asset::FungibleAsset, note::NoteType, rpc::Endpoint, transaction::TransactionRequestBuilder,
+    ClientError,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    client::ClientSetup,
+    endpoints::{EndpointEntry, EndpointRegistry, ENDPOINTS_FILE},
+    error::{report, ExitGuard},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Compares commitment latency across the networks of the endpoints registry.
+///
+/// Every network gets its own store under `./telemetry`, so the client flags of
+/// the other tutorials do not apply here.
+#[derive(Parser)]
+struct Cli {
+    /// Endpoints registry to read
+    #[arg(default_value = ENDPOINTS_FILE)]
+    registry: String,
+}
+
+/// Networks compared by this binary, in report order.
+const NETWORKS: [&str; 2] = ["devnet", "testnet"];
+
+/// Number of transactions submitted after the deployment.
+const SCENARIO_TXS: usize = 3;
+
+/// Commitment latencies collected on one network.
+struct NetworkReport {
+    name: String,
+    samples: Vec<Duration>,
+    error: Option<String>,
+}
+
+/// Nearest-rank percentile of an already sorted sample set.
+fn percentile(sorted: &[Duration], p: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn format_latency(latency: Option<Duration>) -> String {
+    latency
+        .map(|latency| format!("{:.1}s", latency.as_secs_f64()))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Deploys a faucet and mints `SCENARIO_TXS` times, timing each transaction
+/// from submission until the client sees it committed.
+async fn run_scenario(entry: &EndpointEntry) -> Result<Vec<Duration>, Box<dyn std::error::Error>> {
+    let endpoint = Endpoint::try_from(entry.url.as_str())
+        .map_err(|err| ClientError::ClientInitializationError(err.to_string()))?;
+    // Each network gets its own store so the runs do not interfere
+    let data_dir = PathBuf::from("./telemetry").join(&entry.name);
+    let (mut client, keystore) = ClientSetup {
+        endpoint,
+        keystore_path: data_dir.join("keystore"),
+        store_path: data_dir.join("store.sqlite3"),
+        ..ClientSetup::default()
+    }
+    .build()
+    .await?;
+    client.sync_state().await?;
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+
+    // The first mint deploys the faucet, the following ones are regular transactions
+    let mut samples = Vec::with_capacity(SCENARIO_TXS + 1);
+    for i in 0..=SCENARIO_TXS {
+        let mint_request = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), 10).unwrap(),
+                alice_account.id(),
+                NoteType::Public,
+                client.rng(),
+            )
+            .unwrap();
+
+        let latency = submit_and_wait(&mut client, faucet.id(), mint_request)
+            .await?
+            .elapsed;
+
+        let label = if i == 0 { "deploy" } else { "mint" };
+        say!("[{}] {label} committed in {:.1?}", entry.name, latency);
+        samples.push(latency);
+    }
+
+    Ok(samples)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let path = Cli::parse().registry;
+    let registry = EndpointRegistry::load(&path)?;
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Run the micro-scenario on every configured network
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Running deploy + {SCENARIO_TXS} txs on each network");
+    let mut reports = Vec::new();
+    for network in NETWORKS {
+        let Some(entry) = registry.for_network(network).next() else {
+            say!("No {network} endpoint configured in {path}, skipping");
+            continue;
+        };
+        say!("\nUsing {} ({})", entry.name, entry.url);
+
+        let report = match run_scenario(entry).await {
+            Ok(samples) => NetworkReport {
+                name: network.to_string(),
+                samples,
+                error: None,
+            },
+            Err(err) => NetworkReport {
+                name: network.to_string(),
+                samples: Vec::new(),
+                error: Some(err.to_string()),
+            },
+        };
+        reports.push(report);
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Report commitment latency percentiles
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Commitment latency per network\n");
+    say!(
+        "{:<10} {:>7} {:>8} {:>8} {:>8}",
+        "network",
+        "samples",
+        "p50",
+        "p90",
+        "max"
+    );
+    for report in &mut reports {
+        report.samples.sort();
+        say!(
+            "{:<10} {:>7} {:>8} {:>8} {:>8}",
+            report.name,
+            report.samples.len(),
+            format_latency(percentile(&report.samples, 50.0)),
+            format_latency(percentile(&report.samples, 90.0)),
+            format_latency(report.samples.last().copied()),
+        );
+        if let Some(err) = &report.error {
+            say!("{:<10} error: {}", "", err);
+        }
+    }
+
+    let fastest = reports
+        .iter()
+        .filter(|report| report.error.is_none())
+        .min_by_key(|report| percentile(&report.samples, 50.0));
+    if let Some(fastest) = fastest {
+        say!("\nLowest median commitment latency: {}", fastest.name);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::{AccountId, AccountType},
+    asset::{Asset, NonFungibleAsset, NonFungibleAssetDetails},
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, Felt, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, get_full_account},
+    client::{init_client_from_config, network_id},
+    deployer::{ContractAuth, ContractDeployer},
+    error::{report, ExitGuard},
+    masm::{word_to_masm, MasmFile},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Off-chain data the NFT commits to. Only its hash ends up in the asset.
+const NFT_DATA: &[u8] = b"Miden Cat #1 | color: orange | whiskers: 12";
+
+/// Has `account_id` consume `note`.
+async fn consume(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    note: Note,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(note, None)])
+        .build()?;
+    submit_and_wait(client, account_id, consume_request).await?;
+    Ok(())
+}
+
+/// Whether the vault of `account_id` holds `nft`.
+async fn holds(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    nft: NonFungibleAsset,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let account = get_full_account(client, account_id).await?;
+    Ok(account.vault().has_non_fungible_asset(nft)?)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create Alice, Bob and a non-fungible faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and deploying a non-fungible faucet");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    // The faucet signs its transactions, so only the key holder can mint
+    let faucet_deployer = ContractDeployer::from_path(
+        "../masm/accounts/nft_faucet.masm",
+        "external_contract::nft_faucet",
+    )
+    .account_type(AccountType::NonFungibleFaucet)
+    .auth(ContractAuth::new_falcon512(&keystore));
+    let faucet_library = faucet_deployer.library()?;
+    let faucet = faucet_deployer.deploy(&mut client).await?;
+    say!("NFT faucet ID: {:?}", faucet.id().to_bech32(network_id()));
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Mint an NFT to Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting an NFT for Alice");
+
+    let details = NonFungibleAssetDetails::new(faucet.id().prefix(), NFT_DATA.to_vec())?;
+    let nft = NonFungibleAsset::new(&details)?;
+    say!("NFT data: {:?}", String::from_utf8_lossy(NFT_DATA));
+    say!("NFT asset: {:?}", Word::from(nft));
+
+    let mint_note = create_p2id_note(
+        faucet.id(),
+        alice_account.id(),
+        vec![nft.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+
+    let script_template = MasmFile::read("../masm/scripts/nft_mint_script.masm");
+    let script_code = script_template
+        .source
+        .replace("{asset}", &word_to_masm(Word::from(nft)))
+        .replace("{recipient}", &word_to_masm(mint_note.recipient().digest()))
+        .replace(
+            "{tag}",
+            &Felt::from(mint_note.metadata().tag()).as_int().to_string(),
+        );
+    let script_file = script_template.with_source(script_code);
+    let mint_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&faucet_library)?
+            .compile_tx_script(&script_file.source),
+    )?;
+
+    // The script builds the note, so the client is told which recipient to expect
+    let mint_request = TransactionRequestBuilder::new()
+        .custom_script(mint_script)
+        .expected_output_recipients(vec![mint_note.recipient().clone()])
+        .build()?;
+    submit_and_wait(&mut client, faucet.id(), mint_request).await?;
+
+    consume(&mut client, alice_account.id(), mint_note).await?;
+    assert!(
+        holds(&mut client, alice_account.id(), nft).await?,
+        "Alice should hold the NFT"
+    );
+    say!("✅ Alice holds the NFT");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice transfers the NFT to Bob
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice sends the NFT to Bob in a P2ID note");
+
+    let transfer_note = create_p2id_note(
+        alice_account.id(),
+        bob_account.id(),
+        vec![nft.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+    let transfer_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(transfer_note.clone())])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), transfer_request).await?;
+    consume(&mut client, bob_account.id(), transfer_note).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Read the NFT back out of Bob's vault
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Reading the NFT from Bob's vault");
+
+    let bob = get_full_account(&mut client, bob_account.id()).await?;
+    let found = bob
+        .vault()
+        .assets()
+        .find_map(|asset| match asset {
+            Asset::NonFungible(found) if found.faucet_id_prefix() == faucet.id().prefix() => {
+                Some(found)
+            }
+            _ => None,
+        })
+        .expect("Bob's vault should hold an NFT from the faucet");
+    say!(
+        "Found NFT from faucet {}: {:?}",
+        faucet.id(),
+        Word::from(found)
+    );
+
+    // The vault only keeps the commitment, so the data is checked by rehashing it
+    let rebuilt = NonFungibleAsset::new(&NonFungibleAssetDetails::new(
+        faucet.id().prefix(),
+        NFT_DATA.to_vec(),
+    )?)?;
+    assert_eq!(found, rebuilt, "the NFT should commit to the minted data");
+    assert!(
+        !holds(&mut client, alice_account.id(), nft).await?,
+        "Alice should no longer hold the NFT"
+    );
+    say!(
+        "✅ Bob holds the NFT and it matches {:?}",
+        String::from_utf8_lossy(NFT_DATA)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    asset::FungibleAsset,
+    note::{Note, NoteType},
+    rpc::{GrpcClient, NodeRpcClient},
+    transaction::{PaymentNoteDescription, TransactionRequest, TransactionRequestBuilder},
+};
+use rust_client::{
+    accounts::{balance, create_basic_account, create_basic_faucet, top_up},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Builds a request consuming `note`.
+fn consume_request(note: &Note) -> Result<TransactionRequest, Box<dyn std::error::Error>> {
+    Ok(TransactionRequestBuilder::new()
+        .input_notes([(note.clone(), None)])
+        .build()?)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let setup = ClientSetup::from_cli()?;
+    // Nullifiers are checked straight against the node, not the local store
+    let rpc = GrpcClient::new(&setup.endpoint, setup.timeout_ms);
+    let (mut client, keystore) = setup.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice sends Bob a payment she can cancel
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice sends Bob 40 tokens in a cancellable note");
+
+    // A plain P2ID note only accepts its target. A P2IDE note whose reclaim
+    // block is already reached accepts the sender as well, so it stays
+    // cancellable until Bob consumes it
+    let sent_at = client.get_sync_height().await?;
+    let payment = PaymentNoteDescription::new(
+        vec![FungibleAsset::new(faucet.id(), 40)?.into()],
+        alice_account.id(),
+        bob_account.id(),
+    )
+    .with_reclaim_height(sent_at);
+    let payment_request = TransactionRequestBuilder::new().build_pay_to_id(
+        payment,
+        NoteType::Public,
+        client.rng(),
+    )?;
+    let note = payment_request
+        .expected_output_own_notes()
+        .pop()
+        .expect("a payment request creates one note");
+    submit_and_wait(&mut client, alice_account.id(), payment_request).await?;
+    say!("Payment note {} is on chain", note.id().to_hex());
+
+    let alice_balance = balance(&mut client, alice_account.id(), faucet.id()).await?;
+    assert_eq!(alice_balance, 60, "Alice should have paid 40");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice cancels the payment by consuming the note herself
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice consumes her own note before Bob does");
+
+    let cancel = submit_and_wait(&mut client, alice_account.id(), consume_request(&note)?).await?;
+    assert!(
+        cancel.is_committed(),
+        "the cancellation should be committed"
+    );
+
+    let alice_balance = balance(&mut client, alice_account.id(), faucet.id()).await?;
+    say!("✅ Alice holds {alice_balance} again");
+    assert_eq!(alice_balance, 100, "Alice should have her 40 tokens back");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Show that Bob can no longer claim the note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Checking that the note is spent");
+
+    // The local store has seen the consuming transaction
+    client.sync_state().await?;
+    let record = client
+        .get_input_note(note.id())
+        .await?
+        .expect("the note is tracked by the store");
+    say!("Store: note {} is {:?}", note.id().to_hex(), record.state());
+    assert!(
+        record.is_consumed(),
+        "the store should mark the note consumed"
+    );
+
+    // The node holds the nullifier, which is what makes the cancel final: any
+    // later transaction consuming the note would publish the same nullifier
+    let nullifier = note.nullifier();
+    let spent_at = rpc
+        .sync_nullifiers(&[nullifier.prefix()], sent_at, None)
+        .await?
+        .into_iter()
+        .find(|update| update.nullifier == nullifier)
+        .map(|update| update.block_num)
+        .expect("the node should know the nullifier");
+    say!(
+        "Node: nullifier {} spent at block {spent_at}",
+        nullifier.to_hex()
+    );
+
+    // Whether the client or the node catches it, the spent nullifier stops Bob
+    match submit_and_wait(&mut client, bob_account.id(), consume_request(&note)?).await {
+        Ok(report) if report.is_committed() => {
+            panic!("Bob should not be able to consume a cancelled note")
+        }
+        Ok(report) => say!("Bob's claim was discarded: {:?}", report.status),
+        Err(err) => say!("Bob's claim rejected as expected: {}", err),
+    }
+
+    let bob_balance = balance(&mut client, bob_account.id(), faucet.id()).await?;
+    say!("✅ Bob holds {bob_balance}, the payment is cancelled");
+    assert_eq!(bob_balance, 0, "Bob should not have received anything");
+
+    Ok(())
+}
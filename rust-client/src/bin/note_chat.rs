@@ -0,0 +1,222 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    note::{Note, NoteScript},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Word,
+};
+use rust_client::{
+    accounts::create_basic_account,
+    chat::{ChatMessage, Conversation, MAX_MESSAGE_LEN},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    profiles::{Profile, ProfileManager},
+    say,
+    wait::{submit_and_wait, wait_for, ConsumableNote},
+};
+
+/// Who says what, in order. `true` is Alice, `false` is Bob.
+const SCRIPT: &[(bool, &str)] = &[
+    (true, "Hey Bob! Did my note reach you?"),
+    (
+        false,
+        "It did, and it carried no tokens at all. Just your words.",
+    ),
+    (
+        true,
+        "A note is a message that may carry assets. Today we only send the message.",
+    ),
+    (
+        false,
+        "Nobody consumes these, so the chat stays on chain. It is public though, so no secrets!",
+    ),
+];
+
+/// One side of the chat. Its client lives in the participant's profile.
+struct Participant {
+    name: &'static str,
+    account_id: AccountId,
+    script: NoteScript,
+    conversation: Conversation,
+}
+
+impl Participant {
+    /// Opens the profile of `name` and creates the account it chats from.
+    async fn join(
+        name: &'static str,
+        profiles: &mut ProfileManager,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let profile = profiles.switch(name).await?;
+
+        // Messages are never consumed, so a fresh account keeps the messages of
+        // earlier runs out of this conversation
+        let account = create_basic_account(&mut profile.client, &profile.keystore).await?;
+        say!(
+            "{name}'s account ID: {:?}",
+            account.id().to_bech32(network_id())
+        );
+
+        let note_file = MasmFile::read("../masm/notes/chat_message_note.masm");
+        let script = note_file.check(
+            profile
+                .client
+                .code_builder()
+                .compile_note_script(&note_file.source),
+        )?;
+
+        Ok(Self {
+            name,
+            account_id: account.id(),
+            script,
+            conversation: Conversation::new(account.id()),
+        })
+    }
+
+    /// Sends `text` to `to` in a zero-asset note tagged for it.
+    async fn send(
+        &mut self,
+        profile: &mut Profile,
+        to: AccountId,
+        text: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let message = ChatMessage {
+            from: self.account_id,
+            to,
+            seq: self.conversation.next_seq(),
+            text: text.to_string(),
+        };
+        let note = NoteBuilder::new(self.account_id, self.script.clone())
+            .inputs(message.to_inputs()?)
+            .target(to)
+            .build(&mut SerialNumbers::random(), profile.client.rng())?;
+        say!(
+            "{} sends message #{} in note {}",
+            self.name,
+            message.seq,
+            note.id().to_hex()
+        );
+
+        let request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note)])
+            .build()?;
+        submit_and_wait(&mut profile.client, self.account_id, request).await?;
+        self.conversation.insert(message);
+        Ok(())
+    }
+
+    /// Polls for notes under this participant's tag until message `seq` has arrived.
+    async fn receive(
+        &mut self,
+        profile: &mut Profile,
+        seq: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let account_id = self.account_id;
+        let root = self.script.root();
+        let notes = wait_for(&mut profile.client, |notes| {
+            chat_messages(notes, account_id, root)
+                .iter()
+                .any(|message| message.seq == seq)
+        })
+        .await?;
+
+        for message in chat_messages(&notes, account_id, root) {
+            if self.conversation.insert(message) {
+                say!("{} received a new message", self.name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the chat messages `account_id` can consume, skipping any other note.
+fn chat_messages(notes: &[ConsumableNote], account_id: AccountId, root: Word) -> Vec<ChatMessage> {
+    notes
+        .iter()
+        .filter(|(_, consumers)| consumers.iter().any(|(id, _)| *id == account_id))
+        .filter_map(|(record, _)| Note::try_from(record.clone()).ok())
+        .filter(|note| note.script().root() == root)
+        .filter_map(|note| ChatMessage::from_note(&note).ok())
+        .filter(|message| message.to == account_id)
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut profiles = ProfileManager::new(ClientSetup::from_cli()?);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Open a profile, with its own client, per participant
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Opening the profiles of Alice and Bob");
+
+    let mut alice = Participant::join("Alice", &mut profiles).await?;
+    let mut bob = Participant::join("Bob", &mut profiles).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Messages have to fit in the note inputs
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Checking the message size limit of {MAX_MESSAGE_LEN} bytes");
+
+    let rant = ChatMessage {
+        from: alice.account_id,
+        to: bob.account_id,
+        seq: 1,
+        text: "a".repeat(MAX_MESSAGE_LEN + 1),
+    };
+    match rant.to_inputs() {
+        Ok(_) => panic!("a message over {MAX_MESSAGE_LEN} bytes should be refused"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Chat, each client polling its own tag for replies
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice and Bob take turns");
+
+    for &(from_alice, text) in SCRIPT {
+        let (sender, receiver) = if from_alice {
+            (&mut alice, &mut bob)
+        } else {
+            (&mut bob, &mut alice)
+        };
+        let seq = sender.conversation.next_seq();
+        sender
+            .send(
+                profiles.switch(sender.name).await?,
+                receiver.account_id,
+                text,
+            )
+            .await?;
+        receiver
+            .receive(profiles.switch(receiver.name).await?, seq)
+            .await?;
+        receiver.conversation.print(receiver.name, sender.name);
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Both sides see the same conversation
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Comparing both views");
+
+    alice.conversation.print(alice.name, bob.name);
+    bob.conversation.print(bob.name, alice.name);
+    assert_eq!(alice.conversation.messages().count(), SCRIPT.len());
+    assert!(
+        alice
+            .conversation
+            .messages()
+            .eq(bob.conversation.messages()),
+        "Alice and Bob should hold the same messages"
+    );
+    say!("✅ Both clients hold all {} messages", SCRIPT.len());
+
+    Ok(())
+}
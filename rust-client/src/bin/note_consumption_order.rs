@@ -0,0 +1,187 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::{AccountId, StorageSlot, StorageSlotName},
+    assembly::Library,
+    keystore::FilesystemKeyStore,
+    note::NoteScript,
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, Felt, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, get_full_account},
+    client::{init_client_from_config, network_id},
+    deployer::ContractDeployer,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    output::{emit, Event},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Compiles a note script that calls into the note order contract.
+fn compile_note(
+    client: &Client<FilesystemKeyStore>,
+    library: &Library,
+    path: &str,
+) -> Result<NoteScript, Box<dyn std::error::Error>> {
+    let file = MasmFile::read(path);
+    let builder = client
+        .code_builder()
+        .with_dynamically_linked_library(library)?;
+    Ok(file.check(builder.compile_note_script(&file.source))?)
+}
+
+/// A note from `sender` carrying `ticket` as its only input.
+fn ticket_note(sender: AccountId, script: &NoteScript, ticket: u64) -> NoteBuilder {
+    NoteBuilder::new(sender, script.clone()).inputs([Felt::new(ticket)])
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Deploy the contract and create Alice's account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating the note order contract and Alice's account");
+
+    let open_ticket_slot = StorageSlotName::new("miden::tutorials::note_order::open_ticket")?;
+    let settled_slot = StorageSlotName::new("miden::tutorials::note_order::settled")?;
+    let deployer = ContractDeployer::from_path(
+        "../masm/accounts/note_order.masm",
+        "external_contract::note_order_contract",
+    )
+    .with_slots([
+        StorageSlot::with_value(open_ticket_slot.clone(), Word::default()),
+        StorageSlot::with_value(settled_slot.clone(), Word::default()),
+    ]);
+    let library = deployer.library()?;
+    let contract = deployer.deploy(&mut client).await?;
+    say!(
+        "Note order contract ID: {:?}",
+        contract.id().to_bech32(network_id())
+    );
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice sends all notes at once
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice creates note A (open ticket 1) and two notes B (settle ticket 1)");
+
+    let open_script = compile_note(&client, &library, "../masm/notes/note_order_open_note.masm")?;
+    let settle_script = compile_note(
+        &client,
+        &library,
+        "../masm/notes/note_order_settle_note.masm",
+    )?;
+
+    let mut serials = SerialNumbers::random();
+    let note_a =
+        ticket_note(alice_account.id(), &open_script, 1).build(&mut serials, client.rng())?;
+    let note_b =
+        ticket_note(alice_account.id(), &settle_script, 1).build(&mut serials, client.rng())?;
+    let note_b_again =
+        ticket_note(alice_account.id(), &settle_script, 1).build(&mut serials, client.rng())?;
+    say!("Note A: {}", note_a.id().to_hex());
+    say!("Note B: {}", note_b.id().to_hex());
+
+    // All three notes land in the same block, so the chain says nothing about
+    // which one the contract should consume first
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![
+            OutputNote::Full(note_a.clone()),
+            OutputNote::Full(note_b.clone()),
+            OutputNote::Full(note_b_again.clone()),
+        ])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: B depends on A's effect, so it cannot go first
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] The contract tries to consume note B before note A");
+
+    // Executing locally is enough to see the failure, nothing is submitted
+    let consume_b = TransactionRequestBuilder::new()
+        .input_notes([(note_b.clone(), None)])
+        .build()?;
+    match client.execute_transaction(contract.id(), consume_b).await {
+        Ok(_) => panic!("note B should not settle a ticket that was never opened"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Consume A and wait for its commitment
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] The contract consumes note A and waits until it is committed");
+
+    // B executes against the contract state this client knows. Submitting it
+    // right after A would build it on A's pending state: if A were discarded,
+    // B would be discarded with it. Waiting for A's commitment makes the
+    // dependency final before anything builds on it.
+    let consume_a = TransactionRequestBuilder::new()
+        .input_notes([(note_a, None)])
+        .build()?;
+    let report = submit_and_wait(&mut client, contract.id(), consume_a).await?;
+    assert!(report.is_committed(), "note A was not committed");
+
+    let account = get_full_account(&mut client, contract.id()).await?;
+    let open_ticket = account.storage().get_item(&open_ticket_slot)?;
+    // The ticket sits on top of the stack, which is the last element of the word
+    assert_eq!(open_ticket[3].as_int(), 1, "ticket 1 should be open");
+    say!("✅ Ticket {} is open", open_ticket[3].as_int());
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Now B goes through
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] The contract consumes note B");
+
+    let consume_b = TransactionRequestBuilder::new()
+        .input_notes([(note_b, None)])
+        .build()?;
+    let report = submit_and_wait(&mut client, contract.id(), consume_b).await?;
+    assert!(report.is_committed(), "note B was not committed");
+
+    let account = get_full_account(&mut client, contract.id()).await?;
+    let open_ticket = account.storage().get_item(&open_ticket_slot)?;
+    let settled = account.storage().get_item(&settled_slot)?;
+    assert_eq!(open_ticket[3].as_int(), 0, "ticket 1 should be closed");
+    assert_eq!(settled[3].as_int(), 1, "one ticket should be settled");
+    say!("✅ Ticket settled, {} settled so far", settled[3].as_int());
+    emit(Event::storage_value(account.id(), &settled_slot, settled));
+
+    // -------------------------------------------------------------------------
+    // STEP 6: Each B needs its own A
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] The contract tries a second settle note for the same ticket");
+
+    // Settling closed the ticket, so a copy of B depends on an A that is used up
+    let consume_again = TransactionRequestBuilder::new()
+        .input_notes([(note_b_again, None)])
+        .build()?;
+    match client
+        .execute_transaction(contract.id(), consume_again)
+        .await
+    {
+        Ok(_) => panic!("a closed ticket should not be settled twice"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    Ok(())
+}
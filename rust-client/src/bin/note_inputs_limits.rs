@@ -0,0 +1,112 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    crypto::FeltRng,
+    note::{Note, NoteAssets, NoteInputs, NoteMetadata, NoteRecipient, NoteTag, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Felt,
+};
+use miden_protocol::{Hasher, MAX_INPUTS_PER_NOTE};
+use rust_client::{
+    accounts::create_basic_account,
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    say,
+    wait::submit_and_wait,
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Grow note inputs until they hit the limit
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Building note inputs of increasing size");
+    say!("Maximum number of note inputs: {}", MAX_INPUTS_PER_NOTE);
+
+    for num_inputs in [8, 32, 64, MAX_INPUTS_PER_NOTE, MAX_INPUTS_PER_NOTE + 1] {
+        let values = (0..num_inputs as u64).map(Felt::new).collect::<Vec<_>>();
+        match NoteInputs::new(values) {
+            Ok(inputs) => say!(
+                "{:>4} inputs: ok (commitment {})",
+                num_inputs,
+                inputs.commitment().to_hex()
+            ),
+            Err(err) => say!("{:>4} inputs: rejected ({})", num_inputs, err),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Commit to a large payload instead of inlining it
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Committing to a payload larger than the input limit");
+
+    // The payload is hashed in whole double-words, so pad it to a multiple of 8
+    let mut payload = (0..1_000_u64).map(Felt::new).collect::<Vec<_>>();
+    payload.resize(payload.len().next_multiple_of(8), Felt::new(0));
+    let num_words = payload.len() / 4;
+    let commitment = Hasher::hash_elements(&payload);
+    say!(
+        "Payload of {} elements ({} words) committed to {}",
+        payload.len(),
+        num_words,
+        commitment.to_hex()
+    );
+
+    // Only the commitment and the payload length travel in the note inputs
+    let mut inputs = commitment.to_vec();
+    inputs.push(Felt::new(num_words as u64));
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+    say!("Note inputs used: {}", note_inputs.num_values());
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Create the note and consume it with the payload
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Creating the note and consuming it with the payload");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    let note_file = MasmFile::read("../masm/notes/large_payload_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+    let serial_num = client.rng().draw_word();
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+    let metadata = NoteMetadata::new(alice_account.id(), NoteType::Public, NoteTag::new(0));
+    let payload_note = Note::new(NoteAssets::default(), metadata, recipient);
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(payload_note.clone())])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // Bob hands the payload to the note script through the advice map
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payload_note, None)])
+        .extend_advice_map([(commitment, payload)])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, bob_account.id(), consume_request).await?;
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    asset::FungibleAsset,
+    note::{create_p2id_note, Note, NoteAttachment, NoteMetadata, NoteTag, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    profiles::ProfileManager,
+    say,
+    wait::{submit_and_wait, wait_for},
+};
+
+/// Tag the receiver listens on. Any value works as long as both sides agree on it.
+const TOPIC_TAG: u32 = 0x7475_7400;
+
+/// Tag nobody registered, for a note the receiver must not discover.
+const OTHER_TAG: u32 = 0x6f74_6800;
+
+/// Replaces the tag of `note`, keeping its assets and recipient.
+///
+/// The tag only decides who fetches the note during sync. Who can consume it
+/// is still up to the recipient, here the P2ID script and its target.
+fn retag(note: Note, tag: NoteTag) -> Note {
+    let metadata = NoteMetadata::new(note.metadata().sender(), note.metadata().note_type(), tag);
+    Note::new(note.assets().clone(), metadata, note.recipient().clone())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Two clients with their own stores, so the receiver only ever learns about
+    // the notes through its own syncs
+    let mut profiles = ProfileManager::new(ClientSetup::from_cli()?);
+    let topic = NoteTag::new(TOPIC_TAG);
+    let other = NoteTag::new(OTHER_TAG);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: The receiver registers the tag it wants to hear about
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Registering tag {TOPIC_TAG:#010x} on the receiver");
+
+    let receiver = profiles.switch("Receiver").await?;
+    let wallet = receiver.wallet().await?;
+    say!("Receiver's account ID: {}", wallet.to_bech32(network_id()));
+    receiver.client.add_note_tag(topic).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: The sender publishes notes under the topic, and one under another tag
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Sending two notes tagged {TOPIC_TAG:#010x} and one tagged {OTHER_TAG:#010x}");
+
+    let sender = profiles.switch("Sender").await?;
+    let sender_wallet = create_basic_account(&mut sender.client, &sender.keystore)
+        .await?
+        .id();
+    let faucet = create_basic_faucet(&mut sender.client, &sender.keystore).await?;
+    top_up(&mut sender.client, faucet.id(), sender_wallet, 100).await?;
+
+    // P2ID notes would be tagged for the receiver's account, which every client
+    // tracking that account fetches anyway. Retagging them leaves the topic tag
+    // as the only way the receiver can find them
+    let mut notes = Vec::new();
+    for (amount, tag) in [(10, topic), (15, topic), (20, other)] {
+        let note = create_p2id_note(
+            sender_wallet,
+            wallet,
+            vec![FungibleAsset::new(faucet.id(), amount)?.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            sender.client.rng(),
+        )?;
+        notes.push(retag(note, tag));
+    }
+    let unregistered_id = notes[2].id();
+
+    let send_request = TransactionRequestBuilder::new()
+        .own_output_notes(notes.iter().cloned().map(OutputNote::Full).collect())
+        .build()?;
+    submit_and_wait(&mut sender.client, sender_wallet, send_request).await?;
+    say!("✅ Notes committed, nothing sent to the receiver directly");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: The receiver discovers the notes by syncing
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Syncing the receiver until both topic notes show up");
+
+    let receiver = profiles.switch("Receiver").await?;
+    let consumable = wait_for(&mut receiver.client, |notes| {
+        notes
+            .iter()
+            .filter(|(note, _)| note.metadata().is_some_and(|m| m.tag() == topic))
+            .count()
+            >= 2
+    })
+    .await?;
+    let found: Vec<Note> = consumable
+        .into_iter()
+        .filter(|(note, _)| note.metadata().is_some_and(|m| m.tag() == topic))
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<_, _>>()?;
+    for note in &found {
+        say!("Discovered note {} tagged {TOPIC_TAG:#010x}", note.id());
+    }
+
+    // All three notes landed in the same block, and the sync that found the
+    // topic notes skipped the third one
+    assert!(
+        receiver
+            .client
+            .get_input_note(unregistered_id)
+            .await?
+            .is_none(),
+        "a note under an unregistered tag should not be fetched"
+    );
+    say!("Note {unregistered_id} tagged {OTHER_TAG:#010x} was not fetched");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Consume what was discovered
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Consuming the discovered notes");
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes(found.into_iter().map(|note| (note, None)))
+        .build()?;
+    submit_and_wait(&mut receiver.client, wallet, consume_request).await?;
+
+    let balance = get_full_account(&mut receiver.client, wallet)
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    say!("✅ Receiver holds {balance} tokens");
+    assert_eq!(balance, 25, "only the topic notes should be consumed");
+
+    // Stop fetching notes under the topic once they are no longer wanted
+    receiver.client.remove_note_tag(topic).await?;
+
+    Ok(())
+}
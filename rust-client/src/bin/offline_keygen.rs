@@ -0,0 +1,32 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use miden_client::auth::AuthSecretKey;
+use rust_client::{error::report, keys::KeyFile, say};
+
+/// Generates a Falcon512 key pair without touching the network or the keystore.
+///
+/// Run it on the offline machine and carry the file over to import it.
+#[derive(Parser)]
+struct Cli {
+    /// Where to write the key file
+    output: PathBuf,
+}
+
+fn main() -> ExitCode {
+    report(run())
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let path = Cli::parse().output;
+
+    let secret_key = AuthSecretKey::new_falcon512_rpo();
+    let key_file = KeyFile::from_secret_key(&secret_key);
+    key_file.save(&path)?;
+
+    say!("Wrote key file to {}", path.display());
+    say!("Public key commitment: {}", key_file.public_key_commitment);
+    say!("Keep this file secret, it holds the secret key.");
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use clap::Parser;
+use std::{path::PathBuf, process::ExitCode};
+
+use rust_client::{
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    proving::{self, ProofBundle},
+    say,
+};
+
+/// Executes and proves a transaction without submitting it, and writes the
+/// proof for `submit_proven` to send from another machine.
+///
+/// Execution and proving read only the local store, so this side can run
+/// offline once its store has been synced; the keys never leave it.
+#[derive(Parser)]
+struct Cli {
+    /// Directory the proof bundle is written to
+    #[arg(long, default_value = "offline_tx")]
+    out_dir: PathBuf,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+
+    // The last network call of this side: afterwards the machine can go offline
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create Alice's account, keyed on this machine
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice's account");
+
+    let alice_account = proving::create_nonce_bump_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Execute and prove the transaction locally
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Executing and proving the transaction");
+
+    let request = proving::nonce_bump_request(&mut client)?;
+    let tx_result = client
+        .execute_transaction(alice_account.id(), request)
+        .await?;
+    let proven_transaction = client.prove_transaction(&tx_result).await?;
+    say!("✅ Proved transaction {}", proven_transaction.id().to_hex());
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Write the proof bundle
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Writing the proof to {}", cli.out_dir.display());
+
+    // This store is not updated: the transaction only counts once it is
+    // submitted, so Alice's account here stays at the state the proof starts from
+    ProofBundle {
+        account: alice_account,
+        proven_transaction,
+        tx_result,
+    }
+    .write(&cli.out_dir)?;
+    say!(
+        "✅ Wrote {}, {} and {}",
+        ProofBundle::ACCOUNT_FILE,
+        ProofBundle::PROVEN_FILE,
+        ProofBundle::RESULT_FILE
+    );
+    say!(
+        "Submit it with: cargo run --bin submit_proven -- --bundle {}",
+        cli.out_dir.display()
+    );
+
+    Ok(())
+}
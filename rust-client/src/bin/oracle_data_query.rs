@@ -1,25 +1,25 @@
+use std::process::ExitCode;
+
 use miden_client::{
-    account::{
-        AccountBuilder, AccountComponent, AccountId, AccountStorageMode, AccountType, StorageSlot,
-        StorageSlotName, StorageSlotType,
-    },
-    assembly::{
-        Assembler, CodeBuilder, DefaultSourceManager, Module, ModuleKind, Path as AssemblyPath,
-    },
-    auth::NoAuth,
-    builder::ClientBuilder,
+    account::{AccountId, StorageSlot, StorageSlotName, StorageSlotType},
     keystore::FilesystemKeyStore,
-    rpc::{
-        domain::account::{AccountStorageRequirements, StorageMapKey},
-        Endpoint, GrpcClient,
-    },
-    store::AccountRecordData,
-    transaction::{ForeignAccount, TransactionKernel, TransactionRequestBuilder},
+    rpc::domain::account::{AccountStorageRequirements, StorageMapKey},
+    transaction::{ForeignAccount, TransactionRequestBuilder},
     Client, ClientError, Word,
 };
-use miden_client_sqlite_store::ClientBuilderSqliteExt;
-use rand::RngCore;
-use std::{fs, path::Path, sync::Arc};
+use rust_client::{
+    accounts::get_full_account,
+    aliases::lookup_alias,
+    client::init_client_from_config,
+    deployer::ContractDeployer,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    say,
+    wait::submit_and_wait,
+};
+
+/// Pragma oracle on testnet, used unless an `oracle` alias names another one.
+const ORACLE_ID: &str = "mtst1qq0zffxzdykm7qqqqdt24cc2du5ghx99";
 
 /// Import the oracle + its publishers and return the ForeignAccount list
 /// Due to Pragma's decentralized oracle architecture, we need to get the
@@ -31,16 +31,7 @@ pub async fn get_oracle_foreign_accounts(
 ) -> Result<Vec<ForeignAccount>, ClientError> {
     client.import_account_by_id(oracle_account_id).await?;
 
-    let oracle_record = client
-        .get_account(oracle_account_id)
-        .await
-        .expect("RPC failed")
-        .expect("oracle account not found");
-
-    let oracle_account = match oracle_record.account_data() {
-        AccountRecordData::Full(account) => account,
-        AccountRecordData::Partial(_) => panic!("oracle account is missing full account data"),
-    };
+    let oracle_account = get_full_account(client, oracle_account_id).await?;
     let storage = oracle_account.storage();
     let publisher_count_slot = storage
         .slots()
@@ -82,17 +73,7 @@ pub async fn get_oracle_foreign_accounts(
     for pid in publisher_ids {
         client.import_account_by_id(pid).await?;
 
-        let publisher_record = client
-            .get_account(pid)
-            .await
-            .expect("RPC failed")
-            .expect("publisher account not found");
-        let publisher_account = match publisher_record.account_data() {
-            AccountRecordData::Full(account) => account,
-            AccountRecordData::Partial(_) => {
-                panic!("publisher account is missing full account data")
-            }
-        };
+        let publisher_account = get_full_account(client, pid).await?;
         let map_slot_names: Vec<StorageSlotName> = publisher_account
             .storage()
             .slots()
@@ -118,55 +99,33 @@ pub async fn get_oracle_foreign_accounts(
     Ok(foreign_accounts)
 }
 
-fn create_library(
-    assembler: Assembler,
-    library_path: &str,
-    source_code: &str,
-) -> Result<miden_client::assembly::Library, Box<dyn std::error::Error>> {
-    let source_manager = Arc::new(DefaultSourceManager::default());
-    let module = Module::parser(ModuleKind::Library).parse_str(
-        AssemblyPath::new(library_path),
-        source_code,
-        source_manager.clone(),
-    )?;
-    let library = assembler.clone().assemble_library([module])?;
-    Ok(library)
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
 }
 
-#[tokio::main]
-async fn main() -> Result<(), ClientError> {
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // -------------------------------------------------------------------------
     // Initialize Client
     // -------------------------------------------------------------------------
-    let endpoint = Endpoint::devnet();
-    let timeout_ms = 10_000;
-    let rpc_client = Arc::new(GrpcClient::new(&endpoint, timeout_ms));
-
-    let keystore_path = std::path::PathBuf::from("./keystore");
-    let keystore = Arc::new(FilesystemKeyStore::new(keystore_path).unwrap());
+    let (mut client, _keystore) = init_client_from_config().await?;
 
-    let store_path = std::path::PathBuf::from("./store.sqlite3");
-
-    let mut client = ClientBuilder::new()
-        .rpc(rpc_client)
-        .sqlite_store(store_path)
-        .authenticator(keystore.clone())
-        .in_debug_mode(true.into())
-        .build()
-        .await?;
-
-    println!("Latest block: {}", client.sync_state().await?.block_num);
+    say!("Latest block: {}", client.sync_state().await?.block_num);
 
     // -------------------------------------------------------------------------
     // Get all foreign accounts for oracle data
     // -------------------------------------------------------------------------
-    let oracle_bech32 = "mtst1qq0zffxzdykm7qqqqdt24cc2du5ghx99";
-    let (_, oracle_account_id) = AccountId::from_bech32(oracle_bech32).unwrap();
+    // An `oracle` alias points the tutorial at another deployment of the oracle
+    let oracle_account_id = match lookup_alias("oracle")? {
+        Some(id) => id,
+        None => AccountId::from_bech32(ORACLE_ID).unwrap().1,
+    };
     let btc_usd_pair_id = 120195681;
     let foreign_accounts: Vec<ForeignAccount> =
         get_oracle_foreign_accounts(&mut client, oracle_account_id, btc_usd_pair_id).await?;
 
-    println!(
+    say!(
         "Oracle accountId prefix: {:?} suffix: {:?}",
         oracle_account_id.prefix(),
         oracle_account_id.suffix()
@@ -175,57 +134,28 @@ async fn main() -> Result<(), ClientError> {
     // -------------------------------------------------------------------------
     // Create Oracle Reader contract
     // -------------------------------------------------------------------------
-    let contract_code =
-        fs::read_to_string(Path::new("../masm/accounts/oracle_reader.masm")).unwrap();
-
     let contract_slot_name =
         StorageSlotName::new("miden::tutorials::oracle_reader").expect("valid slot name");
-    let contract_component_code = CodeBuilder::new()
-        .compile_component_code("external_contract::oracle_reader", &contract_code)
-        .unwrap();
-    let contract_component = AccountComponent::new(
-        contract_component_code,
-        vec![StorageSlot::with_value(
-            contract_slot_name.clone(),
-            Word::default(),
-        )],
+    let contract_deployer = ContractDeployer::from_path(
+        "../masm/accounts/oracle_reader.masm",
+        "external_contract::oracle_reader",
     )
-    .unwrap()
-    .with_supports_all_types();
-
-    let mut seed = [0_u8; 32];
-    client.rng().fill_bytes(&mut seed);
-
-    let oracle_reader_contract = AccountBuilder::new(seed)
-        .account_type(AccountType::RegularAccountImmutableCode)
-        .storage_mode(AccountStorageMode::Public)
-        .with_component(contract_component.clone())
-        .with_auth_component(NoAuth)
-        .build()
-        .unwrap();
-
-    client
-        .add_account(&oracle_reader_contract, false)
-        .await
-        .unwrap();
+    .with_slot(StorageSlot::with_value(contract_slot_name, Word::default()));
+    let account_component_lib = contract_deployer.library()?;
+    let oracle_reader_contract = contract_deployer.deploy(&mut client).await?;
 
     // -------------------------------------------------------------------------
     // Build the script that calls our `get_price` procedure
     // -------------------------------------------------------------------------
-    let script_path = Path::new("../masm/scripts/oracle_reader_script.masm");
-    let script_code = fs::read_to_string(script_path).unwrap();
-
-    let assembler = TransactionKernel::assembler();
-    let library_path = "external_contract::oracle_reader";
-    let account_component_lib =
-        create_library(assembler.clone(), library_path, &contract_code).unwrap();
-
-    let tx_script = client
-        .code_builder()
-        .with_dynamically_linked_library(&account_component_lib)
-        .unwrap()
-        .compile_tx_script(&script_code)
-        .unwrap();
+    let script_file = MasmFile::read("../masm/scripts/oracle_reader_script.masm");
+
+    let tx_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&account_component_lib)
+            .unwrap()
+            .compile_tx_script(&script_file.source),
+    )?;
 
     let tx_increment_request = TransactionRequestBuilder::new()
         .foreign_accounts(foreign_accounts)
@@ -233,17 +163,13 @@ async fn main() -> Result<(), ClientError> {
         .build()
         .unwrap();
 
-    let tx_id = client
-        .submit_new_transaction(oracle_reader_contract.id(), tx_increment_request)
-        .await
-        .unwrap();
-
-    println!(
-        "View transaction on MidenScan: https://testnet.midenscan.com/tx/{:?}",
-        tx_id
-    );
-
-    client.sync_state().await.unwrap();
+    submit_and_wait(
+        &mut client,
+        oracle_reader_contract.id(),
+        tx_increment_request,
+    )
+    .await
+    .unwrap();
 
     Ok(())
 }
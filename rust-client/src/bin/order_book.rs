@@ -0,0 +1,235 @@
+use std::process::ExitCode;
+
+use tokio::time::{sleep, Duration};
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    note::{NoteTag, NoteType},
+    transaction::TransactionRequestBuilder,
+};
+use rust_client::{
+    accounts::{balance, create_basic_faucet},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    notes::{consume_all, ConsumeOptions},
+    order_book::{post_order, settle, OrderBook, ORDER_BOOK_FILE, ORDER_TAG},
+    profiles::{Profile, ProfileManager},
+    say,
+    wait::{submit_and_wait, wait_for_notes, wait_for_tx},
+};
+
+/// How often the taker syncs before giving up on the posted orders.
+const MAX_POLLS: usize = 20;
+
+/// Waits for `expected` notes and consumes the ones carrying the given tokens.
+async fn collect(
+    profile: &mut Profile,
+    faucets: &[AccountId],
+    expected: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let wallet = profile.wallet().await?;
+    wait_for_notes(&mut profile.client, wallet, expected).await?;
+    let options = ConsumeOptions::default().with_known_faucets(faucets.iter().copied());
+    if let Some(tx_id) = consume_all(&mut profile.client, wallet, &options).await? {
+        wait_for_tx(&mut profile.client, tx_id).await?;
+    }
+    Ok(())
+}
+
+/// Returns the balance of the profile's wallet in `faucet_id`'s token.
+async fn wallet_balance(
+    profile: &mut Profile,
+    faucet_id: AccountId,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let wallet = profile.wallet().await?;
+    Ok(balance(&mut profile.client, wallet, faucet_id).await?)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Makers and the taker each run a client of their own, so the taker only
+    // learns about the orders through the tag it syncs
+    let mut profiles = ProfileManager::new(ClientSetup::from_cli()?);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Open the profiles of the two makers and the taker
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Opening the profiles of Alice and Bob the makers and Carol the taker");
+
+    let mut wallets = Vec::new();
+    for name in ["Alice", "Bob", "Carol"] {
+        let wallet = profiles.switch(name).await?.wallet().await?;
+        say!("{name}'s wallet: {:?}", wallet.to_bech32(network_id()));
+        wallets.push(wallet);
+    }
+    let [alice_wallet, bob_wallet, carol_wallet] = wallets[..] else {
+        unreachable!("three profiles were opened");
+    };
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice issues token A for the makers and token B for the taker
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting 100 A for Alice and Bob each, and 200 B for Carol");
+
+    let alice = profiles.switch("Alice").await?;
+    let token_a = create_basic_faucet(&mut alice.client, &alice.keystore)
+        .await?
+        .id();
+    say!("Token A faucet: {:?}", token_a.to_bech32(network_id()));
+    let token_b = create_basic_faucet(&mut alice.client, &alice.keystore)
+        .await?
+        .id();
+    say!("Token B faucet: {:?}", token_b.to_bech32(network_id()));
+
+    for (faucet_id, wallet, amount) in [
+        (token_a, alice_wallet, 100),
+        (token_a, bob_wallet, 100),
+        (token_b, carol_wallet, 200),
+    ] {
+        let mint_request = TransactionRequestBuilder::new().build_mint_fungible_asset(
+            FungibleAsset::new(faucet_id, amount)?,
+            wallet,
+            NoteType::Public,
+            alice.client.rng(),
+        )?;
+        submit_and_wait(&mut alice.client, faucet_id, mint_request).await?;
+    }
+    for name in ["Alice", "Bob", "Carol"] {
+        collect(profiles.switch(name).await?, &[token_a, token_b], 1).await?;
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 3: The makers post their orders
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice offers 40 A for 60 B, Bob 40 A for 50 B and 20 A for 40 B");
+
+    // Every order is a swap note under the market's tag, not addressed to anyone
+    let mut posted = Vec::new();
+    for (name, wallet, offered, requested) in [
+        ("Alice", alice_wallet, 40, 60),
+        ("Bob", bob_wallet, 40, 50),
+        ("Bob", bob_wallet, 20, 40),
+    ] {
+        let maker = profiles.switch(name).await?;
+        let order = post_order(
+            &mut maker.client,
+            wallet,
+            FungibleAsset::new(token_a, offered)?,
+            FungibleAsset::new(token_b, requested)?,
+        )
+        .await?;
+        say!("{name} posted order {}", order.id());
+        posted.push(order.id());
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Carol discovers the orders by their tag and saves the book
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Carol syncs tag {ORDER_TAG:#010x} until all three orders show up");
+
+    let carol = profiles.switch("Carol").await?;
+    carol.client.add_note_tag(NoteTag::new(ORDER_TAG)).await?;
+    let discovered = |book: &OrderBook| {
+        posted
+            .iter()
+            .all(|id| book.orders().iter().any(|order| order.id() == *id))
+    };
+    let mut book = OrderBook::open_default()?;
+    for _ in 0..MAX_POLLS {
+        let new = book.refresh(&mut carol.client, carol_wallet).await?;
+        if new > 0 {
+            say!("Discovered {new} new orders");
+        }
+        if discovered(&book) {
+            break;
+        }
+        sleep(Duration::from_secs(3)).await;
+    }
+    assert!(
+        discovered(&book),
+        "Carol did not discover every posted order"
+    );
+    book.save()?;
+    for order in book.orders() {
+        say!(
+            "  {} sells {} A for {} B",
+            order.maker().to_bech32(network_id()),
+            order.offered.amount(),
+            order.requested.amount()
+        );
+    }
+    say!("Saved {} orders to {ORDER_BOOK_FILE}", book.orders().len());
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Carol matches against the best order her budget covers
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Carol buys A with up to 60 B at the best price on the book");
+
+    // The book is read back from disk, as a taker running later would do
+    let mut book = OrderBook::open_default()?;
+    let best = book
+        .best_match(token_a, FungibleAsset::new(token_b, 60)?)
+        .cloned()
+        .ok_or("no order on the book matches Carol's budget")?;
+    assert_eq!(
+        best.id(),
+        posted[1],
+        "Bob's 40 A for 50 B is the best price"
+    );
+    say!(
+        "Best order: {} A for {} B from {}",
+        best.offered.amount(),
+        best.requested.amount(),
+        best.maker().to_bech32(network_id())
+    );
+
+    let report = settle(&mut carol.client, carol_wallet, &best).await?;
+    assert!(report.is_committed(), "the settlement was not committed");
+    book.remove(best.id());
+    book.save()?;
+
+    // -------------------------------------------------------------------------
+    // STEP 6: Bob collects the payback and the balances are checked
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Bob collects his payment and every side is checked");
+
+    collect(profiles.switch("Bob").await?, &[token_b], 1).await?;
+    let bob = profiles.switch("Bob").await?;
+    let bob_b = wallet_balance(bob, token_b).await?;
+    let bob_a = wallet_balance(bob, token_a).await?;
+    let carol = profiles.switch("Carol").await?;
+    let carol_a = wallet_balance(carol, token_a).await?;
+    let carol_b = wallet_balance(carol, token_b).await?;
+    say!("Bob holds {bob_a} A and {bob_b} B, Carol holds {carol_a} A and {carol_b} B");
+    // Bob's other order still holds 20 A until somebody fills it
+    assert_eq!(
+        (bob_a, bob_b),
+        (40, 50),
+        "Bob should have sold 40 A for 50 B"
+    );
+    assert_eq!(
+        (carol_a, carol_b),
+        (40, 150),
+        "Carol should have bought 40 A for 50 B"
+    );
+
+    // A filled note is no longer consumable, so a refresh agrees with the book
+    book.refresh(&mut carol.client, carol_wallet).await?;
+    assert!(
+        book.orders().iter().all(|order| order.id() != best.id()),
+        "the filled order should be gone from the book"
+    );
+    say!(
+        "✅ Order settled, {} orders left on the book",
+        book.orders().len()
+    );
+    book.save()?;
+
+    Ok(())
+}
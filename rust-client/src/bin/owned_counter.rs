@@ -0,0 +1,168 @@
+use std::{error::Error, process::ExitCode};
+
+use miden_client::{
+    account::{AccountId, StorageSlot, StorageSlotName},
+    assembly::Library,
+    keystore::FilesystemKeyStore,
+    note::NoteScript,
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, get_full_account},
+    client::{init_client_from_config, network_id},
+    deployer::ContractDeployer,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    say,
+    wait::{submit_and_wait, TxReport},
+};
+
+const COUNTER_SLOT: &str = "miden::tutorials::owned_counter::count";
+const OWNER_SLOT: &str = "miden::tutorials::owned_counter::owner";
+/// `ERR_NOT_OWNER` of `owned_counter.masm`.
+const ERR_NOT_OWNER: &str = "Only the owner can increment the counter";
+
+/// Sends an increment note from `sender` and has the counter consume it.
+async fn increment(
+    client: &mut Client<FilesystemKeyStore>,
+    library: &Library,
+    counter_id: AccountId,
+    sender: AccountId,
+) -> Result<TxReport, Box<dyn std::error::Error>> {
+    let file = MasmFile::read("../masm/notes/owned_increment_note.masm");
+    let script: NoteScript = file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(library)?
+            .compile_note_script(&file.source),
+    )?;
+    let note = NoteBuilder::new(sender, script)
+        .target(counter_id)
+        .build(&mut SerialNumbers::random(), client.rng())?;
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note.clone())])
+        .build()?;
+    submit_and_wait(client, sender, note_request).await?;
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(note, None)])
+        .build()?;
+    Ok(submit_and_wait(client, counter_id, consume_request).await?)
+}
+
+/// Whether the counter's owner check is what failed `err`, rather than the
+/// network or anything else about the transaction.
+fn is_owner_check_failure(err: &(dyn Error + 'static)) -> bool {
+    let mut cause = Some(err);
+    while let Some(current) = cause {
+        // The assertion message sits deep inside the executor's error
+        if let Some(err @ ClientError::TransactionExecutorError(_)) = current.downcast_ref() {
+            return format!("{err:?}").contains(ERR_NOT_OWNER);
+        }
+        cause = current.source();
+    }
+    false
+}
+
+/// The counter's value, as of the last sync.
+async fn count(
+    client: &mut Client<FilesystemKeyStore>,
+    counter_id: AccountId,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let account = get_full_account(client, counter_id).await?;
+    let count = account
+        .storage()
+        .get_item(&StorageSlotName::new(COUNTER_SLOT)?)?;
+    // The count sits on top of the stack, which is the last element of the word
+    Ok(count[3].as_int())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the owner, a stranger and a counter only the owner can move
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Deploying a counter owned by Alice");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    // The owner is on top of the stack when the slot is read, so it goes last
+    let owner = Word::from([
+        Felt::new(0),
+        Felt::new(0),
+        alice_account.id().suffix(),
+        alice_account.id().prefix().as_felt(),
+    ]);
+    let deployer = ContractDeployer::from_path(
+        "../masm/accounts/owned_counter.masm",
+        "external_contract::owned_counter",
+    )
+    .with_slots([
+        StorageSlot::with_value(StorageSlotName::new(COUNTER_SLOT)?, Word::default()),
+        StorageSlot::with_value(StorageSlotName::new(OWNER_SLOT)?, owner),
+    ]);
+    let library = deployer.library()?;
+    let counter = deployer.deploy(&mut client).await?;
+    say!(
+        "Counter contract ID: {:?}",
+        counter.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: The owner increments the counter
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice increments the counter");
+
+    let report = increment(&mut client, &library, counter.id(), alice_account.id()).await?;
+    assert!(
+        report.is_committed(),
+        "the owner's increment was not committed"
+    );
+    let value = count(&mut client, counter.id()).await?;
+    say!("✅ Count is now {value}");
+    assert_eq!(value, 1, "the owner's increment should count");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Anyone else is turned away
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob tries to increment the counter");
+
+    match increment(&mut client, &library, counter.id(), bob_account.id()).await {
+        Ok(report) if report.is_committed() => panic!("the counter accepted Bob's increment"),
+        Ok(_) => say!("Bob's increment discarded as expected"),
+        Err(err) if is_owner_check_failure(err.as_ref()) => {
+            say!("Rejected as expected: {}", err)
+        }
+        Err(err) => return Err(err),
+    }
+
+    client.sync_state().await?;
+    let value = count(&mut client, counter.id()).await?;
+    say!("✅ Count is still {value}");
+    assert_eq!(value, 1, "a stranger's increment should not count");
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    block::BlockNumber,
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteType},
+    transaction::{PaymentNoteDescription, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::{balance, create_basic_account, create_basic_faucet, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    say,
+    wait::{submit_and_wait, wait_for_block, WaitOptions},
+};
+
+/// Blocks the sender waits before it may take back an unclaimed note.
+const RECLAIM_DELAY: u32 = 10;
+
+/// Sends `asset` from `sender` to `target` in a P2IDE note that `sender` can
+/// reclaim from `reclaim_block` on.
+async fn send_p2ide(
+    client: &mut Client<FilesystemKeyStore>,
+    sender: AccountId,
+    target: AccountId,
+    asset: FungibleAsset,
+    reclaim_block: u32,
+) -> Result<Note, Box<dyn std::error::Error>> {
+    let payment = PaymentNoteDescription::new(vec![asset.into()], sender, target)
+        .with_reclaim_height(BlockNumber::from(reclaim_block));
+    let request = TransactionRequestBuilder::new().build_pay_to_id(
+        payment,
+        NoteType::Public,
+        client.rng(),
+    )?;
+    let note = request
+        .expected_output_own_notes()
+        .pop()
+        .expect("a payment request creates one note");
+    say!(
+        "P2IDE note {} carries {} tokens, reclaimable from block {}",
+        note.id().to_hex(),
+        asset.amount(),
+        reclaim_block
+    );
+    submit_and_wait(client, sender, request).await?;
+    Ok(note)
+}
+
+/// Has `account_id` consume `note`.
+async fn consume(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    note: Note,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(note, None)])
+        .build()?;
+    submit_and_wait(client, account_id, consume_request).await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Bob consumes a P2IDE note like any P2ID note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice sends Bob 30 tokens and Bob consumes them");
+
+    let current_block = client.get_sync_height().await?.as_u32();
+    let note = send_p2ide(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        FungibleAsset::new(faucet.id(), 30)?,
+        current_block + 100,
+    )
+    .await?;
+    consume(&mut client, bob_account.id(), note).await?;
+
+    let alice_balance = balance(&mut client, alice_account.id(), faucet.id()).await?;
+    let bob_balance = balance(&mut client, bob_account.id(), faucet.id()).await?;
+    say!("✅ Alice holds {alice_balance}, Bob holds {bob_balance}");
+    assert_eq!(alice_balance, 70, "Alice should have paid 30");
+    assert_eq!(bob_balance, 30, "Bob should have received 30");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice cannot take back a note before its reclaim block
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice sends Bob 20 more tokens and tries to reclaim them at once");
+
+    let current_block = client.get_sync_height().await?.as_u32();
+    let reclaim_block = current_block + RECLAIM_DELAY;
+    let note = send_p2ide(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        FungibleAsset::new(faucet.id(), 20)?,
+        reclaim_block,
+    )
+    .await?;
+
+    // Executing locally is enough to see the failure, nothing is submitted
+    let height = client.sync_state().await?.block_num.as_u32();
+    if height < reclaim_block {
+        let reclaim_request = TransactionRequestBuilder::new()
+            .input_notes([(note.clone(), None)])
+            .build()?;
+        match client
+            .execute_transaction(alice_account.id(), reclaim_request)
+            .await
+        {
+            Ok(_) => panic!("the sender should not reclaim before block {reclaim_block}"),
+            Err(err) => say!("Rejected as expected at block {height}: {}", err),
+        }
+    } else {
+        say!("Block {height} is already past the reclaim block, skipping the early attempt");
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Bob never claims it, so Alice reclaims it
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Waiting for block {reclaim_block}, then Alice reclaims the note");
+
+    wait_for_block(
+        &mut client,
+        reclaim_block,
+        &WaitOptions::for_blocks(RECLAIM_DELAY),
+    )
+    .await?;
+    consume(&mut client, alice_account.id(), note).await?;
+
+    let alice_balance = balance(&mut client, alice_account.id(), faucet.id()).await?;
+    let bob_balance = balance(&mut client, bob_account.id(), faucet.id()).await?;
+    say!("✅ Alice holds {alice_balance}, Bob holds {bob_balance}");
+    assert_eq!(alice_balance, 70, "Alice should have her 20 tokens back");
+    assert_eq!(
+        bob_balance, 30,
+        "Bob should not have received the reclaimed note"
+    );
+
+    Ok(())
+}
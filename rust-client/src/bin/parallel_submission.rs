@@ -0,0 +1,214 @@
+use clap::Parser;
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use tokio::time::Instant;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::NoteType,
+    transaction::{TransactionId, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    middleware::submit_logged,
+    say,
+    wait::{submit_and_wait, wait_for_notes, wait_for_tx},
+};
+
+/// Mints from several faucets one after another and then from one task per
+/// faucet at the same time, and compares the wall-clock times.
+#[derive(Parser)]
+struct Cli {
+    /// Independent faucets, one task each in the parallel run
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=16))]
+    accounts: u16,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// Tokens every mint sends to the recipient.
+const AMOUNT: u64 = 10;
+
+/// Store of the client used by worker `index`, next to the main store.
+fn worker_store_path(setup: &ClientSetup, index: usize) -> PathBuf {
+    setup
+        .store_path
+        .with_file_name(format!("parallel_worker_{index}.sqlite3"))
+}
+
+/// Builds the client of one worker and starts tracking its faucet.
+///
+/// The worker has a store of its own but the keystore directory of the main
+/// client, so it signs with the key the main client generated for the faucet.
+async fn open_worker(
+    setup: ClientSetup,
+    index: usize,
+    faucet: AccountId,
+) -> Result<Client<FilesystemKeyStore>, String> {
+    let store_path = worker_store_path(&setup, index);
+    // Start from an empty store, so the faucet is read from the chain
+    let _ = fs::remove_file(&store_path);
+    let (mut client, _keystore) = ClientSetup {
+        store_path,
+        // Scratch stores, and store encryption is installed once per process
+        store_passphrase: None,
+        ..setup
+    }
+    .build()
+    .await
+    .map_err(|err| err.to_string())?;
+    client.sync_state().await.map_err(|err| err.to_string())?;
+    client
+        .import_account_by_id(faucet)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(client)
+}
+
+/// Mints to `recipient` from `faucet` and waits for the transaction to be committed.
+async fn mint(
+    client: &mut Client<FilesystemKeyStore>,
+    faucet: AccountId,
+    recipient: AccountId,
+) -> Result<TransactionId, String> {
+    let request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet, AMOUNT).map_err(|err| err.to_string())?,
+            recipient,
+            NoteType::Public,
+            client.rng(),
+        )
+        .map_err(|err| err.to_string())?;
+    let tx_id = submit_logged(client, faucet, request)
+        .await
+        .map_err(|err| err.to_string())?;
+    wait_for_tx(client, tx_id)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(tx_id)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let accounts = usize::from(cli.accounts);
+
+    // Initialize client
+    let setup = ClientSetup::from_args(cli.client)?;
+    let (mut client, keystore) = setup.clone().build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the faucets and deploy them with a first mint
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating {accounts} faucets and a recipient");
+
+    let recipient = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Recipient's account ID: {:?}",
+        recipient.id().to_bech32(network_id())
+    );
+    let mut faucets = Vec::with_capacity(accounts);
+    for _ in 0..accounts {
+        let faucet = create_basic_faucet(&mut client, &keystore).await?;
+        say!(
+            "Faucet account ID: {:?}",
+            faucet.id().to_bech32(network_id())
+        );
+        // The first transaction deploys the faucet, so the workers can import
+        // it and both timed runs send regular transactions
+        let request = TransactionRequestBuilder::new().build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), AMOUNT)?,
+            recipient.id(),
+            NoteType::Public,
+            client.rng(),
+        )?;
+        submit_and_wait(&mut client, faucet.id(), request).await?;
+        faucets.push(faucet.id());
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Mint from every faucet, one after another
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting from {accounts} faucets sequentially");
+
+    // Like the loop in `unauthenticated_note_transfer`, one client sends the
+    // transactions one at a time, so every proof waits for the one before it
+    let start = Instant::now();
+    for &faucet in &faucets {
+        let tx_id = mint(&mut client, faucet, recipient.id()).await?;
+        say!("[sequential] {} committed", tx_id.to_hex());
+    }
+    let sequential = start.elapsed();
+    say!("✅ Sequential run took {sequential:.2?}");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Mint from every faucet at the same time, one task each
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Minting from {accounts} faucets in parallel");
+
+    // A `Client` needs `&mut` to touch its store, so every task gets a client
+    // and a SQLite file of its own. The accounts are independent, so no two
+    // tasks ever build on the same account state. Only the keystore is shared:
+    // the keys were written before the tasks start and are only read by them.
+    let workers: Vec<_> = faucets
+        .iter()
+        .enumerate()
+        .map(|(index, &faucet)| tokio::spawn(open_worker(setup.clone(), index, faucet)))
+        .collect();
+    let mut clients = Vec::with_capacity(accounts);
+    for worker in workers {
+        clients.push(worker.await??);
+    }
+
+    let recipient_id = recipient.id();
+    let start = Instant::now();
+    let tasks: Vec<_> = clients
+        .into_iter()
+        .zip(faucets.iter().copied())
+        .enumerate()
+        .map(|(index, (mut client, faucet))| {
+            tokio::spawn(async move {
+                let tx_id = mint(&mut client, faucet, recipient_id).await?;
+                say!("[worker {index}] {} committed", tx_id.to_hex());
+                Ok::<_, String>(())
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await??;
+    }
+    let parallel = start.elapsed();
+    say!("✅ Parallel run took {parallel:.2?}");
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Check the notes and compare both runs
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Comparing both runs ({accounts} transactions each)");
+
+    // Deployment, sequential and parallel mints
+    wait_for_notes(&mut client, recipient.id(), 3 * accounts).await?;
+    say!("✅ The recipient received all {} notes", 3 * accounts);
+
+    say!("{:<12} {:>12}", "run", "wall-clock");
+    say!("{:<12} {:>12}", "sequential", format!("{sequential:.2?}"));
+    say!("{:<12} {:>12}", "parallel", format!("{parallel:.2?}"));
+    say!(
+        "Speedup: {:.1}x",
+        sequential.as_secs_f64() / parallel.as_secs_f64()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use std::{fs, path::Path, process::ExitCode, time::Duration};
+use tokio::time::Instant;
+
+use clap::Parser;
+use miden_client::{
+    account::{AccountId, StorageSlot, StorageSlotName},
+    keystore::FilesystemKeyStore,
+    rpc::domain::account::AccountStorageRequirements,
+    store::{AccountRecord, AccountRecordData},
+    transaction::{ForeignAccount, TransactionRequestBuilder, TransactionScript},
+    Client, Felt, Word,
+};
+use rust_client::{
+    client::{parse_account_id, ClientArgs, ClientSetup},
+    deployer::ContractDeployer,
+    deployments,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    say,
+    wait::submit_and_wait,
+};
+
+/// Reads the counter deployed by `counter_contract_deploy` through FPI.
+#[derive(Parser)]
+struct Cli {
+    /// Bech32 ID of the counter contract [default: the one in the deployments registry]
+    #[arg(long, value_parser = parse_account_id)]
+    counter_id: Option<AccountId>,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// Resources spent by one way of reading the foreign account.
+struct UsageReport {
+    name: &'static str,
+    elapsed: Duration,
+    store_growth: u64,
+}
+
+/// Size of the SQLite store on disk, including its write-ahead log.
+fn store_size(store_path: &Path) -> u64 {
+    let wal_path = store_path.with_extension("sqlite3-wal");
+    [store_path, wal_path.as_path()]
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Prints what the client knows about an account, treating partial data as a valid state.
+fn describe_record(label: &str, record: Option<AccountRecord>) {
+    match record.as_ref().map(|record| record.account_data()) {
+        None => say!("{label}: not tracked, nothing stored locally"),
+        Some(AccountRecordData::Full(account)) => say!(
+            "{label}: full state, nonce {}, {} storage slot(s), {} asset(s)",
+            account.nonce(),
+            account.storage().slots().len(),
+            account.vault().assets().count()
+        ),
+        // Only the commitments and the requested slots are known, which is all FPI needs
+        Some(AccountRecordData::Partial(account)) => say!(
+            "{label}: partial state, nonce {}, commitment {}",
+            account.nonce(),
+            account.commitment().to_hex()
+        ),
+    }
+}
+
+/// Calls `copy_count` on the reader, reading the counter through FPI.
+async fn read_counter(
+    client: &mut Client<FilesystemKeyStore>,
+    reader_id: AccountId,
+    tx_script: TransactionScript,
+    foreign_account: ForeignAccount,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tx_request = TransactionRequestBuilder::new()
+        .foreign_accounts([foreign_account])
+        .custom_script(tx_script)
+        .build()
+        .unwrap();
+    submit_and_wait(client, reader_id, tx_request).await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let cli = Cli::parse();
+    let setup = ClientSetup::from_args(cli.client)?;
+    let store_path = setup.store_path.clone();
+    let (mut client, _keystore) = setup.build().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the Count Reader Contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating count reader contract.");
+
+    let count_reader_slot_name =
+        StorageSlotName::new("miden::tutorials::count_reader").expect("valid slot name");
+    let count_reader_deployer = ContractDeployer::from_path(
+        "../masm/accounts/count_reader.masm",
+        "external_contract::count_reader_contract",
+    )
+    .with_slot(StorageSlot::with_value(
+        count_reader_slot_name,
+        Word::default(),
+    ));
+    let account_component_lib = count_reader_deployer.library()?;
+    let count_reader_contract = count_reader_deployer.deploy(&mut client).await?;
+    say!("count reader id: {:?}", count_reader_contract.id());
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Prepare the FPI script
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Preparing the FPI script");
+
+    // The counter recorded by counter_contract_deploy, unless given with --counter-id
+    let counter_contract_id = deployments::resolve(
+        cli.counter_id,
+        deployments::COUNTER,
+        "counter_contract_deploy",
+    )?;
+
+    let counter_library = MasmFile::read("../masm/accounts/counter.masm")
+        .library("external_contract::counter_contract")?;
+    let get_count_hash = counter_library
+        .get_procedure_root_by_path("external_contract::counter_contract::get_count")
+        .expect("get_count export not found")
+        .as_elements()
+        .iter()
+        .map(|f: &Felt| format!("{}", f.as_int()))
+        .collect::<Vec<_>>()
+        .join(".");
+
+    let script_template = MasmFile::read("../masm/scripts/reader_script.masm");
+    let script_code = script_template
+        .source
+        .replace("{get_count_proc_hash}", &get_count_hash)
+        .replace(
+            "{account_id_suffix}",
+            &counter_contract_id.suffix().to_string(),
+        )
+        .replace(
+            "{account_id_prefix}",
+            &counter_contract_id.prefix().to_string(),
+        );
+    let script_file = script_template.with_source(script_code);
+    let tx_script = script_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&account_component_lib)
+            .unwrap()
+            .compile_tx_script(&script_file.source),
+    )?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: FPI without tracking the counter contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Reading the counter through FPI without importing it");
+
+    // The storage requirements list the map entries the script touches. The
+    // counter keeps its value in a value slot, so no map entries are needed and
+    // the node only returns the account header, code and value slots.
+    let size_before = store_size(&store_path);
+    let start = Instant::now();
+    let foreign_account =
+        ForeignAccount::public(counter_contract_id, AccountStorageRequirements::default()).unwrap();
+    read_counter(
+        &mut client,
+        count_reader_contract.id(),
+        tx_script.clone(),
+        foreign_account,
+    )
+    .await?;
+    let lazy = UsageReport {
+        name: "lazy (FPI only)",
+        elapsed: start.elapsed(),
+        store_growth: store_size(&store_path).saturating_sub(size_before),
+    };
+    describe_record(
+        "counter contract after lazy read",
+        client.get_account(counter_contract_id).await?,
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Import the full counter contract and read it again
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Importing the full counter contract and reading it again");
+
+    let size_before = store_size(&store_path);
+    let start = Instant::now();
+    client.import_account_by_id(counter_contract_id).await?;
+    let foreign_account =
+        ForeignAccount::public(counter_contract_id, AccountStorageRequirements::default()).unwrap();
+    read_counter(
+        &mut client,
+        count_reader_contract.id(),
+        tx_script,
+        foreign_account,
+    )
+    .await?;
+    let full = UsageReport {
+        name: "full import",
+        elapsed: start.elapsed(),
+        store_growth: store_size(&store_path).saturating_sub(size_before),
+    };
+    describe_record(
+        "counter contract after full import",
+        client.get_account(counter_contract_id).await?,
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Compare resource usage
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Resource usage\n");
+    say!(
+        "{:<16} {:>10} {:>14}",
+        "strategy",
+        "elapsed",
+        "store growth"
+    );
+    for report in [&lazy, &full] {
+        say!(
+            "{:<16} {:>10} {:>12} B",
+            report.name,
+            format!("{:.1?}", report.elapsed),
+            report.store_growth
+        );
+    }
+    say!(
+        "\nA full import also keeps the account in sync on every `sync_state`, \
+         which adds up for large public accounts that are only ever read."
+    );
+
+    Ok(())
+}
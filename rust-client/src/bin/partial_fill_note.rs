@@ -0,0 +1,184 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    note::{Note, NoteDetails, NoteTag, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Felt,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumberPolicy, SerialNumbers},
+    output::{emit, Event},
+    say,
+    wait::{submit_and_wait, wait_for_notes},
+};
+
+/// Tokens locked in the first note.
+const TOTAL: u64 = 100;
+
+/// Most tokens the target receives per consumption.
+const TRANCHE: u64 = 40;
+
+fn note_amount(note: &Note) -> u64 {
+    note.assets()
+        .iter()
+        .next()
+        .map_or(0, |asset| asset.unwrap_fungible().amount())
+}
+
+/// Builds the remainder note the script emits when `note` is consumed, if any.
+///
+/// It carries what is left after one tranche, under the same script and inputs
+/// and with the next serial number in the sequence, just like the `push.1 add`
+/// in the script.
+fn remainder_of(note: &Note, consumer: AccountId, faucet_id: AccountId) -> Option<Note> {
+    let remainder = note_amount(note).saturating_sub(TRANCHE);
+    if remainder == 0 {
+        return None;
+    }
+
+    let serial_num = SerialNumberPolicy::Sequential(note.serial_num())
+        .derive(1)
+        .expect("sequential serial numbers are derived");
+    // The consumer creates the remainder note, so it is its sender
+    let remainder_note = NoteBuilder::new(consumer, note.script().clone())
+        .inputs(note.recipient().inputs().values().to_vec())
+        .asset(FungibleAsset::new(faucet_id, remainder).unwrap())
+        .tag(note.metadata().tag())
+        .build_with_serial(serial_num)
+        .unwrap();
+    Some(remainder_note)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and deploy faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating new accounts");
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    say!("\nDeploying a new fungible faucet.");
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Mint tokens for Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Mint {TOTAL} tokens for Alice");
+    let mint_amount = FungibleAsset::new(faucet.id(), TOTAL).unwrap();
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            mint_amount,
+            alice_account.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    submit_and_wait(&mut client, faucet.id(), mint_request).await?;
+
+    wait_for_notes(&mut client, alice_account.id(), 1).await?;
+    let consumable_notes = client
+        .get_consumable_notes(Some(alice_account.id()))
+        .await?;
+    let minted_notes = consumable_notes
+        .into_iter()
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(minted_notes)?;
+    submit_and_wait(&mut client, alice_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Lock the tokens in a partial fill note for Bob
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice locks {TOTAL} tokens in a note paying Bob {TRANCHE} at a time");
+
+    let note_file = MasmFile::read("../masm/notes/partial_fill_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+
+    let tag = NoteTag::with_account_target(bob_account.id());
+    let partial_fill_note = NoteBuilder::new(alice_account.id(), note_script)
+        .inputs([
+            bob_account.id().prefix().as_felt(),
+            bob_account.id().suffix(),
+            tag.into(),
+            Felt::new(TRANCHE),
+        ])
+        .asset(mint_amount)
+        .target(bob_account.id())
+        .build(&mut SerialNumbers::random(), client.rng())?;
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(partial_fill_note.clone())])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Bob consumes the note one tranche at a time
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Bob fills the note until it is exhausted");
+
+    let mut current = Some(partial_fill_note);
+    let mut fill = 1;
+    while let Some(note) = current {
+        let remainder = remainder_of(&note, bob_account.id(), faucet.id());
+
+        // Unauthenticated consumption lets Bob chain the fills without waiting
+        // for each remainder note to show up as consumable
+        let mut builder = TransactionRequestBuilder::new().input_notes([(note, None)]);
+        if let Some(remainder) = &remainder {
+            builder = builder
+                .expected_future_notes(vec![(
+                    NoteDetails::from(remainder.clone()),
+                    remainder.metadata().tag(),
+                )])
+                .expected_output_recipients(vec![remainder.recipient().clone()]);
+        }
+        let fill_request = builder.build().unwrap();
+        submit_and_wait(&mut client, bob_account.id(), fill_request).await?;
+
+        let bob = get_full_account(&mut client, bob_account.id()).await?;
+        let remaining = remainder.as_ref().map_or(0, note_amount);
+        let balance = bob.vault().get_balance(faucet.id()).unwrap();
+        say!("Fill #{fill}: Bob holds {balance} tokens, {remaining} remain in the note");
+        emit(Event::balance(bob.id(), faucet.id(), balance));
+
+        current = remainder;
+        fill += 1;
+    }
+
+    say!("\nThe note is exhausted after {} fills", fill - 1);
+
+    Ok(())
+}
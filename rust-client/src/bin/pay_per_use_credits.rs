@@ -0,0 +1,280 @@
+use std::{collections::HashSet, process::ExitCode};
+
+use miden_client::{
+    account::AccountId,
+    asset::{Asset, FungibleAsset},
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteId, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    notes::check_vault_covers,
+    output::{emit, Event},
+    say,
+    wait::{submit_and_wait, wait_for_notes},
+};
+
+/// Credits one API call costs.
+const CREDITS_PER_CALL: u64 = 1;
+
+/// Result of an API call as seen by the user.
+#[derive(Debug)]
+enum CallOutcome {
+    Served(String),
+    Refused(String),
+}
+
+/// The service side: issues credits and only answers calls paid with a credit note.
+struct CreditService {
+    account_id: AccountId,
+    faucet_id: AccountId,
+    /// Payment notes already redeemed, so a note ID cannot pay for two calls.
+    redeemed: HashSet<NoteId>,
+}
+
+impl CreditService {
+    /// Mints `credits` to the user, who consumes them into their wallet.
+    async fn issue_credits(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        credits: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let asset = FungibleAsset::new(self.faucet_id, credits).unwrap();
+        let mint_request = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(asset, user, NoteType::Public, client.rng())
+            .unwrap();
+        submit_and_wait(client, self.faucet_id, mint_request).await?;
+
+        wait_for_notes(client, user, 1).await?;
+        let notes = client
+            .get_consumable_notes(Some(user))
+            .await?
+            .into_iter()
+            .map(|(note, _)| note.try_into())
+            .collect::<Result<Vec<Note>, _>>()?;
+        let consume_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+        submit_and_wait(client, user, consume_request).await?;
+        Ok(())
+    }
+
+    /// Returns why `note` cannot pay for a call from `user`, if it cannot.
+    fn payment_problem(&self, note: &Note, user: AccountId) -> Option<String> {
+        if self.redeemed.contains(&note.id()) {
+            return Some("payment note was already redeemed".to_string());
+        }
+        if note.metadata().sender() != user {
+            return Some(format!(
+                "payment note was sent by {}",
+                note.metadata().sender()
+            ));
+        }
+        let paid: u64 = note
+            .assets()
+            .iter()
+            .map(|asset| match asset {
+                Asset::Fungible(fungible) if fungible.faucet_id() == self.faucet_id => {
+                    fungible.amount()
+                }
+                _ => 0,
+            })
+            .sum();
+        if paid != CREDITS_PER_CALL {
+            return Some(format!(
+                "payment carries {} credits, expected {}",
+                paid, CREDITS_PER_CALL
+            ));
+        }
+        None
+    }
+
+    /// Verifies and redeems the payment note, then answers the request.
+    async fn handle_call(
+        &mut self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        payment_id: NoteId,
+        request: &str,
+    ) -> Result<CallOutcome, Box<dyn std::error::Error>> {
+        client.sync_state().await?;
+        let Some(record) = client.get_input_note(payment_id).await? else {
+            return Ok(CallOutcome::Refused("unknown payment note".to_string()));
+        };
+        if !record.is_authenticated() {
+            return Ok(CallOutcome::Refused(
+                "payment note is not committed yet".to_string(),
+            ));
+        }
+        let note: Note = record.try_into()?;
+        if let Some(problem) = self.payment_problem(&note, user) {
+            return Ok(CallOutcome::Refused(problem));
+        }
+
+        // Only answer once the credit is actually in the service's vault
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .build()?;
+        let report = submit_and_wait(client, self.account_id, consume_request).await?;
+        if !report.is_committed() {
+            return Ok(CallOutcome::Refused(
+                "payment could not be redeemed".to_string(),
+            ));
+        }
+        self.redeemed.insert(payment_id);
+
+        Ok(CallOutcome::Served(format!(
+            "response to {:?} (call #{})",
+            request,
+            self.redeemed.len()
+        )))
+    }
+}
+
+/// The user side: pays for one call with a P2ID credit note to the service.
+async fn pay_for_call(
+    client: &mut Client<FilesystemKeyStore>,
+    user: AccountId,
+    service: &CreditService,
+) -> Result<NoteId, Box<dyn std::error::Error>> {
+    let credit = FungibleAsset::new(service.faucet_id, CREDITS_PER_CALL).unwrap();
+    let payment_note = create_p2id_note(
+        user,
+        service.account_id,
+        vec![credit.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+    let payment_id = payment_note.id();
+
+    // Refuse up front instead of failing inside transaction execution
+    let sender = get_full_account(client, user).await?;
+    check_vault_covers(&sender, std::slice::from_ref(&payment_note))?;
+
+    let payment_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(payment_note)])
+        .build()
+        .unwrap();
+    submit_and_wait(client, user, payment_request).await?;
+    Ok(payment_id)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the user, the service and its credit faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating the user, the service and its credit faucet");
+
+    let user_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "User account ID: {:?}",
+        user_account.id().to_bech32(network_id())
+    );
+    let service_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Service account ID: {:?}",
+        service_account.id().to_bech32(network_id())
+    );
+    let credit_faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Credit faucet ID: {:?}",
+        credit_faucet.id().to_bech32(network_id())
+    );
+
+    let mut service = CreditService {
+        account_id: service_account.id(),
+        faucet_id: credit_faucet.id(),
+        redeemed: HashSet::new(),
+    };
+
+    // -------------------------------------------------------------------------
+    // STEP 2: The service issues credits to the user
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] The service issues 3 credits to the user");
+
+    service
+        .issue_credits(&mut client, user_account.id(), 3)
+        .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Every API call is paid with one credit note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] The user makes three paid API calls");
+
+    let mut last_payment = None;
+    for request in ["GET /price/ETH", "GET /price/BTC", "GET /price/MIDEN"] {
+        let payment_id = pay_for_call(&mut client, user_account.id(), &service).await?;
+        let outcome = service
+            .handle_call(&mut client, user_account.id(), payment_id, request)
+            .await?;
+        say!("{} => {:?}", request, outcome);
+        assert!(
+            matches!(outcome, CallOutcome::Served(_)),
+            "paid call was refused"
+        );
+        last_payment = Some(payment_id);
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: A payment note cannot be presented twice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] The user replays the last payment note");
+
+    let outcome = service
+        .handle_call(
+            &mut client,
+            user_account.id(),
+            last_payment.unwrap(),
+            "GET /price/ETH",
+        )
+        .await?;
+    say!("Replayed payment => {:?}", outcome);
+    assert!(
+        matches!(outcome, CallOutcome::Refused(_)),
+        "replayed payment was served"
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Without credits left, the user cannot pay
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] The user tries to pay with no credits left");
+
+    let user = get_full_account(&mut client, user_account.id()).await?;
+    let user_credits = user.vault().get_balance(credit_faucet.id()).unwrap();
+    say!("User credits left: {}", user_credits);
+    emit(Event::balance(user.id(), credit_faucet.id(), user_credits));
+    let service_state = get_full_account(&mut client, service_account.id()).await?;
+    let collected = service_state
+        .vault()
+        .get_balance(credit_faucet.id())
+        .unwrap();
+    say!("Service credits collected: {}", collected);
+    emit(Event::balance(
+        service_state.id(),
+        credit_faucet.id(),
+        collected,
+    ));
+
+    match pay_for_call(&mut client, user_account.id(), &service).await {
+        Ok(_) => panic!("payment without credits should fail"),
+        Err(err) => say!("✅ Payment rejected as expected: {}", err),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use std::process::ExitCode;
+
+use rust_client::{
+    accounts::{collect, create_basic_account, create_basic_faucet, get_full_account, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    payment_stream::{PaymentStream, StreamTerms},
+    say,
+    wait::{wait_for_block, WaitOptions},
+};
+
+/// Blocks between deploying the stream and its start.
+const START_DELAY: u32 = 5;
+
+/// Tokens streamed per block.
+const RATE: u64 = 2;
+
+/// The whole deposit, which streams over `TOTAL / RATE` blocks.
+const TOTAL: u64 = 100;
+
+/// Blocks between two withdrawals.
+const WITHDRAW_EVERY: u32 = 8;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the payer, the payee and the faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice the payer, Bob the payee and a faucet");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), TOTAL).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice deploys and funds the stream
+    // -------------------------------------------------------------------------
+    let height = client.sync_state().await?.block_num.as_u32();
+    let terms = StreamTerms {
+        start: height + START_DELAY,
+        rate: RATE,
+        total: TOTAL,
+    };
+    say!(
+        "\n[STEP 2] Alice streams Bob {TOTAL} tokens, {RATE} per block from block {} to block {}",
+        terms.start,
+        terms.end()
+    );
+
+    let stream = PaymentStream::deploy(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        faucet.id(),
+        terms,
+    )
+    .await?;
+    say!(
+        "Stream contract ID: {:?}",
+        stream.account_id.to_bech32(network_id())
+    );
+    stream.fund(&mut client).await?;
+    say!("✅ {TOTAL} tokens deposited in the stream");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Bob withdraws twice as the tokens stream in
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Bob withdraws every {WITHDRAW_EVERY} blocks");
+
+    let mut bob_balance = 0;
+    for (round, block) in [
+        terms.start + WITHDRAW_EVERY,
+        terms.start + 2 * WITHDRAW_EVERY,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        wait_for_block(&mut client, block, &WaitOptions::for_blocks(WITHDRAW_EVERY)).await?;
+
+        // The stream is measured at the reference block, which is the client's
+        // sync height, so the withdrawable amount is known before executing
+        let withdrawable = stream.withdrawable(&mut client).await?;
+        say!(
+            "Withdrawal {}: Bob can withdraw {withdrawable} tokens",
+            round + 1
+        );
+        let (report, payout) = stream.withdraw(&mut client, withdrawable).await?;
+        assert!(report.is_committed(), "the withdrawal was not committed");
+
+        bob_balance = collect(&mut client, bob_account.id(), payout, faucet.id()).await?;
+        say!("✅ Bob holds {bob_balance} tokens");
+    }
+    assert_eq!(bob_balance, stream.state(&mut client).await?.withdrawn);
+    assert!(
+        bob_balance >= 2 * WITHDRAW_EVERY as u64 * RATE,
+        "Bob should hold two rounds of the stream"
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Alice cancels and gets back what has not streamed yet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice cancels the stream");
+
+    let alice_before = get_full_account(&mut client, alice_account.id())
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    let (report, refund) = stream.cancel(&mut client).await?;
+    assert!(report.is_committed(), "the cancellation was not committed");
+    let refunded = refund
+        .assets()
+        .iter()
+        .map(|asset| asset.unwrap_fungible().amount())
+        .sum::<u64>();
+    let alice_balance = collect(&mut client, alice_account.id(), refund, faucet.id()).await?;
+    assert_eq!(alice_balance, alice_before + refunded);
+
+    let cancelled_at = stream
+        .state(&mut client)
+        .await?
+        .cancelled_at
+        .expect("the stream was cancelled");
+    say!("✅ Stream stopped at block {cancelled_at}, {refunded} tokens went back to Alice");
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Bob withdraws what streamed until the cancellation
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Bob withdraws the rest of the stream");
+
+    let withdrawable = stream.withdrawable(&mut client).await?;
+    if withdrawable > 0 {
+        let (report, payout) = stream.withdraw(&mut client, withdrawable).await?;
+        assert!(report.is_committed(), "the withdrawal was not committed");
+        bob_balance = collect(&mut client, bob_account.id(), payout, faucet.id()).await?;
+    }
+
+    // Nothing streams after the cancel block
+    match stream.withdraw(&mut client, RATE).await {
+        Ok((report, _)) if report.is_committed() => {
+            panic!("the stream paid out after it was cancelled")
+        }
+        Ok(_) => say!("Withdrawal after the cancellation discarded as expected"),
+        Err(err) => say!(
+            "Withdrawal after the cancellation rejected as expected: {}",
+            err
+        ),
+    }
+
+    say!("✅ Bob holds {bob_balance} tokens, Alice got {refunded} back");
+    assert_eq!(bob_balance, terms.streamed_at(cancelled_at));
+    assert_eq!(bob_balance + refunded, TOTAL);
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    asset::FungibleAsset,
+    crypto::FeltRng,
+    note::{
+        create_p2id_note, Note, NoteAssets, NoteAttachment, NoteDetails, NoteFile, NoteInputs,
+        NoteMetadata, NoteRecipient, NoteTag, NoteType,
+    },
+    transaction::{OutputNote, TransactionRequestBuilder},
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    notes::check_vault_covers,
+    say,
+    wait::{submit_and_wait, wait_for, wait_for_notes},
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and deploy faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating new accounts");
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    say!("\nDeploying a new fungible faucet.");
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Mint tokens for Alice");
+    let mint_amount = FungibleAsset::new(faucet.id(), 100).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            mint_amount,
+            alice_account.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    submit_and_wait(&mut client, faucet.id(), tx_request).await?;
+
+    wait_for_notes(&mut client, alice_account.id(), 1).await?;
+    let consumable_notes = client
+        .get_consumable_notes(Some(alice_account.id()))
+        .await?;
+    let notes = consumable_notes
+        .into_iter()
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+    submit_and_wait(&mut client, alice_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice prepares the receipt she expects back
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice prepares the receipt she expects back");
+
+    // The receipt is an empty P2ID note from Bob to Alice. Alice picks its
+    // serial number, so she knows the receipt's ID before Bob creates it.
+    let receipt_note = create_p2id_note(
+        bob_account.id(),
+        alice_account.id(),
+        vec![],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+    let receipt_tag = receipt_note.metadata().tag();
+    say!("Expected receipt note: {}", receipt_note.id().to_hex());
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Alice sends the payment note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice sends the payment note to Bob");
+
+    let note_file = MasmFile::read("../masm/notes/payment_with_ack_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+
+    let mut inputs = receipt_note.recipient().digest().to_vec();
+    inputs.extend([
+        receipt_tag.into(),
+        bob_account.id().prefix().as_felt(),
+        bob_account.id().suffix(),
+    ]);
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+
+    let serial_num = client.rng().draw_word();
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+    let tag = NoteTag::with_account_target(bob_account.id());
+    let metadata = NoteMetadata::new(alice_account.id(), NoteType::Public, tag);
+    let payment_amount = FungibleAsset::new(faucet.id(), 40).unwrap();
+    let vault = NoteAssets::new(vec![payment_amount.into()])?;
+    let payment_note = Note::new(vault, metadata, recipient);
+
+    let alice = get_full_account(&mut client, alice_account.id()).await?;
+    check_vault_covers(&alice, std::slice::from_ref(&payment_note))
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(payment_note.clone())])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // Alice starts watching for the receipt before Bob has even seen the payment
+    client
+        .import_note(NoteFile::NoteDetails {
+            details: NoteDetails::from(receipt_note.clone()),
+            after_block_num: sync_summary.block_num,
+            tag: Some(receipt_tag),
+        })
+        .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Bob consumes the payment, emitting the receipt
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Bob consumes the payment and emits the receipt");
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payment_note, None)])
+        .expected_output_recipients(vec![receipt_note.recipient().clone()])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, bob_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 6: Alice waits for and verifies the receipt
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Alice waits for the receipt");
+
+    // The receipt only counts once it is committed, not while it is expected
+    let receipt_id = receipt_note.id();
+    let notes = wait_for(&mut client, |notes| {
+        notes
+            .iter()
+            .any(|(note, _)| note.id() == receipt_id && note.is_authenticated())
+    })
+    .await?;
+    let (record, _) = notes
+        .iter()
+        .find(|(note, _)| note.id() == receipt_id)
+        .expect("wait_for returned without the receipt");
+    let sender = record.metadata().map(|metadata| metadata.sender());
+    assert_eq!(sender, Some(bob_account.id()), "receipt not sent by Bob");
+    assert!(record.assets().is_empty(), "receipt should carry no assets");
+    say!("✅ Receipt {} received from Bob", receipt_id.to_hex());
+
+    // Consuming the receipt clears it from Alice's inbox
+    let consume_request =
+        TransactionRequestBuilder::new().build_consume_notes(vec![receipt_note])?;
+    submit_and_wait(&mut client, alice_account.id(), consume_request).await?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::{collections::BTreeSet, process::ExitCode};
+
+use clap::Parser;
+use rust_client::{
+    endpoints::{healthiest, EndpointRegistry, ENDPOINTS_FILE, ENDPOINT_ENV},
+    error::{report, ExitGuard},
+    say,
+};
+
+/// Health-checks every endpoint of the registry and suggests the fastest one per network.
+#[derive(Parser)]
+struct Cli {
+    /// Endpoints registry to read
+    #[arg(default_value = ENDPOINTS_FILE)]
+    registry: String,
+    /// Ping timeout in milliseconds
+    #[arg(long, default_value_t = 5_000)]
+    timeout_ms: u64,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let path = cli.registry;
+    let registry = EndpointRegistry::load(&path)?;
+    say!(
+        "Pinging {} endpoint(s) from {}\n",
+        registry.entries.len(),
+        path
+    );
+
+    let results = registry.ping_all(cli.timeout_ms).await;
+
+    say!(
+        "{:<12} {:<10} {:>10} {:>12}  url",
+        "name",
+        "network",
+        "latency",
+        "block"
+    );
+    for health in &results {
+        let block = match &health.block_height {
+            Ok(height) => height.to_string(),
+            Err(_) => "down".to_string(),
+        };
+        say!(
+            "{:<12} {:<10} {:>10} {:>12}  {}",
+            health.entry.name,
+            health.entry.network,
+            format!("{:.0?}", health.latency),
+            block,
+            health.entry.url
+        );
+        if let Err(err) = &health.block_height {
+            say!("{:<12} error: {}", "", err);
+        }
+    }
+
+    let networks: BTreeSet<_> = registry
+        .entries
+        .iter()
+        .map(|entry| entry.network.as_str())
+        .collect();
+
+    say!();
+    for network in networks {
+        let candidates = results
+            .iter()
+            .filter(|health| health.entry.network == network);
+        match healthiest(candidates) {
+            Some(best) => {
+                say!("Healthiest {network} endpoint: {}", best.entry.name);
+                say!("  export {ENDPOINT_ENV}={}", best.entry.url);
+            }
+            None => say!("No healthy {network} endpoint"),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use clap::Parser;
+use std::{process::ExitCode, sync::Arc};
+
+use tokio::{
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use miden_client::{
+    account::Account,
+    keystore::FilesystemKeyStore,
+    transaction::{
+        LocalTransactionProver, ProvenTransaction, ProvingOptions, TransactionId,
+        TransactionProver, TransactionResult,
+    },
+    Client,
+};
+use rust_client::{
+    client::{ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    middleware::submit_proven_logged,
+    proving, say,
+    wait::wait_for_tx,
+};
+
+/// Overlaps proving transaction N with executing transaction N+1 and compares
+/// the pipeline with running execute → prove → submit one after another.
+#[derive(Parser)]
+struct Cli {
+    /// Transactions sent by each of the two runs
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=16))]
+    transactions: u32,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// Executed transactions waiting for the prover. A queue of one keeps the
+/// executor at most one transaction ahead of the prover.
+const QUEUE: usize = 1;
+
+/// Time spent in each phase of a run, summed over its transactions.
+#[derive(Debug, Default)]
+struct RunTimes {
+    execute: Duration,
+    prove: Duration,
+    submit: Duration,
+    wall: Duration,
+}
+
+/// Submits a proven transaction and applies it to the local store.
+async fn submit(
+    client: &mut Client<FilesystemKeyStore>,
+    proven_transaction: ProvenTransaction,
+    tx_result: &TransactionResult,
+) -> Result<TransactionId, Box<dyn std::error::Error>> {
+    let tx_id = proven_transaction.id();
+    let submission_height = submit_proven_logged(client, proven_transaction, tx_result).await?;
+    client
+        .apply_transaction(tx_result, submission_height)
+        .await?;
+    Ok(tx_id)
+}
+
+/// Executes, proves and submits one transaction at a time.
+async fn run_sequential(
+    client: &mut Client<FilesystemKeyStore>,
+    accounts: &[Account],
+) -> Result<(RunTimes, Vec<TransactionId>), Box<dyn std::error::Error>> {
+    let mut times = RunTimes::default();
+    let mut tx_ids = Vec::with_capacity(accounts.len());
+    let start = Instant::now();
+    for account in accounts {
+        let phase = Instant::now();
+        let request = proving::nonce_bump_request(client)?;
+        let tx_result = client.execute_transaction(account.id(), request).await?;
+        times.execute += phase.elapsed();
+
+        let phase = Instant::now();
+        let proven_transaction = client.prove_transaction(&tx_result).await?;
+        times.prove += phase.elapsed();
+
+        let phase = Instant::now();
+        tx_ids.push(submit(client, proven_transaction, &tx_result).await?);
+        times.submit += phase.elapsed();
+        say!("[sequential] submitted a transaction of {}", account.id());
+    }
+    times.wall = start.elapsed();
+    Ok((times, tx_ids))
+}
+
+/// The prover stage: proves whatever the executor hands it, in order.
+///
+/// It needs no client, only the transaction inputs recorded by execution, so it
+/// can run on its own task while the client executes the next transaction.
+async fn run_prover(
+    mut executed: mpsc::Receiver<TransactionResult>,
+    proven: mpsc::UnboundedSender<Result<(ProvenTransaction, TransactionResult), String>>,
+) -> Duration {
+    let tx_prover: Arc<dyn TransactionProver> =
+        Arc::new(LocalTransactionProver::new(ProvingOptions::default()));
+    let mut busy = Duration::ZERO;
+    while let Some(tx_result) = executed.recv().await {
+        let phase = Instant::now();
+        let outcome = tx_prover
+            .prove(tx_result.executed_transaction().tx_inputs().clone())
+            .await
+            .map(|proven_transaction| (proven_transaction, tx_result))
+            .map_err(|err| err.to_string());
+        busy += phase.elapsed();
+        if let Ok((proven_transaction, _)) = &outcome {
+            say!("[prover] proved {}", proven_transaction.id().to_hex());
+        }
+        if proven.send(outcome).is_err() {
+            break;
+        }
+    }
+    busy
+}
+
+/// Executes on the client while a prover task proves the previous transaction,
+/// and submits each proof as soon as it is back.
+async fn run_pipelined(
+    client: &mut Client<FilesystemKeyStore>,
+    accounts: &[Account],
+) -> Result<(RunTimes, Vec<TransactionId>), Box<dyn std::error::Error>> {
+    let mut times = RunTimes::default();
+    let mut tx_ids = Vec::with_capacity(accounts.len());
+    let (executed_tx, executed_rx) = mpsc::channel(QUEUE);
+    let (proven_tx, mut proven_rx) = mpsc::unbounded_channel();
+    let start = Instant::now();
+    let prover = tokio::spawn(run_prover(executed_rx, proven_tx));
+
+    // The client stays on this task: it executes and submits, and only the
+    // proving, which does not touch the store, moves to the prover
+    for account in accounts {
+        let phase = Instant::now();
+        let request = proving::nonce_bump_request(client)?;
+        let tx_result = client.execute_transaction(account.id(), request).await?;
+        times.execute += phase.elapsed();
+        say!("[executor] executed a transaction of {}", account.id());
+        executed_tx
+            .send(tx_result)
+            .await
+            .map_err(|_| "the prover stopped")?;
+
+        // Submit whatever the prover finished in the meantime
+        while let Ok(outcome) = proven_rx.try_recv() {
+            let (proven_transaction, tx_result) = outcome?;
+            let phase = Instant::now();
+            tx_ids.push(submit(client, proven_transaction, &tx_result).await?);
+            times.submit += phase.elapsed();
+        }
+    }
+
+    // Closing the queue lets the prover finish the last transactions and stop
+    drop(executed_tx);
+    while let Some(outcome) = proven_rx.recv().await {
+        let (proven_transaction, tx_result) = outcome?;
+        let phase = Instant::now();
+        tx_ids.push(submit(client, proven_transaction, &tx_result).await?);
+        times.submit += phase.elapsed();
+    }
+    times.prove = prover.await?;
+    times.wall = start.elapsed();
+    Ok((times, tx_ids))
+}
+
+/// Creates `count` accounts with one nonce bump each to send, so no
+/// transaction waits for the state another one leaves behind.
+async fn create_accounts(
+    client: &mut Client<FilesystemKeyStore>,
+    keystore: &Arc<FilesystemKeyStore>,
+    count: u32,
+) -> Result<Vec<Account>, Box<dyn std::error::Error>> {
+    let mut accounts = Vec::new();
+    for _ in 0..count {
+        accounts.push(proving::create_nonce_bump_account(client, keystore).await?);
+    }
+    Ok(accounts)
+}
+
+async fn wait_all(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_ids: &[TransactionId],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for tx_id in tx_ids {
+        wait_for_tx(client, *tx_id).await?;
+    }
+    Ok(())
+}
+
+fn print_times(label: &str, times: &RunTimes) {
+    say!(
+        "{:<11} {:>10} {:>10} {:>10} {:>10}",
+        label,
+        format!("{:.2?}", times.execute),
+        format!("{:.2?}", times.prove),
+        format!("{:.2?}", times.submit),
+        format!("{:.2?}", times.wall)
+    );
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create one account per transaction
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 1] Creating {} accounts for each run",
+        cli.transactions
+    );
+
+    // Two transactions of the same account cannot overlap: the second one
+    // executes against the state the first one leaves, which only exists once
+    // the first is applied. Independent accounts have no such ordering.
+    let sequential = create_accounts(&mut client, &keystore, cli.transactions).await?;
+    let pipelined = create_accounts(&mut client, &keystore, cli.transactions).await?;
+    say!("Created {} accounts", sequential.len() + pipelined.len());
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Execute, prove and submit one transaction after another
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Running the transactions one after another");
+
+    let (sequential_times, sequential_ids) = run_sequential(&mut client, &sequential).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Run the same transactions through the pipeline
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Running the transactions through the pipeline");
+
+    let (pipelined_times, pipelined_ids) = run_pipelined(&mut client, &pipelined).await?;
+
+    say!("Waiting for both runs to be committed...");
+    wait_all(&mut client, &sequential_ids).await?;
+    wait_all(&mut client, &pipelined_ids).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Compare the runs
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Comparing the runs\n");
+
+    say!(
+        "{:<11} {:>10} {:>10} {:>10} {:>10}",
+        "run",
+        "execute",
+        "prove",
+        "submit",
+        "wall"
+    );
+    print_times("sequential", &sequential_times);
+    print_times("pipelined", &pipelined_times);
+
+    // Proving usually dwarfs execution, so the pipeline can hide at most the
+    // execution time: the speedup stays close to (execute + prove) / prove
+    say!(
+        "\nPipeline speedup: {:.2}x",
+        sequential_times.wall.as_secs_f64() / pipelined_times.wall.as_secs_f64()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use clap::Parser;
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use miden_client::{
+    asset::FungibleAsset,
+    note::{create_p2id_note, NoteAttachment, NoteFile, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    utils::Serializable,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, top_up},
+    aliases::resolve_alias,
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Sends tokens in a private note and writes the note to a file for the
+/// recipient, who imports it with `private_note_import`.
+#[derive(Parser)]
+struct Cli {
+    /// Wallet receiving the note, as an ID or an alias such as `recipient`,
+    /// which `private_note_import address` saves
+    #[arg(long)]
+    to: String,
+    /// Where the note is written
+    #[arg(long, default_value = "private_note.mno")]
+    out: PathBuf,
+    /// Tokens the note carries
+    #[arg(long, default_value_t = 25)]
+    amount: u64,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // Initialize client
+    let (mut client, keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+    // Aliases are kept per network, so they resolve once the network is known
+    let to = resolve_alias(&cli.to)?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the sender's wallet and fund it
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating and funding the sender's wallet");
+
+    let sender = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Sender's account ID: {:?}",
+        sender.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), sender.id(), cli.amount).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Send a private note to the recipient
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 2] Sending {} tokens to {} in a private note",
+        cli.amount,
+        to.to_bech32(network_id())
+    );
+
+    // Only the note's commitment goes on chain, so the recipient cannot find
+    // its contents there; the file below is the only way they learn them
+    let note = create_p2id_note(
+        sender.id(),
+        to,
+        vec![FungibleAsset::new(faucet.id(), cli.amount)?.into()],
+        NoteType::Private,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+    let submitted_at = client.get_sync_height().await?;
+    let request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(note.clone())])
+        .build()?;
+    submit_and_wait(&mut client, sender.id(), request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Write the note to a file
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Writing the note to {}", cli.out.display());
+
+    // With an inclusion proof the recipient can consume the note right away;
+    // otherwise the tag tells their client where to look for the commitment
+    let proof = client
+        .get_output_note(note.id())
+        .await?
+        .and_then(|record| record.inclusion_proof().cloned());
+    let note_file = match proof {
+        Some(proof) => NoteFile::NoteWithProof(note.clone(), proof),
+        None => NoteFile::NoteDetails {
+            details: note.clone().into(),
+            after_block_num: submitted_at,
+            tag: Some(note.metadata().tag()),
+        },
+    };
+    fs::write(&cli.out, note_file.to_bytes())?;
+    say!("✅ Note {} written to {}", note.id(), cli.out.display());
+    say!(
+        "Import it with: cargo run --bin private_note_import -- consume {}",
+        cli.out.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use clap::{Parser, Subcommand};
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use miden_client::{
+    asset::Asset,
+    note::{Note, NoteFile},
+    transaction::TransactionRequestBuilder,
+    utils::Deserializable,
+};
+use rust_client::{
+    accounts::get_full_account,
+    aliases::save_alias,
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    output::{emit, Event},
+    profiles::ProfileManager,
+    say,
+    wait::{has_note, submit_and_wait, wait_for},
+};
+
+/// Profile of the recipient, whose store and keystore are kept apart from the
+/// sender's default ones.
+const RECIPIENT: &str = "Recipient";
+
+/// The recipient's side of a private note sent by `private_note_export`.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints the recipient's wallet ID to hand to the sender
+    Address,
+    /// Imports a note file and consumes the note
+    Consume {
+        /// File written by `private_note_export`
+        #[arg(default_value = "private_note.mno")]
+        file: PathBuf,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // The recipient runs a client of their own, with its own store and keys
+    let mut profiles = ProfileManager::new(ClientSetup::from_args(cli.client)?);
+    say!(
+        "Recipient store: {}",
+        profiles.store_path(RECIPIENT).display()
+    );
+    let recipient = profiles.switch(RECIPIENT).await?;
+    let wallet = recipient.wallet().await?;
+
+    let file = match cli.command {
+        Command::Address => {
+            say!("Recipient's account ID: {}", wallet.to_bech32(network_id()));
+            save_alias("recipient", wallet)?;
+            say!("Send to it with: cargo run --bin private_note_export -- --to recipient");
+            return Ok(());
+        }
+        Command::Consume { file } => file,
+    };
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Import the note file
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Importing the note from {}", file.display());
+
+    let note_file = NoteFile::read_from_bytes(&fs::read(&file)?)?;
+    let note_id = recipient.client.import_note(note_file).await?;
+    say!("Imported note {note_id}");
+
+    // A note imported without a proof becomes consumable once a sync finds
+    // its commitment on chain
+    wait_for(&mut recipient.client, has_note(note_id)).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Consume it into the recipient's wallet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Consuming the note");
+
+    let record = recipient
+        .client
+        .get_input_note(note_id)
+        .await?
+        .expect("the note was just imported");
+    let note: Note = record.try_into()?;
+    let assets: Vec<_> = note.assets().iter().copied().collect();
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(note, None)])
+        .build()?;
+    let report = submit_and_wait(&mut recipient.client, wallet, consume_request).await?;
+    assert!(report.is_committed(), "the note should be consumed");
+
+    let account = get_full_account(&mut recipient.client, wallet).await?;
+    for asset in assets {
+        if let Asset::Fungible(fungible) = asset {
+            let balance = account
+                .vault()
+                .get_balance(fungible.faucet_id())
+                .unwrap_or(0);
+            emit(Event::balance(wallet, fungible.faucet_id(), balance));
+            say!(
+                "✅ Received {} tokens, the wallet now holds {balance}",
+                fungible.amount()
+            );
+        }
+    }
+
+    Ok(())
+}
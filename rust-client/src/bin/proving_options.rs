@@ -0,0 +1,68 @@
+use std::process::ExitCode;
+
+use rust_client::{
+    client::init_client_from_config,
+    error::{report, ExitGuard},
+    proving, say,
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Execute the dummy transaction of `delegated_prover`
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Executing a transaction that only bumps Alice's nonce");
+
+    let alice_account = proving::create_nonce_bump_account(&mut client, &keystore).await?;
+    let request = proving::nonce_bump_request(&mut client)?;
+
+    // The proofs below all prove this one execution
+    let tx_result = client
+        .execute_transaction(alice_account.id(), request)
+        .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Prove it under every preset
+    // -------------------------------------------------------------------------
+    let presets = proving::presets();
+    say!(
+        "\n[STEP 2] Proving the transaction under {} presets",
+        presets.len()
+    );
+
+    let mut metrics = Vec::with_capacity(presets.len());
+    for (preset, options) in presets {
+        say!("Proving with the {preset} preset...");
+        let (_, proof_metrics) =
+            proving::prove_with(&mut client, &tx_result, preset, options).await?;
+        say!(
+            "✅ {} bytes in {:.2?}",
+            proof_metrics.proof_bytes,
+            proof_metrics.elapsed
+        );
+        metrics.push(proof_metrics);
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Compare the proofs
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Comparing the proofs\n");
+
+    proving::print_comparison(&metrics);
+
+    // The proofs are only measured, so Alice's account never reaches the chain
+    say!("\nNo proof was submitted");
+
+    Ok(())
+}
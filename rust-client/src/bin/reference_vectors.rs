@@ -0,0 +1,71 @@
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+use rust_client::{
+    error::report,
+    say,
+    vectors::{ReferenceVectors, VECTORS_FILE},
+};
+
+/// Writes the reference vectors the web tutorials check the TypeScript SDK against.
+///
+/// Needs no node: every vector is computed from fixed seeds and parameters.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Writes the vectors as JSON (the default)
+    Dump {
+        /// Where to write them
+        #[arg(long, default_value = VECTORS_FILE)]
+        out: PathBuf,
+    },
+    /// Fails when this client computes different vectors than a file holds
+    Check {
+        #[arg(default_value = VECTORS_FILE)]
+        file: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    report(run())
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let command = Cli::parse().command.unwrap_or(Command::Dump {
+        out: PathBuf::from(VECTORS_FILE),
+    });
+    let vectors = ReferenceVectors::generate()?;
+
+    match command {
+        Command::Dump { out } => {
+            fs::write(&out, vectors.to_json() + "\n")?;
+            say!(
+                "Wrote {} account, {} note, {} note script and {} procedure vectors to {}",
+                vectors.accounts.len(),
+                vectors.notes.len(),
+                vectors.note_scripts.len(),
+                vectors.procedures.len(),
+                out.display()
+            );
+        }
+        Command::Check { file } => {
+            let expected: ReferenceVectors = serde_json::from_str(&fs::read_to_string(&file)?)?;
+            let drift = vectors.diff(&expected);
+            if !drift.is_empty() {
+                for line in &drift {
+                    say!("{line}");
+                }
+                return Err(
+                    format!("{} vectors differ from {}", drift.len(), file.display()).into(),
+                );
+            }
+            say!("✅ All vectors match {}", file.display());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use std::{process::ExitCode, sync::Arc};
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    block::BlockNumber,
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteType},
+    rpc::{GrpcClient, NodeRpcClient},
+    transaction::{
+        LocalTransactionProver, ProvenTransaction, ProvingOptions, TransactionInputs,
+        TransactionProver, TransactionRequest, TransactionRequestBuilder,
+    },
+    utils::{Deserializable, Serializable},
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    run_mode::exit_if_dry_run_proven,
+    say,
+    wait::{print_explorer_link, wait_for_notes, wait_for_tx},
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// A proven transaction handed to the relayer, in its serialized wire form.
+struct RelayRequest {
+    proven_transaction: Vec<u8>,
+    transaction_inputs: Vec<u8>,
+    /// Block height the transaction was submitted at, or why it was not.
+    reply: oneshot::Sender<Result<BlockNumber, String>>,
+}
+
+/// The relayer role: owns its own RPC connection and submits whatever it is handed.
+///
+/// It never sees a key or a store. It only forwards proofs, so the node's proof
+/// check is what keeps it honest.
+async fn run_relayer(rpc: Arc<dyn NodeRpcClient>, mut requests: mpsc::Receiver<RelayRequest>) {
+    while let Some(request) = requests.recv().await {
+        let result = relay(&rpc, &request).await;
+        match &result {
+            Ok(block_num) => say!("[relayer] submitted at block {}", block_num),
+            Err(err) => say!("[relayer] refused: {}", err),
+        }
+        let _ = request.reply.send(result);
+    }
+}
+
+async fn relay(
+    rpc: &Arc<dyn NodeRpcClient>,
+    request: &RelayRequest,
+) -> Result<BlockNumber, String> {
+    let proven_transaction = ProvenTransaction::read_from_bytes(&request.proven_transaction)
+        .map_err(|err| format!("malformed proven transaction: {err}"))?;
+    let transaction_inputs = TransactionInputs::read_from_bytes(&request.transaction_inputs)
+        .map_err(|err| format!("malformed transaction inputs: {err}"))?;
+    say!(
+        "[relayer] relaying transaction {} for account {}",
+        proven_transaction.id().to_hex(),
+        proven_transaction.account_id()
+    );
+
+    rpc.submit_proven_transaction(proven_transaction, transaction_inputs)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// The user role: executes and proves locally, then lets the relayer submit.
+async fn prove_and_relay(
+    client: &mut Client<FilesystemKeyStore>,
+    relayer: &mpsc::Sender<RelayRequest>,
+    account_id: AccountId,
+    request: TransactionRequest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tx_result = client.execute_transaction(account_id, request).await?;
+    let tx_prover: Arc<dyn TransactionProver> =
+        Arc::new(LocalTransactionProver::new(ProvingOptions::default()));
+    let proven_transaction = client.prove_transaction_with(&tx_result, tx_prover).await?;
+    let tx_id = proven_transaction.id();
+    say!("[user] proved transaction {}", tx_id.to_hex());
+    // The relayer would submit it, so a dry run stops before handing it over
+    exit_if_dry_run_proven(tx_id);
+
+    let (reply, response) = oneshot::channel();
+    relayer
+        .send(RelayRequest {
+            proven_transaction: proven_transaction.to_bytes(),
+            transaction_inputs: tx_result.executed_transaction().tx_inputs().to_bytes(),
+            reply,
+        })
+        .await?;
+    let submission_height = response.await??;
+
+    // The relayer only submits; updating the local store stays with the user
+    client
+        .apply_transaction(&tx_result, submission_height)
+        .await?;
+    print_explorer_link("View transaction", tx_id);
+    wait_for_tx(client, tx_id).await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let setup = ClientSetup::from_cli()?;
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Start the relayer
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Starting the relayer");
+
+    let relayer_rpc: Arc<dyn NodeRpcClient> =
+        Arc::new(GrpcClient::new(&setup.endpoint, setup.timeout_ms));
+    let (relayer, requests) = mpsc::channel(8);
+    let relayer_task = tokio::spawn(run_relayer(relayer_rpc, requests));
+
+    // Initialize the user's client
+    let (mut client, keystore) = setup.build().await?;
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Create accounts
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Creating Alice's account and a faucet");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    say!(
+        "Faucet account ID: {:?}",
+        faucet.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Mint through the relayer
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Minting tokens for Alice through the relayer");
+
+    let mint_amount = FungibleAsset::new(faucet.id(), 100).unwrap();
+    let mint_request = TransactionRequestBuilder::new().build_mint_fungible_asset(
+        mint_amount,
+        alice_account.id(),
+        NoteType::Public,
+        client.rng(),
+    )?;
+    prove_and_relay(&mut client, &relayer, faucet.id(), mint_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Consume through the relayer
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice consumes the minted note through the relayer");
+
+    wait_for_notes(&mut client, alice_account.id(), 1).await?;
+    let notes = client
+        .get_consumable_notes(Some(alice_account.id()))
+        .await?
+        .into_iter()
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    let consume_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+    prove_and_relay(&mut client, &relayer, alice_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 5: The relayer cannot be handed garbage
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Handing the relayer a malformed payload");
+
+    let (reply, response) = oneshot::channel();
+    relayer
+        .send(RelayRequest {
+            proven_transaction: vec![0; 32],
+            transaction_inputs: vec![],
+            reply,
+        })
+        .await?;
+    match response.await? {
+        Ok(_) => panic!("relayer should refuse a malformed payload"),
+        Err(err) => say!("✅ Refused as expected: {}", err),
+    }
+
+    drop(relayer);
+    relayer_task.await?;
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    note::{create_p2id_note, Note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionRequest, TransactionRequestBuilder},
+    ClientError,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    say,
+    wait::{submit_and_wait, wait_for_notes},
+};
+
+/// What went wrong with a failed consumption, as far as we can tell.
+#[derive(Debug)]
+enum Diagnosis {
+    /// The note's script only lets another account consume it.
+    WrongConsumer { expected: AccountId },
+    /// Anything we do not know how to fix automatically.
+    Unknown(String),
+}
+
+/// Reads the target account out of the inputs of a P2ID note.
+fn p2id_target(note: &Note) -> Option<AccountId> {
+    let inputs = note.recipient().inputs().values();
+    let [suffix, prefix] = inputs else {
+        return None;
+    };
+    AccountId::try_from([*prefix, *suffix]).ok()
+}
+
+/// Inspects an execution error together with the note and account that caused it.
+fn diagnose(err: &ClientError, note: &Note, account_id: AccountId) -> Diagnosis {
+    match err {
+        // The note script aborted while running inside the executing account
+        ClientError::TransactionExecutorError(_) => match p2id_target(note) {
+            Some(target) if target != account_id => Diagnosis::WrongConsumer { expected: target },
+            _ => Diagnosis::Unknown(err.to_string()),
+        },
+        _ => Diagnosis::Unknown(err.to_string()),
+    }
+}
+
+fn consume_request(note: &Note) -> TransactionRequest {
+    TransactionRequestBuilder::new()
+        .input_notes([(note.clone(), None)])
+        .build()
+        .unwrap()
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    client.sync_state().await?;
+
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 100).unwrap(),
+            alice_account.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    submit_and_wait(&mut client, faucet.id(), mint_request).await?;
+
+    wait_for_notes(&mut client, alice_account.id(), 1).await?;
+    let consumable_notes = client
+        .get_consumable_notes(Some(alice_account.id()))
+        .await?;
+    let notes = consumable_notes
+        .into_iter()
+        .map(|(note, _)| note.try_into())
+        .collect::<Result<Vec<Note>, _>>()?;
+    submit_and_wait(
+        &mut client,
+        alice_account.id(),
+        TransactionRequestBuilder::new().build_consume_notes(notes)?,
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice sends a P2ID note to Bob
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice sends a P2ID note to Bob");
+    let p2id_note = create_p2id_note(
+        alice_account.id(),
+        bob_account.id(),
+        vec![FungibleAsset::new(faucet.id(), 30).unwrap().into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(p2id_note.clone())])
+        .build()
+        .unwrap();
+    say!("Creating note {}", p2id_note.id().to_hex());
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Try to consume the note with the wrong account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice tries to consume the note herself");
+
+    // Executing without submitting surfaces the failure before anything is proven
+    let mut consumer = alice_account.id();
+    let err = match client
+        .execute_transaction(consumer, consume_request(&p2id_note))
+        .await
+    {
+        Ok(_) => panic!("Alice should not be able to consume Bob's note"),
+        Err(err) => err,
+    };
+    say!("Execution failed: {}", err);
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Diagnose the failure and fix the request
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Diagnosing the failure");
+    match diagnose(&err, &p2id_note, consumer) {
+        Diagnosis::WrongConsumer { expected } => {
+            say!(
+                "The note can only be consumed by {}, retrying with that account",
+                expected.to_bech32(network_id())
+            );
+            consumer = expected;
+        }
+        Diagnosis::Unknown(reason) => {
+            say!("Cannot fix this automatically: {}", reason);
+            return Err(err);
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Replay the transaction with the fixed inputs
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Replaying the transaction");
+    submit_and_wait(&mut client, consumer, consume_request(&p2id_note)).await?;
+
+    Ok(())
+}
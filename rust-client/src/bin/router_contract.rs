@@ -0,0 +1,194 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::{StorageSlot, StorageSlotName},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Felt, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, get_full_account},
+    client::{init_client_from_config, network_id},
+    deployer::ContractDeployer,
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    output::{emit, Event},
+    say,
+    storage::print_account_storage,
+    wait::submit_and_wait,
+};
+
+/// A call routed through the router contract, encoded as `[selector, value]` note inputs.
+#[derive(Debug, Clone, Copy)]
+enum RouterCall {
+    Increment,
+    Reset,
+    Set(u64),
+}
+
+impl RouterCall {
+    fn selector(&self) -> u64 {
+        match self {
+            RouterCall::Increment => 1,
+            RouterCall::Reset => 2,
+            RouterCall::Set(_) => 3,
+        }
+    }
+
+    fn inputs(&self) -> Vec<Felt> {
+        let value = match self {
+            RouterCall::Set(value) => *value,
+            _ => 0,
+        };
+        vec![Felt::new(self.selector()), Felt::new(value)]
+    }
+
+    /// The counter value the contract should hold after this call.
+    fn apply(&self, count: u64) -> u64 {
+        match self {
+            RouterCall::Increment => count + 1,
+            RouterCall::Reset => 0,
+            RouterCall::Set(value) => *value,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Deploy the router contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating the router contract");
+
+    let counter_slot_name =
+        StorageSlotName::new("miden::tutorials::router_counter").expect("valid slot name");
+    let router_deployer = ContractDeployer::from_path(
+        "../masm/accounts/router.masm",
+        "external_contract::router_contract",
+    )
+    .with_slot(StorageSlot::with_value(
+        counter_slot_name.clone(),
+        Word::default(),
+    ));
+    let router_lib = router_deployer.library()?;
+    let router_contract = router_deployer.deploy(&mut client).await?;
+    say!(
+        "Router contract id: {}",
+        router_contract.id().to_bech32(network_id())
+    );
+    print_account_storage(&router_contract);
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Create one note per call
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice creates a note for every router call");
+
+    let note_file = MasmFile::read("../masm/notes/router_call_note.masm");
+    let note_script = note_file.check(
+        client
+            .code_builder()
+            .with_dynamically_linked_library(&router_lib)
+            .unwrap()
+            .compile_note_script(&note_file.source),
+    )?;
+
+    let calls = [
+        RouterCall::Increment,
+        RouterCall::Increment,
+        RouterCall::Set(42),
+        RouterCall::Increment,
+        RouterCall::Reset,
+    ];
+    let mut call_notes = Vec::with_capacity(calls.len());
+    let mut serials = SerialNumbers::random();
+    for call in calls {
+        let note = NoteBuilder::new(alice_account.id(), note_script.clone())
+            .inputs(call.inputs())
+            .build(&mut serials, client.rng())?;
+        say!("{:?} => note {}", call, note.id().to_hex());
+        call_notes.push((call, note));
+    }
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(
+            call_notes
+                .iter()
+                .map(|(_, note)| OutputNote::Full(note.clone()))
+                .collect(),
+        )
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Route every call and verify the dispatch path
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] The router consumes the notes one by one");
+
+    let mut expected = 0;
+    for (call, note) in call_notes {
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .build()
+            .unwrap();
+        submit_and_wait(&mut client, router_contract.id(), consume_request).await?;
+
+        expected = call.apply(expected);
+        let router = get_full_account(&mut client, router_contract.id()).await?;
+        // The count sits on top of the stack, which is the last element of the word
+        let count = router.storage().get_item(&counter_slot_name).unwrap()[3].as_int();
+        assert_eq!(count, expected, "unexpected counter after {:?}", call);
+        say!("✅ {:?} routed, counter is now {}", call, count);
+        emit(Event::storage_value(
+            router.id(),
+            &counter_slot_name,
+            router.storage().get_item(&counter_slot_name)?,
+        ));
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Show that unknown selectors are rejected
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Sending a note with an unknown selector");
+
+    let unknown_note = NoteBuilder::new(alice_account.id(), note_script)
+        .inputs([Felt::new(7), Felt::new(0)])
+        .build(&mut serials, client.rng())?;
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(unknown_note.clone())])
+        .build()
+        .unwrap();
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(unknown_note, None)])
+        .build()
+        .unwrap();
+    match client
+        .execute_transaction(router_contract.id(), consume_request)
+        .await
+    {
+        Ok(_) => panic!("router should reject an unknown selector"),
+        Err(err) => say!("Rejected as expected: {}", err),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::process::ExitCode;
+
+use miden_client::transaction::TransactionRequestBuilder;
+use rust_client::{
+    client::{init_client_from_config, network_id},
+    contracts::{CountReader, Counter, Mapping},
+    cycles::{execute_with_cycles, print_comparison},
+    error::{report, ExitGuard},
+    say,
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, _keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the counter, mapping and count reader contracts
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating the counter, mapping and count reader contracts");
+
+    let counter = Counter::deploy(&mut client).await?;
+    say!(
+        "Counter contract ID: {:?}",
+        counter.account_id.to_bech32(network_id())
+    );
+    let mapping = Mapping::deploy(&mut client).await?;
+    say!(
+        "Mapping contract ID: {:?}",
+        mapping.account_id.to_bech32(network_id())
+    );
+    let reader = CountReader::deploy(&mut client).await?;
+    say!(
+        "Count reader contract ID: {:?}",
+        reader.account_id.to_bech32(network_id())
+    );
+
+    // FPI reads the counter from the chain, and its first increment deploys it
+    counter.increment(&mut client).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Execute each script locally and count its cycles
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Executing each script locally");
+
+    // Execution alone yields the measurements, nothing is proven or submitted
+    let increment_request = TransactionRequestBuilder::new()
+        .custom_script(Counter::increment_script()?)
+        .build()?;
+    let (_, increment) = execute_with_cycles(
+        &mut client,
+        counter.account_id,
+        increment_request,
+        "counter increment",
+    )
+    .await?;
+
+    let write_request = TransactionRequestBuilder::new()
+        .custom_script(Mapping::write_script()?)
+        .build()?;
+    let (_, write) = execute_with_cycles(
+        &mut client,
+        mapping.account_id,
+        write_request,
+        "mapping write",
+    )
+    .await?;
+
+    let (_, fpi_read) = execute_with_cycles(
+        &mut client,
+        reader.account_id,
+        reader.copy_count_request(counter.account_id)?,
+        "FPI count copy",
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Compare the scripts
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Comparing the cycles\n");
+
+    print_comparison(&[
+        ("counter increment", increment),
+        ("mapping write", write),
+        ("FPI count copy", fpi_read),
+    ]);
+
+    // The kernel dominates the totals of scripts this small, so compare the
+    // script column when judging a contract change
+    say!(
+        "\nThe FPI copy spends {} more script cycles than the increment",
+        fpi_read.tx_script.saturating_sub(increment.tx_script)
+    );
+
+    Ok(())
+}
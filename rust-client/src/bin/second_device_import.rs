@@ -0,0 +1,189 @@
+use std::{fs, process::ExitCode};
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    auth::AuthSecretKey,
+    keystore::FilesystemKeyStore,
+    note::{NoteFile, NoteId, NoteType},
+    store::TransactionFilter,
+    transaction::{PaymentNoteDescription, TransactionRequestBuilder},
+    Client, Felt,
+};
+use rust_client::{
+    accounts::{
+        create_basic_account, create_basic_account_with_key, create_basic_faucet, get_full_account,
+        top_up,
+    },
+    client::{network_id, ClientSetup},
+    error::{report, ExitGuard},
+    keys::KeyFile,
+    notes::{consume_all, ConsumeOptions},
+    profiles::ProfileManager,
+    say,
+    wait::{submit_and_wait, wait_for_tx},
+};
+
+/// Balance and nonce of `wallet` as one client sees it.
+async fn wallet_state(
+    client: &mut Client<FilesystemKeyStore>,
+    wallet: AccountId,
+    faucet_id: AccountId,
+) -> Result<(u64, Felt), Box<dyn std::error::Error>> {
+    let account = get_full_account(client, wallet).await?;
+    let balance = account.vault().get_balance(faucet_id).unwrap_or(0);
+    Ok((balance, account.nonce()))
+}
+
+/// Transactions of `wallet` the client's own store has a record of.
+async fn known_transactions(
+    client: &mut Client<FilesystemKeyStore>,
+    wallet: AccountId,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    Ok(client
+        .get_transactions(TransactionFilter::All)
+        .await?
+        .iter()
+        .filter(|tx| tx.details.account_id == wallet)
+        .count())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut profiles = ProfileManager::new(ClientSetup::from_cli()?);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Build up some history on the laptop
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Using a fresh wallet on the laptop");
+
+    let laptop = profiles.switch("Laptop").await?;
+    let key = AuthSecretKey::new_falcon512_rpo();
+    let wallet = create_basic_account_with_key(&mut laptop.client, &laptop.keystore, &key)
+        .await?
+        .id();
+    say!("Wallet ID: {:?}", wallet.to_bech32(network_id()));
+    let merchant = create_basic_account(&mut laptop.client, &laptop.keystore)
+        .await?
+        .id();
+    let faucet = create_basic_faucet(&mut laptop.client, &laptop.keystore).await?;
+
+    top_up(&mut laptop.client, faucet.id(), wallet, 100).await?;
+    let payment_request = TransactionRequestBuilder::new().build_pay_to_id(
+        PaymentNoteDescription::new(
+            vec![FungibleAsset::new(faucet.id(), 30)?.into()],
+            wallet,
+            merchant,
+        ),
+        NoteType::Public,
+        laptop.client.rng(),
+    )?;
+    submit_and_wait(&mut laptop.client, wallet, payment_request).await?;
+
+    // A second mint the laptop never gets around to consuming
+    let mint_request = TransactionRequestBuilder::new().build_mint_fungible_asset(
+        FungibleAsset::new(faucet.id(), 20)?,
+        wallet,
+        NoteType::Public,
+        laptop.client.rng(),
+    )?;
+    submit_and_wait(&mut laptop.client, faucet.id(), mint_request).await?;
+    laptop.client.sync_state().await?;
+
+    let (balance, nonce) = wallet_state(&mut laptop.client, wallet, faucet.id()).await?;
+    let pending: Vec<NoteId> = laptop
+        .client
+        .get_consumable_notes(Some(wallet))
+        .await?
+        .into_iter()
+        .map(|(note, _)| note.id())
+        .collect();
+    say!(
+        "Laptop: balance {balance}, nonce {nonce}, {} transactions, {} unconsumed notes",
+        known_transactions(&mut laptop.client, wallet).await?,
+        pending.len()
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Carry the key and the unconsumed note IDs to the phone
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Exporting the wallet key for the phone");
+
+    // The key file is the only secret. The wallet's state is public on chain,
+    // so besides the key the phone only needs the IDs of the waiting notes
+    let key_path = profiles.profile_dir("Phone").join("wallet-key.json");
+    fs::create_dir_all(profiles.profile_dir("Phone"))?;
+    KeyFile::from_secret_key(&key).save(&key_path)?;
+    say!("Key written to {}", key_path.display());
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Import the wallet on the phone, mid-history
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Importing the wallet on the phone");
+
+    let phone = profiles.switch("Phone").await?;
+    phone
+        .keystore
+        .add_key(&KeyFile::load(&key_path)?.secret_key()?)?;
+    phone.client.import_account_by_id(wallet).await?;
+    phone.client.sync_state().await?;
+
+    // The phone gets the current state, not the transactions that led to it
+    let (phone_balance, phone_nonce) = wallet_state(&mut phone.client, wallet, faucet.id()).await?;
+    say!(
+        "Phone: balance {phone_balance}, nonce {phone_nonce}, {} transactions",
+        known_transactions(&mut phone.client, wallet).await?
+    );
+    assert_eq!(
+        (phone_balance, phone_nonce),
+        (balance, nonce),
+        "the imported state should match the laptop's"
+    );
+
+    // Notes committed before the import were never matched by the phone's
+    // syncs, so it fetches them by ID
+    for note_id in &pending {
+        phone.client.import_note(NoteFile::NoteId(*note_id)).await?;
+        say!("Phone imported note {}", note_id.to_hex());
+    }
+    phone.client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Spend on the phone and let the laptop catch up
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] The phone consumes the pending note, the laptop syncs");
+
+    if let Some(tx_id) = consume_all(&mut phone.client, wallet, &ConsumeOptions::default()).await? {
+        wait_for_tx(&mut phone.client, tx_id).await?;
+    }
+    let phone_state = wallet_state(&mut phone.client, wallet, faucet.id()).await?;
+
+    let laptop = profiles.switch("Laptop").await?;
+    let laptop_state = wallet_state(&mut laptop.client, wallet, faucet.id()).await?;
+    let left_over = laptop
+        .client
+        .get_consumable_notes(Some(wallet))
+        .await?
+        .len();
+    say!(
+        "Laptop: balance {}, nonce {}, {left_over} unconsumed notes",
+        laptop_state.0,
+        laptop_state.1
+    );
+    say!("Phone:  balance {}, nonce {}", phone_state.0, phone_state.1);
+
+    assert_eq!(phone_state.0, 90, "the wallet should hold 100 - 30 + 20");
+    assert_eq!(
+        laptop_state, phone_state,
+        "both devices should agree on balance and nonce"
+    );
+    assert_eq!(left_over, 0, "the laptop should see the note as consumed");
+    say!("✅ Laptop and phone agree on the wallet");
+
+    Ok(())
+}
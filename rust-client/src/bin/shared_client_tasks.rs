@@ -0,0 +1,202 @@
+use std::{process::ExitCode, time::Instant};
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::NoteType,
+    transaction::{PaymentNoteDescription, TransactionId, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    actor::{Actor, ActorHandle},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    middleware::submit_logged,
+    notes::{consume_all, ConsumeOptions},
+    say,
+    wait::{wait_for_notes, wait_for_tx},
+};
+
+/// One client, reachable from any task through its actor.
+type SharedClient = ActorHandle<Client<FilesystemKeyStore>>;
+
+/// Tasks paying Bob at the same time.
+const PAYERS: usize = 4;
+
+/// Tokens each payer sends.
+const AMOUNT: u64 = 10;
+
+/// Times the reader task syncs and reads Alice's balance.
+const READS: usize = 5;
+
+/// Pays Bob from Alice's account through the shared client.
+async fn pay(
+    client: SharedClient,
+    payer: usize,
+    alice: AccountId,
+    bob: AccountId,
+    faucet: AccountId,
+) -> Result<TransactionId, String> {
+    client
+        .call(move |client| {
+            Box::pin(async move {
+                // Built inside the job, so it sees the account state left by
+                // the jobs before it
+                let payment = PaymentNoteDescription::new(
+                    vec![FungibleAsset::new(faucet, AMOUNT)
+                        .map_err(|err| err.to_string())?
+                        .into()],
+                    alice,
+                    bob,
+                );
+                let request = TransactionRequestBuilder::new()
+                    .build_pay_to_id(payment, NoteType::Public, client.rng())
+                    .map_err(|err| err.to_string())?;
+                let tx_id = submit_logged(client, alice, request)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                say!("[payer {payer}] submitted {}", tx_id.to_hex());
+                Ok::<_, String>(tx_id)
+            })
+        })
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+/// Syncs and reads Alice's balance while the payers run.
+async fn read(client: SharedClient, alice: AccountId, faucet: AccountId) -> Result<(), String> {
+    for _ in 0..READS {
+        let (block, balance) = client
+            .call(move |client| {
+                Box::pin(async move {
+                    let block = client
+                        .sync_state()
+                        .await
+                        .map_err(|err| err.to_string())?
+                        .block_num;
+                    let balance = get_full_account(client, alice)
+                        .await
+                        .map_err(|err| err.to_string())?
+                        .vault()
+                        .get_balance(faucet)
+                        .unwrap_or(0);
+                    Ok::<_, String>((block, balance))
+                })
+            })
+            .await
+            .map_err(|err| err.to_string())??;
+        say!("[reader] block {block}: Alice holds {balance}");
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Hand the client to an actor and use it from several tasks
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] {PAYERS} tasks pay Bob while another one syncs");
+
+    // Opening a second client on the same store file for each task looks
+    // simpler, but every client caches its own view of the accounts: two of
+    // them would build transactions on the same Alice state and one would be
+    // rejected. The actor keeps a single client and runs one job at a time.
+    let (actor, handle) = Actor::new(client);
+    let start = Instant::now();
+
+    let payers: Vec<_> = (0..PAYERS)
+        .map(|payer| {
+            tokio::spawn(pay(
+                handle.clone(),
+                payer,
+                alice_account.id(),
+                bob_account.id(),
+                faucet.id(),
+            ))
+        })
+        .collect();
+    let reader = tokio::spawn(read(handle.clone(), alice_account.id(), faucet.id()));
+    drop(handle);
+
+    // The actor runs here, on the main task, and hands the client back once
+    // every task has dropped its handle
+    let mut client = actor.run().await;
+    let mut tx_ids = Vec::new();
+    for payer in payers {
+        tx_ids.push(payer.await??);
+    }
+    reader.await??;
+    say!(
+        "✅ {} jobs from {} tasks ran in {:?}",
+        PAYERS + READS,
+        PAYERS + 1,
+        start.elapsed()
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Wait for the payments and let Bob collect them
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Waiting for the payments and collecting them");
+
+    for tx_id in tx_ids {
+        wait_for_tx(&mut client, tx_id).await?;
+    }
+    wait_for_notes(&mut client, bob_account.id(), PAYERS).await?;
+    if let Some(tx_id) =
+        consume_all(&mut client, bob_account.id(), &ConsumeOptions::default()).await?
+    {
+        wait_for_tx(&mut client, tx_id).await?;
+    }
+
+    let alice_balance = get_full_account(&mut client, alice_account.id())
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    let bob_balance = get_full_account(&mut client, bob_account.id())
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    say!("✅ Alice holds {alice_balance}, Bob holds {bob_balance}");
+    let paid = PAYERS as u64 * AMOUNT;
+    assert_eq!(
+        alice_balance,
+        100 - paid,
+        "every payment should leave Alice"
+    );
+    assert_eq!(bob_balance, paid, "every payment should reach Bob");
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use std::{error::Error, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+use miden_client::{
+    account::AccountId,
+    asset::{Asset, NoteAssets},
+    keystore::FilesystemKeyStore,
+    store::{NoteFilter, TransactionFilter},
+    transaction::TransactionStatus,
+    Word,
+};
+use rust_client::{
+    address_book::{AccountAuth, AddressBook},
+    aliases::Aliases,
+    client::{network, network_id, parse_account_id, ClientArgs, ClientSetup},
+    error::{report, ConfigError, ExitGuard},
+    reset::LocalState,
+    run_mode::confirm,
+    say,
+};
+
+/// Shows what previous tutorial runs left in the store without syncing, or
+/// deletes it.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Tracked accounts with their nonce and auth
+    Accounts,
+    /// Input notes
+    Notes {
+        /// Only notes a tracked account can consume
+        #[arg(long)]
+        consumable: bool,
+    },
+    /// Transactions
+    Txs {
+        /// Only transactions that are not committed yet
+        #[arg(long)]
+        pending: bool,
+    },
+    /// Checks that the keystore holds the key an account signs with
+    Keys {
+        /// Bech32 ID of the account
+        #[arg(value_parser = parse_account_id)]
+        account: AccountId,
+    },
+    /// Account aliases saved for the network
+    Aliases,
+    /// Deletes the store and keystore, e.g. after a devnet reset
+    Reset,
+}
+
+fn format_assets(assets: &NoteAssets) -> String {
+    if assets.is_empty() {
+        return "-".to_string();
+    }
+    assets
+        .iter()
+        .map(|asset| match asset {
+            Asset::Fungible(fungible) => format!(
+                "{}@{}",
+                fungible.amount(),
+                fungible.faucet_id().to_bech32(network_id())
+            ),
+            Asset::NonFungible(non_fungible) => format!("nft:{}", non_fungible.faucet_id_prefix()),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+/// Deletes the store and keystore of `setup` once the user confirms.
+fn reset(setup: &ClientSetup) -> Result<(), Box<dyn Error>> {
+    let state = LocalState::of(setup);
+    if state.is_empty() {
+        say!(
+            "Nothing to reset: neither {} nor {} exists",
+            setup.store_path.display(),
+            setup.keystore_path.display()
+        );
+        return Ok(());
+    }
+
+    say!("Resetting deletes:");
+    for path in state.paths() {
+        say!("  {}", path.display());
+    }
+    say!("Accounts whose keys are deleted can never sign again.");
+    if !confirm("Delete the local store and keystore?") {
+        return Err("reset not confirmed, pass --yes to skip the prompt".into());
+    }
+
+    state.remove()?;
+    say!("✅ Local state deleted; the next run starts with an empty store");
+    Ok(())
+}
+
+/// Looks up the key `account_id` signs with in the keystore of `setup`.
+///
+/// Only the address book and the keystore are read, so this works without a
+/// store or a node.
+fn keys(setup: &ClientSetup, account_id: AccountId) -> Result<(), Box<dyn Error>> {
+    let account = account_id.to_bech32(network_id());
+    let address_book = AddressBook::open_default()?;
+    let Some(key) = address_book.require_keys(account_id)? else {
+        return Err(ConfigError::new(format!(
+            "{account} has no key recorded in {}, so its auth is unknown",
+            address_book.path().display()
+        ))
+        .into());
+    };
+    let key_hex = Word::from(key).to_hex();
+    say!("{account} signs with key {key_hex}");
+
+    // Opening a keystore creates its directory, which a check should not do
+    let present = setup.keystore_path.is_dir()
+        && FilesystemKeyStore::new(setup.keystore_path.clone())?
+            .get_key(key)?
+            .is_some();
+    if !present {
+        return Err(ConfigError::new(format!(
+            "key {key_hex} of {account} is not in {}",
+            setup.keystore_path.display()
+        ))
+        .into());
+    }
+    say!("✅ The key is in {}", setup.keystore_path.display());
+    Ok(())
+}
+
+async fn run() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let setup = ClientSetup::from_args(cli.client)?;
+
+    // Building the client opens the store, so the reset has to come first, and
+    // the key check only reads local files
+    match cli.command {
+        Command::Reset => return reset(&setup),
+        Command::Keys { account } => return keys(&setup, account),
+        _ => {}
+    }
+    let (client, _keystore) = setup.build().await?;
+
+    match cli.command {
+        Command::Reset | Command::Keys { .. } => {
+            unreachable!("handled before the client is built")
+        }
+        Command::Accounts => {
+            let headers = client.get_account_headers().await?;
+            let address_book = AddressBook::open_default()?;
+            let aliases = Aliases::open_default()?.entries(network())?;
+            say!(
+                "{:<44} {:<30} {:<8} {:>6} {:<8} {:<12}  status",
+                "account",
+                "type",
+                "storage",
+                "nonce",
+                "auth",
+                "alias"
+            );
+            for (header, status) in headers {
+                let id = header.id();
+                let auth = match address_book.auth(id) {
+                    Some(AccountAuth::Keys) => "keys",
+                    Some(AccountAuth::NoAuth) => "no-auth",
+                    None => "unknown",
+                };
+                let alias = aliases
+                    .iter()
+                    .filter(|(_, aliased)| *aliased == id)
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                say!(
+                    "{:<44} {:<30} {:<8} {:>6} {:<8} {:<12}  {:?}",
+                    id.to_bech32(network_id()),
+                    format!("{:?}", id.account_type()),
+                    format!("{:?}", id.storage_mode()),
+                    header.nonce().as_int(),
+                    auth,
+                    if alias.is_empty() { "-" } else { &alias },
+                    status
+                );
+            }
+        }
+        Command::Aliases => {
+            let aliases = Aliases::open_default()?;
+            say!("{:<16} account", "alias");
+            for (name, id) in aliases.entries(network())? {
+                say!("{:<16} {}", name, id.to_bech32(network_id()));
+            }
+        }
+        Command::Notes { consumable: false } => {
+            let notes = client.get_input_notes(NoteFilter::All).await?;
+            say!("{:<66} {:<24}  assets", "note", "state");
+            for note in notes {
+                say!(
+                    "{:<66} {:<24}  {}",
+                    note.id().to_hex(),
+                    note.state().to_string(),
+                    format_assets(note.assets())
+                );
+            }
+        }
+        Command::Notes { consumable: true } => {
+            let notes = client.get_consumable_notes(None).await?;
+            say!("{:<66} {:<44}  assets", "note", "consumable by");
+            for (note, consumability) in notes {
+                let accounts = consumability
+                    .iter()
+                    .map(|(account_id, _)| account_id.to_bech32(network_id()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                say!(
+                    "{:<66} {:<44}  {}",
+                    note.id().to_hex(),
+                    accounts,
+                    format_assets(note.assets())
+                );
+            }
+        }
+        Command::Txs { pending } => {
+            let filter = if pending {
+                TransactionFilter::Uncommitted
+            } else {
+                TransactionFilter::All
+            };
+            let txs = client.get_transactions(filter).await?;
+            say!("{:<66} {:<44}  status", "transaction", "account");
+            for tx in txs {
+                let status = match &tx.status {
+                    TransactionStatus::Pending => "pending".to_string(),
+                    TransactionStatus::Committed { block_number, .. } => {
+                        format!("committed in block {block_number}")
+                    }
+                    TransactionStatus::Discarded(cause) => format!("discarded ({cause:?})"),
+                };
+                say!(
+                    "{:<66} {:<44}  {}",
+                    tx.id.to_hex(),
+                    tx.details.account_id.to_bech32(network_id()),
+                    status
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
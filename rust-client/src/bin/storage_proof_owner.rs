@@ -0,0 +1,94 @@
+use std::{collections::BTreeSet, process::ExitCode};
+
+use miden_client::{
+    account::StorageSlotName,
+    rpc::{AccountStateAt, GrpcClient, NodeRpcClient},
+    Word,
+};
+use rust_client::{
+    accounts::get_full_account,
+    client::{network_id, ClientSetup},
+    contracts::Mapping,
+    error::{report, ExitGuard},
+    say,
+    storage_proof::MapEntryProof,
+};
+
+/// Where the proof is written for `storage_proof_verifier`.
+const PROOF_FILE: &str = "map_entry_proof.json";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let setup = ClientSetup::from_cli()?;
+    let rpc = GrpcClient::new(&setup.endpoint, setup.timeout_ms);
+    let (mut client, _keystore) = setup.build().await?;
+
+    let sync_summary = client.sync_state().await.unwrap();
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Deploy the mapping contract
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Deploying the mapping contract");
+
+    // Deploying writes [1, 2, 3, 4] under the zero key
+    let mapping = Mapping::deploy(&mut client).await?;
+    say!(
+        "Mapping contract ID: {}",
+        mapping.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fetch the node's witness for the account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Fetching the account witness at the chain tip");
+
+    client.sync_state().await?;
+    let (block_num, account_proofs) = rpc
+        .get_account_proofs(
+            &BTreeSet::from([mapping.account_id]),
+            AccountStateAt::ChainTip,
+            BTreeSet::new(),
+        )
+        .await?;
+    let account_witness = account_proofs
+        .first()
+        .expect("node returned no proof for the requested account")
+        .account_witness()
+        .clone();
+    say!("Account witnessed at block {block_num}");
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Open the key and write the proof
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Proving the value under the zero key");
+
+    let account = get_full_account(&mut client, mapping.account_id).await?;
+    let proof = MapEntryProof::prove(
+        &account,
+        StorageSlotName::new(Mapping::MAP_SLOT)?,
+        Word::default(),
+        block_num,
+        account_witness,
+    )?;
+    say!("Key:   {}", proof.key.to_hex());
+    say!("Value: {}", proof.value.to_hex());
+
+    // Check the proof once before handing it out
+    let (header, _) = rpc
+        .get_block_header_by_number(Some(block_num), false)
+        .await?;
+    proof.verify(&header)?;
+
+    proof.save(PROOF_FILE)?;
+    say!("\nProof written to {PROOF_FILE}");
+    say!("Verify it with: cargo run --bin storage_proof_verifier -- {PROOF_FILE}");
+
+    Ok(())
+}
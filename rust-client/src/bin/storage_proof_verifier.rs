@@ -0,0 +1,72 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use miden_client::rpc::{GrpcClient, NodeRpcClient};
+use rust_client::{
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    say,
+    storage_proof::MapEntryProof,
+};
+
+/// Checks a proof written by `storage_proof_owner` against the header of its block.
+///
+/// Only the block header is fetched from the node; the verifier keeps no store and
+/// does not need the account.
+#[derive(Parser)]
+struct Cli {
+    /// Proof file written by `storage_proof_owner`
+    #[arg(default_value = "map_entry_proof.json")]
+    proof: PathBuf,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let setup = ClientSetup::from_args(cli.client)?;
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Load the proof
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Loading the proof from {}", cli.proof.display());
+
+    let proof = MapEntryProof::load(&cli.proof)?;
+    say!(
+        "Claim: account {} held {} under key {} in slot {} at block {}",
+        proof.account_id().to_bech32(network_id()),
+        proof.value.to_hex(),
+        proof.key.to_hex(),
+        proof.slot_name,
+        proof.block_num
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fetch the block header and check the proof
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 2] Checking the proof against block {}",
+        proof.block_num
+    );
+
+    // A light client would check the header against the chain MMR; here the node is trusted for it
+    let rpc = GrpcClient::new(&setup.endpoint, setup.timeout_ms);
+    let (header, _) = rpc
+        .get_block_header_by_number(Some(proof.block_num), false)
+        .await?;
+    say!("Account root: {}", header.account_root().to_hex());
+
+    proof.verify(&header)?;
+    say!(
+        "✅ The proof holds: the value was committed at block {}",
+        proof.block_num
+    );
+
+    Ok(())
+}
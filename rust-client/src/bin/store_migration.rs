@@ -0,0 +1,198 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use miden_client::{
+    account::AccountFile,
+    note::NoteFile,
+    store::{AccountRecordData, NoteFilter},
+    utils::{Deserializable, Serializable},
+};
+use rust_client::{
+    client::{ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    say,
+};
+
+/// Moves accounts, keys and notes between stores of different client versions.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compare the store with the schema of this client version
+    Detect,
+    /// Write accounts, keys and notes from the store to `dir`
+    Export { dir: PathBuf },
+    /// Load a previous export into a fresh store
+    Import { dir: PathBuf },
+}
+
+/// Reads the schema version recorded by the store migrations.
+fn schema_version(store_path: &Path) -> Result<u32, rusqlite::Error> {
+    let connection = rusqlite::Connection::open(store_path)?;
+    connection.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Creates an empty store with the current client to learn its schema version.
+async fn current_schema_version() -> Result<u32, Box<dyn std::error::Error>> {
+    let scratch_dir = std::env::temp_dir().join(format!("store-schema-{}", std::process::id()));
+    fs::create_dir_all(&scratch_dir)?;
+    let store_path = scratch_dir.join("store.sqlite3");
+
+    let setup = ClientSetup {
+        keystore_path: scratch_dir.join("keystore"),
+        store_path: store_path.clone(),
+        ..ClientSetup::default()
+    };
+    drop(setup.build().await?);
+
+    let version = schema_version(&store_path)?;
+    fs::remove_dir_all(&scratch_dir)?;
+    Ok(version)
+}
+
+async fn detect(setup: &ClientSetup) -> Result<(), Box<dyn std::error::Error>> {
+    if !setup.store_path.exists() {
+        say!("No store found at {}", setup.store_path.display());
+        return Ok(());
+    }
+
+    let found = schema_version(&setup.store_path)?;
+    let expected = current_schema_version().await?;
+    say!("Store schema version: {found}");
+    say!("Client schema version: {expected}");
+
+    if found == expected {
+        say!("✅ The store matches this client version, no migration needed.");
+    } else {
+        say!("⚠️  The store was created by a different client version.");
+        say!("Run `export` with the old client, then `import` with this one.");
+    }
+    Ok(())
+}
+
+async fn export(setup: ClientSetup, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let keystore_path = setup.keystore_path.clone();
+    let (mut client, _keystore) = setup.build().await?;
+
+    let accounts_dir = dir.join("accounts");
+    let keys_dir = dir.join("keys");
+    let notes_dir = dir.join("notes");
+    for path in [&accounts_dir, &keys_dir, &notes_dir] {
+        fs::create_dir_all(path)?;
+    }
+
+    // Accounts are exported with their full state so private accounts survive too
+    for (header, _) in client.get_account_headers().await? {
+        let account_id = header.id();
+        let record = client
+            .get_account(account_id)
+            .await?
+            .expect("listed account is tracked");
+        let account = match record.account_data() {
+            AccountRecordData::Full(account) => account.clone(),
+            AccountRecordData::Partial(_) => {
+                say!("Skipping {account_id}: only partial state is tracked");
+                continue;
+            }
+        };
+        let account_file = AccountFile::new(account, vec![]);
+        fs::write(
+            accounts_dir.join(format!("{}.mac", account_id.to_hex())),
+            account_file.to_bytes(),
+        )?;
+        say!("Exported account {account_id}");
+    }
+
+    // Keystore entries are plain files, so they can be copied as-is
+    if keystore_path.exists() {
+        for entry in fs::read_dir(&keystore_path)? {
+            let entry = entry?;
+            fs::copy(entry.path(), keys_dir.join(entry.file_name()))?;
+        }
+    }
+    say!("Exported keys from {}", keystore_path.display());
+
+    // Only notes that can still be consumed are worth carrying over
+    for note_record in client.get_input_notes(NoteFilter::Unspent).await? {
+        let note_id = note_record.id();
+        let note_file = match note_record.inclusion_proof().cloned() {
+            Some(proof) => NoteFile::NoteWithProof(note_record.try_into()?, proof),
+            None => NoteFile::NoteDetails {
+                details: note_record.into(),
+                after_block_num: 0.into(),
+                tag: None,
+            },
+        };
+        fs::write(
+            notes_dir.join(format!("{}.mno", note_id.to_hex())),
+            note_file.to_bytes(),
+        )?;
+        say!("Exported note {note_id}");
+    }
+
+    say!("\nExport written to {}", dir.display());
+    Ok(())
+}
+
+async fn import(setup: ClientSetup, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if setup.store_path.exists() {
+        let backup = setup.store_path.with_extension("sqlite3.bak");
+        fs::rename(&setup.store_path, &backup)?;
+        say!("Moved the old store to {}", backup.display());
+    }
+
+    // Keys go in first so imported accounts can sign right away
+    fs::create_dir_all(&setup.keystore_path)?;
+    for entry in fs::read_dir(dir.join("keys"))? {
+        let entry = entry?;
+        fs::copy(entry.path(), setup.keystore_path.join(entry.file_name()))?;
+    }
+
+    let (mut client, _keystore) = setup.build().await?;
+    client.sync_state().await?;
+
+    for entry in fs::read_dir(dir.join("accounts"))? {
+        let bytes = fs::read(entry?.path())?;
+        let account_file = AccountFile::read_from_bytes(&bytes)?;
+        client.add_account(&account_file.account, true).await?;
+        say!("Imported account {}", account_file.account.id());
+    }
+
+    for entry in fs::read_dir(dir.join("notes"))? {
+        let bytes = fs::read(entry?.path())?;
+        let note_file = NoteFile::read_from_bytes(&bytes)?;
+        let note_id = client.import_note(note_file).await?;
+        say!("Imported note {note_id}");
+    }
+
+    client.sync_state().await?;
+    say!("\n✅ Import complete");
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let setup = ClientSetup::from_args(cli.client)?;
+
+    match cli.command {
+        Command::Detect => detect(&setup).await,
+        Command::Export { dir } => export(setup, &dir).await,
+        Command::Import { dir } => import(setup, &dir).await,
+    }
+}
@@ -0,0 +1,278 @@
+use clap::Parser;
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use tokio::time::{Duration, Instant};
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionRequest, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet},
+    client::{network, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    say,
+    stress::{ClientStats, LoadSummary, Operation},
+    wait::submit_and_wait,
+};
+
+/// Runs a mint → consume → send loop on several independent clients at once
+/// and reports the throughput and error rate of the network under that load.
+///
+/// Every client gets its own store and keystore under `--data-dir`, so
+/// `--store-path` and `--keystore-path` do not apply here.
+#[derive(Parser)]
+struct Cli {
+    /// Independent clients generating load at the same time
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=32))]
+    clients: u16,
+    /// Mint → consume → send loops per client
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+    /// Directory holding one store and keystore per client, emptied first
+    #[arg(long, default_value = "./stress")]
+    data_dir: PathBuf,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// Tokens minted in every loop.
+const MINT_AMOUNT: u64 = 10;
+
+/// Tokens sent on to the next client in every loop.
+const SEND_AMOUNT: u64 = 5;
+
+/// A client with the wallet and faucet its loop uses.
+struct LoadClient {
+    index: usize,
+    client: Client<FilesystemKeyStore>,
+    wallet: AccountId,
+    faucet: AccountId,
+}
+
+/// Builds client `index` on a fresh store and keystore and creates its accounts.
+async fn open_client(
+    setup: ClientSetup,
+    data_dir: PathBuf,
+    index: usize,
+) -> Result<LoadClient, String> {
+    let dir = data_dir.join(format!("client-{index}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    let (mut client, keystore) = ClientSetup {
+        keystore_path: dir.join("keystore"),
+        store_path: dir.join("store.sqlite3"),
+        // Scratch stores, and store encryption is installed once per process
+        store_passphrase: None,
+        ..setup
+    }
+    .build()
+    .await
+    .map_err(|err| err.to_string())?;
+    client.sync_state().await.map_err(|err| err.to_string())?;
+
+    let wallet = create_basic_account(&mut client, &keystore)
+        .await
+        .map_err(|err| err.to_string())?;
+    let faucet = create_basic_faucet(&mut client, &keystore)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(LoadClient {
+        index,
+        client,
+        wallet: wallet.id(),
+        faucet: faucet.id(),
+    })
+}
+
+/// Submits `request` and returns the time until it was committed.
+async fn submit_timed(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    request: TransactionRequest,
+) -> Result<Duration, String> {
+    let report = submit_and_wait(client, account_id, request)
+        .await
+        .map_err(|err| err.to_string())?;
+    if !report.is_committed() {
+        return Err(format!(
+            "transaction {} ended as {:?}",
+            report.tx_id.to_hex(),
+            report.status
+        ));
+    }
+    Ok(report.elapsed)
+}
+
+/// Runs one mint → consume → send loop, stopping at the first failed step.
+async fn run_loop(
+    load: &mut LoadClient,
+    recipient: AccountId,
+    stats: &mut ClientStats,
+) -> Result<(), String> {
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(load.faucet, MINT_AMOUNT).map_err(|err| err.to_string())?,
+            load.wallet,
+            NoteType::Public,
+            load.client.rng(),
+        )
+        .map_err(|err| err.to_string())?;
+    let note = mint_request
+        .expected_output_own_notes()
+        .pop()
+        .expect("a mint request creates one note");
+    let outcome = submit_timed(&mut load.client, load.faucet, mint_request).await;
+    step(load.index, stats, Operation::Mint, outcome)?;
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(note, None)])
+        .build()
+        .map_err(|err| err.to_string())?;
+    let outcome = submit_timed(&mut load.client, load.wallet, consume_request).await;
+    step(load.index, stats, Operation::Consume, outcome)?;
+
+    let payment = create_p2id_note(
+        load.wallet,
+        recipient,
+        vec![FungibleAsset::new(load.faucet, SEND_AMOUNT)
+            .map_err(|err| err.to_string())?
+            .into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        load.client.rng(),
+    )
+    .map_err(|err| err.to_string())?;
+    let send_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(payment)])
+        .build()
+        .map_err(|err| err.to_string())?;
+    let outcome = submit_timed(&mut load.client, load.wallet, send_request).await;
+    step(load.index, stats, Operation::Send, outcome)
+}
+
+/// Records the outcome of one step and passes its error on.
+fn step(
+    index: usize,
+    stats: &mut ClientStats,
+    operation: Operation,
+    outcome: Result<Duration, String>,
+) -> Result<(), String> {
+    match &outcome {
+        Ok(latency) => say!("[client {index}] {operation} committed in {latency:.1?}"),
+        Err(err) => say!("[client {index}] {operation} failed: {err}"),
+    }
+    stats.record(operation, outcome.clone());
+    outcome.map(|_| ())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let clients = usize::from(cli.clients);
+    let setup = ClientSetup::from_args(cli.client)?;
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Start the clients, each with its own store and keystore
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 1] Starting {clients} clients on {} in {}",
+        network().name(),
+        cli.data_dir.display()
+    );
+
+    let openers: Vec<_> = (0..clients)
+        .map(|index| tokio::spawn(open_client(setup.clone(), cli.data_dir.clone(), index)))
+        .collect();
+    let mut load_clients = Vec::with_capacity(clients);
+    for opener in openers {
+        load_clients.push(opener.await??);
+    }
+    // Each wallet pays the next one, so every client also receives notes
+    let recipients: Vec<AccountId> = (0..clients)
+        .map(|index| load_clients[(index + 1) % clients].wallet)
+        .collect();
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Run the loops on all clients at once
+    // -------------------------------------------------------------------------
+    say!(
+        "\n[STEP 2] Running {} mint → consume → send loops per client",
+        cli.iterations
+    );
+
+    // A failed step ends its loop, but the client goes on with the next one, so
+    // a flaky node shows up in the error rate instead of stopping the test
+    let iterations = cli.iterations;
+    let start = Instant::now();
+    let tasks: Vec<_> = load_clients
+        .into_iter()
+        .zip(recipients)
+        .map(|(mut load, recipient)| {
+            tokio::spawn(async move {
+                let mut stats = ClientStats::default();
+                for _ in 0..iterations {
+                    let _ = run_loop(&mut load, recipient, &mut stats).await;
+                }
+                stats
+            })
+        })
+        .collect();
+    let mut stats = Vec::with_capacity(clients);
+    for task in tasks {
+        stats.push(task.await?);
+    }
+    let summary = LoadSummary::aggregate(&stats, start.elapsed());
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Report throughput and error rate
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Load results\n");
+    say!(
+        "{:<8} {:>9} {:>7} {:>8} {:>8}",
+        "step",
+        "committed",
+        "failed",
+        "p50",
+        "p95"
+    );
+    let format_latency = |latency: Option<Duration>| {
+        latency
+            .map(|latency| format!("{:.1}s", latency.as_secs_f64()))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    for operation in &summary.operations {
+        say!(
+            "{:<8} {:>9} {:>7} {:>8} {:>8}",
+            operation.operation.name(),
+            operation.committed,
+            operation.failed,
+            format_latency(operation.p50),
+            format_latency(operation.p95)
+        );
+    }
+    say!(
+        "\n{} clients, {} transactions committed in {:.1?}",
+        summary.clients,
+        summary.committed,
+        summary.elapsed
+    );
+    say!("Throughput: {:.2} tx/s", summary.throughput());
+    say!(
+        "Error rate: {:.1}% ({} of {} attempts)",
+        summary.error_rate() * 100.0,
+        summary.failed,
+        summary.attempts
+    );
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use clap::Parser;
+use std::{path::PathBuf, process::ExitCode};
+
+use rust_client::{
+    accounts::get_full_account,
+    client::{network_id, ClientArgs, ClientSetup},
+    error::{report, ExitGuard},
+    middleware::submit_proven_logged,
+    proving::ProofBundle,
+    say,
+    wait::wait_for_tx,
+};
+
+/// Submits a transaction proven elsewhere by `offline_prove`.
+///
+/// This side needs network access but no keys: pass a `--store-path` and
+/// `--keystore-path` of its own to play the online machine.
+#[derive(Parser)]
+struct Cli {
+    /// Directory written by `offline_prove`
+    #[arg(long, default_value = "offline_tx")]
+    bundle: PathBuf,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // Initialize client
+    let (mut client, _keystore) = ClientSetup::from_args(cli.client)?.build().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Read the proof bundle
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Reading the proof from {}", cli.bundle.display());
+
+    let bundle = ProofBundle::read(&cli.bundle)?;
+    let account_id = bundle.account.id();
+    let tx_id = bundle.proven_transaction.id();
+    say!(
+        "Transaction {} of account {:?}",
+        tx_id.to_hex(),
+        account_id.to_bech32(network_id())
+    );
+
+    // Applying the transaction updates the account in this store, so a client
+    // that has not seen the account yet starts tracking it first
+    if client.get_account(account_id).await?.is_none() {
+        client.add_account(&bundle.account, false).await?;
+        say!("Tracking the account from {}", ProofBundle::ACCOUNT_FILE);
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Submit the proven transaction and apply it
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Submitting the proven transaction");
+
+    // Nothing is executed or proven here; the node verifies the proof as is
+    let submission_height =
+        submit_proven_logged(&mut client, bundle.proven_transaction, &bundle.tx_result).await?;
+    client
+        .apply_transaction(&bundle.tx_result, submission_height)
+        .await?;
+    wait_for_tx(&mut client, tx_id).await?;
+    say!("✅ Transaction committed");
+
+    let account = get_full_account(&mut client, account_id).await?;
+    say!("Account nonce is now {}", account.nonce().as_int());
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, NoteAttachment, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, Word,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    output::{emit, Event},
+    say,
+    wait::submit_and_wait,
+};
+
+/// Tokens of A Alice offers.
+const OFFERED: u64 = 40;
+
+/// Tokens of B Alice asks for in return.
+const REQUESTED: u64 = 60;
+
+/// Returns the balances of `account_id` in both tokens and reports them.
+async fn balances(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    token_a: AccountId,
+    token_b: AccountId,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let account = get_full_account(client, account_id).await?;
+    let balance_a = account.vault().get_balance(token_a).unwrap_or(0);
+    let balance_b = account.vault().get_balance(token_b).unwrap_or(0);
+    emit(Event::balance(account_id, token_a, balance_a));
+    emit(Event::balance(account_id, token_b, balance_b));
+    Ok((balance_a, balance_b))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the two tokens and the accounts
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating tokens A and B, Alice and Bob");
+
+    let token_a = create_basic_faucet(&mut client, &keystore).await?;
+    say!("Token A faucet: {:?}", token_a.id().to_bech32(network_id()));
+    let token_b = create_basic_faucet(&mut client, &keystore).await?;
+    say!("Token B faucet: {:?}", token_b.id().to_bech32(network_id()));
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Fund Alice with token A and Bob with token B
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Minting 100 A for Alice and 100 B for Bob");
+
+    top_up(&mut client, token_a.id(), alice_account.id(), 100).await?;
+    top_up(&mut client, token_b.id(), bob_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice offers A for B in a swap note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice offers {OFFERED} A for {REQUESTED} B");
+
+    // Alice fixes the payback note up front, so whoever fills the offer can
+    // only pay her. The sender only goes into the note metadata, not into its
+    // recipient, so naming Bob here does not restrict who may consume the offer.
+    let requested = FungibleAsset::new(token_b.id(), REQUESTED)?;
+    let payback = create_p2id_note(
+        bob_account.id(),
+        alice_account.id(),
+        vec![requested.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        client.rng(),
+    )?;
+
+    let mut inputs = payback.recipient().digest().to_vec();
+    inputs.extend(Word::from(requested));
+    inputs.push(payback.metadata().tag().into());
+
+    let note_file = MasmFile::read("../masm/notes/swap_note.masm");
+    let note_script =
+        note_file.check(client.code_builder().compile_note_script(&note_file.source))?;
+    let swap_note = NoteBuilder::new(alice_account.id(), note_script)
+        .inputs(inputs)
+        .asset(FungibleAsset::new(token_a.id(), OFFERED)?)
+        .build(&mut SerialNumbers::random(), client.rng())?;
+    say!("Swap note ID: {}", swap_note.id().to_hex());
+
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(swap_note.clone())])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), note_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Bob fills the offer
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Bob consumes the swap note, paying Alice in the same transaction");
+
+    let fill_request = TransactionRequestBuilder::new()
+        .input_notes([(swap_note, None)])
+        .expected_output_recipients(vec![payback.recipient().clone()])
+        .build()?;
+    submit_and_wait(&mut client, bob_account.id(), fill_request).await?;
+    say!("Payback note ID: {}", payback.id().to_hex());
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Alice collects the payback note
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Alice consumes the payback note");
+
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(payback, None)])
+        .build()?;
+    submit_and_wait(&mut client, alice_account.id(), consume_request).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 6: Verify balances
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Verifying balances");
+
+    let (alice_a, alice_b) =
+        balances(&mut client, alice_account.id(), token_a.id(), token_b.id()).await?;
+    let (bob_a, bob_b) =
+        balances(&mut client, bob_account.id(), token_a.id(), token_b.id()).await?;
+    say!("Alice holds {alice_a} A and {alice_b} B");
+    say!("Bob holds {bob_a} A and {bob_b} B");
+
+    assert_eq!(
+        (alice_a, alice_b),
+        (100 - OFFERED, REQUESTED),
+        "Alice should have traded {OFFERED} A for {REQUESTED} B"
+    );
+    assert_eq!(
+        (bob_a, bob_b),
+        (OFFERED, 100 - REQUESTED),
+        "Bob should have traded {REQUESTED} B for {OFFERED} A"
+    );
+    say!("✅ Swap settled: {OFFERED} A went to Bob and {REQUESTED} B to Alice");
+
+    Ok(())
+}
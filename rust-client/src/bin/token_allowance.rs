@@ -0,0 +1,157 @@
+use std::process::ExitCode;
+
+use miden_client::{account::AccountId, keystore::FilesystemKeyStore, Client};
+use rust_client::{
+    accounts::{collect, create_basic_account, create_basic_faucet, get_full_account, top_up},
+    allowance::Allowance,
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    say,
+};
+
+/// Has `spender` try to pull `amount` tokens and expects the vault to refuse.
+async fn expect_refused(
+    client: &mut Client<FilesystemKeyStore>,
+    allowance: &Allowance,
+    spender: AccountId,
+    amount: u64,
+    attempt: &str,
+) {
+    match allowance.spend(client, spender, amount).await {
+        Ok((report, _)) if report.is_committed() => panic!("{attempt} should have been refused"),
+        Ok(_) => say!("✅ {attempt} discarded as expected"),
+        Err(err) => say!("✅ {attempt} refused as expected: {}", err),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create Alice the owner, Bob the spender and Carol
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice the owner, Bob the spender and Carol");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let carol_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Carol's account ID: {:?}",
+        carol_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice deploys the book and the vault, and fills the vault
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Alice deploys the allowance book and vault and deposits 100 tokens");
+
+    let allowance = Allowance::deploy(&mut client, alice_account.id(), faucet.id()).await?;
+    say!(
+        "Book account ID: {:?}",
+        allowance.book_id.to_bech32(network_id())
+    );
+    say!(
+        "Vault account ID: {:?}",
+        allowance.vault_id.to_bech32(network_id())
+    );
+    allowance.deposit(&mut client, 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice approves Bob for 30 tokens
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice approves Bob for 30 tokens");
+
+    allowance
+        .approve(&mut client, alice_account.id(), bob_account.id(), 30)
+        .await?;
+    say!(
+        "✅ Bob's allowance: {}",
+        allowance.allowance(&mut client, bob_account.id()).await?
+    );
+
+    // Only the owner writes to the book
+    match allowance
+        .approve(&mut client, bob_account.id(), bob_account.id(), 1_000)
+        .await
+    {
+        Ok(report) if report.is_committed() => panic!("Bob approved an allowance in Alice's book"),
+        Ok(_) => say!("✅ Bob's own approval discarded as expected"),
+        Err(err) => say!("✅ Bob's own approval refused as expected: {}", err),
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Bob pulls from the vault within the allowance
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Bob pulls 20 tokens from the vault");
+
+    let (report, payout) = allowance.spend(&mut client, bob_account.id(), 20).await?;
+    assert!(report.is_committed(), "the spend was not committed");
+    let bob_balance = collect(&mut client, bob_account.id(), payout, faucet.id()).await?;
+    let remaining = allowance.remaining(&mut client, bob_account.id()).await?;
+    say!("✅ Bob holds {bob_balance} tokens and can still pull {remaining}");
+    assert_eq!((bob_balance, remaining), (20, 10));
+
+    // -------------------------------------------------------------------------
+    // STEP 5: The vault refuses spends over the allowance
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Bob and Carol try to pull more than they were approved for");
+
+    expect_refused(
+        &mut client,
+        &allowance,
+        bob_account.id(),
+        20,
+        "Bob pulling 20 more tokens",
+    )
+    .await;
+    expect_refused(
+        &mut client,
+        &allowance,
+        carol_account.id(),
+        5,
+        "Carol pulling without an allowance",
+    )
+    .await;
+
+    // -------------------------------------------------------------------------
+    // STEP 6: Bob pulls the rest of the allowance
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 6] Bob pulls the last 10 tokens");
+
+    let (report, payout) = allowance.spend(&mut client, bob_account.id(), 10).await?;
+    assert!(report.is_committed(), "the spend was not committed");
+    let bob_balance = collect(&mut client, bob_account.id(), payout, faucet.id()).await?;
+    let spent = allowance.spent(&mut client, bob_account.id()).await?;
+    let vault_balance = get_full_account(&mut client, allowance.vault_id)
+        .await?
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap_or(0);
+    say!("✅ Bob holds {bob_balance} tokens, the vault {vault_balance}");
+    assert_eq!(bob_balance, 30);
+    assert_eq!(spent, 30);
+    assert_eq!(vault_balance, 70);
+    assert_eq!(allowance.remaining(&mut client, bob_account.id()).await?, 0);
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use std::process::ExitCode;
+
+use rust_client::{
+    accounts::{collect, create_basic_account, create_basic_faucet, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    say,
+    vesting::{Vesting, VestingSchedule},
+    wait::{wait_for_block, WaitOptions},
+};
+
+/// Blocks between deploying the grant and the start of its first period.
+const START_DELAY: u32 = 5;
+
+/// Blocks per vesting period.
+const PERIOD: u32 = 10;
+
+/// Periods until the whole grant has vested.
+const PERIODS: u32 = 4;
+
+/// Tokens released per period.
+const PER_PERIOD: u64 = 25;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create the funder, the beneficiary and the faucet
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating Alice the funder, Bob the beneficiary and a faucet");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Alice deploys and funds the grant
+    // -------------------------------------------------------------------------
+    let height = client.sync_state().await?.block_num.as_u32();
+    let schedule = VestingSchedule {
+        start: height + START_DELAY,
+        period: PERIOD,
+        periods: PERIODS,
+        per_period: PER_PERIOD,
+    };
+    say!(
+        "\n[STEP 2] Alice grants Bob {} tokens, {PER_PERIOD} every {PERIOD} blocks from block {}",
+        schedule.total(),
+        schedule.start
+    );
+
+    let grant = Vesting::deploy(
+        &mut client,
+        alice_account.id(),
+        bob_account.id(),
+        faucet.id(),
+        schedule,
+    )
+    .await?;
+    say!(
+        "Vesting contract ID: {:?}",
+        grant.account_id.to_bech32(network_id())
+    );
+    grant.fund(&mut client).await?;
+    say!("✅ {} tokens locked in the grant", schedule.total());
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Bob claims before the first period has passed
+    // -------------------------------------------------------------------------
+    let first_share = schedule
+        .vesting_block(PER_PERIOD)
+        .expect("one share is part of the grant");
+    say!("\n[STEP 3] Bob claims {PER_PERIOD} tokens before block {first_share}");
+
+    // Vesting is checked against the reference block, which is the client's
+    // sync height, so the outcome is known before executing
+    let height = client.sync_state().await?.block_num.as_u32();
+    if height < first_share {
+        match grant.claim(&mut client, PER_PERIOD).await {
+            Ok((report, _)) if report.is_committed() => {
+                panic!("the grant paid out before anything vested")
+            }
+            Ok(_) => say!("Early claim discarded as expected"),
+            Err(err) => say!("Rejected as expected at block {height}: {}", err),
+        }
+    } else {
+        say!("Block {height} has already vested a share, skipping the early claim");
+    }
+
+    // -------------------------------------------------------------------------
+    // STEP 4: Two periods later Bob claims what has vested
+    // -------------------------------------------------------------------------
+    let two_shares = schedule
+        .vesting_block(2 * PER_PERIOD)
+        .expect("two shares are part of the grant");
+    say!("\n[STEP 4] Waiting for block {two_shares}, then claiming the vested tokens");
+
+    wait_for_block(
+        &mut client,
+        two_shares,
+        &WaitOptions::for_blocks(START_DELAY + 2 * PERIOD),
+    )
+    .await?;
+
+    let claimable = grant.claimable(&mut client).await?;
+    say!("Bob can claim {claimable} tokens");
+    let (report, payout) = grant.claim(&mut client, claimable).await?;
+    assert!(report.is_committed(), "the claim was not committed");
+
+    let balance = collect(&mut client, bob_account.id(), payout, faucet.id()).await?;
+    let claimed = grant.claimed(&mut client).await?;
+    say!(
+        "✅ Bob holds {balance} tokens, {} are still locked",
+        schedule.total() - claimed
+    );
+    assert!(
+        balance >= 2 * PER_PERIOD,
+        "Bob should hold two vested shares"
+    );
+    assert_eq!(balance, claimed, "Bob should hold exactly what was claimed");
+
+    Ok(())
+}
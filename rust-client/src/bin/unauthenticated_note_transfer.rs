@@ -1,119 +1,55 @@
-use rand::RngCore;
-use std::sync::Arc;
-use tokio::time::{sleep, Duration, Instant};
+use std::process::ExitCode;
+
+use tokio::time::{Duration, Instant};
 
 use miden_client::{
-    account::{
-        component::{BasicFungibleFaucet, BasicWallet},
-        AccountBuilder, AccountStorageMode, AccountType,
-    },
-    address::NetworkId,
-    asset::{FungibleAsset, TokenSymbol},
-    auth::{AuthFalcon512Rpo, AuthSecretKey},
-    builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
+    asset::FungibleAsset,
     note::{create_p2id_note, Note, NoteAttachment, NoteType},
-    rpc::{Endpoint, GrpcClient},
-    store::{AccountRecordData, TransactionFilter},
-    transaction::{OutputNote, TransactionId, TransactionRequestBuilder, TransactionStatus},
+    transaction::{OutputNote, TransactionRequestBuilder},
     utils::{Deserializable, Serializable},
-    Client, ClientError, Felt,
 };
-use miden_client_sqlite_store::ClientBuilderSqliteExt;
-
-/// Waits for a specific transaction to be committed.
-async fn wait_for_tx(
-    client: &mut Client<FilesystemKeyStore>,
-    tx_id: TransactionId,
-) -> Result<(), ClientError> {
-    loop {
-        client.sync_state().await?;
-
-        // Check transaction status
-        let txs = client
-            .get_transactions(TransactionFilter::Ids(vec![tx_id]))
-            .await?;
-        let tx_committed = if !txs.is_empty() {
-            matches!(txs[0].status, TransactionStatus::Committed { .. })
-        } else {
-            false
-        };
-
-        if tx_committed {
-            println!("✅ transaction {} committed", tx_id.to_hex());
-            break;
-        }
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    middleware::submit_logged,
+    output::{emit, Event},
+    say,
+    wait::{print_explorer_link, submit_and_wait, wait_for_tx},
+};
 
-        println!(
-            "Transaction {} not yet committed. Waiting...",
-            tx_id.to_hex()
-        );
-        sleep(Duration::from_secs(2)).await;
-    }
-    Ok(())
+/// End-to-end measurements for one note chaining strategy.
+struct StrategyReport {
+    name: &'static str,
+    elapsed: Duration,
+    blocks: u32,
 }
 
 #[tokio::main]
-async fn main() -> Result<(), ClientError> {
-    // Initialize client
-    let endpoint = Endpoint::devnet();
-    let timeout_ms = 10_000;
-    let rpc_client = Arc::new(GrpcClient::new(&endpoint, timeout_ms));
-
-    // Initialize keystore
-    let keystore_path = std::path::PathBuf::from("./keystore");
-    let keystore = Arc::new(FilesystemKeyStore::new(keystore_path).unwrap());
-
-    let store_path = std::path::PathBuf::from("./store.sqlite3");
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
 
-    let mut client = ClientBuilder::new()
-        .rpc(rpc_client)
-        .sqlite_store(store_path)
-        .authenticator(keystore.clone())
-        .in_debug_mode(true.into())
-        .build()
-        .await?;
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
 
     let sync_summary = client.sync_state().await.unwrap();
-    println!("Latest block: {}", sync_summary.block_num);
+    say!("Latest block: {}", sync_summary.block_num);
 
     //------------------------------------------------------------
     // STEP 1: Deploy a fungible faucet
     //------------------------------------------------------------
-    println!("\n[STEP 1] Deploying a new fungible faucet.");
-
-    // Faucet seed
-    let mut init_seed = [0_u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
-
-    // Generate key pair
-    let key_pair = AuthSecretKey::new_falcon512_rpo();
-
-    // Faucet parameters
-    let symbol = TokenSymbol::new("MID").unwrap();
-    let decimals = 8;
-    let max_supply = Felt::new(1_000_000);
-
-    // Build the account
-    let faucet_account = AccountBuilder::new(init_seed)
-        .account_type(AccountType::FungibleFaucet)
-        .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
-        .with_component(BasicFungibleFaucet::new(symbol, decimals, max_supply).unwrap())
-        .build()
-        .unwrap();
+    say!("\n[STEP 1] Deploying a new fungible faucet.");
 
-    // Add the faucet to the client
-    client.add_account(&faucet_account, false).await?;
+    let faucet_account = create_basic_faucet(&mut client, &keystore).await?;
 
-    println!(
+    say!(
         "Faucet account ID: {}",
-        faucet_account.id().to_bech32(NetworkId::Testnet)
+        faucet_account.id().to_bech32(network_id())
     );
 
-    // Add the key pair to the keystore
-    keystore.add_key(&key_pair).unwrap();
-
     // Resync to show newly deployed faucet
     tokio::time::sleep(Duration::from_secs(2)).await;
     client.sync_state().await?;
@@ -121,35 +57,19 @@ async fn main() -> Result<(), ClientError> {
     //------------------------------------------------------------
     // STEP 2: Create basic wallet accounts
     //------------------------------------------------------------
-    println!("\n[STEP 2] Creating new accounts");
+    say!("\n[STEP 2] Creating new accounts");
 
     let mut accounts = vec![];
     let number_of_accounts = 5;
 
     for i in 0..number_of_accounts {
-        let mut init_seed = [0_u8; 32];
-        client.rng().fill_bytes(&mut init_seed);
-
-        let key_pair = AuthSecretKey::new_falcon512_rpo();
-
-        let account = AccountBuilder::new(init_seed)
-            .account_type(AccountType::RegularAccountUpdatableCode)
-            .storage_mode(AccountStorageMode::Public)
-            .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
-            .with_component(BasicWallet)
-            .build()
-            .unwrap();
-
+        let account = create_basic_account(&mut client, &keystore).await?;
         accounts.push(account.clone());
-        println!(
+        say!(
             "account id {:?}: {}",
             i,
-            account.id().to_bech32(NetworkId::Testnet)
+            account.id().to_bech32(network_id())
         );
-        client.add_account(&account, true).await?;
-
-        // Add the key pair to the keystore
-        keystore.add_key(&key_pair).unwrap();
     }
 
     // For demo purposes, Alice is the first account.
@@ -158,8 +78,8 @@ async fn main() -> Result<(), ClientError> {
     //------------------------------------------------------------
     // STEP 3: Mint and consume tokens for Alice
     //------------------------------------------------------------
-    println!("\n[STEP 3] Mint tokens");
-    println!("Minting tokens for Alice...");
+    say!("\n[STEP 3] Mint tokens");
+    say!("Minting tokens for Alice...");
     let amount: u64 = 100;
     let fungible_asset_mint_amount = FungibleAsset::new(faucet_account.id(), amount).unwrap();
     let transaction_request = TransactionRequestBuilder::new()
@@ -171,13 +91,8 @@ async fn main() -> Result<(), ClientError> {
         )
         .unwrap();
 
-    let tx_id = client
-        .submit_new_transaction(faucet_account.id(), transaction_request)
-        .await?;
-    println!("Minted tokens. TX: {:?}", tx_id);
-
     // Wait for mint transaction to be committed
-    wait_for_tx(&mut client, tx_id).await?;
+    submit_and_wait(&mut client, faucet_account.id(), transaction_request).await?;
 
     // Get the minted note and consume it
     let consumable_notes = client.get_consumable_notes(Some(alice.id())).await?;
@@ -187,29 +102,23 @@ async fn main() -> Result<(), ClientError> {
         let transaction_request =
             TransactionRequestBuilder::new().build_consume_notes(vec![note])?;
 
-        let consume_tx_id = client
-            .submit_new_transaction(alice.id(), transaction_request)
-            .await?;
-        println!("Consumed minted note. TX: {:?}", consume_tx_id);
-
         // Wait for consumption to complete
-        wait_for_tx(&mut client, consume_tx_id).await?;
+        submit_and_wait(&mut client, alice.id(), transaction_request).await?;
     }
 
     //------------------------------------------------------------
     // STEP 4: Create unauthenticated note tx chain
     //------------------------------------------------------------
-    println!("\n[STEP 4] Create unauthenticated note tx chain");
+    say!("\n[STEP 4] Create unauthenticated note tx chain");
+    let unauthenticated_start_block = client.get_sync_height().await?;
     let start = Instant::now();
+    let mut last_tx_id = None;
 
     for i in 0..number_of_accounts - 1 {
         let loop_start = Instant::now();
-        println!("\nunauthenticated tx {:?}", i + 1);
-        println!("sender: {}", accounts[i].id().to_bech32(NetworkId::Testnet));
-        println!(
-            "target: {}",
-            accounts[i + 1].id().to_bech32(NetworkId::Testnet)
-        );
+        say!("\nunauthenticated tx {:?}", i + 1);
+        say!("sender: {}", accounts[i].id().to_bech32(network_id()));
+        say!("target: {}", accounts[i + 1].id().to_bech32(network_id()));
 
         // Time the creation of the p2id note
         let send_amount = 20;
@@ -241,10 +150,8 @@ async fn main() -> Result<(), ClientError> {
             .build()
             .unwrap();
 
-        let tx_id = client
-            .submit_new_transaction(accounts[i].id(), transaction_request)
-            .await?;
-        println!("Created note. TX: {:?}", tx_id);
+        let tx_id = submit_logged(&mut client, accounts[i].id(), transaction_request).await?;
+        say!("Created note. TX: {:?}", tx_id);
 
         // Note serialization/deserialization
         // This demonstrates how you could send the serialized note to another client instance
@@ -257,46 +164,118 @@ async fn main() -> Result<(), ClientError> {
             .build()
             .unwrap();
 
-        let tx_id = client
-            .submit_new_transaction(accounts[i + 1].id(), consume_note_request)
-            .await?;
-
-        println!(
-            "Consumed Note Tx on MidenScan: https://testnet.midenscan.com/tx/{:?}",
-            tx_id
-        );
-        println!(
+        // No waiting here: the next hop builds on top of the pending state
+        let tx_id = submit_logged(&mut client, accounts[i + 1].id(), consume_note_request).await?;
+        print_explorer_link("Consumed Note Tx", tx_id);
+        say!(
             "Total time for loop iteration {}: {:?}",
             i,
             loop_start.elapsed()
         );
+        last_tx_id = Some(tx_id);
     }
 
-    println!(
+    say!(
         "\nTotal execution time for unauthenticated note txs: {:?}",
         start.elapsed()
     );
 
+    // The chain is only done once its last transaction lands in a block
+    if let Some(tx_id) = last_tx_id {
+        wait_for_tx(&mut client, tx_id).await?;
+    }
+    let unauthenticated_report = StrategyReport {
+        name: "unauthenticated",
+        elapsed: start.elapsed(),
+        blocks: client.get_sync_height().await?.as_u32() - unauthenticated_start_block.as_u32(),
+    };
+
+    //------------------------------------------------------------
+    // STEP 5: Create authenticated note tx chain
+    //------------------------------------------------------------
+    say!("\n[STEP 5] Create authenticated note tx chain");
+    let authenticated_start_block = client.get_sync_height().await?;
+    let start = Instant::now();
+
+    for i in 0..number_of_accounts - 1 {
+        let loop_start = Instant::now();
+        say!("\nauthenticated tx {:?}", i + 1);
+        say!("sender: {}", accounts[i].id().to_bech32(network_id()));
+        say!("target: {}", accounts[i + 1].id().to_bech32(network_id()));
+
+        let send_amount = 20;
+        let fungible_asset_send_amount =
+            FungibleAsset::new(faucet_account.id(), send_amount).unwrap();
+
+        // Public notes are used so the receiver can find them in the note tree
+        let p2id_note = create_p2id_note(
+            accounts[i].id(),
+            accounts[i + 1].id(),
+            vec![fungible_asset_send_amount.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )
+        .unwrap();
+
+        let transaction_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(p2id_note.clone())])
+            .build()
+            .unwrap();
+
+        // Authenticated consumption requires the note to be committed first
+        submit_and_wait(&mut client, accounts[i].id(), transaction_request).await?;
+
+        let consume_note_request =
+            TransactionRequestBuilder::new().build_consume_notes(vec![p2id_note])?;
+
+        // The next hop spends the received funds, so they must be committed
+        submit_and_wait(&mut client, accounts[i + 1].id(), consume_note_request).await?;
+        say!(
+            "Total time for loop iteration {}: {:?}",
+            i,
+            loop_start.elapsed()
+        );
+    }
+
+    let authenticated_report = StrategyReport {
+        name: "authenticated",
+        elapsed: start.elapsed(),
+        blocks: client.get_sync_height().await?.as_u32() - authenticated_start_block.as_u32(),
+    };
+
+    //------------------------------------------------------------
+    // STEP 6: Compare both strategies
+    //------------------------------------------------------------
+    say!(
+        "\n[STEP 6] Strategy comparison ({} hops each)",
+        number_of_accounts - 1
+    );
+    say!("{:<16} {:>14} {:>8}", "strategy", "end-to-end", "blocks");
+    for report in [&unauthenticated_report, &authenticated_report] {
+        say!(
+            "{:<16} {:>14} {:>8}",
+            report.name,
+            format!("{:.2?}", report.elapsed),
+            report.blocks
+        );
+    }
+
     // Final resync and display account balances
     tokio::time::sleep(Duration::from_secs(3)).await;
     client.sync_state().await?;
     for account in accounts.clone() {
-        let new_account_record = client.get_account(account.id()).await.unwrap().unwrap();
-        let new_account = match new_account_record.account_data() {
-            AccountRecordData::Full(account) => account,
-            AccountRecordData::Partial(_) => {
-                panic!("account is missing full account data")
-            }
-        };
+        let new_account = get_full_account(&mut client, account.id()).await?;
         let balance = new_account
             .vault()
             .get_balance(faucet_account.id())
             .unwrap();
-        println!(
+        say!(
             "Account: {} balance: {}",
-            account.id().to_bech32(NetworkId::Testnet),
+            account.id().to_bech32(network_id()),
             balance
         );
+        emit(Event::balance(account.id(), faucet_account.id(), balance));
     }
 
     Ok(())
@@ -0,0 +1,145 @@
+use std::process::ExitCode;
+
+use miden_client::{
+    account::AccountId,
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteType},
+    transaction::{PaymentNoteDescription, TransactionRequestBuilder},
+    Client,
+};
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    client::{init_client_from_config, network_id},
+    error::{report, ExitGuard},
+    say,
+    wait::submit_and_wait,
+    wrapped_token::WrappedToken,
+};
+
+/// Has `account_id` consume `note`.
+async fn consume(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    note: Note,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let consume_request = TransactionRequestBuilder::new()
+        .input_notes([(note, None)])
+        .build()?;
+    submit_and_wait(client, account_id, consume_request).await?;
+    Ok(())
+}
+
+/// Returns the balances of `account_id` in the underlying and the wrapped token.
+async fn balances(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    wrapper: &WrappedToken,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let vault = get_full_account(client, account_id).await?.vault().clone();
+    Ok((
+        vault.get_balance(wrapper.underlying).unwrap_or(0),
+        vault.get_balance(wrapper.account_id).unwrap_or(0),
+    ))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _guard = ExitGuard::new();
+    report(run().await)
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize client
+    let (mut client, keystore) = init_client_from_config().await?;
+
+    let sync_summary = client.sync_state().await?;
+    say!("Latest block: {}", sync_summary.block_num);
+
+    // -------------------------------------------------------------------------
+    // STEP 1: Create accounts and fund Alice with the underlying token
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 1] Creating accounts and funding Alice");
+
+    let alice_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Alice's account ID: {:?}",
+        alice_account.id().to_bech32(network_id())
+    );
+    let bob_account = create_basic_account(&mut client, &keystore).await?;
+    say!(
+        "Bob's account ID: {:?}",
+        bob_account.id().to_bech32(network_id())
+    );
+    let faucet = create_basic_faucet(&mut client, &keystore).await?;
+    top_up(&mut client, faucet.id(), alice_account.id(), 100).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 2: Deploy the wrapper, a wallet and a faucet in one account
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 2] Deploying the wrapper with the BasicWallet and wrapped-token components");
+
+    let wrapper = WrappedToken::deploy(&mut client, &keystore, faucet.id()).await?;
+    say!(
+        "Wrapper account ID: {:?}",
+        wrapper.account_id.to_bech32(network_id())
+    );
+
+    // -------------------------------------------------------------------------
+    // STEP 3: Alice wraps 60 tokens
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 3] Alice wraps 60 tokens");
+
+    let (report, payout) = wrapper.wrap(&mut client, alice_account.id(), 60).await?;
+    assert!(report.is_committed(), "the wrap was not committed");
+    consume(&mut client, alice_account.id(), payout).await?;
+
+    let (underlying, wrapped) = balances(&mut client, alice_account.id(), &wrapper).await?;
+    let reserves = wrapper.reserves(&mut client).await?;
+    say!("✅ Alice holds {underlying} underlying and {wrapped} wrapped tokens, the wrapper holds {reserves}");
+    assert_eq!((underlying, wrapped), (40, 60));
+    assert_eq!(reserves, 60);
+
+    // -------------------------------------------------------------------------
+    // STEP 4: The wrapped token moves like any other token
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 4] Alice pays Bob 20 wrapped tokens");
+
+    let payment = PaymentNoteDescription::new(
+        vec![wrapper.wrapped_asset(20)?.into()],
+        alice_account.id(),
+        bob_account.id(),
+    );
+    let request = TransactionRequestBuilder::new().build_pay_to_id(
+        payment,
+        NoteType::Public,
+        client.rng(),
+    )?;
+    let note = request
+        .expected_output_own_notes()
+        .pop()
+        .expect("a payment request creates one note");
+    submit_and_wait(&mut client, alice_account.id(), request).await?;
+    consume(&mut client, bob_account.id(), note).await?;
+
+    // -------------------------------------------------------------------------
+    // STEP 5: Bob unwraps them for the underlying token
+    // -------------------------------------------------------------------------
+    say!("\n[STEP 5] Bob unwraps the 20 wrapped tokens");
+
+    let (report, payout) = wrapper.unwrap(&mut client, bob_account.id(), 20).await?;
+    assert!(report.is_committed(), "the unwrap was not committed");
+    consume(&mut client, bob_account.id(), payout).await?;
+
+    let (alice_underlying, alice_wrapped) =
+        balances(&mut client, alice_account.id(), &wrapper).await?;
+    let (bob_underlying, bob_wrapped) = balances(&mut client, bob_account.id(), &wrapper).await?;
+    let reserves = wrapper.reserves(&mut client).await?;
+    say!("Alice holds {alice_underlying} underlying and {alice_wrapped} wrapped tokens");
+    say!("Bob holds {bob_underlying} underlying and {bob_wrapped} wrapped tokens");
+    assert_eq!((bob_underlying, bob_wrapped), (20, 0));
+    // Alice holds the only wrapped tokens left, and the wrapper exactly their backing
+    assert_eq!(reserves, alice_wrapped);
+    say!("✅ The wrapper holds {reserves} underlying tokens for {alice_wrapped} wrapped ones");
+
+    Ok(())
+}
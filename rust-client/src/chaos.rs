@@ -0,0 +1,176 @@
+//! Fault injection for resilience testing, enabled with the `chaos` feature.
+
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use miden_client::{
+    block::BlockNumber,
+    rpc::{NodeRpcClient, RpcError},
+    ClientError,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::time::{sleep, Duration};
+use tracing::warn;
+
+use crate::middleware::impl_node_rpc_client;
+
+/// Environment variable selecting a [`ChaosConfig`] preset.
+pub const CHAOS_ENV: &str = "MIDEN_TUTORIAL_CHAOS";
+
+/// How often and how badly [`ChaosRpcClient`] misbehaves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    /// Upper bound of the random delay added before every call.
+    pub max_latency: Duration,
+    /// Probability that a call hangs for `timeout` and then fails.
+    pub timeout_rate: f64,
+    /// Probability that a call fails immediately with a transient error.
+    pub error_rate: f64,
+    pub timeout: Duration,
+    /// Seeds the fault schedule so that a run can be replayed; random if unset.
+    pub seed: Option<u64>,
+}
+
+impl ChaosConfig {
+    /// 6% of calls fail.
+    pub fn light() -> Self {
+        Self {
+            max_latency: Duration::from_millis(200),
+            timeout_rate: 0.01,
+            error_rate: 0.05,
+            timeout: Duration::from_secs(2),
+            seed: None,
+        }
+    }
+
+    /// 10% of calls fail, the rate the tutorials are tested to survive.
+    pub fn moderate() -> Self {
+        Self {
+            max_latency: Duration::from_millis(500),
+            timeout_rate: 0.02,
+            error_rate: 0.08,
+            timeout: Duration::from_secs(3),
+            seed: None,
+        }
+    }
+
+    /// 35% of calls fail; expect some runs to give up.
+    pub fn heavy() -> Self {
+        Self {
+            max_latency: Duration::from_secs(1),
+            timeout_rate: 0.10,
+            error_rate: 0.25,
+            timeout: Duration::from_secs(5),
+            seed: None,
+        }
+    }
+
+    /// Probability that a call fails, by timeout or error.
+    pub fn fault_rate(&self) -> f64 {
+        self.timeout_rate + self.error_rate
+    }
+
+    /// Reads a preset name (`light`, `moderate`, `heavy`) from [`CHAOS_ENV`].
+    ///
+    /// An empty or unset variable disables fault injection, while an unknown
+    /// name fails rather than silently running without faults.
+    pub fn from_env() -> Result<Option<Self>, ClientError> {
+        let name = std::env::var(CHAOS_ENV).unwrap_or_default();
+        match name.as_str() {
+            "" => Ok(None),
+            "light" => Ok(Some(Self::light())),
+            "moderate" => Ok(Some(Self::moderate())),
+            "heavy" => Ok(Some(Self::heavy())),
+            _ => Err(ClientError::ClientInitializationError(format!(
+                "unknown {CHAOS_ENV} preset '{name}', expected light, moderate or heavy"
+            ))),
+        }
+    }
+}
+
+enum Fault {
+    Timeout,
+    Error,
+}
+
+/// RPC client wrapper that randomly delays calls and injects transient failures.
+pub struct ChaosRpcClient {
+    inner: Arc<dyn NodeRpcClient>,
+    config: ChaosConfig,
+    rng: Mutex<StdRng>,
+}
+
+impl ChaosRpcClient {
+    pub fn new(inner: Arc<dyn NodeRpcClient>, config: ChaosConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        Self {
+            inner,
+            config,
+            rng: Mutex::new(rng),
+        }
+    }
+
+    async fn around<T, F, Fut>(&self, method: &'static str, call: F) -> Result<T, RpcError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        // The lock must not be held across an await point
+        let (latency, fault) = {
+            let mut rng = self.rng.lock().unwrap_or_else(|err| err.into_inner());
+            let latency = self.config.max_latency.mul_f64(rng.random::<f64>());
+            let roll = rng.random::<f64>();
+            let fault = if roll < self.config.timeout_rate {
+                Some(Fault::Timeout)
+            } else if roll < self.config.fault_rate() {
+                Some(Fault::Error)
+            } else {
+                None
+            };
+            (latency, fault)
+        };
+
+        sleep(latency).await;
+
+        match fault {
+            Some(Fault::Timeout) => {
+                sleep(self.config.timeout).await;
+                warn!(method, "chaos: injected timeout");
+                Err(injected_error(
+                    std::io::ErrorKind::TimedOut,
+                    "injected timeout",
+                ))
+            }
+            Some(Fault::Error) => {
+                warn!(method, "chaos: injected transient error");
+                Err(injected_error(
+                    std::io::ErrorKind::ConnectionReset,
+                    "injected transient error",
+                ))
+            }
+            None => call().await,
+        }
+    }
+
+    async fn around_submit<F, Fut>(&self, call: F) -> Result<BlockNumber, RpcError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<BlockNumber, RpcError>>,
+    {
+        self.around("submit_proven_transaction", call).await
+    }
+}
+
+fn injected_error(kind: std::io::ErrorKind, message: &str) -> RpcError {
+    RpcError::ConnectionError(Box::new(std::io::Error::new(
+        kind,
+        format!("chaos: {message}"),
+    )))
+}
+
+impl_node_rpc_client!(ChaosRpcClient);
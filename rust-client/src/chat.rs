@@ -0,0 +1,186 @@
+//! Short text messages carried by zero-asset notes.
+//!
+//! A note does not have to move funds: a public note without assets is a
+//! message anyone watching its tag can read. Each chat message is one such note,
+//! tagged for the recipient, with the text packed into its inputs:
+//!
+//! ```text
+//! [to_prefix, to_suffix, seq, len, text...]
+//! ```
+//!
+//! The text is UTF-8, seven bytes per felt so that every chunk stays below the
+//! field modulus. `seq` numbers the messages of a conversation, so both sides
+//! can print it in order whatever order the notes arrive in.
+
+use std::{collections::BTreeMap, fmt};
+
+use miden_client::{account::AccountId, note::Note, Felt};
+
+use crate::say;
+
+/// Longest message in bytes, which keeps a message well below the note input limit.
+pub const MAX_MESSAGE_LEN: usize = 280;
+
+/// Inputs in front of the text: recipient prefix and suffix, `seq` and `len`.
+const HEADER_LEN: usize = 4;
+const BYTES_PER_FELT: usize = 7;
+
+/// Why a message could not be encoded or read back from a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatError {
+    /// The text is longer than [`MAX_MESSAGE_LEN`] bytes.
+    TooLong(usize),
+    /// The note inputs do not hold a chat message.
+    Malformed(String),
+}
+
+impl fmt::Display for ChatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatError::TooLong(len) => {
+                write!(f, "message is {len} bytes, the limit is {MAX_MESSAGE_LEN}")
+            }
+            ChatError::Malformed(reason) => write!(f, "not a chat message: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ChatError {}
+
+/// Packs `text` into felts of [`BYTES_PER_FELT`] bytes each, little-endian.
+pub fn encode_text(text: &str) -> Vec<Felt> {
+    text.as_bytes()
+        .chunks(BYTES_PER_FELT)
+        .map(|chunk| {
+            let mut bytes = [0_u8; 8];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            Felt::new(u64::from_le_bytes(bytes))
+        })
+        .collect()
+}
+
+/// Unpacks `len` bytes of text from the output of [`encode_text`].
+pub fn decode_text(felts: &[Felt], len: usize) -> Result<String, ChatError> {
+    if felts.len() != len.div_ceil(BYTES_PER_FELT) {
+        return Err(ChatError::Malformed(format!(
+            "{} text inputs cannot hold {len} bytes",
+            felts.len()
+        )));
+    }
+    let mut bytes = Vec::with_capacity(felts.len() * BYTES_PER_FELT);
+    for felt in felts {
+        let chunk = felt.as_int().to_le_bytes();
+        if chunk[BYTES_PER_FELT] != 0 {
+            return Err(ChatError::Malformed(format!(
+                "input {} holds more than {BYTES_PER_FELT} bytes",
+                felt.as_int()
+            )));
+        }
+        bytes.extend_from_slice(&chunk[..BYTES_PER_FELT]);
+    }
+    bytes.truncate(len);
+    String::from_utf8(bytes).map_err(|err| ChatError::Malformed(err.to_string()))
+}
+
+/// One message of a conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub from: AccountId,
+    pub to: AccountId,
+    /// Position in the conversation, starting at 1.
+    pub seq: u64,
+    pub text: String,
+}
+
+impl ChatMessage {
+    /// The note inputs carrying this message.
+    pub fn to_inputs(&self) -> Result<Vec<Felt>, ChatError> {
+        if self.text.len() > MAX_MESSAGE_LEN {
+            return Err(ChatError::TooLong(self.text.len()));
+        }
+        let mut inputs = vec![
+            self.to.prefix().as_felt(),
+            self.to.suffix(),
+            Felt::new(self.seq),
+            Felt::new(self.text.len() as u64),
+        ];
+        inputs.extend(encode_text(&self.text));
+        Ok(inputs)
+    }
+
+    /// Reads a message sent by `from` back from its note inputs.
+    pub fn from_inputs(from: AccountId, inputs: &[Felt]) -> Result<Self, ChatError> {
+        if inputs.len() < HEADER_LEN {
+            return Err(ChatError::Malformed(format!(
+                "{} inputs, expected at least {HEADER_LEN}",
+                inputs.len()
+            )));
+        }
+        let to = AccountId::try_from([inputs[0], inputs[1]])
+            .map_err(|err| ChatError::Malformed(err.to_string()))?;
+        let len = inputs[3].as_int() as usize;
+        if len > MAX_MESSAGE_LEN {
+            return Err(ChatError::TooLong(len));
+        }
+        Ok(Self {
+            from,
+            to,
+            seq: inputs[2].as_int(),
+            text: decode_text(&inputs[HEADER_LEN..], len)?,
+        })
+    }
+
+    /// Reads the message carried by `note`, whose sender wrote it.
+    pub fn from_note(note: &Note) -> Result<Self, ChatError> {
+        Self::from_inputs(note.metadata().sender(), note.recipient().inputs().values())
+    }
+}
+
+/// The messages one side of a chat has sent and received, ordered by `seq`.
+#[derive(Debug, Clone)]
+pub struct Conversation {
+    owner: AccountId,
+    messages: BTreeMap<u64, ChatMessage>,
+}
+
+impl Conversation {
+    pub fn new(owner: AccountId) -> Self {
+        Self {
+            owner,
+            messages: BTreeMap::new(),
+        }
+    }
+
+    /// Adds `message`, returning `false` if its `seq` was already seen.
+    pub fn insert(&mut self, message: ChatMessage) -> bool {
+        if self.messages.contains_key(&message.seq) {
+            return false;
+        }
+        self.messages.insert(message.seq, message);
+        true
+    }
+
+    /// The messages in `seq` order.
+    pub fn messages(&self) -> impl Iterator<Item = &ChatMessage> {
+        self.messages.values()
+    }
+
+    /// The `seq` of the next message, one past the latest.
+    pub fn next_seq(&self) -> u64 {
+        self.messages.keys().next_back().map_or(1, |seq| seq + 1)
+    }
+
+    /// Prints the conversation as its owner sees it, naming the other side `peer`.
+    pub fn print(&self, owner_name: &str, peer: &str) {
+        say!("┌─ {owner_name}'s chat with {peer}");
+        for message in self.messages.values() {
+            let who = if message.from == self.owner {
+                "you"
+            } else {
+                peer
+            };
+            say!("│ #{:<2} {:>5}: {}", message.seq, who, message.text);
+        }
+        say!("└─");
+    }
+}
@@ -0,0 +1,446 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use clap::Parser;
+use miden_client::{
+    account::AccountId,
+    address::NetworkId,
+    builder::ClientBuilder,
+    keystore::FilesystemKeyStore,
+    rpc::{Endpoint, GrpcClient, NodeRpcClient},
+    Client, ClientError,
+};
+use miden_client_sqlite_store::ClientBuilderSqliteExt;
+use tracing_subscriber::EnvFilter;
+
+#[cfg(feature = "chaos")]
+use crate::chaos::{ChaosConfig, ChaosRpcClient};
+use crate::{
+    address_book,
+    config::{TutorialConfig, CONFIG_FILE},
+    data_dir,
+    endpoints::ENDPOINT_ENV,
+    error, memory_store,
+    middleware::{LoggingRpcClient, RetryRpcClient},
+    network::Network,
+    output::{Reporter, Sink},
+    run_mode::RunMode,
+    session,
+    store_encryption::{self, EncryptedStore, StorePassphrase, PASSPHRASE_ENV},
+};
+
+/// Environment variable that turns on RPC logging without code changes.
+pub const LOG_RPC_ENV: &str = "MIDEN_TUTORIAL_LOG_RPC";
+
+/// Command-line flags shared by every tutorial binary.
+///
+/// Binaries with arguments of their own flatten this into their parser and pass
+/// it to [`ClientSetup::from_args`]; the others call [`init_client_from_config`].
+/// Flags that are not given fall back to the config file, see [`crate::config`].
+#[derive(Debug, Clone, clap::Args)]
+pub struct ClientArgs {
+    /// Config file with defaults for the other flags
+    #[arg(long, default_value = CONFIG_FILE)]
+    pub config: PathBuf,
+    /// Network preset picking the endpoint, address prefix and explorer [default: devnet]
+    #[arg(long, value_enum)]
+    pub network: Option<Network>,
+    /// RPC endpoint URL [default: `MIDEN_TUTORIAL_ENDPOINT`, the config file or the network's]
+    #[arg(long, value_parser = parse_endpoint)]
+    pub endpoint: Option<Endpoint>,
+    /// SQLite store of the client [default: ./store.sqlite3]
+    #[arg(long)]
+    pub store_path: Option<PathBuf>,
+    /// Directory holding the signing keys [default: ./keystore]
+    #[arg(long)]
+    pub keystore_path: Option<PathBuf>,
+    /// Faucet used when a binary is not given one
+    #[arg(long, value_parser = parse_account_id)]
+    pub faucet_id: Option<AccountId>,
+    /// RPC timeout in milliseconds
+    #[arg(long, default_value_t = 10_000)]
+    pub timeout_ms: u64,
+    /// Answer yes to every prompt, for scripts and CI
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+    /// Build accounts and requests locally and stop before the first submission
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Print results as JSON lines on stdout and the narration on stderr
+    #[arg(long)]
+    pub json: bool,
+    /// Mirror events to a file or webhook URL, or to `stdout`; repeat for several sinks
+    #[arg(long = "report", value_name = "SINK")]
+    pub report: Vec<Sink>,
+    /// Keep the store encrypted on disk, with the passphrase in `MIDEN_TUTORIAL_STORE_PASSPHRASE`
+    #[arg(long)]
+    pub encrypt_store: bool,
+    /// Keep the store and keys in a temp directory removed on exit, for throwaway runs
+    #[arg(long, conflicts_with_all = ["store_path", "keystore_path", "encrypt_store"])]
+    pub memory_store: bool,
+    /// Keep the store and keys of this binary apart, under `data/<binary>/` next to the store
+    #[arg(long, conflicts_with_all = ["keystore_path", "memory_store"])]
+    pub isolate: bool,
+    /// Wipe this binary's data directory first; implies `--isolate`
+    #[arg(long, conflicts_with_all = ["keystore_path", "memory_store"])]
+    pub fresh: bool,
+}
+
+#[derive(Parser)]
+struct ClientCli {
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+fn parse_endpoint(url: &str) -> Result<Endpoint, String> {
+    Endpoint::try_from(url).map_err(|err| err.to_string())
+}
+
+/// Parses a bech32 account ID given on the command line.
+pub fn parse_account_id(id: &str) -> Result<AccountId, String> {
+    AccountId::from_bech32(id)
+        .map(|(_, account_id)| account_id)
+        .map_err(|err| err.to_string())
+}
+
+static NETWORK: OnceLock<Network> = OnceLock::new();
+static DEFAULT_FAUCET_ID: OnceLock<Option<AccountId>> = OnceLock::new();
+static REGISTRY_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Network picked with `--network` or in the config file.
+pub fn network() -> Network {
+    NETWORK.get().copied().unwrap_or_default()
+}
+
+/// Network used to print bech32 addresses, derived from [`network`].
+pub fn network_id() -> NetworkId {
+    network().network_id()
+}
+
+/// Faucet picked with `--faucet-id` or `default_faucet_id` in the config file.
+pub fn default_faucet_id() -> Option<AccountId> {
+    DEFAULT_FAUCET_ID.get().copied().flatten()
+}
+
+/// Directory of the configured store, where the tutorials keep the registries
+/// they share between runs, such as the address book.
+///
+/// It stays the same under `--isolate` and `--memory-store`, which only move
+/// the store and keystore.
+pub fn registry_dir() -> PathBuf {
+    REGISTRY_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Reports an invalid value in the config file as a client initialization error.
+fn config_error(key: &str, err: impl std::fmt::Display) -> ClientError {
+    ClientError::ClientInitializationError(format!("invalid `{key}` in config file: {err}"))
+}
+
+/// Settings used to build the client shared by the tutorials.
+#[derive(Debug, Clone)]
+pub struct ClientSetup {
+    pub network: Network,
+    /// Defaults to the endpoint of `network`.
+    pub endpoint: Endpoint,
+    pub timeout_ms: u64,
+    pub keystore_path: PathBuf,
+    pub store_path: PathBuf,
+    /// Keeps the store encrypted on disk, see [`crate::store_encryption`].
+    pub store_passphrase: Option<StorePassphrase>,
+    pub log_rpc: bool,
+    /// How often a transient RPC failure is retried before giving up.
+    pub rpc_retries: u32,
+    /// Faults injected into every RPC call, see [`crate::chaos`].
+    #[cfg(feature = "chaos")]
+    pub chaos: Option<ChaosConfig>,
+}
+
+impl Default for ClientSetup {
+    fn default() -> Self {
+        let network = Network::default();
+        Self {
+            network,
+            // An invalid pinned endpoint is reported by `from_args`
+            endpoint: endpoint_from_env()
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| network.endpoint()),
+            timeout_ms: 10_000,
+            keystore_path: PathBuf::from("./keystore"),
+            store_path: PathBuf::from("./store.sqlite3"),
+            store_passphrase: None,
+            log_rpc: std::env::var(LOG_RPC_ENV).is_ok_and(|value| value != "0"),
+            rpc_retries: 3,
+            // An unknown chaos preset is reported by `from_args`
+            #[cfg(feature = "chaos")]
+            chaos: ChaosConfig::from_env().ok().flatten(),
+        }
+    }
+}
+
+impl ClientSetup {
+    /// Parses the shared [`ClientArgs`] from the command line, exiting with usage on error.
+    pub fn from_cli() -> Result<Self, ClientError> {
+        Self::from_args(ClientCli::parse().client)
+    }
+
+    /// Applies the config file and then the command-line flags on top of the defaults.
+    pub fn from_args(args: ClientArgs) -> Result<Self, ClientError> {
+        let config = TutorialConfig::load(&args.config).map_err(|err| {
+            ClientError::ClientInitializationError(format!(
+                "failed to load {}: {err}",
+                args.config.display()
+            ))
+        })?;
+
+        let network = match (args.network, config.network) {
+            (Some(network), _) => network,
+            (None, Some(name)) => {
+                Network::from_name(&name).map_err(|err| config_error("network", err))?
+            }
+            (None, None) => Network::default(),
+        };
+        let faucet_id = match (args.faucet_id, config.default_faucet_id) {
+            (Some(faucet_id), _) => Some(faucet_id),
+            (None, Some(id)) => {
+                Some(parse_account_id(&id).map_err(|err| config_error("default_faucet_id", err))?)
+            }
+            (None, None) => None,
+        };
+        session::start();
+        // The first parse wins, later calls keep printing with the same network
+        let _ = NETWORK.set(network);
+        let _ = DEFAULT_FAUCET_ID.set(faucet_id);
+        RunMode {
+            assume_yes: args.yes,
+            dry_run: args.dry_run,
+            json: args.json,
+        }
+        .install();
+
+        // Sinks on the command line replace those in the file rather than adding to them
+        let sinks = if args.report.is_empty() {
+            config
+                .report
+                .unwrap_or_default()
+                .iter()
+                .map(|sink| sink.parse::<Sink>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| config_error("report", err))?
+        } else {
+            args.report
+        };
+        if !sinks.is_empty() {
+            Reporter::open(sinks)
+                .map_err(|err| {
+                    ClientError::ClientInitializationError(format!(
+                        "cannot open report sink: {err}"
+                    ))
+                })?
+                .install();
+        }
+
+        let encrypt_store = args.encrypt_store || config.encrypt_store.unwrap_or(false);
+        let store_passphrase = if encrypt_store && !args.memory_store {
+            Some(StorePassphrase::from_env().ok_or_else(|| {
+                ClientError::ClientInitializationError(format!(
+                    "an encrypted store needs its passphrase in {PASSPHRASE_ENV}"
+                ))
+            })?)
+        } else {
+            None
+        };
+
+        let defaults = Self::default();
+        let endpoint = match args.endpoint {
+            Some(endpoint) => endpoint,
+            // The environment pins an endpoint for the session, so it wins over the file
+            None => match (endpoint_from_env()?, config.endpoint) {
+                (Some(endpoint), _) => endpoint,
+                (None, Some(url)) => {
+                    parse_endpoint(&url).map_err(|err| config_error("endpoint", err))?
+                }
+                (None, None) => network.endpoint(),
+            },
+        };
+
+        let setup = Self {
+            network,
+            endpoint,
+            timeout_ms: args.timeout_ms,
+            keystore_path: args
+                .keystore_path
+                .or(config.keystore_path)
+                .unwrap_or(defaults.keystore_path),
+            store_path: args
+                .store_path
+                .or(config.store_path)
+                .unwrap_or(defaults.store_path),
+            store_passphrase,
+            #[cfg(feature = "chaos")]
+            chaos: ChaosConfig::from_env()?,
+            ..defaults
+        };
+        let _ = REGISTRY_DIR.set(
+            setup
+                .store_path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .map_or_else(|| PathBuf::from("."), Path::to_path_buf),
+        );
+        if args.memory_store {
+            return setup.with_memory_store();
+        }
+        if args.fresh || args.isolate || config.isolate.unwrap_or(false) {
+            return setup.with_data_dir(&data_dir::binary_name(), args.fresh);
+        }
+        Ok(setup)
+    }
+
+    /// Logs every RPC call and submitted transaction at DEBUG level.
+    pub fn with_rpc_logging(mut self, enabled: bool) -> Self {
+        self.log_rpc = enabled;
+        self
+    }
+
+    /// Moves the store and keystore to a scratch directory that
+    /// [`crate::error::report`] or the [`crate::error::ExitGuard`] deletes on exit,
+    /// see [`crate::memory_store`].
+    ///
+    /// Nothing the run creates survives it, including the keys of its accounts.
+    pub fn with_memory_store(mut self) -> Result<Self, ClientError> {
+        let dir = memory_store::create_scratch_dir().map_err(|err| {
+            ClientError::ClientInitializationError(format!(
+                "cannot create a scratch store directory: {err}"
+            ))
+        })?;
+        self.store_path = dir.join("store.sqlite3");
+        self.keystore_path = dir.join("keystore");
+        // The config file may ask for encryption, which a throwaway store does not need
+        self.store_passphrase = None;
+        Ok(self)
+    }
+
+    /// Moves the store and keystore to the data directory of binary `name`,
+    /// see [`crate::data_dir`], wiping it first when `fresh` is set.
+    pub fn with_data_dir(mut self, name: &str, fresh: bool) -> Result<Self, ClientError> {
+        let dir = data_dir::binary_data_dir(&self.store_path, name);
+        let prepared = if fresh {
+            data_dir::wipe(&dir)
+        } else {
+            std::fs::create_dir_all(&dir)
+        };
+        prepared.map_err(|err| {
+            ClientError::ClientInitializationError(format!(
+                "cannot prepare {}: {err}",
+                dir.display()
+            ))
+        })?;
+        self.store_path = dir.join("store.sqlite3");
+        self.keystore_path = dir.join("keystore");
+        Ok(self)
+    }
+
+    /// Injects faults into every RPC call to exercise the retry layer.
+    #[cfg(feature = "chaos")]
+    pub fn with_chaos(mut self, config: ChaosConfig) -> Self {
+        self.chaos = Some(config);
+        self
+    }
+
+    /// Builds the client and returns it together with its keystore.
+    pub async fn build(
+        self,
+    ) -> Result<(Client<FilesystemKeyStore>, Arc<FilesystemKeyStore>), ClientError> {
+        let mut rpc_client: Arc<dyn NodeRpcClient> =
+            Arc::new(GrpcClient::new(&self.endpoint, self.timeout_ms));
+
+        // Faults sit closest to the wire so that every layer above sees them
+        #[cfg(feature = "chaos")]
+        if let Some(config) = self.chaos {
+            init_tracing();
+            rpc_client = Arc::new(ChaosRpcClient::new(rpc_client, config));
+        }
+        if self.log_rpc {
+            init_tracing();
+            rpc_client = Arc::new(LoggingRpcClient::new(rpc_client));
+        }
+        if self.rpc_retries > 0 {
+            rpc_client = Arc::new(RetryRpcClient::new(rpc_client, self.rpc_retries));
+        }
+
+        let keystore = Arc::new(FilesystemKeyStore::new(self.keystore_path.clone()).map_err(
+            |err| {
+                ClientError::ClientInitializationError(format!(
+                    "cannot open keystore {}: {err}",
+                    self.keystore_path.display()
+                ))
+            },
+        )?);
+        address_book::register_keystore(&self.keystore_path);
+        error::clean_up_on_interrupt();
+
+        let store_path = match self.store_passphrase {
+            Some(passphrase) => {
+                let store = EncryptedStore::open(&self.store_path, passphrase)
+                    .map_err(|err| ClientError::ClientInitializationError(err.to_string()))?;
+                let working_path = store.working_path().to_path_buf();
+                store_encryption::install(store);
+                working_path
+            }
+            // Opening the sealed store's old plaintext path would silently start over
+            None if EncryptedStore::sealed_path(&self.store_path).exists() => {
+                return Err(ClientError::ClientInitializationError(format!(
+                    "{} is encrypted, pass --encrypt-store and set {PASSPHRASE_ENV}",
+                    EncryptedStore::sealed_path(&self.store_path).display()
+                )));
+            }
+            None => self.store_path,
+        };
+
+        let client = ClientBuilder::new()
+            .rpc(rpc_client)
+            .sqlite_store(store_path)
+            .authenticator(keystore.clone())
+            .in_debug_mode(true.into())
+            .build()
+            .await?;
+
+        Ok((client, keystore))
+    }
+}
+
+/// Builds the client from the command-line flags and [`CONFIG_FILE`].
+///
+/// This is how the tutorial binaries without arguments of their own start.
+pub async fn init_client_from_config(
+) -> Result<(Client<FilesystemKeyStore>, Arc<FilesystemKeyStore>), ClientError> {
+    ClientSetup::from_cli()?.build().await
+}
+
+/// Installs a tracing subscriber that shows this crate's DEBUG output.
+///
+/// `RUST_LOG` overrides the default filter. Calling this more than once is a no-op.
+pub fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("info,rust_client=debug"));
+    let _ = tracing_subscriber::fmt().with_env_filter(filter).try_init();
+}
+
+/// Reads the endpoint pinned with [`ENDPOINT_ENV`], e.g. the one picked by the `ping` binary.
+///
+/// A value that is not a valid endpoint fails rather than silently falling
+/// back to the network's endpoint.
+fn endpoint_from_env() -> Result<Option<Endpoint>, ClientError> {
+    let Ok(url) = std::env::var(ENDPOINT_ENV) else {
+        return Ok(None);
+    };
+    parse_endpoint(&url).map(Some).map_err(|err| {
+        ClientError::ClientInitializationError(format!("invalid {ENDPOINT_ENV} '{url}': {err}"))
+    })
+}
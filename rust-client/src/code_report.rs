@@ -0,0 +1,132 @@
+//! Size of an account's code, checked against the protocol limits at deploy time.
+//!
+//! An account can export at most [`MAX_PROCEDURES`] procedures, and a new
+//! account's code travels in its first account update, which the node caps at
+//! [`MAX_CODE_SIZE`] bytes. Without a check, going over either shows up as an
+//! opaque build or submission error. [`ContractDeployer`] prints a
+//! [`CodeReport`] for every account it builds, warns from [`WARN_PERCENT`] of a
+//! limit on and refuses code that is over it.
+//!
+//! [`ContractDeployer`]: crate::deployer::ContractDeployer
+
+use std::fmt;
+
+use miden_client::{
+    account::{AccountCode, AccountComponent},
+    utils::Serializable,
+    Word,
+};
+use miden_protocol::ACCOUNT_UPDATE_MAX_SIZE;
+
+use crate::say;
+
+/// Most procedures one account may export.
+pub const MAX_PROCEDURES: usize = AccountCode::MAX_NUM_PROCEDURES;
+
+/// Largest serialized code that fits in the account update deploying it.
+pub const MAX_CODE_SIZE: usize = ACCOUNT_UPDATE_MAX_SIZE as usize;
+
+/// Share of a limit from which the report warns.
+pub const WARN_PERCENT: usize = 80;
+
+/// Code size, procedure count and commitment of an account's code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeReport {
+    /// Serialized size in bytes.
+    pub code_size: usize,
+    /// Procedures across all components, the auth procedure included.
+    pub procedures: usize,
+    /// Commitment to the MAST roots of all procedures.
+    pub commitment: Word,
+}
+
+impl CodeReport {
+    pub fn of(code: &AccountCode) -> Self {
+        Self {
+            code_size: code.to_bytes().len(),
+            procedures: code.num_procedures(),
+            commitment: code.commitment(),
+        }
+    }
+
+    /// Each limit the code is over, as `(what, used, limit)`.
+    fn over(&self, percent: usize) -> Vec<(&'static str, usize, usize)> {
+        [
+            ("procedures", self.procedures, MAX_PROCEDURES),
+            ("code bytes", self.code_size, MAX_CODE_SIZE),
+        ]
+        .into_iter()
+        .filter(|(_, used, limit)| used * 100 > limit * percent)
+        .collect()
+    }
+
+    /// Warnings for every limit the code is close to.
+    pub fn warnings(&self) -> Vec<String> {
+        self.over(WARN_PERCENT)
+            .into_iter()
+            .map(|(what, used, limit)| {
+                format!(
+                    "{used} {what} is {}% of the limit of {limit}",
+                    used * 100 / limit
+                )
+            })
+            .collect()
+    }
+
+    /// Fails when the code is over a protocol limit.
+    pub fn check(&self, name: &str) -> Result<(), CodeLimitExceeded> {
+        match self.over(100).first() {
+            Some(&(what, used, limit)) => Err(CodeLimitExceeded {
+                name: name.to_string(),
+                what,
+                used,
+                limit,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Prints the report and its warnings under `name`.
+    pub fn print(&self, name: &str) {
+        say!(
+            "Code of {name}: {} bytes, {} procedures, commitment {}",
+            self.code_size,
+            self.procedures,
+            self.commitment.to_hex()
+        );
+        for warning in self.warnings() {
+            say!("⚠️  {name}: {warning}");
+        }
+    }
+}
+
+/// Prints the procedures and MAST root a compiled component exports.
+pub fn print_component(name: &str, component: &AccountComponent) {
+    let library = component.library();
+    say!(
+        "Component {name}: {} exported procedures, MAST root {}",
+        library.exports().count(),
+        library.digest().to_hex()
+    );
+}
+
+/// Account code over a protocol limit, so the account cannot be deployed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeLimitExceeded {
+    pub name: String,
+    pub what: &'static str,
+    pub used: usize,
+    pub limit: usize,
+}
+
+impl fmt::Display for CodeLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} has {} {}, over the protocol limit of {}; split the contract or drop procedures",
+            self.name, self.used, self.what, self.limit
+        )
+    }
+}
+
+impl std::error::Error for CodeLimitExceeded {}
@@ -0,0 +1,57 @@
+//! Optional `miden-tutorials.toml` holding the client settings of every binary.
+//!
+//! Each key can be left out. Command-line flags override the file, and the file
+//! overrides the built-in defaults of [`crate::client::ClientSetup`]. Only the
+//! `MIDEN_TUTORIAL_ENDPOINT` environment variable sits between the two:
+//!
+//! ```toml
+//! endpoint = "http://localhost:57291"
+//! network = "devnet"
+//! store_path = "./store.sqlite3"
+//! keystore_path = "./keystore"
+//! encrypt_store = false
+//! isolate = false
+//! default_faucet_id = "mtst1..."
+//! report = ["run.jsonl", "https://example.com/hook"]
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// Default location of the config file, relative to the working directory.
+pub const CONFIG_FILE: &str = "miden-tutorials.toml";
+
+/// Settings read from [`CONFIG_FILE`], still unparsed.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TutorialConfig {
+    /// RPC endpoint URL.
+    pub endpoint: Option<String>,
+    /// Network preset: `testnet`, `devnet` or `localhost`.
+    pub network: Option<String>,
+    pub store_path: Option<PathBuf>,
+    pub keystore_path: Option<PathBuf>,
+    /// Keep the store encrypted on disk, like `--encrypt-store`.
+    pub encrypt_store: Option<bool>,
+    /// Keep each binary's store and keystore apart, like `--isolate`.
+    pub isolate: Option<bool>,
+    /// Bech32 ID of the faucet used when a binary is not given one.
+    pub default_faucet_id: Option<String>,
+    /// Sinks events are mirrored to, like `--report`, see [`crate::output::Sink`].
+    pub report: Option<Vec<String>>,
+}
+
+impl TutorialConfig {
+    /// Loads the config, starting empty if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(toml::from_str(&contents)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Known tutorial contracts that other projects can deploy as fixtures.
+//!
+//! Each wrapper compiles the MASM embedded from `masm/accounts`, so it works from
+//! any working directory. Use `deploy` to create the contract in a client,
+//! `component` to add it to an account built for a `MockChain`, or `deployer`
+//! to change how the account is built.
+
+use miden_client::{
+    account::{AccountComponent, AccountId, StorageMap, StorageSlot, StorageSlotName},
+    assembly::{CodeBuilder, Library},
+    keystore::FilesystemKeyStore,
+    rpc::domain::account::AccountStorageRequirements,
+    transaction::{
+        ForeignAccount, TransactionRequest, TransactionRequestBuilder, TransactionScript,
+    },
+    Client, ClientError, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    cycles::CycleBudget,
+    deployer::ContractDeployer,
+    masm::{MasmError, MasmFile},
+    wait::{submit_and_wait, TxReport},
+};
+
+const COUNTER_FILE: &str = "masm/accounts/counter.masm";
+const COUNTER_CODE: &str = include_str!("../../masm/accounts/counter.masm");
+const COUNTER_SCRIPT_FILE: &str = "masm/scripts/counter_script.masm";
+const COUNTER_SCRIPT_CODE: &str = include_str!("../../masm/scripts/counter_script.masm");
+const MAPPING_FILE: &str = "masm/accounts/mapping_example_contract.masm";
+const MAPPING_CODE: &str = include_str!("../../masm/accounts/mapping_example_contract.masm");
+const MAPPING_SCRIPT_FILE: &str = "masm/scripts/mapping_example_script.masm";
+const MAPPING_SCRIPT_CODE: &str = include_str!("../../masm/scripts/mapping_example_script.masm");
+const COUNT_READER_FILE: &str = "masm/accounts/count_reader.masm";
+const COUNT_READER_CODE: &str = include_str!("../../masm/accounts/count_reader.masm");
+const READER_SCRIPT_FILE: &str = "masm/scripts/reader_script.masm";
+const READER_SCRIPT_CODE: &str = include_str!("../../masm/scripts/reader_script.masm");
+const ORACLE_READER_FILE: &str = "masm/accounts/oracle_reader.masm";
+const ORACLE_READER_CODE: &str = include_str!("../../masm/accounts/oracle_reader.masm");
+const ORACLE_READER_SCRIPT_FILE: &str = "masm/scripts/oracle_reader_script.masm";
+const ORACLE_READER_SCRIPT_CODE: &str =
+    include_str!("../../masm/scripts/oracle_reader_script.masm");
+
+/// The counter contract from `counter_contract_deploy`.
+pub struct Counter {
+    pub account_id: AccountId,
+}
+
+impl Counter {
+    /// Library path the counter procedures are exported under.
+    pub const LIBRARY_PATH: &'static str = "external_contract::counter_contract";
+    pub const COUNT_SLOT: &'static str = "miden::tutorials::counter";
+    /// Cycles one increment may use, enforced by `tests/cycle_budget.rs`.
+    ///
+    /// The script only reads and writes one storage slot, so most of the total
+    /// is the transaction kernel.
+    pub const INCREMENT_BUDGET: CycleBudget = CycleBudget {
+        name: "counter increment",
+        tx_script: 10_000,
+        total: 80_000,
+    };
+
+    /// Deployer of the counter, starting at zero.
+    pub fn deployer() -> Result<ContractDeployer, Box<dyn std::error::Error>> {
+        Ok(ContractDeployer::new(
+            MasmFile::embedded(COUNTER_FILE, COUNTER_CODE),
+            Self::LIBRARY_PATH,
+        )
+        .with_slot(StorageSlot::with_value(
+            StorageSlotName::new(Self::COUNT_SLOT)?,
+            Word::default(),
+        )))
+    }
+
+    pub fn component() -> Result<AccountComponent, Box<dyn std::error::Error>> {
+        Self::deployer()?.component()
+    }
+
+    pub fn library() -> Result<Library, MasmError> {
+        MasmFile::embedded(COUNTER_FILE, COUNTER_CODE).library(Self::LIBRARY_PATH)
+    }
+
+    /// Script that calls `increment_count` on the account executing it.
+    pub fn increment_script() -> Result<TransactionScript, Box<dyn std::error::Error>> {
+        compile_script(COUNTER_SCRIPT_FILE, COUNTER_SCRIPT_CODE, &Self::library()?)
+    }
+
+    /// Builds a new counter and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with its first increment.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let account = Self::deployer()?.deploy(client).await?;
+        Ok(Self {
+            account_id: account.id(),
+        })
+    }
+
+    pub async fn increment(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let request = TransactionRequestBuilder::new()
+            .custom_script(Self::increment_script()?)
+            .build()?;
+        Ok(submit_and_wait(client, self.account_id, request).await?)
+    }
+
+    /// Returns the current count, as of the last sync.
+    pub async fn count(&self, client: &mut Client<FilesystemKeyStore>) -> Result<u64, ClientError> {
+        let account = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(Self::COUNT_SLOT).expect("valid slot name");
+        let count = account
+            .storage()
+            .get_item(&slot)
+            .expect("counter account is missing its count slot");
+        // The count sits on top of the stack, which is the last element of the word
+        Ok(count[3].as_int())
+    }
+}
+
+/// The storage map contract from `mapping_example`.
+pub struct Mapping {
+    pub account_id: AccountId,
+}
+
+impl Mapping {
+    /// Library path the mapping procedures are exported under.
+    pub const LIBRARY_PATH: &'static str = "miden_by_example::mapping_example_contract";
+    pub const VALUE_SLOT: &'static str = "miden::tutorials::mapping::value";
+    pub const MAP_SLOT: &'static str = "miden::tutorials::mapping::map";
+
+    /// Deployer of the mapping contract with an empty map.
+    pub fn deployer() -> Result<ContractDeployer, Box<dyn std::error::Error>> {
+        Ok(ContractDeployer::new(
+            MasmFile::embedded(MAPPING_FILE, MAPPING_CODE),
+            Self::LIBRARY_PATH,
+        )
+        .with_slots([
+            StorageSlot::with_value(StorageSlotName::new(Self::VALUE_SLOT)?, Word::default()),
+            StorageSlot::with_map(StorageSlotName::new(Self::MAP_SLOT)?, StorageMap::new()),
+        ]))
+    }
+
+    pub fn component() -> Result<AccountComponent, Box<dyn std::error::Error>> {
+        Self::deployer()?.component()
+    }
+
+    pub fn library() -> Result<Library, MasmError> {
+        MasmFile::embedded(MAPPING_FILE, MAPPING_CODE).library(Self::LIBRARY_PATH)
+    }
+
+    /// Script that writes `[1, 2, 3, 4]` under the zero key.
+    pub fn write_script() -> Result<TransactionScript, Box<dyn std::error::Error>> {
+        compile_script(MAPPING_SCRIPT_FILE, MAPPING_SCRIPT_CODE, &Self::library()?)
+    }
+
+    /// Builds a new mapping contract and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with its first write.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let account = Self::deployer()?.deploy(client).await?;
+        Ok(Self {
+            account_id: account.id(),
+        })
+    }
+
+    pub async fn write(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let request = TransactionRequestBuilder::new()
+            .custom_script(Self::write_script()?)
+            .build()?;
+        Ok(submit_and_wait(client, self.account_id, request).await?)
+    }
+
+    /// Returns the value stored under `key`, as of the last sync.
+    pub async fn get(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        key: Word,
+    ) -> Result<Word, ClientError> {
+        let account = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(Self::MAP_SLOT).expect("valid slot name");
+        Ok(account
+            .storage()
+            .get_map_item(&slot, key)
+            .expect("mapping account is missing its map slot"))
+    }
+}
+
+/// The contract from `counter_contract_fpi` that copies a counter's count via FPI.
+pub struct CountReader {
+    pub account_id: AccountId,
+}
+
+impl CountReader {
+    /// Library path the reader procedures are exported under.
+    pub const LIBRARY_PATH: &'static str = "external_contract::count_reader_contract";
+    pub const SLOT: &'static str = "miden::tutorials::count_reader";
+
+    pub fn deployer() -> Result<ContractDeployer, Box<dyn std::error::Error>> {
+        Ok(ContractDeployer::new(
+            MasmFile::embedded(COUNT_READER_FILE, COUNT_READER_CODE),
+            Self::LIBRARY_PATH,
+        )
+        .with_slot(StorageSlot::with_value(
+            StorageSlotName::new(Self::SLOT)?,
+            Word::default(),
+        )))
+    }
+
+    pub fn component() -> Result<AccountComponent, Box<dyn std::error::Error>> {
+        Self::deployer()?.component()
+    }
+
+    pub fn library() -> Result<Library, MasmError> {
+        MasmFile::embedded(COUNT_READER_FILE, COUNT_READER_CODE).library(Self::LIBRARY_PATH)
+    }
+
+    /// Script that calls `copy_count` on the counter `counter_id`.
+    ///
+    /// The counter must be passed to the transaction as a foreign account.
+    pub fn copy_count_script(
+        counter_id: AccountId,
+    ) -> Result<TransactionScript, Box<dyn std::error::Error>> {
+        let get_count = Counter::library()?
+            .get_procedure_root_by_path(&format!("{}::get_count", Counter::LIBRARY_PATH))
+            .expect("get_count export not found")
+            .as_elements()
+            .iter()
+            .map(|felt| felt.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        let code = READER_SCRIPT_CODE
+            .replace("{get_count_proc_hash}", &get_count)
+            .replace("{account_id_suffix}", &counter_id.suffix().to_string())
+            .replace("{account_id_prefix}", &counter_id.prefix().to_string());
+        compile_script(READER_SCRIPT_FILE, &code, &Self::library()?)
+    }
+
+    /// Builds a new reader and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with its first copy.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let account = Self::deployer()?.deploy(client).await?;
+        Ok(Self {
+            account_id: account.id(),
+        })
+    }
+
+    /// Copies the count of the public counter `counter_id` into the reader.
+    pub async fn copy_count(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        counter_id: AccountId,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let request = self.copy_count_request(counter_id)?;
+        Ok(submit_and_wait(client, self.account_id, request).await?)
+    }
+
+    /// Request running [`Self::copy_count_script`] with the counter as a foreign account.
+    pub fn copy_count_request(
+        &self,
+        counter_id: AccountId,
+    ) -> Result<TransactionRequest, Box<dyn std::error::Error>> {
+        let counter = ForeignAccount::public(counter_id, AccountStorageRequirements::default())?;
+        Ok(TransactionRequestBuilder::new()
+            .foreign_accounts([counter])
+            .custom_script(Self::copy_count_script(counter_id)?)
+            .build()?)
+    }
+}
+
+/// The contract from `oracle_data_query` that reads the Pragma BTC/USD price via FPI.
+pub struct OracleReader {
+    pub account_id: AccountId,
+}
+
+impl OracleReader {
+    /// Library path the reader procedures are exported under.
+    pub const LIBRARY_PATH: &'static str = "external_contract::oracle_reader";
+    pub const SLOT: &'static str = "miden::tutorials::oracle_reader";
+
+    pub fn deployer() -> Result<ContractDeployer, Box<dyn std::error::Error>> {
+        Ok(ContractDeployer::new(
+            MasmFile::embedded(ORACLE_READER_FILE, ORACLE_READER_CODE),
+            Self::LIBRARY_PATH,
+        )
+        .with_slot(StorageSlot::with_value(
+            StorageSlotName::new(Self::SLOT)?,
+            Word::default(),
+        )))
+    }
+
+    pub fn component() -> Result<AccountComponent, Box<dyn std::error::Error>> {
+        Self::deployer()?.component()
+    }
+
+    pub fn library() -> Result<Library, MasmError> {
+        MasmFile::embedded(ORACLE_READER_FILE, ORACLE_READER_CODE).library(Self::LIBRARY_PATH)
+    }
+
+    /// Script that calls `get_price`, which prints the price with `debug.stack`.
+    pub fn get_price_script() -> Result<TransactionScript, Box<dyn std::error::Error>> {
+        compile_script(
+            ORACLE_READER_SCRIPT_FILE,
+            ORACLE_READER_SCRIPT_CODE,
+            &Self::library()?,
+        )
+    }
+
+    /// Builds a new reader and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with its first price query.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let account = Self::deployer()?.deploy(client).await?;
+        Ok(Self {
+            account_id: account.id(),
+        })
+    }
+
+    /// Reads the price from the oracle and its publishers in `foreign_accounts`.
+    pub async fn get_price(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        foreign_accounts: Vec<ForeignAccount>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let request = TransactionRequestBuilder::new()
+            .foreign_accounts(foreign_accounts)
+            .custom_script(Self::get_price_script()?)
+            .build()?;
+        Ok(submit_and_wait(client, self.account_id, request).await?)
+    }
+}
+
+fn compile_script(
+    file: &str,
+    code: &str,
+    library: &Library,
+) -> Result<TransactionScript, Box<dyn std::error::Error>> {
+    let script_file = MasmFile::embedded(file, code);
+    let builder = CodeBuilder::new().with_dynamically_linked_library(library)?;
+    Ok(script_file.check(builder.compile_tx_script(code))?)
+}
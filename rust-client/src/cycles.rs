@@ -0,0 +1,165 @@
+//! Cycle counts of executed transactions, checked against per-contract budgets.
+//!
+//! Every MASM instruction costs VM cycles, and the cycles decide how long a
+//! transaction takes to prove. A [`CycleBudget`] states how many cycles a
+//! contract call may use. Checking it in a test turns an accidental loop or an
+//! extra storage access into a failure instead of a slower tutorial.
+//!
+//! Budgets are upper bounds with headroom, not exact counts. When a contract
+//! change legitimately costs more, raise the budget in the same change and say
+//! why, so the increase is reviewed.
+
+use std::fmt;
+
+use miden_client::{
+    account::AccountId,
+    keystore::FilesystemKeyStore,
+    transaction::{ExecutedTransaction, TransactionRequest, TransactionResult},
+    Client, ClientError,
+};
+
+use crate::{address_book::check_signer, say};
+
+/// Cycles spent in each phase of an executed transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleCount {
+    pub prologue: usize,
+    /// All input notes, including their scripts.
+    pub notes: usize,
+    pub tx_script: usize,
+    /// The epilogue, including the auth procedure.
+    pub epilogue: usize,
+    pub auth: usize,
+    pub total: usize,
+    /// Rows of the execution trace, the cycles rounded up to a power of two.
+    pub trace_length: usize,
+}
+
+impl CycleCount {
+    pub fn of(executed: &ExecutedTransaction) -> Self {
+        let measurements = executed.measurements();
+        Self {
+            prologue: measurements.prologue,
+            notes: measurements.notes_processing,
+            tx_script: measurements.tx_script_processing,
+            epilogue: measurements.epilogue,
+            auth: measurements.auth_procedure,
+            total: measurements.total_cycles(),
+            trace_length: measurements.trace_length(),
+        }
+    }
+
+    /// Prints the cycles per phase.
+    pub fn print(&self) {
+        say!("  prologue:     {:>7}", self.prologue);
+        say!("  notes:        {:>7}", self.notes);
+        say!("  tx script:    {:>7}", self.tx_script);
+        say!("  epilogue:     {:>7} (auth {})", self.epilogue, self.auth);
+        say!("  total:        {:>7}", self.total);
+        say!("  trace length: {:>7}", self.trace_length);
+    }
+}
+
+/// Executes `request` locally, prints the cycles of its script under `label`
+/// and returns the result, ready to be proven and submitted.
+///
+/// Call it in place of `execute_transaction` while changing a contract to see
+/// what the change costs before paying for a proof.
+pub async fn execute_with_cycles(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    request: TransactionRequest,
+    label: &str,
+) -> Result<(TransactionResult, CycleCount), ClientError> {
+    check_signer(client, account_id, &request).await?;
+    let tx_result = client.execute_transaction(account_id, request).await?;
+    let count = CycleCount::of(tx_result.executed_transaction());
+    say!(
+        "{label}: {} tx script cycles of {} in total, trace length {}",
+        count.tx_script,
+        count.total,
+        count.trace_length
+    );
+    Ok((tx_result, count))
+}
+
+/// Prints the cycles of several executions side by side.
+///
+/// The trace length decides the proving time, so two scripts whose totals
+/// round up to the same power of two cost the same to prove.
+pub fn print_comparison(rows: &[(&str, CycleCount)]) {
+    say!(
+        "{:<20} {:>10} {:>8} {:>8} {:>10} {:>8}",
+        "execution",
+        "tx script",
+        "notes",
+        "total",
+        "trace",
+        "script%"
+    );
+    for (label, count) in rows {
+        say!(
+            "{:<20} {:>10} {:>8} {:>8} {:>10} {:>7}%",
+            label,
+            count.tx_script,
+            count.notes,
+            count.total,
+            count.trace_length,
+            count.tx_script * 100 / count.total.max(1)
+        );
+    }
+}
+
+/// Cycles a transaction may use before it counts as a regression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleBudget {
+    /// What the budget covers, e.g. `counter increment`.
+    pub name: &'static str,
+    /// Cycles of the transaction script, where the contract code runs.
+    pub tx_script: usize,
+    /// Cycles of the whole transaction, kernel included.
+    pub total: usize,
+}
+
+impl CycleBudget {
+    /// Fails when either the script or the whole transaction is over budget.
+    pub fn check(&self, count: &CycleCount) -> Result<(), CycleBudgetExceeded> {
+        let sections = [
+            ("tx script", count.tx_script, self.tx_script),
+            ("total", count.total, self.total),
+        ];
+        for (section, used, budget) in sections {
+            if used > budget {
+                return Err(CycleBudgetExceeded {
+                    name: self.name,
+                    section,
+                    used,
+                    budget,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A transaction used more cycles than its [`CycleBudget`] allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleBudgetExceeded {
+    pub name: &'static str,
+    pub section: &'static str,
+    pub used: usize,
+    pub budget: usize,
+}
+
+impl fmt::Display for CycleBudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} used {} {} cycles, over its budget of {}; \
+             if the contract change is intended, raise the budget with it",
+            self.name, self.used, self.section, self.budget
+        )
+    }
+}
+
+impl std::error::Error for CycleBudgetExceeded {}
@@ -0,0 +1,51 @@
+//! Per-binary data directories, so one tutorial does not see another's state.
+//!
+//! By default every binary shares `./store.sqlite3` and `./keystore`, which
+//! lets one binary pick up what another deployed but also fills each run with
+//! the accounts and notes of earlier tutorials. With `--isolate` a binary
+//! keeps its store and keystore under `data/<binary>/`, next to the configured
+//! store, and `--fresh` wipes that directory before the run:
+//!
+//! ```text
+//! data/counter_contract_deploy/store.sqlite3
+//! data/counter_contract_deploy/keystore/
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Directory the per-binary directories live in, relative to the configured store.
+pub const DATA_DIR: &str = "data";
+
+/// Name of the running binary, e.g. `counter_contract_deploy`.
+pub fn binary_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "tutorial".to_string())
+}
+
+/// Directory holding the store and keystore of binary `name`, given the
+/// configured store path.
+pub fn binary_data_dir(store_path: &Path, name: &str) -> PathBuf {
+    store_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(DATA_DIR)
+        .join(name)
+}
+
+/// Deletes `dir` with everything in it and creates it again empty.
+pub fn wipe(dir: &Path) -> io::Result<()> {
+    match fs::remove_dir_all(dir) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    fs::create_dir_all(dir)
+}
@@ -0,0 +1,197 @@
+//! Contract that commits to an off-chain dataset through a single Merkle root.
+//!
+//! The dataset never goes on-chain. The contract in `masm/accounts/data_root.masm`
+//! stores the root of a Merkle tree over its entries, and only the operator can
+//! replace it, by sending a note with the new root and the next version.
+//! Consumers holding an entry and its [`MembershipProof`] check it against the
+//! root read from the contract, without sending a transaction.
+
+use miden_client::{
+    account::{AccountId, StorageSlot, StorageSlotName},
+    assembly::Library,
+    keystore::FilesystemKeyStore,
+    note::NoteScript,
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+use miden_protocol::{
+    crypto::merkle::{MerkleError, MerklePath, MerkleTree, NodeIndex},
+    Hasher,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const ROOT_SLOT: &str = "miden::tutorials::data_root::root";
+pub const VERSION_SLOT: &str = "miden::tutorials::data_root::version";
+pub const OPERATOR_SLOT: &str = "miden::tutorials::data_root::operator";
+
+/// Library path the data root procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::data_root_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/data_root.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/data_root.masm");
+const UPDATE_NOTE_FILE: &str = "masm/notes/data_root_update_note.masm";
+const UPDATE_NOTE_CODE: &str = include_str!("../../masm/notes/data_root_update_note.masm");
+
+/// An off-chain dataset and the Merkle tree over its entries.
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    entries: Vec<Vec<u8>>,
+    tree: MerkleTree,
+}
+
+impl Dataset {
+    /// Builds the tree, padding it with empty leaves to a power of two.
+    pub fn new(entries: Vec<Vec<u8>>) -> Result<Self, MerkleError> {
+        let width = entries.len().next_power_of_two().max(2);
+        let leaves = entries
+            .iter()
+            .map(|entry| Self::leaf(entry))
+            .chain(std::iter::repeat(Word::default()))
+            .take(width);
+        let tree = MerkleTree::new(leaves)?;
+        Ok(Self { entries, tree })
+    }
+
+    /// Leaf committing to one entry.
+    pub fn leaf(entry: &[u8]) -> Word {
+        Hasher::hash(entry)
+    }
+
+    pub fn root(&self) -> Word {
+        self.tree.root()
+    }
+
+    pub fn entries(&self) -> &[Vec<u8>] {
+        &self.entries
+    }
+
+    /// Proves that the entry at `index` is part of the dataset.
+    pub fn prove(&self, index: usize) -> Option<MembershipProof> {
+        let entry = self.entries.get(index)?.clone();
+        let node = NodeIndex::new(self.tree.depth(), index as u64).ok()?;
+        let path = self.tree.get_path(node).ok()?;
+        Some(MembershipProof {
+            index: index as u64,
+            entry,
+            path,
+        })
+    }
+}
+
+/// Proof that an entry sits at `index` of the dataset committed to by a root.
+#[derive(Debug, Clone)]
+pub struct MembershipProof {
+    pub index: u64,
+    pub entry: Vec<u8>,
+    pub path: MerklePath,
+}
+
+impl MembershipProof {
+    /// Checks the proof against a root, e.g. the one read from the contract.
+    pub fn verify(&self, root: Word) -> bool {
+        self.path
+            .verify(self.index, Dataset::leaf(&self.entry), &root)
+            .is_ok()
+    }
+}
+
+/// A deployed contract holding the data root published by its operator.
+pub struct DataRootContract {
+    pub account_id: AccountId,
+    pub operator_id: AccountId,
+    library: Library,
+}
+
+impl DataRootContract {
+    /// Builds a new contract that accepts roots from `operator_id` and starts tracking it.
+    ///
+    /// The contract starts with an empty root at version zero and is deployed
+    /// on-chain with the first update it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        operator_id: AccountId,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let operator = Word::from([
+            Felt::new(0),
+            Felt::new(0),
+            operator_id.suffix(),
+            operator_id.prefix().as_felt(),
+        ]);
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(ROOT_SLOT)?, Word::default()),
+            StorageSlot::with_value(StorageSlotName::new(VERSION_SLOT)?, Word::default()),
+            StorageSlot::with_value(StorageSlotName::new(OPERATOR_SLOT)?, operator),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            operator_id,
+            library,
+        })
+    }
+
+    /// Sends an update note from `sender` and has the contract consume it.
+    ///
+    /// Only the operator's updates carrying the next version are accepted; any
+    /// other note fails to execute and leaves the root untouched.
+    pub async fn publish(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        root: Word,
+        version: u64,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        let file = MasmFile::embedded(UPDATE_NOTE_FILE, UPDATE_NOTE_CODE);
+        let script: NoteScript = file.check(builder.compile_note_script(&file.source))?;
+
+        let note = NoteBuilder::new(sender, script)
+            .inputs(root.to_vec())
+            .inputs([Felt::new(version)])
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, sender, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .build()?;
+        Ok(submit_and_wait(client, self.account_id, consume_request).await?)
+    }
+
+    /// Returns the published root and its version, as of the last sync.
+    pub async fn root(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<(Word, u64), ClientError> {
+        let account = get_full_account(client, self.account_id).await?;
+        let read = |name: &str| {
+            let slot = StorageSlotName::new(name).expect("valid slot name");
+            account
+                .storage()
+                .get_item(&slot)
+                .expect("data root account is missing a slot")
+        };
+        // The version sits on top of the stack, which is the last element of the word
+        Ok((read(ROOT_SLOT), read(VERSION_SLOT)[3].as_int()))
+    }
+}
@@ -0,0 +1,201 @@
+//! Builder that deploys a contract account from a MASM component.
+//!
+//! Every contract tutorial used to compile its MASM with
+//! `CodeBuilder::compile_component_code`, wrap it in an `AccountComponent`, build
+//! the account and track it. [`ContractDeployer`] does all of it:
+//!
+//! ```ignore
+//! let counter = ContractDeployer::from_path(
+//!     "../masm/accounts/counter.masm",
+//!     "external_contract::counter_contract",
+//! )
+//! .with_slot(StorageSlot::with_value(slot_name, Word::default()))
+//! .deploy(&mut client)
+//! .await?;
+//! ```
+
+use std::{path::Path, sync::Arc};
+
+use rand::RngCore;
+
+use miden_client::{
+    account::{
+        Account, AccountBuilder, AccountComponent, AccountStorageMode, AccountType, StorageSlot,
+    },
+    assembly::{CodeBuilder, Library},
+    auth::{AuthFalcon512Rpo, AuthSecretKey, NoAuth},
+    keystore::FilesystemKeyStore,
+    Client,
+};
+
+use crate::{
+    accounts::{add_no_auth_account, record_auth},
+    code_report::{print_component, CodeLimitExceeded, CodeReport, MAX_PROCEDURES},
+    masm::{MasmError, MasmFile},
+};
+
+/// How a deployed contract authenticates its transactions.
+#[derive(Clone)]
+pub enum ContractAuth {
+    /// Anyone can execute transactions against the contract.
+    NoAuth,
+    /// Transactions are signed with `key`, which is added to `keystore` on deploy.
+    Falcon512 {
+        key: AuthSecretKey,
+        keystore: Arc<FilesystemKeyStore>,
+    },
+}
+
+impl ContractAuth {
+    /// Signs with a new Falcon key stored in `keystore`.
+    pub fn new_falcon512(keystore: &Arc<FilesystemKeyStore>) -> Self {
+        ContractAuth::Falcon512 {
+            key: AuthSecretKey::new_falcon512_rpo(),
+            keystore: keystore.clone(),
+        }
+    }
+}
+
+/// Deploys an account around the component compiled from one MASM file.
+///
+/// Defaults to a public account with immutable code and [`ContractAuth::NoAuth`].
+pub struct ContractDeployer {
+    file: MasmFile,
+    library_path: String,
+    slots: Vec<StorageSlot>,
+    components: Vec<AccountComponent>,
+    storage_mode: AccountStorageMode,
+    account_type: AccountType,
+    auth: ContractAuth,
+}
+
+impl ContractDeployer {
+    /// Deploys `file`, exporting its procedures under `library_path`.
+    pub fn new(file: MasmFile, library_path: impl Into<String>) -> Self {
+        Self {
+            file,
+            library_path: library_path.into(),
+            slots: Vec::new(),
+            components: Vec::new(),
+            storage_mode: AccountStorageMode::Public,
+            account_type: AccountType::RegularAccountImmutableCode,
+            auth: ContractAuth::NoAuth,
+        }
+    }
+
+    /// Reads the MASM from `path`, relative to the working directory.
+    pub fn from_path(path: impl AsRef<Path>, library_path: impl Into<String>) -> Self {
+        Self::new(MasmFile::read(path), library_path)
+    }
+
+    pub fn with_slot(mut self, slot: StorageSlot) -> Self {
+        self.slots.push(slot);
+        self
+    }
+
+    pub fn with_slots(mut self, slots: impl IntoIterator<Item = StorageSlot>) -> Self {
+        self.slots.extend(slots);
+        self
+    }
+
+    /// Adds another component, e.g. `BasicWallet` or contract metadata.
+    pub fn with_component(mut self, component: impl Into<AccountComponent>) -> Self {
+        self.components.push(component.into());
+        self
+    }
+
+    pub fn storage_mode(mut self, storage_mode: AccountStorageMode) -> Self {
+        self.storage_mode = storage_mode;
+        self
+    }
+
+    pub fn account_type(mut self, account_type: AccountType) -> Self {
+        self.account_type = account_type;
+        self
+    }
+
+    pub fn auth(mut self, auth: ContractAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Compiles the MASM into a component holding the configured slots.
+    pub fn component(&self) -> Result<AccountComponent, Box<dyn std::error::Error>> {
+        let code = self.file.check(
+            CodeBuilder::new().compile_component_code(&self.library_path, &self.file.source),
+        )?;
+        Ok(AccountComponent::new(code, self.slots.clone())?.with_supports_all_types())
+    }
+
+    /// The library scripts and notes link against to call the contract.
+    pub fn library(&self) -> Result<Library, MasmError> {
+        self.file.library(&self.library_path)
+    }
+
+    /// Builds the account without tracking it, for a `MockChain` or to inspect it first.
+    ///
+    /// Prints the size of the code and fails with [`CodeLimitExceeded`] when it
+    /// is over a protocol limit, see [`crate::code_report`].
+    pub fn build(&self, init_seed: [u8; 32]) -> Result<Account, Box<dyn std::error::Error>> {
+        let component = self.component()?;
+        print_component(&self.library_path, &component);
+
+        // Too many procedures already fail the account builder, so count them
+        // first to say what went wrong. The auth component adds one.
+        let procedures = 1 + std::iter::once(&component)
+            .chain(&self.components)
+            .map(|component| component.library().exports().count())
+            .sum::<usize>();
+        if procedures > MAX_PROCEDURES {
+            return Err(CodeLimitExceeded {
+                name: self.library_path.clone(),
+                what: "procedures",
+                used: procedures,
+                limit: MAX_PROCEDURES,
+            }
+            .into());
+        }
+
+        let mut builder = AccountBuilder::new(init_seed)
+            .account_type(self.account_type)
+            .storage_mode(self.storage_mode)
+            .with_component(component);
+        for component in &self.components {
+            builder = builder.with_component(component.clone());
+        }
+
+        let account = match &self.auth {
+            ContractAuth::NoAuth => builder.with_auth_component(NoAuth).build()?,
+            ContractAuth::Falcon512 { key, .. } => builder
+                .with_auth_component(AuthFalcon512Rpo::new(key.public_key().to_commitment()))
+                .build()?,
+        };
+
+        let report = CodeReport::of(account.code());
+        report.print(&self.library_path);
+        report.check(&self.library_path)?;
+        Ok(account)
+    }
+
+    /// Builds the account, tracks it and stores its key if it has one.
+    ///
+    /// Like any new account, it is deployed on-chain with its first transaction.
+    pub async fn deploy(
+        self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Account, Box<dyn std::error::Error>> {
+        let mut init_seed = [0_u8; 32];
+        client.rng().fill_bytes(&mut init_seed);
+
+        let account = self.build(init_seed)?;
+        match &self.auth {
+            ContractAuth::NoAuth => add_no_auth_account(client, &account).await?,
+            ContractAuth::Falcon512 { key, keystore } => {
+                client.add_account(&account, false).await?;
+                keystore.add_key(key)?;
+                record_auth(&account, Some(key.public_key().to_commitment()));
+            }
+        }
+        Ok(account)
+    }
+}
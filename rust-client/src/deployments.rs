@@ -0,0 +1,123 @@
+//! Records the contracts deployed by one tutorial so later ones can find them.
+//!
+//! `counter_contract_deploy` writes the ID of its counter here, and the
+//! tutorials that call the counter read it back instead of hardcoding an ID
+//! that goes stale whenever the network is reset. IDs are kept per network,
+//! since a contract only exists on the network it was deployed to:
+//!
+//! ```toml
+//! [devnet]
+//! counter = "mdev1..."
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use miden_client::account::AccountId;
+
+use crate::{
+    client::{network, parse_account_id, registry_dir},
+    network::Network,
+};
+
+/// File name of the registry, kept in [`crate::client::registry_dir`].
+pub const DEPLOYMENTS_FILE: &str = "deployments.toml";
+
+/// Environment variable pointing at another registry, e.g. one shared by runs
+/// in different directories.
+pub const DEPLOYMENTS_ENV: &str = "MIDEN_TUTORIAL_DEPLOYMENTS";
+
+/// Name the counter contract is recorded under.
+pub const COUNTER: &str = "counter";
+
+/// Contract IDs by network and name.
+#[derive(Debug)]
+pub struct Deployments {
+    path: PathBuf,
+    networks: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Deployments {
+    /// Loads the registry, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref().to_path_buf();
+        let networks = match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, networks })
+    }
+
+    /// Loads the registry from [`DEPLOYMENTS_ENV`], or from [`DEPLOYMENTS_FILE`] in
+    /// the registry directory if it is unset.
+    pub fn open_default() -> Result<Self, Box<dyn std::error::Error>> {
+        match env::var(DEPLOYMENTS_ENV) {
+            Ok(path) => Self::load(path),
+            Err(_) => Self::load(registry_dir().join(DEPLOYMENTS_FILE)),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records `account_id` as the `name` contract of `network` and writes the registry back to disk.
+    pub fn record(
+        &mut self,
+        network: Network,
+        name: &str,
+        account_id: AccountId,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.networks
+            .entry(network.name().to_string())
+            .or_default()
+            .insert(name.to_string(), account_id.to_bech32(network.network_id()));
+        fs::write(&self.path, toml::to_string(&self.networks)?)?;
+        Ok(())
+    }
+
+    /// Returns the `name` contract recorded for `network`.
+    pub fn get(&self, network: Network, name: &str) -> Result<Option<AccountId>, String> {
+        self.networks
+            .get(network.name())
+            .and_then(|contracts| contracts.get(name))
+            .map(|id| parse_account_id(id))
+            .transpose()
+    }
+}
+
+/// Records a contract deployed on the current network in the default registry.
+pub fn record(name: &str, account_id: AccountId) -> Result<(), Box<dyn std::error::Error>> {
+    Deployments::open_default()?.record(network(), name, account_id)
+}
+
+/// Returns `id` when given on the command line, otherwise the `name` contract
+/// recorded for the current network.
+///
+/// A missing entry fails as a config error that names the tutorial to run.
+pub fn resolve(
+    id: Option<AccountId>,
+    name: &str,
+    deployed_by: &str,
+) -> Result<AccountId, Box<dyn std::error::Error>> {
+    if let Some(id) = id {
+        return Ok(id);
+    }
+    let deployments = Deployments::open_default()?;
+    match deployments.get(network(), name)? {
+        Some(id) => Ok(id),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no {name} contract recorded for {} in {}; run `cargo run --bin {deployed_by}` first or pass its ID",
+                network().name(),
+                deployments.path().display()
+            ),
+        )
+        .into()),
+    }
+}
@@ -0,0 +1,105 @@
+use std::{fs, path::Path, time::Duration};
+
+use miden_client::rpc::{Endpoint, GrpcClient, NodeRpcClient};
+use serde::Deserialize;
+use tokio::time::Instant;
+
+/// Default location of the endpoints registry, relative to `rust-client/`.
+pub const ENDPOINTS_FILE: &str = "endpoints.toml";
+
+/// Environment variable pinning the endpoint used by [`crate::client::ClientSetup`].
+pub const ENDPOINT_ENV: &str = "MIDEN_TUTORIAL_ENDPOINT";
+
+/// One entry of `endpoints.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EndpointEntry {
+    pub name: String,
+    pub network: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    endpoint: Vec<EndpointEntry>,
+}
+
+/// Result of pinging one endpoint.
+#[derive(Debug, Clone)]
+pub struct EndpointHealth {
+    pub entry: EndpointEntry,
+    pub latency: Duration,
+    /// Latest block reported by the node, or the reason the ping failed.
+    pub block_height: Result<u32, String>,
+}
+
+impl EndpointHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.block_height.is_ok()
+    }
+}
+
+/// Known RPC endpoints grouped by network.
+#[derive(Debug, Clone)]
+pub struct EndpointRegistry {
+    pub entries: Vec<EndpointEntry>,
+}
+
+impl EndpointRegistry {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let file: RegistryFile = toml::from_str(&contents)?;
+        Ok(Self {
+            entries: file.endpoint,
+        })
+    }
+
+    pub fn for_network<'a>(&'a self, network: &'a str) -> impl Iterator<Item = &'a EndpointEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.network == network)
+    }
+
+    /// Pings every endpoint of the registry.
+    pub async fn ping_all(&self, timeout_ms: u64) -> Vec<EndpointHealth> {
+        let mut results = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            results.push(ping(entry, timeout_ms).await);
+        }
+        results
+    }
+}
+
+/// Requests the latest block header from an endpoint and times the round trip.
+pub async fn ping(entry: &EndpointEntry, timeout_ms: u64) -> EndpointHealth {
+    let start = Instant::now();
+    let block_height = match Endpoint::try_from(entry.url.as_str()) {
+        Ok(endpoint) => GrpcClient::new(&endpoint, timeout_ms)
+            .get_block_header_by_number(None, false)
+            .await
+            .map(|(header, _)| header.block_num().as_u32())
+            .map_err(|err| err.to_string()),
+        Err(err) => Err(format!("invalid url: {err}")),
+    };
+    EndpointHealth {
+        entry: entry.clone(),
+        latency: start.elapsed(),
+        block_height,
+    }
+}
+
+/// Picks the healthy endpoint with the highest block, preferring lower latency on ties.
+pub fn healthiest<'a>(
+    results: impl IntoIterator<Item = &'a EndpointHealth>,
+) -> Option<&'a EndpointHealth> {
+    results
+        .into_iter()
+        .filter(|health| health.is_healthy())
+        .max_by(|a, b| {
+            let a_height = a.block_height.as_ref().ok();
+            let b_height = b.block_height.as_ref().ok();
+            a_height
+                .cmp(&b_height)
+                .then_with(|| b.latency.cmp(&a.latency))
+        })
+}
@@ -0,0 +1,261 @@
+//! Failure classes shared by the tutorial binaries and their exit codes.
+//!
+//! Each binary runs its body in a `run` function and passes the result to
+//! [`report`], so scripts can tell a bad configuration from an unreachable node
+//! without parsing the output:
+//!
+//! | Code | Class                              |
+//! |------|------------------------------------|
+//! | 0    | success                            |
+//! | 1    | [`TutorialError::Other`]           |
+//! | 2    | [`TutorialError::Config`]          |
+//! | 3    | [`TutorialError::RpcUnreachable`]  |
+//! | 4    | [`TutorialError::Compile`]         |
+//! | 5    | [`TutorialError::TxFailed`]        |
+//! | 6    | [`TutorialError::Timeout`]         |
+//!
+//! A panic still exits with 101; it marks a bug in the tutorial, not a failure class.
+//! Ctrl-C exits with 130. Both still seal the store and print the summary, through
+//! the [`ExitGuard`] `main` holds and the handler [`clean_up_on_interrupt`] installs.
+
+use std::{error::Error, fmt, io, process::ExitCode, sync::Once};
+
+use miden_client::{rpc::RpcError, ClientError};
+
+use crate::{
+    code_report::CodeLimitExceeded, masm::MasmError, memory_store, output, session,
+    store_encryption, wait::WaitError,
+};
+
+/// Why a tutorial binary failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialError {
+    /// Bad arguments, settings or input files. Clap usage errors exit with the same code.
+    Config,
+    /// The node could not be reached.
+    RpcUnreachable,
+    /// A MASM source failed to compile, or its code is over a protocol limit.
+    Compile,
+    /// A transaction failed to execute or prove, the node rejected it, or it was
+    /// discarded or expired before being committed.
+    TxFailed,
+    /// The node did not answer in time, or a transaction stayed pending past its deadline.
+    Timeout,
+    /// Any failure without a more specific class.
+    Other,
+}
+
+impl TutorialError {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            TutorialError::Other => 1,
+            TutorialError::Config => 2,
+            TutorialError::RpcUnreachable => 3,
+            TutorialError::Compile => 4,
+            TutorialError::TxFailed => 5,
+            TutorialError::Timeout => 6,
+        }
+    }
+
+    /// Classifies an error by the first cause in its chain with a known class.
+    ///
+    /// Falls back to the message for causes the client only surfaces as text.
+    pub fn classify(err: &(dyn Error + 'static)) -> Self {
+        let mut cause = Some(err);
+        while let Some(current) = cause {
+            if let Some(class) = Self::classify_cause(current) {
+                return class;
+            }
+            cause = current.source();
+        }
+        Self::from_message(&err.to_string()).unwrap_or(TutorialError::Other)
+    }
+
+    fn classify_cause(err: &(dyn Error + 'static)) -> Option<Self> {
+        if err.is::<MasmError>() || err.is::<CodeLimitExceeded>() {
+            return Some(TutorialError::Compile);
+        }
+        if let Some(err) = err.downcast_ref::<WaitError>() {
+            return match err {
+                WaitError::Timeout { .. }
+                | WaitError::NotesTimeout { .. }
+                | WaitError::BlockTimeout { .. } => Some(TutorialError::Timeout),
+                WaitError::Discarded { .. } | WaitError::Expired { .. } => {
+                    Some(TutorialError::TxFailed)
+                }
+                // Classified by its source
+                WaitError::Client(_) => None,
+            };
+        }
+        if let Some(err) = err.downcast_ref::<ClientError>() {
+            return match err {
+                ClientError::RpcError(err) => Some(Self::classify_rpc(err)),
+                ClientError::TransactionExecutorError(_)
+                | ClientError::TransactionProvingError(_) => Some(TutorialError::TxFailed),
+                ClientError::ClientInitializationError(_) => Some(TutorialError::Config),
+                _ => None,
+            };
+        }
+        if let Some(err) = err.downcast_ref::<RpcError>() {
+            return Some(Self::classify_rpc(err));
+        }
+        if err.is::<tokio::time::error::Elapsed>() {
+            return Some(TutorialError::Timeout);
+        }
+        if let Some(err) = err.downcast_ref::<io::Error>() {
+            return Some(match err.kind() {
+                io::ErrorKind::TimedOut => TutorialError::Timeout,
+                io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::NotConnected => TutorialError::RpcUnreachable,
+                // Reading a key file, registry or store that is missing or unreadable
+                _ => TutorialError::Config,
+            });
+        }
+        if err.is::<toml::de::Error>() || err.is::<serde_json::Error>() || err.is::<ConfigError>() {
+            return Some(TutorialError::Config);
+        }
+        None
+    }
+
+    fn classify_rpc(err: &RpcError) -> Self {
+        match err {
+            RpcError::ConnectionError(_) => TutorialError::RpcUnreachable,
+            err => {
+                let message = err.to_string();
+                Self::from_message(&message).unwrap_or_else(|| {
+                    // The node answered but refused the request
+                    if message.to_lowercase().contains("submit") {
+                        TutorialError::TxFailed
+                    } else {
+                        TutorialError::Other
+                    }
+                })
+            }
+        }
+    }
+
+    /// Detects timeouts and unreachable nodes from gRPC status text.
+    fn from_message(message: &str) -> Option<Self> {
+        let message = message.to_lowercase();
+        let contains_any = |markers: &[&str]| markers.iter().any(|m| message.contains(m));
+        if contains_any(&[
+            "deadline exceeded",
+            "deadlineexceeded",
+            "timed out",
+            "timeout",
+        ]) {
+            Some(TutorialError::Timeout)
+        } else if contains_any(&[
+            "connection refused",
+            "transport error",
+            "dns error",
+            "unavailable",
+        ]) {
+            Some(TutorialError::RpcUnreachable)
+        } else {
+            None
+        }
+    }
+}
+
+/// Marks bad input of any kind, so that it is classified as [`TutorialError::Config`].
+///
+/// Modules reading their own input files or settings wrap their errors in it
+/// rather than teaching [`TutorialError::classify`] about each error type.
+#[derive(Debug)]
+pub struct ConfigError(Box<dyn Error + Send + Sync>);
+
+impl ConfigError {
+    pub fn new(err: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self(err.into())
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Prints the session summary and the error of a failed run, and turns the
+/// outcome into an exit code.
+pub fn report<E: Into<Box<dyn Error>>>(result: Result<(), E>) -> ExitCode {
+    // The client is dropped by now, so an encrypted store can be sealed again
+    // and a throwaway one removed
+    clean_up();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let err = err.into();
+            let class = TutorialError::classify(err.as_ref());
+            eprintln!("Error: {err:?}");
+            eprintln!("Failure class: {class:?} (exit code {})", class.exit_code());
+            ExitCode::from(class.exit_code())
+        }
+    }
+}
+
+/// Seals the store, removes scratch directories, prints the session summary
+/// and flushes the report sinks.
+///
+/// Every step only acts the first time, so [`report`], an [`ExitGuard`] and
+/// the Ctrl-C handler can all call it.
+fn clean_up() {
+    store_encryption::seal_open_store();
+    memory_store::remove_scratch_dirs();
+    session::print_summary();
+    output::shutdown();
+}
+
+/// Runs the cleanup of [`report`] when dropped, for runs that never reach it.
+///
+/// Held by a binary's `main`, it is dropped while a panic unwinds, after the
+/// clients inside `run()`, so a panicking run still seals its store and prints
+/// its summary.
+#[must_use = "the run is cleaned up when the guard is dropped"]
+#[derive(Debug, Default)]
+pub struct ExitGuard;
+
+impl ExitGuard {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        clean_up();
+    }
+}
+
+/// Exit code of a run interrupted with Ctrl-C, as shells report it.
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Runs the cleanup of [`report`] and exits when the run is interrupted with
+/// Ctrl-C, which would otherwise leave a decrypted store behind.
+///
+/// Called when a client is built. It needs the Tokio runtime, so it does
+/// nothing outside one, and only the first call installs the handler.
+pub(crate) fn clean_up_on_interrupt() {
+    static INSTALLED: Once = Once::new();
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    INSTALLED.call_once(|| {
+        runtime.spawn(async {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                eprintln!("\nInterrupted, cleaning up before exiting");
+                clean_up();
+                std::process::exit(INTERRUPTED_EXIT_CODE);
+            }
+        });
+    });
+}
@@ -0,0 +1,250 @@
+//! SDK-style wrapper around the escrow contract in `masm/accounts/escrow.masm`.
+//!
+//! One escrow account holds one deal between a buyer and a seller. The buyer
+//! locks the price in it with a note, then either releases it, after which the
+//! seller claims it, or takes it back once the deadline block has passed
+//! without a release. Every step is a note from the party taking it, and the
+//! contract checks the note's sender.
+
+use miden_client::{
+    account::{AccountId, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const PARTIES_SLOT: &str = "miden::tutorials::escrow::parties";
+pub const TERMS_SLOT: &str = "miden::tutorials::escrow::terms";
+pub const LOCKED_SLOT: &str = "miden::tutorials::escrow::locked";
+
+/// Library path the escrow procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::escrow_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/escrow.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/escrow.masm");
+const LOCK_NOTE_FILE: &str = "masm/notes/escrow_lock_note.masm";
+const LOCK_NOTE_CODE: &str = include_str!("../../masm/notes/escrow_lock_note.masm");
+const RELEASE_NOTE_FILE: &str = "masm/notes/escrow_release_note.masm";
+const RELEASE_NOTE_CODE: &str = include_str!("../../masm/notes/escrow_release_note.masm");
+const CLAIM_NOTE_FILE: &str = "masm/notes/escrow_claim_note.masm";
+const CLAIM_NOTE_CODE: &str = include_str!("../../masm/notes/escrow_claim_note.masm");
+const REFUND_NOTE_FILE: &str = "masm/notes/escrow_refund_note.masm";
+const REFUND_NOTE_CODE: &str = include_str!("../../masm/notes/escrow_refund_note.masm");
+
+/// A deployed escrow for one deal.
+pub struct Escrow {
+    pub account_id: AccountId,
+    pub buyer: AccountId,
+    pub seller: AccountId,
+    /// The price the buyer locks.
+    pub price: FungibleAsset,
+    /// First block at which the buyer can take an unreleased price back.
+    pub deadline: u32,
+    library: Library,
+}
+
+impl Escrow {
+    /// Builds a new escrow for the deal and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the lock note it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        buyer: AccountId,
+        seller: AccountId,
+        price: FungibleAsset,
+        deadline: u32,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let parties = Word::from([
+            seller.suffix(),
+            seller.prefix().as_felt(),
+            buyer.suffix(),
+            buyer.prefix().as_felt(),
+        ]);
+        let terms = Word::from([
+            Felt::new(0),
+            Felt::new(0),
+            Felt::new(0),
+            Felt::new(deadline as u64),
+        ]);
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(PARTIES_SLOT)?, parties),
+            StorageSlot::with_value(StorageSlotName::new(TERMS_SLOT)?, terms),
+            StorageSlot::with_value(StorageSlotName::new(LOCKED_SLOT)?, Word::default()),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            buyer,
+            seller,
+            price,
+            deadline,
+            library,
+        })
+    }
+
+    /// Moves the price from the buyer into the escrow.
+    pub async fn lock(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script =
+            self.note_script(client, MasmFile::embedded(LOCK_NOTE_FILE, LOCK_NOTE_CODE))?;
+        let note = NoteBuilder::new(self.buyer, script)
+            .asset(self.price)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, self.buyer, note, None).await
+    }
+
+    /// Sends a release note from `sender`, which the escrow only accepts from the buyer.
+    pub async fn release(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(RELEASE_NOTE_FILE, RELEASE_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(sender, script)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, sender, note, None).await
+    }
+
+    /// Has the seller claim the released price and returns the P2ID note paying it.
+    pub async fn claim(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        self.pay_out(
+            client,
+            self.seller,
+            MasmFile::embedded(CLAIM_NOTE_FILE, CLAIM_NOTE_CODE),
+        )
+        .await
+    }
+
+    /// Has the buyer take the price back and returns the P2ID note paying it.
+    ///
+    /// Only succeeds without a release and from the deadline block on.
+    pub async fn refund(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        self.pay_out(
+            client,
+            self.buyer,
+            MasmFile::embedded(REFUND_NOTE_FILE, REFUND_NOTE_CODE),
+        )
+        .await
+    }
+
+    /// Returns whether the price was released and whether it is still held,
+    /// as of the last sync.
+    pub async fn state(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<(bool, bool), ClientError> {
+        let escrow = get_full_account(client, self.account_id).await?;
+        let read = |name: &str| {
+            let slot = StorageSlotName::new(name).expect("valid slot name");
+            escrow
+                .storage()
+                .get_item(&slot)
+                .expect("escrow account is missing a slot")
+        };
+        // The flag sits second from the top of the stack, below the deadline
+        let released = read(TERMS_SLOT)[2].as_int() == 1;
+        let held = read(LOCKED_SLOT) != Word::default();
+        Ok((released, held))
+    }
+
+    /// Sends a payout request from `party` with the recipient of a P2ID note to it.
+    async fn pay_out(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        party: AccountId,
+        file: MasmFile,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let payout = create_p2id_note(
+            self.account_id,
+            party,
+            vec![self.price.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        let script = self.note_script(client, file)?;
+        let note = NoteBuilder::new(party, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, party, note, Some(&payout))
+            .await?;
+        Ok((report, payout))
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the escrow itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("escrow note script does not compile");
+        Ok(script)
+    }
+
+    /// Sends `note` from `sender` and has the escrow consume it right away.
+    async fn send_and_consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        note: Note,
+        payout: Option<&Note>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, sender, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .expected_output_recipients(
+                payout
+                    .map(|payout| payout.recipient().clone())
+                    .into_iter()
+                    .collect(),
+            )
+            .build()?;
+        submit_and_wait(client, self.account_id, consume_request).await
+    }
+}
@@ -0,0 +1,538 @@
+//! Index of every MASM contract, note and script in `masm/`.
+//!
+//! Each [`Entry`] records what a file is for and which tutorials use it. What
+//! the file exports, which storage slots it names and which modules it uses is
+//! read from the source itself with [`Details::parse`], so the index cannot
+//! drift from the code. `cargo run --bin gallery -- list` prints it, and
+//! `tests/gallery.rs` fails when a MASM file is added without an entry.
+
+use std::{fmt, fs, io, path::Path};
+
+/// Directory the entry paths are relative to, as seen from `rust-client`.
+pub const MASM_DIR: &str = "../masm";
+
+/// What a MASM file compiles to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    /// An account component, deployed with `ContractDeployer`.
+    Account,
+    /// A note script.
+    Note,
+    /// A transaction script.
+    Script,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Kind::Account => "account",
+            Kind::Note => "note",
+            Kind::Script => "script",
+        })
+    }
+}
+
+/// One MASM file and the tutorials that show it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// Path below [`MASM_DIR`].
+    pub path: &'static str,
+    pub purpose: &'static str,
+    /// Binaries in `src/bin` using the file, directly or through a library module.
+    pub tutorials: &'static [&'static str],
+}
+
+impl Entry {
+    /// File name without the `.masm` extension.
+    pub fn name(&self) -> &'static str {
+        let file = self.path.rsplit('/').next().unwrap_or(self.path);
+        file.strip_suffix(".masm").unwrap_or(file)
+    }
+
+    pub fn kind(&self) -> Kind {
+        if self.path.starts_with("notes/") {
+            Kind::Note
+        } else if self.path.starts_with("scripts/") {
+            Kind::Script
+        } else {
+            Kind::Account
+        }
+    }
+
+    /// Reads the file below `masm_dir` and parses its details.
+    pub fn details(&self, masm_dir: impl AsRef<Path>) -> io::Result<Details> {
+        let source = fs::read_to_string(masm_dir.as_ref().join(self.path))?;
+        Ok(Details::parse(&source))
+    }
+}
+
+/// What a MASM file exports, stores and uses, as written in its source.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Details {
+    /// Names of the `pub proc`s.
+    pub exports: Vec<String>,
+    /// Names of the storage slots declared with `word("...")`.
+    pub slots: Vec<String>,
+    /// Modules pulled in with `use`, without their alias.
+    pub imports: Vec<String>,
+}
+
+impl Details {
+    pub fn parse(source: &str) -> Self {
+        let mut details = Details::default();
+        for line in source.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("pub proc ") {
+                let name = rest.split_whitespace().next().unwrap_or(rest);
+                details.exports.push(name.to_string());
+            } else if let Some(rest) = line.strip_prefix("use ") {
+                let module = rest.split("->").next().unwrap_or(rest).trim();
+                details.imports.push(module.to_string());
+            } else if line.starts_with("const ") {
+                if let Some(slot) = line
+                    .split_once("word(\"")
+                    .and_then(|(_, rest)| rest.split_once("\")"))
+                {
+                    details.slots.push(slot.0.to_string());
+                }
+            }
+        }
+        details
+    }
+
+    /// Whether the file uses a module whose path ends in `module`, e.g. `output_note`.
+    pub fn uses(&self, module: &str) -> bool {
+        self.imports
+            .iter()
+            .any(|import| import == module || import.ends_with(&format!("::{module}")))
+    }
+}
+
+/// Looks an entry up by its name, e.g. `counter` or `swap_note`.
+pub fn find(name: &str) -> Option<&'static Entry> {
+    ENTRIES.iter().find(|entry| entry.name() == name)
+}
+
+/// Every MASM file in the repo, accounts first, then notes, then scripts.
+pub const ENTRIES: &[Entry] = &[
+    Entry {
+        path: "accounts/allowance_book.masm",
+        purpose: "Records what the owner approved each spender to take",
+        tutorials: &["token_allowance"],
+    },
+    Entry {
+        path: "accounts/allowance_vault.masm",
+        purpose: "Holds the owner's tokens for spenders to pull, checked against the book via FPI",
+        tutorials: &["token_allowance"],
+    },
+    Entry {
+        path: "accounts/auth/no_auth.masm",
+        purpose: "Auth procedure that only bumps the nonce, for contracts anyone may call",
+        tutorials: &[],
+    },
+    Entry {
+        path: "accounts/auction.masm",
+        purpose: "Network-account auction keeping the highest bid in a storage map",
+        tutorials: &["auction_contract"],
+    },
+    Entry {
+        path: "accounts/bank.masm",
+        purpose: "Bank holding per-user balances of one accepted token in a storage map",
+        tutorials: &["bank_contract"],
+    },
+    Entry {
+        path: "accounts/count_reader.masm",
+        purpose: "Copies another account's count into its own storage with a foreign call",
+        tutorials: &["counter_contract_fpi", "partial_account_fpi"],
+    },
+    Entry {
+        path: "accounts/counter.masm",
+        purpose: "Counter stored in a value slot, read and incremented by anyone",
+        tutorials: &[
+            "counter_contract_deploy",
+            "counter_contract_increment",
+            "counter_cycle_budget",
+            "mixed_target_notes",
+            "network_note_queue",
+            "network_notes_counter_contract",
+        ],
+    },
+    Entry {
+        path: "accounts/data_root.masm",
+        purpose: "Versioned Merkle root of an off-chain dataset, published by one operator",
+        tutorials: &["data_root_commitment"],
+    },
+    Entry {
+        path: "accounts/emission_faucet.masm",
+        purpose: "Fungible faucet releasing at most a fixed number of tokens per block period",
+        tutorials: &["emission_schedule_faucet"],
+    },
+    Entry {
+        path: "accounts/escrow.masm",
+        purpose:
+            "Holds a buyer's payment until released to the seller or refunded after a deadline",
+        tutorials: &["escrow_contract"],
+    },
+    Entry {
+        path: "accounts/lottery.masm",
+        purpose: "Draws a winner from secrets players committed to by hash and then revealed",
+        tutorials: &["commit_reveal_lottery"],
+    },
+    Entry {
+        path: "accounts/loyalty_program.masm",
+        purpose: "Maps each points faucet to the block its points expire at",
+        tutorials: &["loyalty_points"],
+    },
+    Entry {
+        path: "accounts/mapping_example_contract.masm",
+        purpose: "Writes and reads a storage map",
+        tutorials: &["mapping_example"],
+    },
+    Entry {
+        path: "accounts/metadata.masm",
+        purpose: "Name, icon hash and URL of an account, read by other contracts",
+        tutorials: &["metadata_reader"],
+    },
+    Entry {
+        path: "accounts/name_registry.masm",
+        purpose: "Maps name hashes to the accounts owning them",
+        tutorials: &["name_registry"],
+    },
+    Entry {
+        path: "accounts/nft_faucet.masm",
+        purpose: "Non-fungible faucet minting an NFT straight into a note",
+        tutorials: &["nft_mint_transfer"],
+    },
+    Entry {
+        path: "accounts/note_order.masm",
+        purpose: "Tickets that must be opened and settled in order across notes",
+        tutorials: &["note_consumption_order"],
+    },
+    Entry {
+        path: "accounts/oracle_reader.masm",
+        purpose: "Reads a price from the Pragma oracle with a foreign procedure call",
+        tutorials: &["oracle_data_query"],
+    },
+    Entry {
+        path: "accounts/owned_counter.masm",
+        purpose: "Counter only its owner can increment, checked against the note sender",
+        tutorials: &["owned_counter"],
+    },
+    Entry {
+        path: "accounts/payment_stream.masm",
+        purpose: "Streams a deposit to a payee block by block, cancellable by the payer",
+        tutorials: &["payment_stream"],
+    },
+    Entry {
+        path: "accounts/pool.masm",
+        purpose: "Constant-product liquidity pool over two tokens with provider shares",
+        tutorials: &["amm_network_pool", "liquidity_pool"],
+    },
+    Entry {
+        path: "accounts/record_map.masm",
+        purpose: "Storage map holding one multi-field record per key",
+        tutorials: &["mapping_struct_example"],
+    },
+    Entry {
+        path: "accounts/retirement.masm",
+        purpose: "Forwarding pointer from a retired account to its successor",
+        tutorials: &["account_retirement"],
+    },
+    Entry {
+        path: "accounts/router.masm",
+        purpose: "Dispatches calls by selector to procedures of one contract",
+        tutorials: &["router_contract"],
+    },
+    Entry {
+        path: "accounts/vesting.masm",
+        purpose: "Releases a locked grant in equal shares per period of blocks",
+        tutorials: &["token_vesting"],
+    },
+    Entry {
+        path: "accounts/wrapped_token.masm",
+        purpose: "Faucet minting a wrapped token 1:1 against deposits of another token",
+        tutorials: &["wrapped_token"],
+    },
+    Entry {
+        path: "notes/allowance_approve_note.masm",
+        purpose: "Sets a spender's allowance in the book, from the owner only",
+        tutorials: &["token_allowance"],
+    },
+    Entry {
+        path: "notes/allowance_deposit_note.masm",
+        purpose: "Deposits the note asset into the allowance vault",
+        tutorials: &["token_allowance"],
+    },
+    Entry {
+        path: "notes/allowance_spend_note.masm",
+        purpose: "Pulls tokens from the allowance vault into a P2ID note for the sender",
+        tutorials: &["token_allowance"],
+    },
+    Entry {
+        path: "notes/auction_bid_note.masm",
+        purpose: "Bids its asset in the auction, or returns it to the bidder",
+        tutorials: &["auction_contract"],
+    },
+    Entry {
+        path: "notes/auction_settle_note.masm",
+        purpose: "Settles the auction, paying the highest bid to the seller",
+        tutorials: &["auction_contract"],
+    },
+    Entry {
+        path: "notes/bank_deposit_note.masm",
+        purpose: "Deposits the note asset into the bank for the sender",
+        tutorials: &["bank_contract"],
+    },
+    Entry {
+        path: "notes/bank_withdraw_note.masm",
+        purpose: "Pays part of the sender's bank balance out in a new note",
+        tutorials: &["bank_contract"],
+    },
+    Entry {
+        path: "notes/burn_note.masm",
+        purpose: "Burns its asset in the issuing faucet, lowering the token's issuance",
+        tutorials: &["fungible_burn"],
+    },
+    Entry {
+        path: "notes/chat_message_note.masm",
+        purpose: "Zero-asset note carrying a text message in its inputs",
+        tutorials: &["note_chat"],
+    },
+    Entry {
+        path: "notes/data_root_update_note.masm",
+        purpose: "Publishes a new data root to the contract consuming it",
+        tutorials: &["data_root_commitment"],
+    },
+    Entry {
+        path: "notes/escrow_claim_note.masm",
+        purpose: "Has the seller claim released escrow funds",
+        tutorials: &["escrow_contract"],
+    },
+    Entry {
+        path: "notes/escrow_lock_note.masm",
+        purpose: "Locks the note asset in the escrow for the buyer",
+        tutorials: &["escrow_contract"],
+    },
+    Entry {
+        path: "notes/escrow_refund_note.masm",
+        purpose: "Returns unreleased escrow funds to the buyer after the deadline",
+        tutorials: &["escrow_contract"],
+    },
+    Entry {
+        path: "notes/escrow_release_note.masm",
+        purpose: "Releases the escrow funds to the seller",
+        tutorials: &["escrow_contract"],
+    },
+    Entry {
+        path: "notes/expiring_note.masm",
+        purpose: "Note that can no longer be consumed after an expiry block",
+        tutorials: &["expiring_note"],
+    },
+    Entry {
+        path: "notes/hash_preimage_note.masm",
+        purpose: "Releases its asset to whoever knows the preimage of a hash",
+        tutorials: &["hash_preimage_note"],
+    },
+    Entry {
+        path: "notes/htlc_note.masm",
+        purpose: "Hashed timelock: the recipient claims with the preimage, the sender refunds after the deadline",
+        tutorials: &["htlc_swap"],
+    },
+    Entry {
+        path: "notes/iterative_output_note.masm",
+        purpose: "Note that creates its own successor with half the asset",
+        tutorials: &["note_creation_in_masm"],
+    },
+    Entry {
+        path: "notes/large_payload_note.masm",
+        purpose: "Loads a payload too large for note inputs from the advice map",
+        tutorials: &["note_inputs_limits"],
+    },
+    Entry {
+        path: "notes/lottery_claim_note.masm",
+        purpose: "Claims the lottery pot for the winner into a P2ID note",
+        tutorials: &["commit_reveal_lottery"],
+    },
+    Entry {
+        path: "notes/lottery_commit_note.masm",
+        purpose: "Stakes the carried asset on the hash of a secret",
+        tutorials: &["commit_reveal_lottery"],
+    },
+    Entry {
+        path: "notes/lottery_reveal_note.masm",
+        purpose: "Reveals the secret behind the sender's lottery commitment",
+        tutorials: &["commit_reveal_lottery"],
+    },
+    Entry {
+        path: "notes/loyalty_points_note.masm",
+        purpose: "Points the holder claims before expiry and the issuer reclaims after",
+        tutorials: &["loyalty_points"],
+    },
+    Entry {
+        path: "notes/loyalty_redeem_note.masm",
+        purpose: "Spends unexpired points at a shop, checking the expiry via FPI",
+        tutorials: &["loyalty_points"],
+    },
+    Entry {
+        path: "notes/loyalty_register_note.masm",
+        purpose: "Registers the expiry of a points faucet with the loyalty program",
+        tutorials: &["loyalty_points"],
+    },
+    Entry {
+        path: "notes/name_register_note.masm",
+        purpose: "Registers a free name to the note sender",
+        tutorials: &["name_registry"],
+    },
+    Entry {
+        path: "notes/name_transfer_note.masm",
+        purpose: "Hands a name from its owner to a new owner",
+        tutorials: &["name_registry"],
+    },
+    Entry {
+        path: "notes/network_increment_note.masm",
+        purpose: "Increments the counter when consumed by a network transaction",
+        tutorials: &[
+            "mixed_target_notes",
+            "network_note_queue",
+            "network_notes_counter_contract",
+        ],
+    },
+    Entry {
+        path: "notes/note_order_open_note.masm",
+        purpose: "Opens a ticket on the note order contract",
+        tutorials: &["note_consumption_order"],
+    },
+    Entry {
+        path: "notes/note_order_settle_note.masm",
+        purpose: "Settles a ticket on the note order contract",
+        tutorials: &["note_consumption_order"],
+    },
+    Entry {
+        path: "notes/owned_increment_note.masm",
+        purpose: "Asks the owned counter to increment on behalf of the note sender",
+        tutorials: &["owned_counter"],
+    },
+    Entry {
+        path: "notes/partial_fill_note.masm",
+        purpose: "Order that can be filled in parts, sending the remainder in a new note",
+        tutorials: &["partial_fill_note"],
+    },
+    Entry {
+        path: "notes/payment_with_ack_note.masm",
+        purpose: "Payment that sends an acknowledgment note back to the payer",
+        tutorials: &["payment_with_acknowledgment"],
+    },
+    Entry {
+        path: "notes/pool_add_liquidity_note.masm",
+        purpose: "Adds two tokens to the pool in exchange for shares",
+        tutorials: &["amm_network_pool", "liquidity_pool"],
+    },
+    Entry {
+        path: "notes/pool_remove_liquidity_note.masm",
+        purpose: "Burns pool shares and pays both tokens out",
+        tutorials: &["liquidity_pool"],
+    },
+    Entry {
+        path: "notes/pool_swap_note.masm",
+        purpose: "Swaps the note asset for the pool's other token",
+        tutorials: &["amm_network_pool", "liquidity_pool"],
+    },
+    Entry {
+        path: "notes/router_call_note.masm",
+        purpose: "Forwards the call encoded in its inputs to the router",
+        tutorials: &["router_contract"],
+    },
+    Entry {
+        path: "notes/stream_cancel_note.masm",
+        purpose: "Cancels a payment stream and refunds the payer into a P2ID note",
+        tutorials: &["payment_stream"],
+    },
+    Entry {
+        path: "notes/stream_fund_note.masm",
+        purpose: "Locks the carried asset as a payment stream's deposit",
+        tutorials: &["payment_stream"],
+    },
+    Entry {
+        path: "notes/stream_withdraw_note.masm",
+        purpose: "Withdraws streamed tokens for the payee into a P2ID note",
+        tutorials: &["payment_stream"],
+    },
+    Entry {
+        path: "notes/swap_note.masm",
+        purpose: "Trades the note asset for a requested asset in one transaction",
+        tutorials: &["order_book", "swap_note"],
+    },
+    Entry {
+        path: "notes/unwrap_note.masm",
+        purpose: "Burns wrapped tokens for the underlying ones, paid into a P2ID note",
+        tutorials: &["wrapped_token"],
+    },
+    Entry {
+        path: "notes/vesting_claim_note.masm",
+        purpose: "Claims an amount of vested tokens for the beneficiary",
+        tutorials: &["token_vesting"],
+    },
+    Entry {
+        path: "notes/vesting_fund_note.masm",
+        purpose: "Locks the note asset as the grant of a vesting contract",
+        tutorials: &["token_vesting"],
+    },
+    Entry {
+        path: "notes/wrap_note.masm",
+        purpose: "Deposits the underlying token for wrapped ones, paid into a P2ID note",
+        tutorials: &["wrapped_token"],
+    },
+    Entry {
+        path: "scripts/auction_start_script.masm",
+        purpose: "Opens the auction until an end block, deploying it",
+        tutorials: &["auction_contract"],
+    },
+    Entry {
+        path: "scripts/counter_script.masm",
+        purpose: "Increments the counter from a transaction script",
+        tutorials: &[
+            "counter_contract_deploy",
+            "counter_contract_increment",
+            "mixed_target_notes",
+            "network_notes_counter_contract",
+        ],
+    },
+    Entry {
+        path: "scripts/emission_mint_script.masm",
+        purpose: "Mints tokens into a note through the emission faucet",
+        tutorials: &["emission_schedule_faucet"],
+    },
+    Entry {
+        path: "scripts/mapping_example_script.masm",
+        purpose: "Writes a map entry and reads it back",
+        tutorials: &["mapping_example"],
+    },
+    Entry {
+        path: "scripts/nft_mint_script.masm",
+        purpose: "Mints an NFT into a note through the NFT faucet",
+        tutorials: &["nft_mint_transfer"],
+    },
+    Entry {
+        path: "scripts/oracle_reader_script.masm",
+        purpose: "Reads the oracle price through the oracle reader",
+        tutorials: &["oracle_data_query"],
+    },
+    Entry {
+        path: "scripts/reader_script.masm",
+        purpose: "Has the count reader copy a foreign account's count",
+        tutorials: &[
+            "counter_contract_fpi",
+            "oracle_data_query",
+            "partial_account_fpi",
+        ],
+    },
+    Entry {
+        path: "scripts/record_map_script.masm",
+        purpose: "Stores a record and reads it back",
+        tutorials: &["mapping_struct_example"],
+    },
+    Entry {
+        path: "scripts/retirement_script.masm",
+        purpose: "Points a retiring account at its successor",
+        tutorials: &["account_retirement"],
+    },
+];
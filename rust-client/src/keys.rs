@@ -0,0 +1,73 @@
+//! File format for keys generated outside the client.
+//!
+//! A key file is JSON holding the scheme, the public key commitment and the
+//! hex-encoded secret key. The commitment lets the online machine check the file
+//! before anything is imported.
+
+use std::{fs, path::Path};
+
+use miden_client::{
+    auth::AuthSecretKey,
+    utils::{Deserializable, Serializable},
+    Word,
+};
+use serde::{Deserialize, Serialize};
+
+/// The only scheme the tutorials generate keys for.
+pub const FALCON512_RPO: &str = "falcon512_rpo";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyFile {
+    pub scheme: String,
+    /// Hex of the public key commitment the account's auth component is built with.
+    pub public_key_commitment: String,
+    /// Hex of the serialized secret key.
+    pub secret_key: String,
+}
+
+impl KeyFile {
+    pub fn from_secret_key(secret_key: &AuthSecretKey) -> Self {
+        let commitment: Word = secret_key.public_key().to_commitment().into();
+        Self {
+            scheme: FALCON512_RPO.to_string(),
+            public_key_commitment: commitment.to_hex(),
+            secret_key: to_hex(&secret_key.to_bytes()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Decodes the secret key, checking it against the recorded commitment.
+    pub fn secret_key(&self) -> Result<AuthSecretKey, Box<dyn std::error::Error>> {
+        if self.scheme != FALCON512_RPO {
+            return Err(format!("unsupported key scheme '{}'", self.scheme).into());
+        }
+        let secret_key = AuthSecretKey::read_from_bytes(&from_hex(&self.secret_key)?)?;
+        let commitment: Word = secret_key.public_key().to_commitment().into();
+        if commitment.to_hex() != self.public_key_commitment {
+            return Err("secret key does not match the public key commitment".into());
+        }
+        Ok(secret_key)
+    }
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub(crate) fn from_hex(hex: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if hex.len() % 2 != 0 {
+        return Err("hex string has an odd length".into());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| Ok(u8::from_str_radix(&hex[i..i + 2], 16)?))
+        .collect()
+}
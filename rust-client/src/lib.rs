@@ -0,0 +1,80 @@
+//! Helpers shared by the tutorial binaries in `src/bin`.
+//!
+//! Binaries import these instead of copying them:
+//!
+//! - [`client::init_client_from_config`] builds the client and its keystore from
+//!   the command line and the [`config`] file; [`client::ClientSetup`] tweaks it.
+//! - [`accounts::create_basic_account`] and [`accounts::create_basic_faucet`] deploy
+//!   the wallet and `MID` faucet most tutorials start from,
+//!   [`accounts::get_full_account`] reads a tracked account back,
+//!   [`accounts::balance`] reads its balance in one token and
+//!   [`accounts::collect`] consumes a payout note and returns the new balance.
+//! - [`deployer::ContractDeployer`] compiles a MASM component and deploys the
+//!   account around it.
+//! - [`contracts`] deploys the counter, mapping, count reader and oracle reader
+//!   contracts as fixtures, into a client or a `MockChain`.
+//! - [`note_builder::NoteBuilder`] builds custom notes, taking serial numbers
+//!   from a [`note_builder::SerialNumberPolicy`].
+//! - [`masm::create_library`] and [`masm::MasmFile`] compile MASM sources.
+//! - [`wait::submit_and_wait`], [`wait::wait_for_tx`] and [`wait::wait_for`]
+//!   submit transactions and wait for their effects, and
+//!   [`wait::wait_for_block`] waits for the chain to reach a block.
+//! - [`say!`] prints narration and [`output::emit`] the results that `--json`
+//!   hands to scripts.
+//! - [`error::report`] turns the result of a binary into an exit code per
+//!   [`error::TutorialError`] class, after printing the [`session`] summary.
+
+pub mod accounts;
+pub mod actor;
+pub mod address_book;
+pub mod airdrop;
+pub mod aliases;
+pub mod allowance;
+pub mod auction;
+pub mod bank;
+pub mod benchmark;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod chat;
+pub mod client;
+pub mod code_report;
+pub mod config;
+pub mod contracts;
+pub mod cycles;
+pub mod data_dir;
+pub mod data_root;
+pub mod deployer;
+pub mod deployments;
+pub mod endpoints;
+pub mod error;
+pub mod escrow;
+pub mod gallery;
+pub mod keys;
+pub mod lottery;
+pub mod loyalty;
+pub mod masm;
+pub mod memory_store;
+pub mod metadata;
+pub mod middleware;
+pub mod name_registry;
+pub mod network;
+pub mod network_queue;
+pub mod note_builder;
+pub mod notes;
+pub mod order_book;
+pub mod output;
+pub mod payment_stream;
+pub mod pool;
+pub mod profiles;
+pub mod proving;
+pub mod reset;
+pub mod run_mode;
+pub mod session;
+pub mod storage;
+pub mod storage_proof;
+pub mod store_encryption;
+pub mod stress;
+pub mod vectors;
+pub mod vesting;
+pub mod wait;
+pub mod wrapped_token;
@@ -0,0 +1,331 @@
+//! SDK-style wrapper around the commit-reveal lottery in `masm/accounts/lottery.masm`.
+//!
+//! Players stake the same asset on a commitment, the hash of a secret, as the
+//! hash preimage note does with its digest. Once commitments close each player
+//! reveals their secret, and the numbers of the revealed secrets add up to the
+//! seed that picks the winner among those who revealed. No secret can be chosen
+//! after seeing another, so nobody steers the seed while committing. The winner
+//! claims the whole pot, forfeited stakes included, with a note of their own.
+
+use miden_client::{
+    account::{AccountId, StorageMap, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::{Asset, FungibleAsset},
+    crypto::FeltRng,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+use miden_protocol::Hasher;
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const TERMS_SLOT: &str = "miden::tutorials::lottery::terms";
+pub const STAKE_SLOT: &str = "miden::tutorials::lottery::stake";
+pub const STATE_SLOT: &str = "miden::tutorials::lottery::state";
+pub const COMMITMENTS_SLOT: &str = "miden::tutorials::lottery::commitments";
+pub const REVEALERS_SLOT: &str = "miden::tutorials::lottery::revealers";
+
+/// Library path the lottery procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::lottery_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/lottery.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/lottery.masm");
+const COMMIT_NOTE_FILE: &str = "masm/notes/lottery_commit_note.masm";
+const COMMIT_NOTE_CODE: &str = include_str!("../../masm/notes/lottery_commit_note.masm");
+const REVEAL_NOTE_FILE: &str = "masm/notes/lottery_reveal_note.masm";
+const REVEAL_NOTE_CODE: &str = include_str!("../../masm/notes/lottery_reveal_note.masm");
+const CLAIM_NOTE_FILE: &str = "masm/notes/lottery_claim_note.masm";
+const CLAIM_NOTE_CODE: &str = include_str!("../../masm/notes/lottery_claim_note.masm");
+
+/// A player's secret: the number that goes into the seed, and a salt that
+/// keeps the commitment from being brute-forced over all u32 numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secret {
+    pub number: u32,
+    pub salt: [Felt; 3],
+}
+
+impl Secret {
+    pub fn random(rng: &mut impl FeltRng) -> Self {
+        let salt = rng.draw_word();
+        Self {
+            number: rng.draw_element().as_int() as u32,
+            salt: [salt[0], salt[1], salt[2]],
+        }
+    }
+
+    /// The secret as `[number, salt, salt, salt]` on the stack, so the number
+    /// is the last element of the word.
+    pub fn word(&self) -> Word {
+        Word::from([
+            self.salt[0],
+            self.salt[1],
+            self.salt[2],
+            Felt::new(self.number as u64),
+        ])
+    }
+
+    /// The hash the player commits to, which the contract recomputes on reveal.
+    pub fn commitment(&self) -> Word {
+        Hasher::hash_elements(self.word().as_elements())
+    }
+}
+
+/// Progress of the draw, as stored by the contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LotteryState {
+    pub committed: u64,
+    pub revealed: u64,
+    pub seed: u32,
+    pub paid: bool,
+}
+
+impl LotteryState {
+    /// The seed after revealing `numbers`, a wrapping u32 sum like the contract's.
+    pub fn seed_of(numbers: impl IntoIterator<Item = u32>) -> u32 {
+        numbers.into_iter().fold(0, u32::wrapping_add)
+    }
+
+    /// Index of the winner among the revealers, or `None` while nobody revealed.
+    pub fn winner_index(&self) -> Option<u64> {
+        (self.revealed > 0).then(|| self.seed as u64 % self.revealed)
+    }
+}
+
+/// A deployed lottery.
+pub struct Lottery {
+    pub account_id: AccountId,
+    /// What every player stakes on their commitment.
+    pub stake: FungibleAsset,
+    /// First block at which commitments are no longer taken and secrets can be revealed.
+    pub commit_end: u32,
+    /// First block at which secrets are no longer taken and the winner can claim.
+    pub reveal_end: u32,
+    library: Library,
+}
+
+impl Lottery {
+    /// Builds a new lottery and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the first commitment it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        stake: FungibleAsset,
+        commit_end: u32,
+        reveal_end: u32,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if reveal_end <= commit_end {
+            return Err("the reveal phase must end after the commit phase".into());
+        }
+        let terms = Word::from([
+            Felt::new(0),
+            Felt::new(0),
+            Felt::new(reveal_end as u64),
+            Felt::new(commit_end as u64),
+        ]);
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(TERMS_SLOT)?, terms),
+            StorageSlot::with_value(
+                StorageSlotName::new(STAKE_SLOT)?,
+                Word::from(Asset::from(stake)),
+            ),
+            StorageSlot::with_value(StorageSlotName::new(STATE_SLOT)?, Word::default()),
+            StorageSlot::with_map(
+                StorageSlotName::new(COMMITMENTS_SLOT)?,
+                StorageMap::with_entries([])?,
+            ),
+            StorageSlot::with_map(
+                StorageSlotName::new(REVEALERS_SLOT)?,
+                StorageMap::with_entries([])?,
+            ),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            stake,
+            commit_end,
+            reveal_end,
+            library,
+        })
+    }
+
+    /// Stakes `player`'s tokens on the commitment to `secret`. Only the hash leaves the client.
+    pub async fn commit(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        player: AccountId,
+        secret: &Secret,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(COMMIT_NOTE_FILE, COMMIT_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(player, script)
+            .inputs(secret.commitment().to_vec())
+            .asset(self.stake)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, player, note, None).await
+    }
+
+    /// Reveals `player`'s secret, which the contract checks against the commitment.
+    pub async fn reveal(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        player: AccountId,
+        secret: &Secret,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(REVEAL_NOTE_FILE, REVEAL_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(player, script)
+            .inputs(secret.word().to_vec())
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, player, note, None).await
+    }
+
+    /// Has `player` claim the pot and returns the P2ID note paying it.
+    ///
+    /// The pot holds every committed stake, as of the last sync. Fails on-chain
+    /// unless `player` won.
+    pub async fn claim_prize(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        player: AccountId,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let committed = self.state(client).await?.committed;
+        let pot = FungibleAsset::new(self.stake.faucet_id(), self.stake.amount() * committed)?;
+        let payout = create_p2id_note(
+            self.account_id,
+            player,
+            vec![pot.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        let script =
+            self.note_script(client, MasmFile::embedded(CLAIM_NOTE_FILE, CLAIM_NOTE_CODE))?;
+        let note = NoteBuilder::new(player, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, player, note, Some(&payout))
+            .await?;
+        Ok((report, payout))
+    }
+
+    /// Returns the progress of the draw, as of the last sync.
+    pub async fn state(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<LotteryState, ClientError> {
+        let lottery = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(STATE_SLOT).expect("valid slot name");
+        let state = lottery
+            .storage()
+            .get_item(&slot)
+            .expect("lottery account is missing its state slot");
+        // Stored as [committed, revealed, seed, paid] on the stack, so reversed in the word
+        Ok(LotteryState {
+            committed: state[3].as_int(),
+            revealed: state[2].as_int(),
+            seed: state[1].as_int() as u32,
+            paid: state[0].as_int() != 0,
+        })
+    }
+
+    /// Returns the player who revealed `index`-th, as of the last sync.
+    pub async fn revealer(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        index: u64,
+    ) -> Result<Option<AccountId>, ClientError> {
+        let lottery = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(REVEALERS_SLOT).expect("valid slot name");
+        let key = Word::from([Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(index)]);
+        let player = lottery
+            .storage()
+            .get_map_item(&slot, key)
+            .expect("lottery account is missing its revealers slot");
+        if player == Word::default() {
+            return Ok(None);
+        }
+        // Stored as [player_prefix, player_suffix, 0, 0] on the stack
+        let player =
+            AccountId::try_from([player[3], player[2]]).expect("the lottery stores valid IDs");
+        Ok(Some(player))
+    }
+
+    /// Returns the winner the revealed secrets pick, as of the last sync.
+    pub async fn winner(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Option<AccountId>, ClientError> {
+        match self.state(client).await?.winner_index() {
+            Some(index) => self.revealer(client, index).await,
+            None => Ok(None),
+        }
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the lottery itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("lottery note script does not compile");
+        Ok(script)
+    }
+
+    /// Sends `note` from `player` and has the lottery consume it right away.
+    async fn send_and_consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        player: AccountId,
+        note: Note,
+        payout: Option<&Note>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, player, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .expected_output_recipients(
+                payout
+                    .map(|payout| payout.recipient().clone())
+                    .into_iter()
+                    .collect(),
+            )
+            .build()?;
+        submit_and_wait(client, self.account_id, consume_request).await
+    }
+}
@@ -0,0 +1,253 @@
+//! SDK-style wrapper around the loyalty program in `masm/accounts/loyalty_program.masm`.
+//!
+//! Points are a fungible asset, one faucet per season, and the program maps
+//! each faucet to the block its points expire at. The issuer sends points to a
+//! holder in a points note, which the holder can claim until the expiry and the
+//! issuer can reclaim from then on. Holders spend claimed points by sending a
+//! redemption note to a shop. Both notes read the expiry from the program with
+//! a foreign procedure invocation, since neither is consumed by the program.
+
+use miden_client::{
+    account::{AccountId, StorageMap, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::{Asset, FungibleAsset},
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteScript},
+    rpc::domain::account::{AccountStorageRequirements, StorageMapKey},
+    transaction::{ForeignAccount, OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const ISSUER_SLOT: &str = "miden::tutorials::loyalty::issuer";
+pub const EXPIRIES_SLOT: &str = "miden::tutorials::loyalty::expiries";
+
+/// Library path the loyalty program procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::loyalty_program";
+
+const COMPONENT_FILE: &str = "masm/accounts/loyalty_program.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/loyalty_program.masm");
+const REGISTER_NOTE_FILE: &str = "masm/notes/loyalty_register_note.masm";
+const REGISTER_NOTE_CODE: &str = include_str!("../../masm/notes/loyalty_register_note.masm");
+const POINTS_NOTE_FILE: &str = "masm/notes/loyalty_points_note.masm";
+const POINTS_NOTE_CODE: &str = include_str!("../../masm/notes/loyalty_points_note.masm");
+const REDEEM_NOTE_FILE: &str = "masm/notes/loyalty_redeem_note.masm";
+const REDEEM_NOTE_CODE: &str = include_str!("../../masm/notes/loyalty_redeem_note.masm");
+
+/// A deployed loyalty program.
+pub struct LoyaltyProgram {
+    pub account_id: AccountId,
+    /// The only account that can register points and reclaim expired ones.
+    pub issuer: AccountId,
+    library: Library,
+}
+
+impl LoyaltyProgram {
+    /// Builds a new program run by `issuer` and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the first points it registers.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        issuer: AccountId,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let issuer_word = Word::from([
+            Felt::new(0),
+            Felt::new(0),
+            issuer.suffix(),
+            issuer.prefix().as_felt(),
+        ]);
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(ISSUER_SLOT)?, issuer_word),
+            StorageSlot::with_map(
+                StorageSlotName::new(EXPIRIES_SLOT)?,
+                StorageMap::with_entries([])?,
+            ),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            issuer,
+            library,
+        })
+    }
+
+    /// Sends a note from `sender` registering the expiry of the points of `faucet_id`,
+    /// and has the program consume it. The program only accepts it from the issuer.
+    pub async fn register(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        faucet_id: AccountId,
+        expiry: u32,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(REGISTER_NOTE_FILE, REGISTER_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(sender, script)
+            .inputs([
+                faucet_id.prefix().as_felt(),
+                faucet_id.suffix(),
+                Felt::new(expiry as u64),
+            ])
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, sender, &note).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .build()?;
+        submit_and_wait(client, self.account_id, consume_request).await
+    }
+
+    /// Sends `points` from the issuer to `holder` and returns the points note.
+    pub async fn issue(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        points: FungibleAsset,
+        holder: AccountId,
+    ) -> Result<Note, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(POINTS_NOTE_FILE, POINTS_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(self.issuer, script)
+            .inputs([
+                self.account_id.prefix().as_felt(),
+                self.account_id.suffix(),
+                holder.prefix().as_felt(),
+                holder.suffix(),
+                self.issuer.prefix().as_felt(),
+                self.issuer.suffix(),
+            ])
+            .asset(points)
+            .target(holder)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, self.issuer, &note).await?;
+        Ok(note)
+    }
+
+    /// Sends `points` from `holder` to `shop` in a redemption note and returns it.
+    pub async fn redeem(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        holder: AccountId,
+        points: FungibleAsset,
+        shop: AccountId,
+    ) -> Result<Note, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(REDEEM_NOTE_FILE, REDEEM_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(holder, script)
+            .inputs([
+                self.account_id.prefix().as_felt(),
+                self.account_id.suffix(),
+                shop.prefix().as_felt(),
+                shop.suffix(),
+            ])
+            .asset(points)
+            .target(shop)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send(client, holder, &note).await?;
+        Ok(note)
+    }
+
+    /// Has `consumer` consume a points or redemption note.
+    ///
+    /// The note looks up the expiry of its points in the program, so the
+    /// transaction carries the program as a foreign account, with a proof of
+    /// the one map entry the note reads.
+    pub async fn consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        consumer: AccountId,
+        note: Note,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let faucet_id = match note.assets().iter().next() {
+            Some(Asset::Fungible(points)) => points.faucet_id(),
+            _ => return Err("loyalty notes carry one fungible asset".into()),
+        };
+        let keys: [StorageMapKey; 1] = [faucet_key(faucet_id).into()];
+        let storage_requirements =
+            AccountStorageRequirements::new([(StorageSlotName::new(EXPIRIES_SLOT)?, keys.iter())]);
+        let program = ForeignAccount::public(self.account_id, storage_requirements)?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .foreign_accounts([program])
+            .build()?;
+        Ok(submit_and_wait(client, consumer, consume_request).await?)
+    }
+
+    /// Returns the expiry block of the points of `faucet_id`, as of the last
+    /// sync, or `None` when they were never registered.
+    pub async fn expiry(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        faucet_id: AccountId,
+    ) -> Result<Option<u32>, ClientError> {
+        let program = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(EXPIRIES_SLOT).expect("valid slot name");
+        let expiry = program
+            .storage()
+            .get_map_item(&slot, faucet_key(faucet_id))
+            .expect("loyalty program is missing its expiries slot");
+        // The expiry sits on top of the stack, which is the last element of the word
+        Ok(match expiry[3].as_int() {
+            0 => None,
+            block => Some(block as u32),
+        })
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the program itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("loyalty note script does not compile");
+        Ok(script)
+    }
+
+    async fn send(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        note: &Note,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        Ok(submit_and_wait(client, sender, note_request).await?)
+    }
+}
+
+/// Map key of a faucet's expiry, `[faucet_prefix, faucet_suffix, 0, 0]` on the stack.
+fn faucet_key(faucet_id: AccountId) -> Word {
+    Word::from([
+        Felt::new(0),
+        Felt::new(0),
+        faucet_id.suffix(),
+        faucet_id.prefix().as_felt(),
+    ])
+}
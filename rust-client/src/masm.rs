@@ -0,0 +1,185 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use miden_client::{
+    assembly::{
+        diagnostics::{Diagnostic, LabeledSpan, Report},
+        DefaultSourceManager, Library, Module, ModuleKind, Path as AssemblyPath,
+    },
+    transaction::TransactionKernel,
+    Felt, Word,
+};
+
+/// Creates a Miden library from the provided account code and library path.
+pub fn create_library(
+    library_path: &str,
+    source_code: &str,
+) -> Result<Library, Box<dyn std::error::Error>> {
+    Ok(MasmFile::embedded(library_path, source_code).library(library_path)?)
+}
+
+/// Formats a word so it can be substituted into a `push.{word}` placeholder.
+pub fn word_to_masm(word: Word) -> String {
+    word.iter()
+        .map(|f: &Felt| f.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// A MASM source file, kept around so compile errors can point into it.
+///
+/// Wrap every compile call in [`MasmFile::check`]: on failure the error shows
+/// the file name, line and column and an excerpt of the offending source.
+#[derive(Debug, Clone)]
+pub struct MasmFile {
+    pub path: PathBuf,
+    pub source: String,
+}
+
+impl MasmFile {
+    /// Reads a MASM file from disk.
+    pub fn read(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let source = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+        Self { path, source }
+    }
+
+    /// Wraps source that is not read from disk, e.g. pulled in with `include_str!`.
+    pub fn embedded(name: impl Into<PathBuf>, source: impl Into<String>) -> Self {
+        Self {
+            path: name.into(),
+            source: source.into(),
+        }
+    }
+
+    /// The same file with its source replaced, e.g. after filling in template placeholders.
+    pub fn with_source(&self, source: impl Into<String>) -> Self {
+        Self {
+            path: self.path.clone(),
+            source: source.into(),
+        }
+    }
+
+    /// Assembles the file into a library exported under `library_path`.
+    pub fn library(&self, library_path: &str) -> Result<Library, MasmError> {
+        let assembler = TransactionKernel::assembler();
+        let source_manager = Arc::new(DefaultSourceManager::default());
+        let module = Module::parser(ModuleKind::Library)
+            .parse_str(
+                AssemblyPath::new(library_path),
+                &self.source,
+                source_manager.clone(),
+            )
+            .map_err(|report| self.error(report))?;
+        assembler
+            .assemble_library([module])
+            .map_err(|report| self.error(report))
+    }
+
+    /// Attaches this file's source to the error of a compile call made with it.
+    pub fn check<T, E: Into<Report>>(&self, result: Result<T, E>) -> Result<T, MasmError> {
+        result.map_err(|err| self.error(err.into()))
+    }
+
+    fn error(&self, report: Report) -> MasmError {
+        MasmError {
+            rendered: self.render(&report),
+        }
+    }
+
+    fn render(&self, report: &Report) -> String {
+        let mut out = format!("error: {report}\n");
+        for cause in report.chain().skip(1) {
+            out.push_str(&format!("  caused by: {cause}\n"));
+        }
+
+        let mut labels = Vec::new();
+        let mut help = None;
+        collect_labels(report.as_ref(), &mut labels, &mut help);
+
+        // Labels pointing outside this file, e.g. into the kernel, are skipped
+        let labels = labels
+            .into_iter()
+            .filter(|label| label.offset() + label.len() <= self.source.len());
+        for label in labels {
+            let (line, column) = line_column(&self.source, label.offset());
+            let text = self.source.lines().nth(line - 1).unwrap_or_default();
+            let gutter = " ".repeat(line.to_string().len());
+            let width = label
+                .len()
+                .clamp(1, text.len().saturating_sub(column - 1).max(1));
+
+            out.push_str(&format!(
+                "{gutter}--> {}:{line}:{column}\n",
+                self.path.display()
+            ));
+            out.push_str(&format!("{gutter} |\n"));
+            out.push_str(&format!("{line} | {text}\n"));
+            out.push_str(&format!(
+                "{gutter} | {}{} {}\n",
+                " ".repeat(column - 1),
+                "^".repeat(width),
+                label.label().unwrap_or_default()
+            ));
+        }
+        if let Some(help) = help {
+            out.push_str(&format!("help: {help}\n"));
+        }
+        out
+    }
+}
+
+/// Gathers the source labels of a diagnostic and of everything it wraps.
+fn collect_labels(
+    diagnostic: &dyn Diagnostic,
+    labels: &mut Vec<LabeledSpan>,
+    help: &mut Option<String>,
+) {
+    if let Some(own) = diagnostic.labels() {
+        labels.extend(own);
+    }
+    if help.is_none() {
+        *help = diagnostic.help().map(|help| help.to_string());
+    }
+    if let Some(source) = diagnostic.diagnostic_source() {
+        collect_labels(source, labels, help);
+    }
+    if let Some(related) = diagnostic.related() {
+        for diagnostic in related {
+            collect_labels(diagnostic, labels, help);
+        }
+    }
+}
+
+/// One-based line and column of a byte offset.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    (line, column)
+}
+
+/// A MASM compile error rendered with its location and a source excerpt.
+pub struct MasmError {
+    rendered: String,
+}
+
+impl fmt::Display for MasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.rendered)
+    }
+}
+
+/// Same as `Display`, so that `unwrap()` prints the excerpt instead of a struct dump.
+impl fmt::Debug for MasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f)?;
+        f.write_str(&self.rendered)
+    }
+}
+
+impl std::error::Error for MasmError {}
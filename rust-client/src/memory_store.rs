@@ -0,0 +1,47 @@
+//! Throwaway stores for runs whose state is not worth keeping.
+//!
+//! `--memory-store` keeps a run from leaving `store.sqlite3` and `keystore/`
+//! in the working directory. miden-client only ships a SQLite store for native
+//! clients, and a SQLite `:memory:` database is private to the connection that
+//! opened it, so the store's connection pool cannot share one. The store and
+//! keystore go to a fresh directory under the temp directory instead, which
+//! [`remove_scratch_dirs`] deletes when the binary exits, even after a panic.
+
+use std::{fs, io, path::PathBuf, sync::Mutex};
+
+use rand::RngCore;
+
+static SCRATCH_DIRS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Creates an empty directory for one client's store and keystore and
+/// remembers it for [`remove_scratch_dirs`].
+pub fn create_scratch_dir() -> io::Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!(
+        "miden-memory-store-{}-{:016x}",
+        std::process::id(),
+        rand::rng().next_u64()
+    ));
+    fs::create_dir_all(&dir)?;
+    SCRATCH_DIRS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(dir.clone());
+    Ok(dir)
+}
+
+/// Deletes every directory made by [`create_scratch_dir`] in this process.
+///
+/// Called next to [`crate::store_encryption::seal_open_store`] by
+/// [`crate::error::report`], and by the [`crate::error::ExitGuard`] and Ctrl-C
+/// handler of a run that panics or is interrupted. A failure is printed rather
+/// than returned, since the binary is already exiting.
+pub fn remove_scratch_dirs() {
+    let dirs = std::mem::take(&mut *SCRATCH_DIRS.lock().unwrap_or_else(|err| err.into_inner()));
+    for dir in dirs {
+        if let Err(err) = fs::remove_dir_all(&dir) {
+            if err.kind() != io::ErrorKind::NotFound {
+                eprintln!("Warning: could not remove {}: {err}", dir.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Human-readable contract metadata stored in well-known storage slots.
+//!
+//! Any contract can carry the metadata component next to its own code. Explorers
+//! and the `metadata_reader` binary only need the slot names below to decode it.
+
+use miden_client::{
+    account::{Account, AccountComponent, StorageMap, StorageSlot, StorageSlotName},
+    assembly::CodeBuilder,
+    Felt, Word,
+};
+
+use crate::masm::MasmFile;
+
+pub const NAME_SLOT: &str = "miden::tutorials::metadata::name";
+pub const ICON_HASH_SLOT: &str = "miden::tutorials::metadata::icon_hash";
+pub const URL_SLOT: &str = "miden::tutorials::metadata::url";
+
+/// Library path the component procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::metadata";
+
+const COMPONENT_FILE: &str = "masm/accounts/metadata.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/metadata.masm");
+
+/// Bytes packed into one felt, small enough to always stay below the field modulus.
+const BYTES_PER_FELT: usize = 7;
+const BYTES_PER_WORD: usize = 4 * BYTES_PER_FELT;
+
+/// Name, icon hash and URL of a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractMetadata {
+    /// At most 28 bytes, so that it fits in a single word.
+    pub name: String,
+    /// Hash of the icon image, e.g. its RPO digest.
+    pub icon_hash: Word,
+    pub url: String,
+}
+
+impl ContractMetadata {
+    /// Builds the metadata component holding this metadata.
+    pub fn component(&self) -> Result<AccountComponent, Box<dyn std::error::Error>> {
+        if self.name.len() > BYTES_PER_WORD {
+            return Err(format!("name must be at most {BYTES_PER_WORD} bytes").into());
+        }
+        let name = encode_bytes(self.name.as_bytes()).pop().unwrap_or_default();
+
+        let url_words = encode_bytes(self.url.as_bytes());
+        let mut url_entries = vec![(
+            url_key(0),
+            Word::from([
+                Felt::new(self.url.len() as u64),
+                Felt::new(0),
+                Felt::new(0),
+                Felt::new(0),
+            ]),
+        )];
+        url_entries.extend(
+            url_words
+                .into_iter()
+                .enumerate()
+                .map(|(i, chunk)| (url_key(i as u64 + 1), chunk)),
+        );
+
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(NAME_SLOT)?, name),
+            StorageSlot::with_value(StorageSlotName::new(ICON_HASH_SLOT)?, self.icon_hash),
+            StorageSlot::with_map(
+                StorageSlotName::new(URL_SLOT)?,
+                StorageMap::with_entries(url_entries)?,
+            ),
+        ];
+
+        let component_code = MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE)
+            .check(CodeBuilder::new().compile_component_code(LIBRARY_PATH, COMPONENT_CODE))?;
+        Ok(AccountComponent::new(component_code, slots)?.with_supports_all_types())
+    }
+
+    /// Decodes the metadata of an account carrying the metadata component.
+    pub fn from_account(account: &Account) -> Result<Self, Box<dyn std::error::Error>> {
+        let storage = account.storage();
+        let name_word = storage.get_item(&StorageSlotName::new(NAME_SLOT)?)?;
+        let icon_hash = storage.get_item(&StorageSlotName::new(ICON_HASH_SLOT)?)?;
+
+        let url_slot = StorageSlotName::new(URL_SLOT)?;
+        let url_len = storage.get_map_item(&url_slot, url_key(0))?[0].as_int() as usize;
+        let chunks = url_len.div_ceil(BYTES_PER_WORD);
+        let url_words = (1..=chunks as u64)
+            .map(|i| storage.get_map_item(&url_slot, url_key(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut name = decode_bytes(&[name_word]);
+        while name.last() == Some(&0) {
+            name.pop();
+        }
+        let mut url = decode_bytes(&url_words);
+        url.truncate(url_len);
+
+        Ok(Self {
+            name: String::from_utf8(name)?,
+            icon_hash,
+            url: String::from_utf8(url)?,
+        })
+    }
+}
+
+fn url_key(index: u64) -> Word {
+    [Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(index)].into()
+}
+
+/// Packs bytes into words, 7 bytes per felt, zero padding the last word.
+fn encode_bytes(bytes: &[u8]) -> Vec<Word> {
+    bytes
+        .chunks(BYTES_PER_WORD)
+        .map(|chunk| {
+            let mut felts = [Felt::new(0); 4];
+            for (felt, part) in felts.iter_mut().zip(chunk.chunks(BYTES_PER_FELT)) {
+                let mut buf = [0_u8; 8];
+                buf[..part.len()].copy_from_slice(part);
+                *felt = Felt::new(u64::from_le_bytes(buf));
+            }
+            felts.into()
+        })
+        .collect()
+}
+
+/// Reverses [`encode_bytes`], keeping the padding.
+fn decode_bytes(words: &[Word]) -> Vec<u8> {
+    words
+        .iter()
+        .flat_map(|word| word.iter().copied().collect::<Vec<_>>())
+        .flat_map(|felt| felt.as_int().to_le_bytes().into_iter().take(BYTES_PER_FELT))
+        .collect()
+}
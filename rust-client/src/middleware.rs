@@ -0,0 +1,511 @@
+//! Layers that wrap the node RPC client used by the tutorials.
+//!
+//! Every layer exposes an `around` method that receives the RPC method name and
+//! a closure performing the call on the inner client, and an `around_submit`
+//! method for transaction submissions, which cannot always be sent twice.
+//! [`impl_node_rpc_client!`] turns such a type into a full [`NodeRpcClient`].
+
+use std::{future::Future, sync::Arc};
+
+use miden_client::{
+    account::AccountId,
+    block::BlockNumber,
+    keystore::FilesystemKeyStore,
+    rpc::{NodeRpcClient, RpcError},
+    transaction::{ProvenTransaction, TransactionId, TransactionRequest, TransactionResult},
+    Client, ClientError,
+};
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::{
+    address_book::check_signer,
+    output::{emit, Event},
+    run_mode::{exit_if_dry_run, exit_if_dry_run_proven},
+    session,
+};
+
+/// Implements [`NodeRpcClient`] for a layer with an `inner` client and an `around` method.
+macro_rules! impl_node_rpc_client {
+    ($layer:ty) => {
+        #[async_trait::async_trait]
+        impl miden_client::rpc::NodeRpcClient for $layer {
+            async fn set_genesis_commitment(
+                &self,
+                commitment: miden_client::Word,
+            ) -> Result<(), miden_client::rpc::RpcError> {
+                self.around("set_genesis_commitment", || {
+                    self.inner.set_genesis_commitment(commitment)
+                })
+                .await
+            }
+
+            async fn submit_proven_transaction(
+                &self,
+                proven_transaction: miden_client::transaction::ProvenTransaction,
+                transaction_inputs: miden_client::transaction::TransactionInputs,
+            ) -> Result<miden_client::block::BlockNumber, miden_client::rpc::RpcError> {
+                self.around_submit(|| {
+                    self.inner.submit_proven_transaction(
+                        proven_transaction.clone(),
+                        transaction_inputs.clone(),
+                    )
+                })
+                .await
+            }
+
+            async fn get_block_header_by_number(
+                &self,
+                block_num: Option<miden_client::block::BlockNumber>,
+                include_mmr_proof: bool,
+            ) -> Result<
+                (
+                    miden_client::block::BlockHeader,
+                    Option<miden_client::crypto::MmrProof>,
+                ),
+                miden_client::rpc::RpcError,
+            > {
+                self.around("get_block_header_by_number", || {
+                    self.inner
+                        .get_block_header_by_number(block_num, include_mmr_proof)
+                })
+                .await
+            }
+
+            async fn get_block_by_number(
+                &self,
+                block_num: miden_client::block::BlockNumber,
+            ) -> Result<miden_client::block::ProvenBlock, miden_client::rpc::RpcError> {
+                self.around("get_block_by_number", || {
+                    self.inner.get_block_by_number(block_num)
+                })
+                .await
+            }
+
+            async fn get_notes_by_id(
+                &self,
+                note_ids: &[miden_client::note::NoteId],
+            ) -> Result<
+                Vec<miden_client::rpc::domain::note::FetchedNote>,
+                miden_client::rpc::RpcError,
+            > {
+                self.around("get_notes_by_id", || self.inner.get_notes_by_id(note_ids))
+                    .await
+            }
+
+            async fn sync_state(
+                &self,
+                block_num: miden_client::block::BlockNumber,
+                account_ids: &[miden_client::account::AccountId],
+                note_tags: &std::collections::BTreeSet<miden_client::note::NoteTag>,
+            ) -> Result<miden_client::rpc::domain::sync::StateSyncInfo, miden_client::rpc::RpcError>
+            {
+                self.around("sync_state", || {
+                    self.inner.sync_state(block_num, account_ids, note_tags)
+                })
+                .await
+            }
+
+            async fn get_account_details(
+                &self,
+                account_id: miden_client::account::AccountId,
+            ) -> Result<
+                miden_client::rpc::domain::account::FetchedAccount,
+                miden_client::rpc::RpcError,
+            > {
+                self.around("get_account_details", || {
+                    self.inner.get_account_details(account_id)
+                })
+                .await
+            }
+
+            async fn sync_notes(
+                &self,
+                block_num: miden_client::block::BlockNumber,
+                block_to: Option<miden_client::block::BlockNumber>,
+                note_tags: &std::collections::BTreeSet<miden_client::note::NoteTag>,
+            ) -> Result<miden_client::rpc::domain::note::NoteSyncInfo, miden_client::rpc::RpcError>
+            {
+                self.around("sync_notes", || {
+                    self.inner.sync_notes(block_num, block_to, note_tags)
+                })
+                .await
+            }
+
+            async fn sync_nullifiers(
+                &self,
+                prefix: &[u16],
+                block_num: miden_client::block::BlockNumber,
+                block_to: Option<miden_client::block::BlockNumber>,
+            ) -> Result<
+                Vec<miden_client::rpc::domain::nullifier::NullifierUpdate>,
+                miden_client::rpc::RpcError,
+            > {
+                self.around("sync_nullifiers", || {
+                    self.inner.sync_nullifiers(prefix, block_num, block_to)
+                })
+                .await
+            }
+
+            async fn check_nullifiers(
+                &self,
+                nullifiers: &[miden_client::note::Nullifier],
+            ) -> Result<Vec<miden_client::crypto::SmtProof>, miden_client::rpc::RpcError> {
+                self.around("check_nullifiers", || {
+                    self.inner.check_nullifiers(nullifiers)
+                })
+                .await
+            }
+
+            async fn get_account_proofs(
+                &self,
+                account_ids: &std::collections::BTreeSet<miden_client::account::AccountId>,
+                account_state: miden_client::rpc::AccountStateAt,
+                known_account_codes: std::collections::BTreeSet<miden_client::account::AccountCode>,
+            ) -> Result<
+                miden_client::rpc::domain::account::AccountProofs,
+                miden_client::rpc::RpcError,
+            > {
+                self.around("get_account_proofs", || {
+                    self.inner.get_account_proofs(
+                        account_ids,
+                        account_state,
+                        known_account_codes.clone(),
+                    )
+                })
+                .await
+            }
+
+            async fn get_note_script_by_root(
+                &self,
+                root: miden_client::Word,
+            ) -> Result<miden_client::note::NoteScript, miden_client::rpc::RpcError> {
+                self.around("get_note_script_by_root", || {
+                    self.inner.get_note_script_by_root(root)
+                })
+                .await
+            }
+
+            async fn sync_storage_maps(
+                &self,
+                block_from: miden_client::block::BlockNumber,
+                block_to: Option<miden_client::block::BlockNumber>,
+                account_id: miden_client::account::AccountId,
+            ) -> Result<
+                miden_client::rpc::domain::storage_map::StorageMapInfo,
+                miden_client::rpc::RpcError,
+            > {
+                self.around("sync_storage_maps", || {
+                    self.inner
+                        .sync_storage_maps(block_from, block_to, account_id)
+                })
+                .await
+            }
+
+            async fn sync_account_vault(
+                &self,
+                block_from: miden_client::block::BlockNumber,
+                block_to: Option<miden_client::block::BlockNumber>,
+                account_id: miden_client::account::AccountId,
+            ) -> Result<
+                miden_client::rpc::domain::account_vault::AccountVaultInfo,
+                miden_client::rpc::RpcError,
+            > {
+                self.around("sync_account_vault", || {
+                    self.inner
+                        .sync_account_vault(block_from, block_to, account_id)
+                })
+                .await
+            }
+
+            async fn sync_transactions(
+                &self,
+                block_from: miden_client::block::BlockNumber,
+                block_to: Option<miden_client::block::BlockNumber>,
+                account_ids: Vec<miden_client::account::AccountId>,
+            ) -> Result<
+                miden_client::rpc::domain::transaction::TransactionsInfo,
+                miden_client::rpc::RpcError,
+            > {
+                self.around("sync_transactions", || {
+                    self.inner
+                        .sync_transactions(block_from, block_to, account_ids.clone())
+                })
+                .await
+            }
+        }
+    };
+}
+
+pub(crate) use impl_node_rpc_client;
+
+/// RPC client wrapper that logs the method, duration and status of every call.
+///
+/// Wrap the client handed to `ClientBuilder::rpc` with it to see exactly what the
+/// client sends over the wire.
+pub struct LoggingRpcClient {
+    inner: Arc<dyn NodeRpcClient>,
+}
+
+impl LoggingRpcClient {
+    pub fn new(inner: Arc<dyn NodeRpcClient>) -> Self {
+        Self { inner }
+    }
+
+    async fn around<T, F, Fut>(&self, method: &'static str, call: F) -> Result<T, RpcError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        let start = Instant::now();
+        let result = call().await;
+        let status = match &result {
+            Ok(_) => "ok".to_string(),
+            Err(err) => format!("error: {err}"),
+        };
+        debug!(method, duration = ?start.elapsed(), %status, "rpc call");
+        result
+    }
+
+    async fn around_submit<F, Fut>(&self, call: F) -> Result<BlockNumber, RpcError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<BlockNumber, RpcError>>,
+    {
+        self.around("submit_proven_transaction", call).await
+    }
+}
+
+impl_node_rpc_client!(LoggingRpcClient);
+
+/// RPC client wrapper that retries transient failures with exponential backoff.
+pub struct RetryRpcClient {
+    inner: Arc<dyn NodeRpcClient>,
+    max_retries: u32,
+    base_delay: Duration,
+}
+
+impl RetryRpcClient {
+    pub fn new(inner: Arc<dyn NodeRpcClient>, max_retries: u32) -> Self {
+        Self {
+            inner,
+            max_retries,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+
+    /// Waits `base_delay` before the first retry, doubling it for every further one.
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Connection failures and timeouts are worth retrying; anything the node
+    /// actually answered is not.
+    fn is_transient(err: &RpcError) -> bool {
+        matches!(err, RpcError::ConnectionError(_))
+    }
+
+    async fn around<T, F, Fut>(&self, method: &'static str, call: F) -> Result<T, RpcError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match call().await {
+                Err(err) if Self::is_transient(&err) && attempt < self.max_retries => {
+                    let delay = self.base_delay * 2_u32.pow(attempt);
+                    attempt += 1;
+                    warn!(method, attempt, ?delay, error = %err, "retrying rpc call");
+                    sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Retries a submission like any other call, except that a connection
+    /// error does not tell whether the node received the failed attempt.
+    ///
+    /// If it did, the retry is rejected as [`SubmissionFailure::AlreadySubmitted`].
+    /// The transaction is then in the node's hands, so this counts as success,
+    /// submitted at the current chain tip.
+    async fn around_submit<F, Fut>(&self, call: F) -> Result<BlockNumber, RpcError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<BlockNumber, RpcError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match call().await {
+                Err(err)
+                    if attempt > 0
+                        && SubmissionFailure::from_message(&err.to_string())
+                            == SubmissionFailure::AlreadySubmitted =>
+                {
+                    warn!(attempt, error = %err, "retried submission had already reached the node");
+                    let (header, _) = self.inner.get_block_header_by_number(None, false).await?;
+                    return Ok(header.block_num());
+                }
+                Err(err) if Self::is_transient(&err) && attempt < self.max_retries => {
+                    let delay = self.base_delay * 2_u32.pow(attempt);
+                    attempt += 1;
+                    warn!(attempt, ?delay, error = %err, "retrying transaction submission");
+                    sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl_node_rpc_client!(RetryRpcClient);
+
+/// Submits a transaction and logs what was sent at DEBUG level.
+///
+/// Under `--dry-run` this prints the request and ends the run instead.
+pub async fn submit_logged(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    request: TransactionRequest,
+) -> Result<TransactionId, ClientError> {
+    exit_if_dry_run(account_id, &request);
+    let input_notes = request.input_notes().len();
+    let output_notes = request.expected_output_own_notes().len();
+    let start = Instant::now();
+    let result = execute_and_submit(client, account_id, request).await;
+    if let Ok(tx_id) = &result {
+        emit(Event::transaction_submitted(account_id, *tx_id));
+    }
+    match &result {
+        Ok(tx_id) => debug!(
+            account = %account_id,
+            input_notes,
+            output_notes,
+            tx_id = %tx_id.to_hex(),
+            duration = ?start.elapsed(),
+            "transaction submitted"
+        ),
+        Err(err) => debug!(
+            account = %account_id,
+            input_notes,
+            output_notes,
+            duration = ?start.elapsed(),
+            failure = ?SubmissionFailure::classify(err),
+            error = %err,
+            "transaction submission failed"
+        ),
+    }
+    result
+}
+
+/// Does what `Client::submit_new_transaction` does, but keeps the proven
+/// transaction long enough to record its fee in the [`session`].
+///
+/// An account that cannot sign the request fails before execution, see
+/// [`check_signer`].
+async fn execute_and_submit(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    request: TransactionRequest,
+) -> Result<TransactionId, ClientError> {
+    check_signer(client, account_id, &request).await?;
+    let tx_result = client.execute_transaction(account_id, request).await?;
+    let proven_transaction = client.prove_transaction(&tx_result).await?;
+    let tx_id = proven_transaction.id();
+    let fee = proven_transaction.fee();
+    let submission_height = client
+        .submit_proven_transaction(proven_transaction, &tx_result)
+        .await?;
+    session::record_submitted(
+        account_id,
+        tx_id,
+        fee,
+        tx_result.executed_transaction().output_notes().num_notes(),
+    );
+    client
+        .apply_transaction(&tx_result, submission_height)
+        .await?;
+    Ok(tx_id)
+}
+
+/// Submits an already proven transaction and logs the outcome at DEBUG level.
+///
+/// Unlike [`submit_logged`], this can send the same proof more than once, so a
+/// rejection is classified with [`SubmissionFailure`].
+pub async fn submit_proven_logged(
+    client: &mut Client<FilesystemKeyStore>,
+    proven_transaction: ProvenTransaction,
+    tx_result: &TransactionResult,
+) -> Result<BlockNumber, ClientError> {
+    let tx_id = proven_transaction.id();
+    let account_id = proven_transaction.account_id();
+    let fee = proven_transaction.fee();
+    exit_if_dry_run_proven(tx_id);
+    let start = Instant::now();
+    let result = client
+        .submit_proven_transaction(proven_transaction, tx_result)
+        .await;
+    if result.is_ok() {
+        session::record_submitted(
+            account_id,
+            tx_id,
+            fee,
+            tx_result.executed_transaction().output_notes().num_notes(),
+        );
+        emit(Event::transaction_submitted(account_id, tx_id));
+    }
+    match &result {
+        Ok(block_num) => debug!(
+            tx_id = %tx_id.to_hex(),
+            %block_num,
+            duration = ?start.elapsed(),
+            "proven transaction submitted"
+        ),
+        Err(err) => debug!(
+            tx_id = %tx_id.to_hex(),
+            duration = ?start.elapsed(),
+            failure = ?SubmissionFailure::classify(err),
+            error = %err,
+            "proven transaction submission failed"
+        ),
+    }
+    result
+}
+
+/// Why the node refused a transaction submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionFailure {
+    /// The node already holds this exact transaction, in its mempool or in a block.
+    /// Nothing was lost: wait for the first submission instead of retrying.
+    AlreadySubmitted,
+    /// Any other failure, including a proof built on a stale account state.
+    Other,
+}
+
+impl SubmissionFailure {
+    /// Classifies a submission error from the status message the node returned.
+    pub fn classify(err: &ClientError) -> Self {
+        Self::from_message(&err.to_string())
+    }
+
+    /// Classifies the text of a submission error, e.g. of an [`RpcError`].
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let duplicate = [
+            "already exists",
+            "alreadyexists",
+            "already in mempool",
+            "already submitted",
+            "duplicate transaction",
+        ]
+        .iter()
+        .any(|marker| message.contains(marker));
+        if duplicate {
+            SubmissionFailure::AlreadySubmitted
+        } else {
+            SubmissionFailure::Other
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! SDK-style wrapper around the name registry in `masm/accounts/name_registry.masm`.
+//!
+//! The registry is the storage map of `mapping_example` put to use: each key is
+//! the hash of a name and each value the account owning it. Anyone can register
+//! a free name to themselves by sending a note, and only the owner can hand a
+//! name on. Names are resolved by reading the map, without a transaction.
+
+use std::fmt;
+
+use miden_client::{
+    account::{AccountId, StorageMap, StorageSlot, StorageSlotName},
+    assembly::Library,
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteScript},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Word,
+};
+use miden_protocol::Hasher;
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const NAMES_SLOT: &str = "miden::tutorials::name_registry::names";
+
+/// Library path the registry procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::name_registry_contract";
+
+/// Longest name the registry takes, in bytes.
+pub const MAX_NAME_LEN: usize = 32;
+
+const COMPONENT_FILE: &str = "masm/accounts/name_registry.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/name_registry.masm");
+const REGISTER_NOTE_FILE: &str = "masm/notes/name_register_note.masm";
+const REGISTER_NOTE_CODE: &str = include_str!("../../masm/notes/name_register_note.masm");
+const TRANSFER_NOTE_FILE: &str = "masm/notes/name_transfer_note.masm";
+const TRANSFER_NOTE_CODE: &str = include_str!("../../masm/notes/name_transfer_note.masm");
+
+/// A name the registry does not take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidName {
+    pub name: String,
+}
+
+impl fmt::Display for InvalidName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid name '{}': use 1 to {MAX_NAME_LEN} lowercase letters, digits or '-'",
+            self.name
+        )
+    }
+}
+
+impl std::error::Error for InvalidName {}
+
+/// The map key of `name`: the hash of its bytes.
+///
+/// Only lowercase ASCII letters, digits and `-` are allowed, so names that look
+/// alike cannot map to different owners.
+pub fn name_hash(name: &str) -> Result<Word, InvalidName> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-');
+    if !valid {
+        return Err(InvalidName {
+            name: name.to_string(),
+        });
+    }
+    Ok(Hasher::hash(name.as_bytes()))
+}
+
+/// A deployed name registry.
+pub struct NameRegistry {
+    pub account_id: AccountId,
+    library: Library,
+}
+
+impl NameRegistry {
+    /// Builds a new, empty registry and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the first registration it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots([StorageSlot::with_map(
+            StorageSlotName::new(NAMES_SLOT)?,
+            StorageMap::new(),
+        )]);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            library,
+        })
+    }
+
+    /// Registers `name` to `owner`, which sends the note. Fails on-chain if the name is taken.
+    pub async fn register(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        owner: AccountId,
+        name: &str,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(REGISTER_NOTE_FILE, REGISTER_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(owner, script)
+            .inputs(name_hash(name)?.to_vec())
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, owner, note).await
+    }
+
+    /// Sends a note from `sender` handing `name` to `new_owner`, which the
+    /// registry only accepts from the current owner.
+    pub async fn transfer(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        name: &str,
+        new_owner: AccountId,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(TRANSFER_NOTE_FILE, TRANSFER_NOTE_CODE),
+        )?;
+        let mut inputs = name_hash(name)?.to_vec();
+        inputs.extend([new_owner.prefix().as_felt(), new_owner.suffix()]);
+        let note = NoteBuilder::new(sender, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, sender, note).await
+    }
+
+    /// Returns the owner of `name`, as of the last sync, or `None` while it is free.
+    pub async fn resolve(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        name: &str,
+    ) -> Result<Option<AccountId>, Box<dyn std::error::Error>> {
+        let key = name_hash(name)?;
+        let registry = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(NAMES_SLOT).expect("valid slot name");
+        let owner = registry
+            .storage()
+            .get_map_item(&slot, key)
+            .expect("registry account is missing its names slot");
+        if owner == Word::default() {
+            return Ok(None);
+        }
+        // Stored as [owner_prefix, owner_suffix, 0, 0] on the stack, so the
+        // prefix is the last element of the word
+        let owner =
+            AccountId::try_from([owner[3], owner[2]]).expect("the registry stores valid IDs");
+        Ok(Some(owner))
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the registry itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("name registry note script does not compile");
+        Ok(script)
+    }
+
+    /// Sends `note` from `sender` and has the registry consume it right away.
+    async fn send_and_consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        note: Note,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, sender, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .build()?;
+        Ok(submit_and_wait(client, self.account_id, consume_request).await?)
+    }
+}
@@ -0,0 +1,83 @@
+//! Network presets that keep the endpoint, address prefix and explorer in agreement.
+//!
+//! Picking the endpoint on its own made it easy to talk to devnet while printing
+//! testnet addresses and MidenScan links. A [`Network`] derives all three.
+//!
+//! There is no mainnet preset until its public endpoints are published; pass
+//! `--endpoint` to reach any other node.
+
+use miden_client::{address::NetworkId, rpc::Endpoint, transaction::TransactionId};
+
+/// Bech32 prefix of the addresses printed for [`Network::Localhost`].
+pub const LOCALHOST_HRP: &str = "mlcl";
+
+/// A Miden network the tutorials can run against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Network {
+    Testnet,
+    #[default]
+    Devnet,
+    /// A node started with `miden-node` on this machine.
+    Localhost,
+}
+
+impl Network {
+    /// Parses a preset name as written in the config file, e.g. `devnet`.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        <Self as clap::ValueEnum>::from_str(name, true)
+    }
+
+    /// Name of the preset, as accepted by [`Self::from_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+            Network::Localhost => "localhost",
+        }
+    }
+
+    /// Public RPC endpoint of the network.
+    pub fn endpoint(self) -> Endpoint {
+        match self {
+            Network::Testnet => Endpoint::testnet(),
+            Network::Devnet => Endpoint::devnet(),
+            Network::Localhost => Endpoint::localhost(),
+        }
+    }
+
+    /// Prefix of the bech32 addresses printed for this network.
+    pub fn network_id(self) -> NetworkId {
+        match self {
+            Network::Testnet => NetworkId::Testnet,
+            Network::Devnet => NetworkId::Devnet,
+            // A local node has no public network, so its addresses get a prefix
+            // of their own instead of passing for testnet ones
+            Network::Localhost => NetworkId::new(LOCALHOST_HRP)
+                .expect("the localhost prefix is a valid bech32 prefix"),
+        }
+    }
+
+    /// MidenScan base URL, if the network has an explorer.
+    pub fn explorer_base_url(self) -> Option<&'static str> {
+        match self {
+            Network::Testnet => Some("https://testnet.midenscan.com"),
+            Network::Devnet => Some("https://devnet.midenscan.com"),
+            Network::Localhost => None,
+        }
+    }
+
+    /// Public delegated prover of the network, if it runs one.
+    pub fn remote_prover_url(self) -> Option<&'static str> {
+        match self {
+            Network::Testnet => Some("https://tx-prover.testnet.miden.io"),
+            Network::Devnet => Some("https://tx-prover.devnet.miden.io"),
+            Network::Localhost => None,
+        }
+    }
+
+    /// Link to a transaction on the network's explorer.
+    pub fn explorer_tx_url(self, tx_id: TransactionId) -> Option<String> {
+        self.explorer_base_url()
+            .map(|base| format!("{base}/tx/{}", tx_id.to_hex()))
+    }
+}
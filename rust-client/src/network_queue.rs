@@ -0,0 +1,210 @@
+//! Where notes sent to a network account stand, as far as the node tells.
+//!
+//! A network note is consumed by the node's network transaction builder, not by
+//! the sender, so a counter update lands some time after the note itself is
+//! committed. The builder's queue is not exposed over RPC, but its two edges
+//! are: `get_notes_by_id` tells when a note was committed and `sync_nullifiers`
+//! when it was consumed. In between the note waits
+//! for the builder. Ordering the waiting notes by commit block gives their
+//! position, since the builder works through an account's notes in order.
+
+use tokio::time::{sleep, Duration};
+
+use miden_client::{
+    block::BlockNumber,
+    keystore::FilesystemKeyStore,
+    note::{Note, NoteId, Nullifier},
+    rpc::{NodeRpcClient, RpcError},
+    Client,
+};
+
+use crate::say;
+
+/// What the node reports about one note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteStatus {
+    /// Submitted, but not in a block yet.
+    Submitted,
+    /// Committed and waiting for the network transaction builder.
+    Pending { committed_at: BlockNumber },
+    /// Consumed by a network transaction.
+    Consumed {
+        committed_at: BlockNumber,
+        consumed_at: BlockNumber,
+    },
+}
+
+impl NoteStatus {
+    /// Blocks the note has waited since its commit, or waited in total once consumed.
+    pub fn age(&self, tip: BlockNumber) -> Option<u32> {
+        match *self {
+            NoteStatus::Submitted => None,
+            NoteStatus::Pending { committed_at } => {
+                Some(tip.as_u32().saturating_sub(committed_at.as_u32()))
+            }
+            NoteStatus::Consumed {
+                committed_at,
+                consumed_at,
+            } => Some(consumed_at.as_u32().saturating_sub(committed_at.as_u32())),
+        }
+    }
+}
+
+/// Position of each pending note in the builder's queue, starting at 1.
+///
+/// Notes committed in the same block keep their given order. Notes that are
+/// not pending get `None`.
+pub fn queue_positions(statuses: &[NoteStatus]) -> Vec<Option<usize>> {
+    let mut pending: Vec<(BlockNumber, usize)> = statuses
+        .iter()
+        .enumerate()
+        .filter_map(|(index, status)| match status {
+            NoteStatus::Pending { committed_at } => Some((*committed_at, index)),
+            _ => None,
+        })
+        .collect();
+    pending.sort();
+
+    let mut positions = vec![None; statuses.len()];
+    for (position, (_, index)) in pending.into_iter().enumerate() {
+        positions[index] = Some(position + 1);
+    }
+    positions
+}
+
+/// A note sent to a network account and what the node last said about it.
+#[derive(Debug, Clone)]
+pub struct TrackedNote {
+    pub note_id: NoteId,
+    pub nullifier: Nullifier,
+    pub status: NoteStatus,
+}
+
+/// The notes sent to one network account, refreshed from the node.
+#[derive(Debug, Clone, Default)]
+pub struct NoteQueue {
+    notes: Vec<TrackedNote>,
+}
+
+impl NoteQueue {
+    /// Starts tracking `note`, which was just submitted.
+    pub fn track(&mut self, note: &Note) {
+        self.notes.push(TrackedNote {
+            note_id: note.id(),
+            nullifier: note.nullifier(),
+            status: NoteStatus::Submitted,
+        });
+    }
+
+    pub fn notes(&self) -> &[TrackedNote] {
+        &self.notes
+    }
+
+    /// Whether every note has been consumed.
+    pub fn is_drained(&self) -> bool {
+        self.notes
+            .iter()
+            .all(|note| matches!(note.status, NoteStatus::Consumed { .. }))
+    }
+
+    /// Asks the node which notes are committed and which are consumed.
+    pub async fn refresh(&mut self, rpc: &dyn NodeRpcClient) -> Result<(), RpcError> {
+        let submitted: Vec<NoteId> = self
+            .notes
+            .iter()
+            .filter(|note| note.status == NoteStatus::Submitted)
+            .map(|note| note.note_id)
+            .collect();
+        if !submitted.is_empty() {
+            for fetched in rpc.get_notes_by_id(&submitted).await? {
+                let committed_at = fetched.inclusion_proof().location().block_num();
+                if let Some(note) = self.notes.iter_mut().find(|n| n.note_id == fetched.id()) {
+                    note.status = NoteStatus::Pending { committed_at };
+                }
+            }
+        }
+
+        // One nullifier sync from the oldest pending commit covers every pending note
+        let pending: Vec<(BlockNumber, u16)> = self
+            .notes
+            .iter()
+            .filter_map(|note| match note.status {
+                NoteStatus::Pending { committed_at } => {
+                    Some((committed_at, note.nullifier.prefix()))
+                }
+                _ => None,
+            })
+            .collect();
+        let Some(from) = pending.iter().map(|(committed_at, _)| *committed_at).min() else {
+            return Ok(());
+        };
+        let prefixes: Vec<u16> = pending.iter().map(|(_, prefix)| *prefix).collect();
+        for update in rpc.sync_nullifiers(&prefixes, from, None).await? {
+            let Some(note) = self
+                .notes
+                .iter_mut()
+                .find(|n| n.nullifier == update.nullifier)
+            else {
+                continue;
+            };
+            if let NoteStatus::Pending { committed_at } = note.status {
+                note.status = NoteStatus::Consumed {
+                    committed_at,
+                    consumed_at: update.block_num,
+                };
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints one line per note with its queue position and age at block `tip`.
+    pub fn print(&self, tip: BlockNumber) {
+        let statuses: Vec<NoteStatus> = self.notes.iter().map(|note| note.status).collect();
+        let positions = queue_positions(&statuses);
+
+        say!("Queue at block {tip}:");
+        for (note, position) in self.notes.iter().zip(positions) {
+            let id = note.note_id.to_hex();
+            let short_id = &id[..id.len().min(12)];
+            let line = match note.status {
+                NoteStatus::Submitted => "submitted, not in a block yet".to_string(),
+                NoteStatus::Pending { committed_at } => format!(
+                    "pending #{}, committed at block {committed_at}, waiting {} blocks",
+                    position.unwrap_or_default(),
+                    note.status.age(tip).unwrap_or(0)
+                ),
+                NoteStatus::Consumed { consumed_at, .. } => format!(
+                    "consumed at block {consumed_at} after {} blocks",
+                    note.status.age(tip).unwrap_or(0)
+                ),
+            };
+            say!("  {short_id}…  {line}");
+        }
+    }
+}
+
+/// Polls the node until the network transaction builder has consumed `note`,
+/// at most `polls` times, printing the queue each time. Returns whether it did.
+///
+/// Once it has, `client` is synced again so the network account's new state
+/// is in the store.
+pub async fn wait_consumed(
+    client: &mut Client<FilesystemKeyStore>,
+    rpc: &dyn NodeRpcClient,
+    note: &Note,
+    polls: usize,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut queue = NoteQueue::default();
+    queue.track(note);
+    for _ in 0..polls {
+        let tip = client.sync_state().await?.block_num;
+        queue.refresh(rpc).await?;
+        queue.print(tip);
+        if queue.is_drained() {
+            client.sync_state().await?;
+            return Ok(true);
+        }
+        sleep(Duration::from_secs(3)).await;
+    }
+    Ok(false)
+}
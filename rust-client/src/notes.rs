@@ -0,0 +1,170 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use miden_client::{
+    account::{Account, AccountId},
+    asset::{Asset, NonFungibleAsset},
+    keystore::FilesystemKeyStore,
+    note::Note,
+    transaction::{TransactionId, TransactionRequestBuilder},
+    Client, ClientError,
+};
+
+use crate::{middleware::submit_logged, say};
+
+/// Filters applied by [`consume_all`] before a note is swept into an account.
+///
+/// The default accepts every note.
+#[derive(Debug, Clone, Default)]
+pub struct ConsumeOptions {
+    /// Fungible assets below this amount are treated as dust.
+    pub dust_threshold: u64,
+    /// When set, notes carrying assets from any other faucet are skipped.
+    pub known_faucets: Option<BTreeSet<AccountId>>,
+}
+
+impl ConsumeOptions {
+    pub fn with_dust_threshold(mut self, amount: u64) -> Self {
+        self.dust_threshold = amount;
+        self
+    }
+
+    pub fn with_known_faucets(mut self, faucets: impl IntoIterator<Item = AccountId>) -> Self {
+        self.known_faucets = Some(faucets.into_iter().collect());
+        self
+    }
+
+    /// Returns the reason a note should be skipped, or `None` if it may be consumed.
+    pub fn rejection_reason(&self, note: &Note) -> Option<String> {
+        for asset in note.assets().iter() {
+            match asset {
+                Asset::Fungible(fungible) => {
+                    if let Some(known) = &self.known_faucets {
+                        if !known.contains(&fungible.faucet_id()) {
+                            return Some(format!("unknown faucet {}", fungible.faucet_id()));
+                        }
+                    }
+                    if fungible.amount() < self.dust_threshold {
+                        return Some(format!(
+                            "dust amount {} below threshold {}",
+                            fungible.amount(),
+                            self.dust_threshold
+                        ));
+                    }
+                }
+                Asset::NonFungible(non_fungible) => {
+                    if let Some(known) = &self.known_faucets {
+                        let prefix = non_fungible.faucet_id_prefix();
+                        if !known.iter().any(|faucet| faucet.prefix() == prefix) {
+                            return Some(format!("unknown faucet prefix {prefix}"));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Consumes every consumable note for `account_id` that passes `options`.
+///
+/// Returns `None` when no note was accepted.
+pub async fn consume_all(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    options: &ConsumeOptions,
+) -> Result<Option<TransactionId>, ClientError> {
+    let consumable_notes = client.get_consumable_notes(Some(account_id)).await?;
+
+    let mut notes = Vec::new();
+    for (note_record, _) in consumable_notes {
+        let note: Note = note_record.try_into()?;
+        match options.rejection_reason(&note) {
+            Some(reason) => say!("Skipping note {}: {}", note.id(), reason),
+            None => notes.push(note),
+        }
+    }
+
+    if notes.is_empty() {
+        return Ok(None);
+    }
+
+    say!("Consuming {} note(s) for {}", notes.len(), account_id);
+    let transaction_request = TransactionRequestBuilder::new().build_consume_notes(notes)?;
+    let tx_id = submit_logged(client, account_id, transaction_request).await?;
+    Ok(Some(tx_id))
+}
+
+/// Why a sender's vault cannot fund a set of output notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsufficientBalance {
+    Fungible {
+        faucet_id: AccountId,
+        have: u64,
+        need: u64,
+    },
+    NonFungible(NonFungibleAsset),
+}
+
+impl fmt::Display for InsufficientBalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsufficientBalance::Fungible {
+                faucet_id,
+                have,
+                need,
+            } => write!(
+                f,
+                "insufficient balance of {}: have {}, need {}",
+                faucet_id, have, need
+            ),
+            InsufficientBalance::NonFungible(asset) => {
+                write!(
+                    f,
+                    "insufficient balance: missing non-fungible asset {:?}",
+                    asset
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InsufficientBalance {}
+
+/// Checks that `sender`'s vault holds every asset carried by `notes`.
+///
+/// Call it before building the transaction: an underfunded transaction only
+/// fails deep inside execution, with an error that does not name the asset.
+pub fn check_vault_covers(sender: &Account, notes: &[Note]) -> Result<(), InsufficientBalance> {
+    let mut fungible_needs: BTreeMap<AccountId, u64> = BTreeMap::new();
+    for asset in notes.iter().flat_map(|note| note.assets().iter()) {
+        match asset {
+            Asset::Fungible(fungible) => {
+                *fungible_needs.entry(fungible.faucet_id()).or_default() += fungible.amount();
+            }
+            Asset::NonFungible(non_fungible) => {
+                let held = sender
+                    .vault()
+                    .has_non_fungible_asset(*non_fungible)
+                    .unwrap_or(false);
+                if !held {
+                    return Err(InsufficientBalance::NonFungible(*non_fungible));
+                }
+            }
+        }
+    }
+
+    for (faucet_id, need) in fungible_needs {
+        let have = sender.vault().get_balance(faucet_id).unwrap_or(0);
+        if have < need {
+            return Err(InsufficientBalance::Fungible {
+                faucet_id,
+                have,
+                need,
+            });
+        }
+    }
+    Ok(())
+}
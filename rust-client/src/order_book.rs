@@ -0,0 +1,293 @@
+//! An off-chain order book of open SWAP notes.
+//!
+//! Makers post the swap notes of `masm/notes/swap_note.masm` under a tag the
+//! market agrees on, [`ORDER_TAG`]. A taker registers that tag, collects what it
+//! discovers into an [`OrderBook`] kept as JSON on disk, and settles the best
+//! order by consuming its note, which pays the maker in the same transaction.
+//!
+//! The payback note is a P2ID note whose serial number is drawn from the swap
+//! note's own serial number, so a taker can rebuild it from the public swap
+//! note alone and hand its recipient to the transaction.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use miden_client::{
+    account::AccountId,
+    assembly::CodeBuilder,
+    asset::{Asset, FungibleAsset},
+    crypto::{FeltRng, RpoRandomCoin},
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteId, NoteScript, NoteTag, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    utils::{Deserializable, Serializable},
+    Client, Word,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    keys::{from_hex, to_hex},
+    masm::{MasmError, MasmFile},
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+/// Tag every order is posted under.
+pub const ORDER_TAG: u32 = 0x6f72_6400;
+
+/// Default location of the order book, next to `store.sqlite3`.
+pub const ORDER_BOOK_FILE: &str = "./order_book.json";
+
+const SWAP_NOTE_FILE: &str = "masm/notes/swap_note.masm";
+const SWAP_NOTE_CODE: &str = include_str!("../../masm/notes/swap_note.masm");
+
+/// Inputs of a swap note: `[PAYBACK_RECIPIENT, REQUESTED_ASSET, payback_tag]`.
+const SWAP_INPUTS: usize = 9;
+
+/// The script every order is a note of.
+pub fn swap_script() -> Result<NoteScript, MasmError> {
+    let file = MasmFile::embedded(SWAP_NOTE_FILE, SWAP_NOTE_CODE);
+    file.check(CodeBuilder::new().compile_note_script(&file.source))
+}
+
+/// An open swap note: its maker gives `offered` to whoever pays `requested`.
+#[derive(Debug, Clone)]
+pub struct Order {
+    pub note: Note,
+    pub offered: FungibleAsset,
+    pub requested: FungibleAsset,
+}
+
+impl Order {
+    /// Reads an order back from `note`, or `None` if it is not a swap note
+    /// trading one fungible asset for another.
+    pub fn from_note(note: Note) -> Option<Self> {
+        if note.script().root() != swap_script().ok()?.root() {
+            return None;
+        }
+        let inputs = note.recipient().inputs().values();
+        if inputs.len() != SWAP_INPUTS {
+            return None;
+        }
+        let requested = Word::from([inputs[4], inputs[5], inputs[6], inputs[7]]);
+        let Ok(Asset::Fungible(requested)) = Asset::try_from(requested) else {
+            return None;
+        };
+        let [Asset::Fungible(offered)] = note.assets().iter().copied().collect::<Vec<_>>()[..]
+        else {
+            return None;
+        };
+        Some(Self {
+            note,
+            offered,
+            requested,
+        })
+    }
+
+    pub fn id(&self) -> NoteId {
+        self.note.id()
+    }
+
+    pub fn maker(&self) -> AccountId {
+        self.note.metadata().sender()
+    }
+
+    /// The note paying `requested` to the maker, which settling this order creates.
+    pub fn payback(&self) -> Note {
+        payback_note(self.maker(), self.requested, self.note.serial_num())
+    }
+
+    /// Whether this order gives more per requested token than `other`.
+    ///
+    /// Prices are compared by cross-multiplying, so no precision is lost.
+    pub fn beats(&self, other: &Order) -> bool {
+        self.offered.amount() as u128 * other.requested.amount() as u128
+            > other.offered.amount() as u128 * self.requested.amount() as u128
+    }
+}
+
+/// Builds a maker's swap note giving `offered` for `requested`, tagged [`ORDER_TAG`].
+pub fn new_order(
+    maker: AccountId,
+    offered: FungibleAsset,
+    requested: FungibleAsset,
+    rng: &mut impl FeltRng,
+) -> Result<Order, Box<dyn std::error::Error>> {
+    // The serial number is drawn up front, as the payback's is derived from it
+    let serial_num = SerialNumbers::random().next(rng, None);
+    let payback = payback_note(maker, requested, serial_num);
+
+    let mut inputs = payback.recipient().digest().to_vec();
+    inputs.extend(Word::from(requested));
+    inputs.push(payback.metadata().tag().into());
+    let note = NoteBuilder::new(maker, swap_script()?)
+        .inputs(inputs)
+        .asset(offered)
+        .tag(NoteTag::new(ORDER_TAG))
+        .build_with_serial(serial_num)?;
+
+    Ok(Order {
+        note,
+        offered,
+        requested,
+    })
+}
+
+/// Posts a new order from `maker` and returns it once its note is committed.
+pub async fn post_order(
+    client: &mut Client<FilesystemKeyStore>,
+    maker: AccountId,
+    offered: FungibleAsset,
+    requested: FungibleAsset,
+) -> Result<Order, Box<dyn std::error::Error>> {
+    let order = new_order(maker, offered, requested, client.rng())?;
+    let note_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(order.note.clone())])
+        .build()?;
+    submit_and_wait(client, maker, note_request).await?;
+    Ok(order)
+}
+
+/// Has `taker` fill `order`, paying the maker and receiving the offered asset.
+pub async fn settle(
+    client: &mut Client<FilesystemKeyStore>,
+    taker: AccountId,
+    order: &Order,
+) -> Result<TxReport, Box<dyn std::error::Error>> {
+    let fill_request = TransactionRequestBuilder::new()
+        .input_notes([(order.note.clone(), None)])
+        .expected_output_recipients(vec![order.payback().recipient().clone()])
+        .build()?;
+    Ok(submit_and_wait(client, taker, fill_request).await?)
+}
+
+/// The P2ID note paying `requested` to `maker` for the swap note with `swap_serial`.
+///
+/// The taker sends the real note, but a note's ID and recipient do not depend
+/// on its sender, so the maker stands in for it here.
+fn payback_note(maker: AccountId, requested: FungibleAsset, swap_serial: Word) -> Note {
+    create_p2id_note(
+        maker,
+        maker,
+        vec![requested.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        &mut RpoRandomCoin::new(swap_serial),
+    )
+    .expect("a P2ID note with one fungible asset is valid")
+}
+
+/// Orders a taker has discovered, saved to disk between runs.
+#[derive(Debug)]
+pub struct OrderBook {
+    path: PathBuf,
+    orders: Vec<Order>,
+}
+
+/// JSON layout of a saved book: every note as hex.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OrderBookFile {
+    orders: Vec<String>,
+}
+
+impl OrderBook {
+    /// Loads the book, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref().to_path_buf();
+        let file: OrderBookFile = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => OrderBookFile::default(),
+            Err(err) => return Err(err.into()),
+        };
+        let mut orders = Vec::new();
+        for hex in file.orders {
+            let note = Note::read_from_bytes(&from_hex(&hex)?)?;
+            let order =
+                Order::from_note(note).ok_or("the order book holds a note that is not an order")?;
+            orders.push(order);
+        }
+        Ok(Self { path, orders })
+    }
+
+    /// Loads the book from [`ORDER_BOOK_FILE`].
+    pub fn open_default() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load(ORDER_BOOK_FILE)
+    }
+
+    /// Writes the book back to disk.
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let file = OrderBookFile {
+            orders: self
+                .orders
+                .iter()
+                .map(|order| to_hex(&order.note.to_bytes()))
+                .collect(),
+        };
+        fs::write(&self.path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    pub fn orders(&self) -> &[Order] {
+        &self.orders
+    }
+
+    /// Adds `order` unless the book already holds it. Returns whether it was new.
+    pub fn insert(&mut self, order: Order) -> bool {
+        if self.orders.iter().any(|known| known.id() == order.id()) {
+            return false;
+        }
+        self.orders.push(order);
+        true
+    }
+
+    /// Drops the order of note `id`, once it is filled or no longer wanted.
+    pub fn remove(&mut self, id: NoteId) -> Option<Order> {
+        let index = self.orders.iter().position(|order| order.id() == id)?;
+        Some(self.orders.remove(index))
+    }
+
+    /// Syncs `client` and replaces the book with the orders under [`ORDER_TAG`]
+    /// that `taker` can fill. Returns how many of them are new.
+    ///
+    /// Orders the taker cannot pay for are left out, as are filled ones, which
+    /// no longer show up as consumable.
+    pub async fn refresh(
+        &mut self,
+        client: &mut Client<FilesystemKeyStore>,
+        taker: AccountId,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        client.sync_state().await?;
+        let tag = NoteTag::new(ORDER_TAG);
+        let open: Vec<Order> = client
+            .get_consumable_notes(Some(taker))
+            .await?
+            .into_iter()
+            .filter(|(record, _)| record.metadata().is_some_and(|m| m.tag() == tag))
+            .filter_map(|(record, _)| Note::try_from(record).ok())
+            .filter_map(Order::from_note)
+            .collect();
+
+        let new = open
+            .iter()
+            .filter(|order| !self.orders.iter().any(|known| known.id() == order.id()))
+            .count();
+        self.orders = open;
+        Ok(new)
+    }
+
+    /// The order giving the most `wanted` per token paid, among those that ask
+    /// for the token of `budget` and no more than its amount.
+    pub fn best_match(&self, wanted: AccountId, budget: FungibleAsset) -> Option<&Order> {
+        self.orders
+            .iter()
+            .filter(|order| order.offered.faucet_id() == wanted)
+            .filter(|order| order.requested.faucet_id() == budget.faucet_id())
+            .filter(|order| order.requested.amount() <= budget.amount())
+            .fold(None, |best: Option<&Order>, order| match best {
+                Some(best) if !order.beats(best) => Some(best),
+                _ => Some(order),
+            })
+    }
+}
@@ -0,0 +1,466 @@
+//! Human-readable logging and the machine-readable events of `--json`.
+//!
+//! Tutorials print their narration with [`say!`]. Normally that goes to stdout.
+//! Under `--json` it moves to stderr, and stdout carries one JSON object per line
+//! for each [`Event`], so a script can read the results with `jq` or
+//! `serde_json` without scraping logs:
+//!
+//! ```text
+//! {"event":"account_created","account_id":"mtst1...","account_type":"regular_updatable"}
+//! {"event":"transaction_submitted","account_id":"mtst1...","tx_id":"0x..."}
+//! ```
+//!
+//! The same events can be mirrored to other [`Sink`]s with `--report`, given
+//! once per sink, or with `report = [...]` in the config file. A path appends
+//! the JSON lines to a file, and an `http://` or `https://` URL receives each
+//! event as the body of a POST, sent from a background thread so a slow
+//! endpoint never holds up the run. `[STEP N]` narration lines become
+//! [`Event::Step`], so a monitor can follow a run without reading its logs:
+//!
+//! ```text
+//! cargo run --bin counter_contract_increment -- --report run.jsonl --report https://example.com/hook
+//! ```
+
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+        Mutex, OnceLock,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use miden_client::{
+    account::{Account, AccountId, AccountType},
+    transaction::{TransactionId, TransactionStatus},
+    Word,
+};
+use serde::Serialize;
+
+use crate::{client::network_id, run_mode::run_mode};
+
+/// Prints a line of narration: to stdout, or to stderr under `--json`.
+#[macro_export]
+macro_rules! say {
+    () => {
+        $crate::output::say(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::say(format_args!($($arg)*))
+    };
+}
+
+/// Backs [`say!`].
+pub fn say(args: fmt::Arguments) {
+    if run_mode().json {
+        eprintln!("{args}");
+    } else {
+        println!("{args}");
+    }
+    if run_mode().json || REPORTER.get().is_some() {
+        if let Some(event) = Event::step(&args.to_string()) {
+            emit(event);
+        }
+    }
+}
+
+/// A result worth handing to a script. Account IDs are bech32, the rest hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    AccountCreated {
+        account_id: String,
+        account_type: &'static str,
+    },
+    TransactionSubmitted {
+        account_id: String,
+        tx_id: String,
+    },
+    /// A transaction reached its final status, `committed` or `discarded`.
+    TransactionFinal {
+        tx_id: String,
+        status: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block: Option<u32>,
+    },
+    StorageValue {
+        account_id: String,
+        slot: String,
+        /// Set for an entry of a storage map.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        key: Option<String>,
+        value: String,
+    },
+    Balance {
+        account_id: String,
+        faucet_id: String,
+        amount: u64,
+    },
+    /// A tutorial started step `step`, announced with `[STEP N] title`.
+    Step {
+        step: u32,
+        title: String,
+    },
+}
+
+impl Event {
+    pub fn account_created(account: &Account) -> Self {
+        let account_type = match account.account_type() {
+            AccountType::FungibleFaucet => "fungible_faucet",
+            AccountType::NonFungibleFaucet => "non_fungible_faucet",
+            AccountType::RegularAccountImmutableCode => "regular_immutable",
+            AccountType::RegularAccountUpdatableCode => "regular_updatable",
+        };
+        Event::AccountCreated {
+            account_id: account.id().to_bech32(network_id()),
+            account_type,
+        }
+    }
+
+    pub fn transaction_submitted(account_id: AccountId, tx_id: TransactionId) -> Self {
+        Event::TransactionSubmitted {
+            account_id: account_id.to_bech32(network_id()),
+            tx_id: tx_id.to_hex(),
+        }
+    }
+
+    /// Returns `None` while the transaction is still pending.
+    pub fn transaction_final(tx_id: TransactionId, status: &TransactionStatus) -> Option<Self> {
+        let (status, block) = match status {
+            TransactionStatus::Committed { block_number, .. } => {
+                ("committed", Some(block_number.as_u32()))
+            }
+            TransactionStatus::Discarded(_) => ("discarded", None),
+            TransactionStatus::Pending => return None,
+        };
+        Some(Event::TransactionFinal {
+            tx_id: tx_id.to_hex(),
+            status,
+            block,
+        })
+    }
+
+    pub fn storage_value(account_id: AccountId, slot: impl fmt::Display, value: Word) -> Self {
+        Event::StorageValue {
+            account_id: account_id.to_bech32(network_id()),
+            slot: slot.to_string(),
+            key: None,
+            value: value.to_hex(),
+        }
+    }
+
+    pub fn map_entry(
+        account_id: AccountId,
+        slot: impl fmt::Display,
+        key: Word,
+        value: Word,
+    ) -> Self {
+        Event::StorageValue {
+            account_id: account_id.to_bech32(network_id()),
+            slot: slot.to_string(),
+            key: Some(key.to_hex()),
+            value: value.to_hex(),
+        }
+    }
+
+    pub fn balance(account_id: AccountId, faucet_id: AccountId, amount: u64) -> Self {
+        Event::Balance {
+            account_id: account_id.to_bech32(network_id()),
+            faucet_id: faucet_id.to_bech32(network_id()),
+            amount,
+        }
+    }
+
+    /// Reads a narration line of the form `[STEP N] title`, ignoring leading whitespace.
+    pub fn step(line: &str) -> Option<Self> {
+        let (step, title) = line.trim_start().strip_prefix("[STEP ")?.split_once(']')?;
+        Some(Event::Step {
+            step: step.parse().ok()?,
+            title: title.trim().to_string(),
+        })
+    }
+
+    /// The event as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("events serialize to JSON")
+    }
+}
+
+/// Prints the event on stdout under `--json` and hands it to the installed [`Reporter`].
+pub fn emit(event: Event) {
+    if run_mode().json {
+        println!("{}", event.to_json());
+    }
+    if let Some(reporter) = REPORTER.get() {
+        reporter.send(&event);
+    }
+}
+
+/// Where events are mirrored besides the `--json` stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sink {
+    /// One JSON line per event on stdout, as under `--json`.
+    Stdout,
+    /// One JSON line per event appended to the file.
+    File(PathBuf),
+    /// Each event POSTed as a JSON body to the URL.
+    Webhook(String),
+}
+
+impl FromStr for Sink {
+    type Err = String;
+
+    /// `stdout` or `-`, an `http://` or `https://` URL, or else a file path.
+    fn from_str(sink: &str) -> Result<Self, Self::Err> {
+        match sink {
+            "" => Err("empty report sink".to_string()),
+            "stdout" | "-" => Ok(Sink::Stdout),
+            url if url.starts_with("http://") || url.starts_with("https://") => {
+                Ok(Sink::Webhook(url.to_string()))
+            }
+            path => Ok(Sink::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl fmt::Display for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sink::Stdout => f.write_str("stdout"),
+            Sink::File(path) => write!(f, "{}", path.display()),
+            Sink::Webhook(url) => f.write_str(url),
+        }
+    }
+}
+
+/// How long a webhook may take to accept an event before it is skipped.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Events waiting for a webhook before new ones are dropped.
+const WEBHOOK_QUEUE: usize = 64;
+
+/// How long [`shutdown`] waits for the webhooks to post what is still queued.
+const WEBHOOK_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+static REPORTER: OnceLock<Reporter> = OnceLock::new();
+
+/// The open [`Sink`]s events are sent to.
+///
+/// A sink that fails is reported on stderr and skipped for the rest of the run,
+/// so a monitoring endpoint going down never stops the tutorial it watches.
+/// Webhooks are posted to from a thread of their own. While one is backed up
+/// new events for it are dropped, and [`shutdown`] gives it a few seconds to
+/// post what is still queued when the run exits.
+#[derive(Debug)]
+pub struct Reporter {
+    sinks: Vec<OpenSink>,
+}
+
+#[derive(Debug)]
+struct OpenSink {
+    sink: Sink,
+    target: Target,
+    failed: AtomicBool,
+}
+
+#[derive(Debug)]
+enum Target {
+    Stdout,
+    File(Mutex<File>),
+    Webhook(Webhook),
+}
+
+/// The thread posting to a webhook, see [`spawn_webhook`].
+#[derive(Debug)]
+struct Webhook {
+    /// Taken by [`Reporter::shutdown`], which closes the queue.
+    queue: Mutex<Option<SyncSender<String>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+    backed_up: AtomicBool,
+}
+
+impl Reporter {
+    /// Opens every sink, creating files that do not exist yet.
+    pub fn open(sinks: impl IntoIterator<Item = Sink>) -> io::Result<Self> {
+        let sinks = sinks
+            .into_iter()
+            .map(|sink| {
+                let target = match &sink {
+                    Sink::Stdout => Target::Stdout,
+                    Sink::File(path) => Target::File(Mutex::new(
+                        OpenOptions::new()
+                            .create(true)
+                            .append(true)
+                            .open(path)
+                            .map_err(|err| io::Error::new(err.kind(), format!("{sink}: {err}")))?,
+                    )),
+                    Sink::Webhook(url) => {
+                        let (queue, worker) = spawn_webhook(url.clone())?;
+                        Target::Webhook(Webhook {
+                            queue: Mutex::new(Some(queue)),
+                            worker: Mutex::new(Some(worker)),
+                            backed_up: AtomicBool::new(false),
+                        })
+                    }
+                };
+                Ok(OpenSink {
+                    sink,
+                    target,
+                    failed: AtomicBool::new(false),
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Self { sinks })
+    }
+
+    /// Sends `event` to every sink.
+    pub fn send(&self, event: &Event) {
+        let line = event.to_json();
+        for open in &self.sinks {
+            if open.failed.load(Ordering::Relaxed) {
+                continue;
+            }
+            let result = match &open.target {
+                // `--json` already prints every event on stdout
+                Target::Stdout if run_mode().json => Ok(()),
+                Target::Stdout => {
+                    println!("{line}");
+                    Ok(())
+                }
+                Target::File(file) => {
+                    let mut file = file.lock().unwrap_or_else(|err| err.into_inner());
+                    writeln!(file, "{line}").map_err(|err| err.to_string())
+                }
+                Target::Webhook(webhook) => match webhook
+                    .queue
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .as_ref()
+                    .map(|queue| queue.try_send(line.clone()))
+                {
+                    // Shut down already, the run is exiting
+                    None => Ok(()),
+                    Some(Ok(())) => {
+                        webhook.backed_up.store(false, Ordering::Relaxed);
+                        Ok(())
+                    }
+                    Some(Err(TrySendError::Full(_))) => {
+                        if !webhook.backed_up.swap(true, Ordering::Relaxed) {
+                            eprintln!(
+                                "Report sink {} is backed up, dropping events until it catches up",
+                                open.sink
+                            );
+                        }
+                        Ok(())
+                    }
+                    // The posting thread stopped after reporting the failure
+                    Some(Err(TrySendError::Disconnected(_))) => {
+                        open.failed.store(true, Ordering::Relaxed);
+                        Ok(())
+                    }
+                },
+            };
+            if let Err(err) = result {
+                if !open.failed.swap(true, Ordering::Relaxed) {
+                    eprintln!(
+                        "Report sink {} failed, skipping it from now on: {err}",
+                        open.sink
+                    );
+                }
+            }
+        }
+    }
+
+    /// Sends events to these sinks for the rest of the run. The first call wins.
+    pub fn install(self) {
+        let _ = REPORTER.set(self);
+    }
+
+    /// Closes the webhook queues and waits up to `timeout` for the webhooks to
+    /// post the events still in them.
+    ///
+    /// Events sent afterwards are dropped. Returns `false` if a webhook was
+    /// still posting when `timeout` passed.
+    pub fn shutdown(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut workers = Vec::new();
+        for open in &self.sinks {
+            if let Target::Webhook(webhook) = &open.target {
+                // Dropping the sender ends the worker once the queue is empty
+                drop(
+                    webhook
+                        .queue
+                        .lock()
+                        .unwrap_or_else(|err| err.into_inner())
+                        .take(),
+                );
+                workers.extend(
+                    webhook
+                        .worker
+                        .lock()
+                        .unwrap_or_else(|err| err.into_inner())
+                        .take(),
+                );
+            }
+        }
+
+        // `JoinHandle::join` cannot time out, so poll until the workers finish
+        while workers.iter().any(|worker| !worker.is_finished()) {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        for worker in workers {
+            let _ = worker.join();
+        }
+        true
+    }
+}
+
+/// Flushes the installed [`Reporter`] before the run exits.
+///
+/// Called by [`crate::error::report`] next to the session summary. Events the
+/// webhooks could not post within a few seconds are given up on.
+pub fn shutdown() {
+    let Some(reporter) = REPORTER.get() else {
+        return;
+    };
+    if !reporter.shutdown(WEBHOOK_DRAIN_TIMEOUT) {
+        eprintln!(
+            "Report webhooks did not catch up within {:.0?}, their last events are lost",
+            WEBHOOK_DRAIN_TIMEOUT
+        );
+    }
+}
+
+/// Starts the thread posting events to a webhook and returns its queue.
+///
+/// The thread reports the first failed POST on stderr and stops, which the
+/// sending side sees as a disconnected queue. It also stops once the queue is
+/// closed and empty.
+fn spawn_webhook(url: String) -> io::Result<(SyncSender<String>, JoinHandle<()>)> {
+    let (queue, events) = mpsc::sync_channel::<String>(WEBHOOK_QUEUE);
+    let agent = ureq::AgentBuilder::new().timeout(WEBHOOK_TIMEOUT).build();
+    let worker = thread::Builder::new()
+        .name("report-webhook".to_string())
+        .spawn(move || {
+            for line in events {
+                let result = agent
+                    .post(&url)
+                    .set("Content-Type", "application/json")
+                    .send_string(&line);
+                if let Err(err) = result {
+                    eprintln!("Report sink {url} failed, skipping it from now on: {err}");
+                    return;
+                }
+            }
+        })?;
+    Ok((queue, worker))
+}
@@ -0,0 +1,302 @@
+//! SDK-style wrapper around the payment stream in `masm/accounts/payment_stream.masm`.
+//!
+//! One stream account pays one payee on behalf of one payer. The payer locks
+//! the whole deposit with a note, and from the start block on every block
+//! streams a fixed rate of it to the payee. The payee withdraws streamed tokens
+//! whenever they like with a note naming the amount. The payer can cancel at
+//! any time: the tokens that have not streamed yet go back to the payer, and
+//! the payee keeps what streamed until the cancel block.
+
+use miden_client::{
+    account::{AccountId, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const PARTIES_SLOT: &str = "miden::tutorials::payment_stream::parties";
+pub const TERMS_SLOT: &str = "miden::tutorials::payment_stream::terms";
+pub const DEPOSIT_SLOT: &str = "miden::tutorials::payment_stream::deposit";
+pub const STATE_SLOT: &str = "miden::tutorials::payment_stream::state";
+
+/// Library path the stream procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::payment_stream_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/payment_stream.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/payment_stream.masm");
+const FUND_NOTE_FILE: &str = "masm/notes/stream_fund_note.masm";
+const FUND_NOTE_CODE: &str = include_str!("../../masm/notes/stream_fund_note.masm");
+const WITHDRAW_NOTE_FILE: &str = "masm/notes/stream_withdraw_note.masm";
+const WITHDRAW_NOTE_CODE: &str = include_str!("../../masm/notes/stream_withdraw_note.masm");
+const CANCEL_NOTE_FILE: &str = "masm/notes/stream_cancel_note.masm";
+const CANCEL_NOTE_CODE: &str = include_str!("../../masm/notes/stream_cancel_note.masm");
+
+/// How fast a deposit streams, mirroring `streamed_at` in the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamTerms {
+    /// Block the stream starts at.
+    pub start: u32,
+    /// Tokens streamed per block.
+    pub rate: u64,
+    /// The whole deposit, which the payer locks at once.
+    pub total: u64,
+}
+
+impl StreamTerms {
+    /// First block at which the whole deposit has streamed.
+    pub fn end(&self) -> u32 {
+        self.start + self.total.div_ceil(self.rate) as u32
+    }
+
+    /// Tokens streamed as of `block`: `rate` per block since the start, up to the total.
+    pub fn streamed_at(&self, block: u32) -> u64 {
+        if block >= self.end() {
+            return self.total;
+        }
+        if block < self.start {
+            return 0;
+        }
+        (block - self.start) as u64 * self.rate
+    }
+}
+
+/// Progress of a stream, as stored by the contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamState {
+    /// Tokens the payee has withdrawn so far.
+    pub withdrawn: u64,
+    /// The block the payer cancelled the stream at, if they did.
+    pub cancelled_at: Option<u32>,
+}
+
+/// A deployed payment stream.
+pub struct PaymentStream {
+    pub account_id: AccountId,
+    pub payer: AccountId,
+    pub payee: AccountId,
+    /// The whole deposit, in the token it streams in.
+    pub deposit: FungibleAsset,
+    pub terms: StreamTerms,
+    library: Library,
+}
+
+impl PaymentStream {
+    /// Builds a new stream in the token of `faucet_id` and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the fund note it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        payer: AccountId,
+        payee: AccountId,
+        faucet_id: AccountId,
+        terms: StreamTerms,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if terms.rate == 0 || terms.rate > terms.total {
+            return Err("a stream pays between one token and its total per block".into());
+        }
+        // Fails when the total is above the largest fungible amount
+        let deposit = FungibleAsset::new(faucet_id, terms.total)?;
+
+        let parties = Word::from([
+            payee.suffix(),
+            payee.prefix().as_felt(),
+            payer.suffix(),
+            payer.prefix().as_felt(),
+        ]);
+        let terms_word = Word::from([
+            Felt::new(terms.total),
+            Felt::new(terms.rate),
+            Felt::new(terms.end() as u64),
+            Felt::new(terms.start as u64),
+        ]);
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(PARTIES_SLOT)?, parties),
+            StorageSlot::with_value(StorageSlotName::new(TERMS_SLOT)?, terms_word),
+            StorageSlot::with_value(StorageSlotName::new(DEPOSIT_SLOT)?, Word::default()),
+            StorageSlot::with_value(StorageSlotName::new(STATE_SLOT)?, Word::default()),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            payer,
+            payee,
+            deposit,
+            terms,
+            library,
+        })
+    }
+
+    /// Moves the whole deposit from the payer into the contract.
+    pub async fn fund(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script =
+            self.note_script(client, MasmFile::embedded(FUND_NOTE_FILE, FUND_NOTE_CODE))?;
+        let note = NoteBuilder::new(self.payer, script)
+            .asset(self.deposit)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, self.payer, note, None).await
+    }
+
+    /// Has the payee withdraw `amount` streamed tokens and returns the P2ID note paying them.
+    ///
+    /// The contract checks the amount against the stream at the reference
+    /// block of its transaction, which is the block the client last synced to.
+    pub async fn withdraw(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        amount: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let payout = self.payout_note(client, self.payee, amount)?;
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        inputs.push(Felt::new(amount));
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(WITHDRAW_NOTE_FILE, WITHDRAW_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(self.payee, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, self.payee, note, Some(&payout))
+            .await?;
+        Ok((report, payout))
+    }
+
+    /// Has the payer cancel the stream and returns the P2ID note refunding what
+    /// has not streamed yet.
+    ///
+    /// The stream stops at the block the client last synced to, which is the
+    /// reference block of the cancel transaction.
+    pub async fn cancel(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let height = client.get_sync_height().await?.as_u32();
+        let refund = self.terms.total - self.terms.streamed_at(height);
+        let payout = self.payout_note(client, self.payer, refund)?;
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(CANCEL_NOTE_FILE, CANCEL_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(self.payer, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, self.payer, note, Some(&payout))
+            .await?;
+        Ok((report, payout))
+    }
+
+    /// Returns the progress of the stream, as of the last sync.
+    pub async fn state(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<StreamState, ClientError> {
+        let stream = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(STATE_SLOT).expect("valid slot name");
+        let state = stream
+            .storage()
+            .get_item(&slot)
+            .expect("stream account is missing its state slot");
+        // Stored as [withdrawn, cancelled, cancelled_at, 0] on the stack, so reversed in the word
+        Ok(StreamState {
+            withdrawn: state[3].as_int(),
+            cancelled_at: (state[2].as_int() != 0).then(|| state[1].as_int() as u32),
+        })
+    }
+
+    /// Returns the tokens the payee can withdraw at the block the client last synced to.
+    pub async fn withdrawable(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<u64, ClientError> {
+        let height = client.get_sync_height().await?.as_u32();
+        let state = self.state(client).await?;
+        let block = state.cancelled_at.unwrap_or(height);
+        Ok(self.terms.streamed_at(block) - state.withdrawn)
+    }
+
+    fn payout_note(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        target: AccountId,
+        amount: u64,
+    ) -> Result<Note, Box<dyn std::error::Error>> {
+        Ok(create_p2id_note(
+            self.account_id,
+            target,
+            vec![FungibleAsset::new(self.deposit.faucet_id(), amount)?.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?)
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the contract itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("stream note script does not compile");
+        Ok(script)
+    }
+
+    /// Sends `note` from `sender` and has the stream consume it right away.
+    async fn send_and_consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        note: Note,
+        payout: Option<&Note>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, sender, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .expected_output_recipients(
+                payout
+                    .map(|payout| payout.recipient().clone())
+                    .into_iter()
+                    .collect(),
+            )
+            .build()?;
+        submit_and_wait(client, self.account_id, consume_request).await
+    }
+}
@@ -0,0 +1,393 @@
+//! SDK-style wrapper around the liquidity pool contract in `masm/accounts/pool.masm`.
+//!
+//! The pool holds two tokens and prices swaps between them so that the product
+//! of its reserves never decreases. Liquidity providers deposit both tokens for
+//! shares and burn shares for their part of both reserves. Every operation is a
+//! note sent to the pool; swaps and withdrawals are paid out with a P2ID note.
+//! Deployed on the network, the pool takes swaps without its deployer being
+//! online, as the network transaction builder consumes them.
+//!
+//! The MASM works on u32 values, so amounts must keep every product of an
+//! amount and a reserve below 2^32. The functions below mirror its arithmetic,
+//! rounding included, so the driver can predict each payout.
+
+use miden_client::{
+    account::{AccountId, AccountStorageMode, StorageMap, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::{Asset, FungibleAsset},
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteError, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const TOKEN_A_SLOT: &str = "miden::tutorials::pool::token_a";
+pub const TOKEN_B_SLOT: &str = "miden::tutorials::pool::token_b";
+pub const RESERVES_SLOT: &str = "miden::tutorials::pool::reserves";
+pub const TOTAL_SHARES_SLOT: &str = "miden::tutorials::pool::total_shares";
+pub const SHARES_SLOT: &str = "miden::tutorials::pool::shares";
+
+/// Library path the pool procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::pool_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/pool.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/pool.masm");
+const ADD_NOTE_FILE: &str = "masm/notes/pool_add_liquidity_note.masm";
+const ADD_NOTE_CODE: &str = include_str!("../../masm/notes/pool_add_liquidity_note.masm");
+const SWAP_NOTE_FILE: &str = "masm/notes/pool_swap_note.masm";
+const SWAP_NOTE_CODE: &str = include_str!("../../masm/notes/pool_swap_note.masm");
+const REMOVE_NOTE_FILE: &str = "masm/notes/pool_remove_liquidity_note.masm";
+const REMOVE_NOTE_CODE: &str = include_str!("../../masm/notes/pool_remove_liquidity_note.masm");
+
+/// Output of a swap of `amount_in` against the given reserves.
+///
+/// Rounds down, so the pool keeps the remainder and the product of the
+/// reserves never decreases.
+pub fn quote(amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+    amount_in * reserve_out / (reserve_in + amount_in)
+}
+
+/// Reserves and shares of a pool, as stored by the contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolState {
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub total_shares: u64,
+}
+
+impl PoolState {
+    /// The constant product invariant, `reserve_a * reserve_b`.
+    pub fn k(&self) -> u128 {
+        self.reserve_a as u128 * self.reserve_b as u128
+    }
+
+    /// Shares issued for depositing `amount_a` and `amount_b`.
+    ///
+    /// The first deposit gets one share per token A. Later ones get shares in
+    /// proportion to the smaller of their two contributions.
+    pub fn shares_for(&self, amount_a: u64, amount_b: u64) -> u64 {
+        if self.total_shares == 0 {
+            return amount_a;
+        }
+        let shares_a = amount_a * self.total_shares / self.reserve_a;
+        let shares_b = amount_b * self.total_shares / self.reserve_b;
+        shares_a.min(shares_b)
+    }
+
+    /// Tokens A and B paid out for burning `shares`.
+    pub fn withdrawal(&self, shares: u64) -> (u64, u64) {
+        (
+            shares * self.reserve_a / self.total_shares,
+            shares * self.reserve_b / self.total_shares,
+        )
+    }
+}
+
+/// A deployed pool contract trading `token_a` against `token_b`.
+pub struct Pool {
+    pub account_id: AccountId,
+    pub token_a: AccountId,
+    pub token_b: AccountId,
+    library: Library,
+}
+
+impl Pool {
+    /// Builds a new, empty pool for the two tokens and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the first note it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        token_a: AccountId,
+        token_b: AccountId,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::deploy_with(client, token_a, token_b, AccountStorageMode::Public).await
+    }
+
+    /// Like [`Pool::deploy`], but as a network account, whose swap notes the
+    /// network transaction builder consumes once they are sent with
+    /// [`Pool::send_swap`].
+    ///
+    /// The client still consumes the first deposit itself, which deploys the
+    /// account on the network.
+    pub async fn deploy_on_network(
+        client: &mut Client<FilesystemKeyStore>,
+        token_a: AccountId,
+        token_b: AccountId,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::deploy_with(client, token_a, token_b, AccountStorageMode::Network).await
+    }
+
+    async fn deploy_with(
+        client: &mut Client<FilesystemKeyStore>,
+        token_a: AccountId,
+        token_b: AccountId,
+        storage_mode: AccountStorageMode,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(TOKEN_A_SLOT)?, account_word(token_a)),
+            StorageSlot::with_value(StorageSlotName::new(TOKEN_B_SLOT)?, account_word(token_b)),
+            StorageSlot::with_value(StorageSlotName::new(RESERVES_SLOT)?, Word::default()),
+            StorageSlot::with_value(StorageSlotName::new(TOTAL_SHARES_SLOT)?, Word::default()),
+            StorageSlot::with_map(
+                StorageSlotName::new(SHARES_SLOT)?,
+                StorageMap::with_entries([])?,
+            ),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots)
+        .storage_mode(storage_mode);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            token_a,
+            token_b,
+            library,
+        })
+    }
+
+    /// Returns the reserves and total shares, as of the last sync.
+    pub async fn state(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<PoolState, ClientError> {
+        let pool = get_full_account(client, self.account_id).await?;
+        let reserves = pool
+            .storage()
+            .get_item(&StorageSlotName::new(RESERVES_SLOT).expect("valid slot name"))
+            .expect("pool account is missing its reserves slot");
+        let total_shares = pool
+            .storage()
+            .get_item(&StorageSlotName::new(TOTAL_SHARES_SLOT).expect("valid slot name"))
+            .expect("pool account is missing its total shares slot");
+        // Stored as [reserve_a, reserve_b, 0, 0] on the stack, so reversed in the word
+        Ok(PoolState {
+            reserve_a: reserves[3].as_int(),
+            reserve_b: reserves[2].as_int(),
+            total_shares: total_shares[3].as_int(),
+        })
+    }
+
+    /// Returns the shares held by `user`, as of the last sync.
+    pub async fn shares_of(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+    ) -> Result<u64, ClientError> {
+        let pool = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(SHARES_SLOT).expect("valid slot name");
+        let shares = pool
+            .storage()
+            .get_map_item(&slot, account_word(user))
+            .expect("pool account is missing its shares slot");
+        // The shares sit on top of the stack, which is the last element of the word
+        Ok(shares[3].as_int())
+    }
+
+    /// Moves `amount_a` and `amount_b` from `user` into the pool for shares.
+    pub async fn add_liquidity(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let asset_a = FungibleAsset::new(self.token_a, amount_a).expect("invalid amount of A");
+        let asset_b = FungibleAsset::new(self.token_b, amount_b).expect("invalid amount of B");
+        let script = self.note_script(client, MasmFile::embedded(ADD_NOTE_FILE, ADD_NOTE_CODE))?;
+        let note = NoteBuilder::new(user, script)
+            .asset(asset_a)
+            .asset(asset_b)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        self.send_and_consume(client, user, note, vec![]).await
+    }
+
+    /// Swaps `asset_in` for the other token and returns the P2ID note paying `user`.
+    ///
+    /// The pool rejects the swap if it would pay out less than `min_out`. The
+    /// payout note is predicted from the last synced state, so the pool must not
+    /// trade in between.
+    pub async fn swap(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        asset_in: FungibleAsset,
+        min_out: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let (builder, payout) = self.swap_note(client, user, asset_in, min_out).await?;
+        let note = builder
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, user, note, vec![payout.clone()])
+            .await?;
+        Ok((report, payout))
+    }
+
+    /// Sends a swap of `asset_in` to a pool deployed with [`Pool::deploy_on_network`].
+    ///
+    /// Returns the swap note and the P2ID note paying `user` once the network
+    /// transaction builder has consumed it. As with [`Pool::swap`], the payout
+    /// is predicted from the last synced state, so no other swap may land
+    /// first. If one does and the output drops below `min_out`, the pool
+    /// rejects the note and it stays unconsumed.
+    pub async fn send_swap(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        asset_in: FungibleAsset,
+        min_out: u64,
+    ) -> Result<(Note, Note), Box<dyn std::error::Error>> {
+        let (builder, payout) = self.swap_note(client, user, asset_in, min_out).await?;
+        let note = builder
+            .network_target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, user, note_request).await?;
+        Ok((note, payout))
+    }
+
+    /// Burns `shares` of `user` and returns the P2ID note paying out both tokens.
+    pub async fn remove_liquidity(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        shares: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let (amount_a, amount_b) = self.state(client).await?.withdrawal(shares);
+        let asset_a = FungibleAsset::new(self.token_a, amount_a).expect("invalid amount of A");
+        let asset_b = FungibleAsset::new(self.token_b, amount_b).expect("invalid amount of B");
+        // Same order as the pool adds them to the note
+        let payout = self.payout(client, user, vec![asset_a.into(), asset_b.into()])?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.extend([payout.metadata().tag().into(), Felt::new(shares)]);
+        let script = self.note_script(
+            client,
+            MasmFile::embedded(REMOVE_NOTE_FILE, REMOVE_NOTE_CODE),
+        )?;
+        let note = NoteBuilder::new(user, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, user, note, vec![payout.clone()])
+            .await?;
+        Ok((report, payout))
+    }
+
+    /// A swap note from `user` without its target, and the payout it asks for.
+    async fn swap_note(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        asset_in: FungibleAsset,
+        min_out: u64,
+    ) -> Result<(NoteBuilder, Note), Box<dyn std::error::Error>> {
+        let state = self.state(client).await?;
+        let (token_out, reserve_in, reserve_out) = if asset_in.faucet_id() == self.token_a {
+            (self.token_b, state.reserve_a, state.reserve_b)
+        } else {
+            (self.token_a, state.reserve_b, state.reserve_a)
+        };
+        let amount_out = quote(asset_in.amount(), reserve_in, reserve_out);
+        let asset_out = FungibleAsset::new(token_out, amount_out).expect("invalid swap output");
+        let payout = self.payout(client, user, vec![asset_out.into()])?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.extend([payout.metadata().tag().into(), Felt::new(min_out)]);
+        let script =
+            self.note_script(client, MasmFile::embedded(SWAP_NOTE_FILE, SWAP_NOTE_CODE))?;
+        let builder = NoteBuilder::new(user, script)
+            .inputs(inputs)
+            .asset(asset_in);
+        Ok((builder, payout))
+    }
+
+    /// The public P2ID note the pool creates to pay `user`.
+    fn payout(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        assets: Vec<Asset>,
+    ) -> Result<Note, NoteError> {
+        create_p2id_note(
+            self.account_id,
+            user,
+            assets,
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the pool itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("pool note script does not compile");
+        Ok(script)
+    }
+
+    /// Sends `note` from `user` and has the pool consume it right away.
+    async fn send_and_consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        user: AccountId,
+        note: Note,
+        expected_outputs: Vec<Note>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, user, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .expected_output_recipients(
+                expected_outputs
+                    .iter()
+                    .map(|note| note.recipient().clone())
+                    .collect(),
+            )
+            .build()?;
+        submit_and_wait(client, self.account_id, consume_request).await
+    }
+}
+
+/// An account ID as `[prefix, suffix, 0, 0]` on the stack, used for the token
+/// slots and as the key of a provider's shares.
+fn account_word(account_id: AccountId) -> Word {
+    Word::from([
+        Felt::new(0),
+        Felt::new(0),
+        account_id.suffix(),
+        account_id.prefix().as_felt(),
+    ])
+}
@@ -0,0 +1,154 @@
+//! Several user profiles in one process, each with its own store and keystore.
+//!
+//! Multi-party tutorials usually keep Alice and Bob in one client, which hides
+//! that in practice each of them runs a client of their own and only sees their
+//! own keys and notes. A [`ProfileManager`] builds one client per persona under
+//! `profiles/<name>/`, next to the configured store:
+//!
+//! ```text
+//! profiles/alice/store.sqlite3
+//! profiles/alice/keystore/
+//! ```
+//!
+//! Profiles persist between runs, so a persona keeps its wallet.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use miden_client::{account::AccountId, keystore::FilesystemKeyStore, Client, ClientError};
+
+use crate::{accounts::create_basic_account, client::ClientSetup, say};
+
+/// Directory the profiles live in, relative to the configured store.
+pub const PROFILES_DIR: &str = "profiles";
+
+/// One persona's client, keystore and wallet.
+pub struct Profile {
+    name: String,
+    pub client: Client<FilesystemKeyStore>,
+    pub keystore: Arc<FilesystemKeyStore>,
+    wallet: Option<AccountId>,
+}
+
+impl Profile {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The profile's wallet, found in its store or created on first use.
+    pub async fn wallet(&mut self) -> Result<AccountId, ClientError> {
+        if let Some(wallet) = self.wallet {
+            return Ok(wallet);
+        }
+        let tracked = self
+            .client
+            .get_account_headers()
+            .await?
+            .into_iter()
+            .map(|(header, _)| header.id())
+            .find(|id| !id.is_faucet());
+        let wallet = match tracked {
+            Some(id) => id,
+            None => {
+                say!("Creating a wallet for {}", self.name);
+                create_basic_account(&mut self.client, &self.keystore)
+                    .await?
+                    .id()
+            }
+        };
+        self.wallet = Some(wallet);
+        Ok(wallet)
+    }
+}
+
+/// Opens profiles by name and switches between them.
+pub struct ProfileManager {
+    setup: ClientSetup,
+    dir: PathBuf,
+    profiles: BTreeMap<String, Profile>,
+}
+
+impl ProfileManager {
+    /// Keeps the profiles in [`PROFILES_DIR`] next to the store of `setup`.
+    ///
+    /// Every profile is built from `setup` with only the store and keystore
+    /// paths replaced, so it talks to the same network.
+    pub fn new(setup: ClientSetup) -> Self {
+        let dir = setup
+            .store_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(PROFILES_DIR);
+        Self {
+            setup,
+            dir,
+            profiles: BTreeMap::new(),
+        }
+    }
+
+    /// Keeps the profiles in `dir` instead.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Directory holding the store and keystore of profile `name`.
+    pub fn profile_dir(&self, name: &str) -> PathBuf {
+        self.dir.join(name.to_lowercase())
+    }
+
+    pub fn store_path(&self, name: &str) -> PathBuf {
+        self.profile_dir(name).join("store.sqlite3")
+    }
+
+    pub fn keystore_path(&self, name: &str) -> PathBuf {
+        self.profile_dir(name).join("keystore")
+    }
+
+    /// Names of the profiles opened so far.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.values().map(Profile::name)
+    }
+
+    /// Returns profile `name`, building its client the first time and syncing it.
+    pub async fn switch(&mut self, name: &str) -> Result<&mut Profile, ClientError> {
+        let key = name.to_lowercase();
+        if !self.profiles.contains_key(&key) {
+            let profile = self.open(name).await?;
+            self.profiles.insert(key.clone(), profile);
+        }
+        say!("Acting as {name}");
+
+        let profile = self
+            .profiles
+            .get_mut(&key)
+            .expect("profile was just opened");
+        profile.client.sync_state().await?;
+        Ok(profile)
+    }
+
+    async fn open(&self, name: &str) -> Result<Profile, ClientError> {
+        fs::create_dir_all(self.profile_dir(name)).map_err(|err| {
+            ClientError::ClientInitializationError(format!(
+                "cannot create profile {name} in {}: {err}",
+                self.dir.display()
+            ))
+        })?;
+        let setup = ClientSetup {
+            store_path: self.store_path(name),
+            keystore_path: self.keystore_path(name),
+            ..self.setup.clone()
+        };
+        let (client, keystore) = setup.build().await?;
+        Ok(Profile {
+            name: name.to_string(),
+            client,
+            keystore,
+            wallet: None,
+        })
+    }
+}
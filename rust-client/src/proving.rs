@@ -0,0 +1,203 @@
+//! Local proving under the `ProvingOptions` presets, and the dummy transaction
+//! the proving tutorials prove.
+//!
+//! A preset trades proof size and proving time for security: a higher
+//! security target needs more FRI queries, so the proof grows and takes longer
+//! to build. [`prove_with`] proves a transaction under one preset and returns
+//! [`ProofMetrics`] next to the proof, and [`print_comparison`] lays several of
+//! them out side by side. [`ProofBundle`] carries a proof from the machine
+//! that built it to the one that submits it.
+
+use std::{fs, io, path::Path, sync::Arc, time::Duration};
+
+use rand::RngCore;
+use tokio::time::Instant;
+
+use miden_client::{
+    account::{
+        component::BasicWallet, Account, AccountBuilder, AccountFile, AccountStorageMode,
+        AccountType,
+    },
+    auth::{AuthFalcon512Rpo, AuthSecretKey},
+    keystore::FilesystemKeyStore,
+    transaction::{
+        LocalTransactionProver, ProvenTransaction, ProvingOptions, TransactionProver,
+        TransactionRequest, TransactionRequestBuilder, TransactionResult,
+    },
+    utils::{Deserializable, Serializable},
+    Client, ClientError,
+};
+
+use crate::say;
+
+/// Every `ProvingOptions` preset with a display name, the default first.
+///
+/// All keep the hash function of the default options, so only the proof
+/// parameters differ. The security a proof reaches is also bounded by that hash
+/// function, which is why [`ProofMetrics`] reads the level from the proof
+/// rather than trusting the preset's name.
+pub fn presets() -> [(&'static str, ProvingOptions); 2] {
+    let hash_fn = ProvingOptions::default().hash_fn();
+    [
+        (
+            "96-bit (default)",
+            ProvingOptions::with_96_bit_security(hash_fn),
+        ),
+        ("128-bit", ProvingOptions::with_128_bit_security(hash_fn)),
+    ]
+}
+
+/// What one proof cost and what it guarantees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofMetrics {
+    pub preset: &'static str,
+    /// Size of the serialized execution proof.
+    pub proof_bytes: usize,
+    pub elapsed: Duration,
+    /// Conjectured security of the proof, in bits.
+    pub security_level: u32,
+    /// Hash function the proof commits with.
+    pub hash: String,
+}
+
+/// Proves `tx_result` on this machine under `options` and measures the proof.
+pub async fn prove_with(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_result: &TransactionResult,
+    preset: &'static str,
+    options: ProvingOptions,
+) -> Result<(ProvenTransaction, ProofMetrics), ClientError> {
+    let tx_prover: Arc<dyn TransactionProver> = Arc::new(LocalTransactionProver::new(options));
+    let start = Instant::now();
+    let proven_transaction = client.prove_transaction_with(tx_result, tx_prover).await?;
+    let elapsed = start.elapsed();
+    let proof = proven_transaction.proof();
+    let metrics = ProofMetrics {
+        preset,
+        proof_bytes: proof.to_bytes().len(),
+        elapsed,
+        security_level: proof.security_level(),
+        hash: format!("{:?}", proof.hash_fn()),
+    };
+    Ok((proven_transaction, metrics))
+}
+
+/// Prints one row per proof, with time and size relative to the first row.
+pub fn print_comparison(metrics: &[ProofMetrics]) {
+    say!(
+        "{:<18} {:>12} {:>10} {:>10} {:>10} {:>12}",
+        "preset",
+        "proof bytes",
+        "time",
+        "security",
+        "hash",
+        "vs first"
+    );
+    let Some(first) = metrics.first() else {
+        return;
+    };
+    for row in metrics {
+        say!(
+            "{:<18} {:>12} {:>10} {:>10} {:>10} {:>12}",
+            row.preset,
+            row.proof_bytes,
+            format!("{:.2?}", row.elapsed),
+            format!("{} bits", row.security_level),
+            row.hash,
+            format!(
+                "{:.1}x / {:.1}x",
+                row.elapsed.as_secs_f64() / first.elapsed.as_secs_f64(),
+                row.proof_bytes as f64 / first.proof_bytes as f64
+            )
+        );
+    }
+}
+
+/// Creates a private wallet for the dummy transaction and starts tracking it.
+pub async fn create_nonce_bump_account(
+    client: &mut Client<FilesystemKeyStore>,
+    keystore: &Arc<FilesystemKeyStore>,
+) -> Result<Account, Box<dyn std::error::Error>> {
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let key_pair = AuthSecretKey::new_falcon512_rpo();
+
+    let account = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Private)
+        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
+        .with_component(BasicWallet)
+        .build()?;
+
+    client.add_account(&account, false).await?;
+    keystore.add_key(&key_pair)?;
+    Ok(account)
+}
+
+/// A transaction whose only effect is increasing the account's nonce.
+///
+/// It keeps the proof about as small as a proof gets, so what is measured is
+/// the prover rather than the transaction.
+pub fn nonce_bump_request(
+    client: &mut Client<FilesystemKeyStore>,
+) -> Result<TransactionRequest, Box<dyn std::error::Error>> {
+    let tx_script = client
+        .code_builder()
+        .compile_tx_script("begin push.1 drop end")?;
+    Ok(TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()?)
+}
+
+/// A transaction proven on one machine, in the files another machine needs to
+/// submit it.
+///
+/// The account travels without its keys: the submitting client only tracks it
+/// so it can apply the transaction, and never signs anything.
+pub struct ProofBundle {
+    /// The account as it was before the transaction.
+    pub account: Account,
+    pub proven_transaction: ProvenTransaction,
+    pub tx_result: TransactionResult,
+}
+
+impl ProofBundle {
+    pub const ACCOUNT_FILE: &'static str = "account.mac";
+    pub const PROVEN_FILE: &'static str = "proven_tx.bin";
+    pub const RESULT_FILE: &'static str = "tx_result.bin";
+
+    /// Writes the three files into `dir`, creating it if needed.
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let account_file = AccountFile::new(self.account.clone(), vec![]);
+        fs::write(dir.join(Self::ACCOUNT_FILE), account_file.to_bytes())?;
+        fs::write(
+            dir.join(Self::PROVEN_FILE),
+            self.proven_transaction.to_bytes(),
+        )?;
+        fs::write(dir.join(Self::RESULT_FILE), self.tx_result.to_bytes())
+    }
+
+    /// Reads a bundle written by [`ProofBundle::write`].
+    pub fn read(dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let read = |name: &str| fs::read(dir.join(name));
+        let account_file = AccountFile::read_from_bytes(&read(Self::ACCOUNT_FILE)?)?;
+        let proven_transaction = ProvenTransaction::read_from_bytes(&read(Self::PROVEN_FILE)?)?;
+        let tx_result = TransactionResult::read_from_bytes(&read(Self::RESULT_FILE)?)?;
+        if proven_transaction.account_id() != account_file.account.id() {
+            return Err(format!(
+                "{} proves a transaction of account {}, not {}",
+                Self::PROVEN_FILE,
+                proven_transaction.account_id(),
+                account_file.account.id()
+            )
+            .into());
+        }
+        Ok(Self {
+            account: account_file.account,
+            proven_transaction,
+            tx_result,
+        })
+    }
+}
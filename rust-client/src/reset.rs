@@ -0,0 +1,73 @@
+//! Deletes the local state of a client once it no longer matches the chain.
+//!
+//! After a devnet reset the node starts over from a new genesis block, and a
+//! store synced against the old chain fails on its next sync. Such a store
+//! cannot be repaired, only started again: [`LocalState`] finds the store, its
+//! SQLite side files or sealed form, and the keystore, and removes them. See
+//! `cargo run --bin state -- reset`.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{client::ClientSetup, store_encryption::EncryptedStore};
+
+/// The files and directories a client keeps on disk, as far as they exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalState {
+    paths: Vec<PathBuf>,
+}
+
+impl LocalState {
+    /// Collects the state of the store and keystore configured in `setup`.
+    pub fn of(setup: &ClientSetup) -> Self {
+        let mut candidates: Vec<PathBuf> = ["", "-wal", "-shm"]
+            .into_iter()
+            .map(|suffix| {
+                let mut path = setup.store_path.as_os_str().to_owned();
+                path.push(suffix);
+                PathBuf::from(path)
+            })
+            .collect();
+        candidates.push(EncryptedStore::sealed_path(&setup.store_path));
+        candidates.push(setup.keystore_path.clone());
+        Self {
+            paths: candidates
+                .into_iter()
+                .filter(|path| path.exists())
+                .collect(),
+        }
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Deletes every file and directory found by [`LocalState::of`].
+    ///
+    /// The keys go with the keystore, so funds held by accounts of the old
+    /// state are lost for good; on a reset devnet they are gone anyway.
+    pub fn remove(self) -> io::Result<()> {
+        for path in &self.paths {
+            remove(path)?;
+        }
+        Ok(())
+    }
+}
+
+fn remove(path: &Path) -> io::Result<()> {
+    let removed = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    match removed {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,94 @@
+//! Run-wide switches set with `--yes`, `--dry-run` and `--json`.
+//!
+//! `--yes` answers every [`confirm`] prompt, so scripts never block on stdin.
+//! `--dry-run` lets a tutorial build its accounts and requests locally and ends
+//! the run before the first transaction is sent, printing what it would send.
+
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    sync::OnceLock,
+};
+
+use miden_client::{
+    account::AccountId,
+    transaction::{TransactionId, TransactionRequest},
+};
+
+use crate::{client::network_id, error, say};
+
+static RUN_MODE: OnceLock<RunMode> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunMode {
+    /// Answer yes to every prompt.
+    pub assume_yes: bool,
+    /// Stop before the first transaction is submitted.
+    pub dry_run: bool,
+    /// Print [`crate::output::Event`]s as JSON lines on stdout.
+    pub json: bool,
+}
+
+impl RunMode {
+    /// Sets the mode for the rest of the run. The first call wins.
+    pub fn install(self) {
+        let _ = RUN_MODE.set(self);
+    }
+}
+
+pub fn run_mode() -> RunMode {
+    RUN_MODE.get().copied().unwrap_or_default()
+}
+
+/// Asks a yes/no question on the terminal, defaulting to no.
+///
+/// Returns `true` right away under `--yes`. Without a terminal there is nobody
+/// to answer, so it returns `false` instead of waiting on stdin.
+pub fn confirm(question: &str) -> bool {
+    if run_mode().assume_yes {
+        return true;
+    }
+    if !io::stdin().is_terminal() {
+        eprintln!("{question} Not confirmed: no terminal, pass --yes to run unattended");
+        return false;
+    }
+
+    // On stderr, so that a prompt never mixes with the JSON events on stdout
+    eprint!("{question} [y/N] ");
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Under `--dry-run`, prints the request about to be submitted and ends the run.
+pub fn exit_if_dry_run(account_id: AccountId, request: &TransactionRequest) {
+    if !run_mode().dry_run {
+        return;
+    }
+    say!("\n[DRY RUN] Next transaction, not submitted:");
+    say!("  account:        {}", account_id.to_bech32(network_id()));
+    say!("  input notes:    {}", request.input_notes().len());
+    for note in request.expected_output_own_notes() {
+        say!("  output note:    {}", note.id().to_hex());
+    }
+    stop();
+}
+
+/// Under `--dry-run`, ends the run before an already proven transaction is sent.
+pub fn exit_if_dry_run_proven(tx_id: TransactionId) {
+    if !run_mode().dry_run {
+        return;
+    }
+    say!("\n[DRY RUN] Next transaction, proven but not submitted:");
+    say!("  transaction:    {}", tx_id.to_hex());
+    stop();
+}
+
+fn stop() -> ! {
+    say!("Stopping before submission. Accounts created so far exist only in the local store.");
+    // Through `report`, so a dry run cleans up and prints its summary like any other run
+    let _ = error::report(Ok::<(), Box<dyn std::error::Error>>(()));
+    std::process::exit(0)
+}
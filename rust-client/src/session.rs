@@ -0,0 +1,207 @@
+//! What a tutorial run did, printed when it exits.
+//!
+//! The submission and wait helpers record every transaction they send, the fee
+//! it paid, the notes it created and how long its commitment took, and the
+//! account helpers record every account they create. [`crate::error::report`]
+//! prints the [`Session`] on the way out, whether the run succeeded or not, and
+//! the [`crate::error::ExitGuard`] held by `main` prints it when the run panics
+//! instead, so
+//! every tutorial ends with the same summary:
+//!
+//! ```text
+//! ── Session summary ──────────────────────────────
+//! Ran for 48.2s, 41.7s of it waiting for commitments
+//! Accounts created: 2
+//! Notes created: 1
+//! Fees paid: 0 of mtst1...
+//! Transactions (2):
+//!   0x5f1e... by mtst1... committed in block 5120
+//!   0x9c3a... by mtst1... committed in block 5123
+//! ```
+//!
+//! Transactions that bypass [`crate::middleware::submit_logged`] and
+//! [`crate::middleware::submit_proven_logged`] are not in it.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    transaction::{TransactionId, TransactionStatus},
+};
+
+use crate::{client::network_id, say};
+
+/// A transaction sent during the session.
+#[derive(Debug, Clone)]
+pub struct SessionTx {
+    pub tx_id: TransactionId,
+    pub account_id: AccountId,
+    /// `Pending` until a wait observed its final status.
+    pub status: TransactionStatus,
+}
+
+/// Everything recorded since the session started.
+#[derive(Debug)]
+pub struct Session {
+    started: Instant,
+    pub accounts_created: usize,
+    pub notes_created: usize,
+    pub transactions: Vec<SessionTx>,
+    /// Fees by the faucet of the asset they were paid in.
+    pub fees: BTreeMap<AccountId, u64>,
+    /// Time spent in the wait helpers until a transaction was final.
+    pub waited: Duration,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            accounts_created: 0,
+            notes_created: 0,
+            transactions: Vec::new(),
+            fees: BTreeMap::new(),
+            waited: Duration::ZERO,
+        }
+    }
+}
+
+impl Session {
+    pub fn account_created(&mut self) {
+        self.accounts_created += 1;
+    }
+
+    pub fn transaction_submitted(
+        &mut self,
+        account_id: AccountId,
+        tx_id: TransactionId,
+        fee: FungibleAsset,
+        notes_created: usize,
+    ) {
+        self.transactions.push(SessionTx {
+            tx_id,
+            account_id,
+            status: TransactionStatus::Pending,
+        });
+        *self.fees.entry(fee.faucet_id()).or_default() += fee.amount();
+        self.notes_created += notes_created;
+    }
+
+    /// Updates the status of a transaction submitted during the session.
+    pub fn transaction_final(&mut self, tx_id: TransactionId, status: &TransactionStatus) {
+        if let Some(tx) = self.transactions.iter_mut().find(|tx| tx.tx_id == tx_id) {
+            tx.status = status.clone();
+        }
+    }
+
+    pub fn waited(&mut self, elapsed: Duration) {
+        self.waited += elapsed;
+    }
+
+    /// Whether the run created or sent anything worth summarizing.
+    pub fn is_empty(&self) -> bool {
+        self.accounts_created == 0 && self.transactions.is_empty()
+    }
+
+    /// The summary, one line each.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            "── Session summary ──────────────────────────────".to_string(),
+            format!(
+                "Ran for {:.1?}, {:.1?} of it waiting for commitments",
+                self.started.elapsed(),
+                self.waited
+            ),
+            format!("Accounts created: {}", self.accounts_created),
+            format!("Notes created: {}", self.notes_created),
+        ];
+        if self.fees.is_empty() {
+            lines.push("Fees paid: none".to_string());
+        }
+        for (faucet_id, amount) in &self.fees {
+            lines.push(format!(
+                "Fees paid: {amount} of {}",
+                faucet_id.to_bech32(network_id())
+            ));
+        }
+        lines.push(format!("Transactions ({}):", self.transactions.len()));
+        for tx in &self.transactions {
+            let status = match &tx.status {
+                TransactionStatus::Committed { block_number, .. } => {
+                    format!("committed in block {block_number}")
+                }
+                TransactionStatus::Discarded(cause) => format!("discarded: {cause:?}"),
+                TransactionStatus::Pending => "still pending".to_string(),
+            };
+            lines.push(format!(
+                "  {} by {} {status}",
+                tx.tx_id.to_hex(),
+                tx.account_id.to_bech32(network_id())
+            ));
+        }
+        lines
+    }
+}
+
+static SESSION: OnceLock<Mutex<Session>> = OnceLock::new();
+static PRINTED: AtomicBool = AtomicBool::new(false);
+
+/// Starts the session clock, unless something was already recorded.
+pub fn start() {
+    session();
+}
+
+fn session() -> MutexGuard<'static, Session> {
+    SESSION
+        .get_or_init(Default::default)
+        .lock()
+        // A panic mid-update leaves counters that are still worth printing
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub(crate) fn record_account() {
+    session().account_created();
+}
+
+pub(crate) fn record_submitted(
+    account_id: AccountId,
+    tx_id: TransactionId,
+    fee: FungibleAsset,
+    notes_created: usize,
+) {
+    session().transaction_submitted(account_id, tx_id, fee, notes_created);
+}
+
+pub(crate) fn record_final(tx_id: TransactionId, status: &TransactionStatus) {
+    session().transaction_final(tx_id, status);
+}
+
+pub(crate) fn record_wait(elapsed: Duration) {
+    session().waited(elapsed);
+}
+
+/// Prints the summary of the session, if anything was recorded.
+///
+/// Only the first call prints, so [`crate::error::report`] and an
+/// [`crate::error::ExitGuard`] never print it twice.
+pub fn print_summary() {
+    if PRINTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let session = session();
+    if session.is_empty() {
+        return;
+    }
+    say!();
+    for line in session.lines() {
+        say!("{line}");
+    }
+}
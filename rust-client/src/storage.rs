@@ -0,0 +1,64 @@
+use miden_client::account::{Account, AccountComponent, StorageSlot, StorageSlotContent};
+
+use crate::{
+    output::{emit, Event},
+    say,
+};
+
+/// Prints the storage layout of a built account as a table.
+///
+/// Under `--json`, each value slot is also reported as a `storage_value` event.
+pub fn print_account_storage(account: &Account) {
+    say!("Storage of account {}:", account.id());
+    print_storage_slots(account.storage().slots());
+    for slot in account.storage().slots() {
+        if let StorageSlotContent::Value(word) = slot.content() {
+            emit(Event::storage_value(account.id(), slot.name(), *word));
+        }
+    }
+}
+
+/// Prints the storage layout an account component will publish as a table.
+pub fn print_component_storage(component: &AccountComponent) {
+    say!("Storage declared by component:");
+    print_storage_slots(component.storage_slots());
+}
+
+/// Prints slot names, types and values, one row per slot.
+pub fn print_storage_slots(slots: &[StorageSlot]) {
+    let rows = slots
+        .iter()
+        .map(|slot| {
+            let (slot_type, value) = match slot.content() {
+                StorageSlotContent::Value(word) => ("value", word.to_hex()),
+                StorageSlotContent::Map(map) => (
+                    "map",
+                    format!(
+                        "{} entries, root {}",
+                        map.entries().count(),
+                        map.root().to_hex()
+                    ),
+                ),
+            };
+            (slot.name().to_string(), slot_type, value)
+        })
+        .collect::<Vec<_>>();
+
+    let name_width = rows
+        .iter()
+        .map(|(name, ..)| name.len())
+        .chain(["slot name".len()])
+        .max()
+        .unwrap_or_default();
+
+    say!("  {:<name_width$}  {:<5}  value", "slot name", "type");
+    say!(
+        "  {}  {}  {}",
+        "-".repeat(name_width),
+        "-".repeat(5),
+        "-".repeat(5)
+    );
+    for (name, slot_type, value) in rows {
+        say!("  {:<name_width$}  {:<5}  {}", name, slot_type, value);
+    }
+}
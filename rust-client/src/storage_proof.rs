@@ -0,0 +1,237 @@
+//! Proofs that a storage map entry of a public account was committed in a block.
+//!
+//! A [`MapEntryProof`] links `key -> value` to the `account_root` of a block
+//! header through four commitments, each checked by [`MapEntryProof::verify`]:
+//!
+//! 1. The map witness opens `key` to `value` under the map root.
+//! 2. The storage header holds that root in the named slot and hashes to the
+//!    storage commitment.
+//! 3. The account header holds the storage commitment and hashes to the account
+//!    state commitment.
+//! 4. The account witness places that state commitment in the account tree whose
+//!    root is the `account_root` of the block header.
+//!
+//! A verifier needs nothing but the proof and the header of its block: no client
+//! store, no account code and no foreign procedure invocation.
+
+use std::{fmt, fs, path::Path};
+
+use miden_client::{
+    account::{
+        Account, AccountHeader, AccountId, AccountStorageHeader, StorageMapWitness,
+        StorageSlotContent, StorageSlotName,
+    },
+    block::{AccountWitness, BlockHeader, BlockNumber},
+    crypto::SmtProof,
+    utils::{Deserializable, Serializable},
+    Word,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::keys::{from_hex, to_hex};
+
+/// Proof that a map slot of an account held `key -> value` at `block_num`.
+#[derive(Debug, Clone)]
+pub struct MapEntryProof {
+    pub block_num: BlockNumber,
+    pub slot_name: StorageSlotName,
+    pub key: Word,
+    pub value: Word,
+    pub map_witness: StorageMapWitness,
+    pub storage_header: AccountStorageHeader,
+    pub account_header: AccountHeader,
+    pub account_witness: AccountWitness,
+}
+
+/// Why a [`MapEntryProof`] could not be built or does not hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofError {
+    /// The account has no map slot with this name.
+    NoSuchMap(StorageSlotName),
+    /// The account witness is for another account or state than the local account.
+    StaleAccount { local: Word, witnessed: Word },
+    /// The header belongs to another block than the proof.
+    WrongBlock {
+        proof: BlockNumber,
+        header: BlockNumber,
+    },
+    /// The map witness does not open the key to the claimed value.
+    MapEntry,
+    /// The named slot does not hold the root of the map witness.
+    MapRoot,
+    /// The storage header does not hash to the account's storage commitment.
+    StorageCommitment,
+    /// The account header does not match the witnessed account state.
+    AccountState,
+    /// The account witness does not lead to the block's account root.
+    AccountRoot,
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofError::NoSuchMap(name) => write!(f, "account has no map slot named {name}"),
+            ProofError::StaleAccount { local, witnessed } => write!(
+                f,
+                "local account state {} differs from the witnessed state {}; sync and retry",
+                local.to_hex(),
+                witnessed.to_hex()
+            ),
+            ProofError::WrongBlock { proof, header } => write!(
+                f,
+                "proof is for block {proof} but the header is for block {header}"
+            ),
+            ProofError::MapEntry => f.write_str("map witness does not open the key to the value"),
+            ProofError::MapRoot => f.write_str("slot does not hold the map root"),
+            ProofError::StorageCommitment => {
+                f.write_str("storage header does not match the storage commitment")
+            }
+            ProofError::AccountState => {
+                f.write_str("account header does not match the witnessed account state")
+            }
+            ProofError::AccountRoot => {
+                f.write_str("account witness does not lead to the block's account root")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+impl MapEntryProof {
+    /// Opens `key` in a map slot of a tracked account.
+    ///
+    /// `account_witness` is the node's witness for the account at `block_num`, and
+    /// `account` must be in the state it witnesses.
+    pub fn prove(
+        account: &Account,
+        slot_name: StorageSlotName,
+        key: Word,
+        block_num: BlockNumber,
+        account_witness: AccountWitness,
+    ) -> Result<Self, ProofError> {
+        if account_witness.id() != account.id()
+            || account_witness.state_commitment() != account.commitment()
+        {
+            return Err(ProofError::StaleAccount {
+                local: account.commitment(),
+                witnessed: account_witness.state_commitment(),
+            });
+        }
+
+        let map = account
+            .storage()
+            .slots()
+            .iter()
+            .find(|slot| slot.name() == &slot_name)
+            .and_then(|slot| match slot.content() {
+                StorageSlotContent::Map(map) => Some(map),
+                StorageSlotContent::Value(_) => None,
+            })
+            .ok_or_else(|| ProofError::NoSuchMap(slot_name.clone()))?;
+
+        Ok(Self {
+            block_num,
+            slot_name,
+            key,
+            value: map.get(&key),
+            map_witness: map.open(&key),
+            storage_header: account.storage().to_header(),
+            account_header: AccountHeader::from(account),
+            account_witness,
+        })
+    }
+
+    pub fn account_id(&self) -> AccountId {
+        self.account_header.id()
+    }
+
+    /// Checks the proof against the header of its block.
+    pub fn verify(&self, header: &BlockHeader) -> Result<(), ProofError> {
+        if header.block_num() != self.block_num {
+            return Err(ProofError::WrongBlock {
+                proof: self.block_num,
+                header: header.block_num(),
+            });
+        }
+
+        // 1. key -> value under the map root
+        if self.map_witness.get(&self.key) != Some(self.value) {
+            return Err(ProofError::MapEntry);
+        }
+
+        // 2. map root -> storage commitment
+        let slot_value = self
+            .storage_header
+            .slots()
+            .find(|slot| slot.name() == &self.slot_name)
+            .map(|slot| slot.value());
+        if slot_value != Some(self.map_witness.root()) {
+            return Err(ProofError::MapRoot);
+        }
+        if self.storage_header.to_commitment() != self.account_header.storage_commitment() {
+            return Err(ProofError::StorageCommitment);
+        }
+
+        // 3. storage commitment -> account state commitment
+        if self.account_header.id() != self.account_witness.id()
+            || self.account_header.commitment() != self.account_witness.state_commitment()
+        {
+            return Err(ProofError::AccountState);
+        }
+
+        // 4. account state commitment -> account root of the block
+        let account_proof = SmtProof::from(self.account_witness.clone());
+        if account_proof.compute_root() != header.account_root() {
+            return Err(ProofError::AccountRoot);
+        }
+
+        Ok(())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let file = ProofFile {
+            block_num: self.block_num.as_u32(),
+            slot_name: self.slot_name.to_string(),
+            key: to_hex(&self.key.to_bytes()),
+            value: to_hex(&self.value.to_bytes()),
+            map_witness: to_hex(&self.map_witness.to_bytes()),
+            storage_header: to_hex(&self.storage_header.to_bytes()),
+            account_header: to_hex(&self.account_header.to_bytes()),
+            account_witness: to_hex(&self.account_witness.to_bytes()),
+        };
+        fs::write(path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let file: ProofFile = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Ok(Self {
+            block_num: BlockNumber::from(file.block_num),
+            slot_name: StorageSlotName::new(file.slot_name)?,
+            key: decode(&file.key)?,
+            value: decode(&file.value)?,
+            map_witness: decode(&file.map_witness)?,
+            storage_header: decode(&file.storage_header)?,
+            account_header: decode(&file.account_header)?,
+            account_witness: decode(&file.account_witness)?,
+        })
+    }
+}
+
+/// JSON layout of a saved proof; everything but the block and slot name is hex.
+#[derive(Debug, Serialize, Deserialize)]
+struct ProofFile {
+    block_num: u32,
+    slot_name: String,
+    key: String,
+    value: String,
+    map_witness: String,
+    storage_header: String,
+    account_header: String,
+    account_witness: String,
+}
+
+fn decode<T: Deserializable>(hex: &str) -> Result<T, Box<dyn std::error::Error>> {
+    Ok(T::read_from_bytes(&from_hex(hex)?)?)
+}
@@ -0,0 +1,331 @@
+//! Keeps the SQLite store encrypted on disk between runs.
+//!
+//! The store holds private notes and account state. miden-client opens its
+//! SQLite connections itself, so there is no way to hand them a SQLCipher key;
+//! the whole file is sealed instead. `store.sqlite3` is kept as
+//! `store.sqlite3.enc`, encrypted with XChaCha20-Poly1305 under a key derived
+//! from a passphrase with Argon2id. While a binary runs, the client works on a
+//! decrypted copy in a directory under the temp directory that only the current
+//! user can enter, which [`seal_open_store`] encrypts back and deletes when the
+//! binary exits, even after a panic or Ctrl-C.
+//!
+//! A plaintext store found where the sealed one should be is encrypted on the
+//! first sealed run and then removed.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+
+/// Environment variable holding the passphrase of an encrypted store.
+pub const PASSPHRASE_ENV: &str = "MIDEN_TUTORIAL_STORE_PASSPHRASE";
+
+/// First bytes of a sealed store, followed by the salt, the nonce and the ciphertext.
+const MAGIC: &[u8; 8] = b"MTSTORE1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Passphrase of an encrypted store, kept out of `Debug` output.
+#[derive(Clone)]
+pub struct StorePassphrase(String);
+
+impl StorePassphrase {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self(passphrase.into())
+    }
+
+    /// Reads the passphrase from [`PASSPHRASE_ENV`].
+    pub fn from_env() -> Option<Self> {
+        std::env::var(PASSPHRASE_ENV)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty())
+            .map(Self)
+    }
+
+    fn key(&self, salt: &[u8]) -> Key {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(self.0.as_bytes(), salt, key.as_mut_slice())
+            .expect("salt and key lengths are valid for Argon2");
+        key
+    }
+}
+
+impl fmt::Debug for StorePassphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StorePassphrase(..)")
+    }
+}
+
+/// Why a sealed store could not be opened or sealed again.
+#[derive(Debug)]
+pub enum StoreEncryptionError {
+    /// Reading or writing one of the store files failed.
+    Io(io::Error),
+    /// The file does not start like a sealed store.
+    NotSealed(PathBuf),
+    /// Decryption failed: the passphrase is wrong or the file was modified.
+    WrongPassphrase(PathBuf),
+    /// The write-ahead log could not be folded into the store, so the main
+    /// file alone is not the whole database.
+    Checkpoint { path: PathBuf, reason: String },
+}
+
+impl fmt::Display for StoreEncryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreEncryptionError::Io(err) => write!(f, "store encryption failed: {err}"),
+            StoreEncryptionError::NotSealed(path) => {
+                write!(f, "{} is not an encrypted store", path.display())
+            }
+            StoreEncryptionError::WrongPassphrase(path) => write!(
+                f,
+                "cannot decrypt {}: wrong passphrase in {PASSPHRASE_ENV}, or the file was modified",
+                path.display()
+            ),
+            StoreEncryptionError::Checkpoint { path, reason } => write!(
+                f,
+                "cannot fold the write-ahead log of {} into the store: {reason}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StoreEncryptionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StoreEncryptionError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StoreEncryptionError {
+    fn from(err: io::Error) -> Self {
+        StoreEncryptionError::Io(err)
+    }
+}
+
+/// Encrypts `plaintext` under a key derived from `passphrase` and a fresh salt.
+pub fn encrypt(plaintext: &[u8], passphrase: &StorePassphrase) -> Vec<u8> {
+    let mut salt = [0_u8; SALT_LEN];
+    let mut nonce = [0_u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut salt);
+    rand::rng().fill_bytes(&mut nonce);
+
+    let cipher = XChaCha20Poly1305::new(&passphrase.key(&salt));
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .expect("encrypting a buffer in memory cannot fail");
+
+    let mut sealed = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    sealed.extend_from_slice(MAGIC);
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    sealed
+}
+
+/// Decrypts the output of [`encrypt`], read from `path`.
+pub fn decrypt(
+    sealed: &[u8],
+    passphrase: &StorePassphrase,
+    path: &Path,
+) -> Result<Vec<u8>, StoreEncryptionError> {
+    let header = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if sealed.len() < header || &sealed[..MAGIC.len()] != MAGIC {
+        return Err(StoreEncryptionError::NotSealed(path.to_path_buf()));
+    }
+    let salt = &sealed[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce = &sealed[MAGIC.len() + SALT_LEN..header];
+
+    XChaCha20Poly1305::new(&passphrase.key(salt))
+        .decrypt(XNonce::from_slice(nonce), &sealed[header..])
+        .map_err(|_| StoreEncryptionError::WrongPassphrase(path.to_path_buf()))
+}
+
+/// A store decrypted to a working copy for the duration of a run.
+#[derive(Debug)]
+pub struct EncryptedStore {
+    store_path: PathBuf,
+    working_path: PathBuf,
+    passphrase: StorePassphrase,
+}
+
+impl EncryptedStore {
+    /// Where the sealed form of the store at `store_path` lives.
+    pub fn sealed_path(store_path: &Path) -> PathBuf {
+        let mut path = store_path.as_os_str().to_owned();
+        path.push(".enc");
+        PathBuf::from(path)
+    }
+
+    /// Decrypts the store into a private working copy, or starts an empty one.
+    pub fn open(
+        store_path: &Path,
+        passphrase: StorePassphrase,
+    ) -> Result<Self, StoreEncryptionError> {
+        let sealed_path = Self::sealed_path(store_path);
+        let plaintext = match fs::read(&sealed_path) {
+            Ok(sealed) => decrypt(&sealed, &passphrase, &sealed_path)?,
+            // Adopt a store created before encryption was turned on
+            Err(err) if err.kind() == io::ErrorKind::NotFound && store_path.exists() => {
+                checkpoint(store_path)?;
+                fs::read(store_path)?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        // SQLite creates its -wal and -shm files next to the database with the
+        // default permissions, so the whole directory is kept private. An
+        // empty file is a new database to SQLite.
+        let working_dir = std::env::temp_dir().join(format!(
+            "miden-store-{}-{:016x}",
+            std::process::id(),
+            rand::rng().next_u64()
+        ));
+        create_private_dir(&working_dir)?;
+        let working_path = working_dir.join("store.sqlite3");
+        write_private(&working_path, &plaintext)?;
+
+        Ok(Self {
+            store_path: store_path.to_path_buf(),
+            working_path,
+            passphrase,
+        })
+    }
+
+    /// The decrypted copy the client should open.
+    pub fn working_path(&self) -> &Path {
+        &self.working_path
+    }
+
+    /// Encrypts the working copy back to the sealed file and deletes every plaintext copy.
+    ///
+    /// If the write-ahead log cannot be folded in, nothing is deleted: the
+    /// working copy and its log are the only complete copy of the latest state.
+    pub fn seal(self) -> Result<(), StoreEncryptionError> {
+        if !self.working_path.exists() {
+            return Ok(());
+        }
+
+        checkpoint(&self.working_path)?;
+        let sealed = encrypt(&fs::read(&self.working_path)?, &self.passphrase);
+
+        // Write next to the target and rename, so a crash never leaves half a file
+        let sealed_path = Self::sealed_path(&self.store_path);
+        let partial_path = sealed_path.with_extension("enc.partial");
+        fs::write(&partial_path, sealed)?;
+        fs::rename(&partial_path, &sealed_path)?;
+
+        remove_sqlite_files(&self.working_path)?;
+        if let Some(working_dir) = self.working_path.parent() {
+            match fs::remove_dir(working_dir) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        remove_sqlite_files(&self.store_path)?;
+        Ok(())
+    }
+}
+
+/// Folds the write-ahead log of a SQLite database into its main file, so that
+/// reading the main file alone gives the whole database.
+///
+/// Fails if SQLite cannot open the database or reports the checkpoint as
+/// blocked by another connection.
+fn checkpoint(path: &Path) -> Result<(), StoreEncryptionError> {
+    let failed = |reason: String| StoreEncryptionError::Checkpoint {
+        path: path.to_path_buf(),
+        reason,
+    };
+    let connection = rusqlite::Connection::open(path).map_err(|err| failed(err.to_string()))?;
+    // The first column is 1 when a reader or writer kept the checkpoint from finishing
+    let busy: i64 = connection
+        .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))
+        .map_err(|err| failed(err.to_string()))?;
+    if busy != 0 {
+        return Err(failed("the database is still in use".to_string()));
+    }
+    Ok(())
+}
+
+/// Creates a directory only the current user can enter.
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(path)
+}
+
+/// Writes `contents` to a new file only the current user can read.
+fn write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    io::Write::write_all(&mut options.open(path)?, contents)
+}
+
+/// Removes a SQLite database together with its write-ahead log and shared memory files.
+fn remove_sqlite_files(path: &Path) -> io::Result<()> {
+    for suffix in ["", "-wal", "-shm"] {
+        let mut file = path.as_os_str().to_owned();
+        file.push(suffix);
+        match fs::remove_file(PathBuf::from(file)) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+static OPEN_STORES: Mutex<Vec<EncryptedStore>> = Mutex::new(Vec::new());
+
+/// Remembers a store opened by this process so [`seal_open_store`] can seal it.
+///
+/// A binary may open several, e.g. one per client in the chat tutorial.
+pub fn install(store: EncryptedStore) {
+    OPEN_STORES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(store);
+}
+
+/// Seals every store opened by this process.
+///
+/// Called by [`crate::error::report`] once a binary's clients are dropped, and
+/// by the [`crate::error::ExitGuard`] and Ctrl-C handler of a run that never
+/// gets there. Stores already sealed are not sealed again. A failure is
+/// printed rather than returned, since the binary is already exiting.
+pub fn seal_open_store() {
+    let stores = std::mem::take(&mut *OPEN_STORES.lock().unwrap_or_else(|err| err.into_inner()));
+    for store in stores {
+        let working_path = store.working_path.clone();
+        if let Err(err) = store.seal() {
+            eprintln!(
+                "Warning: {err}; a decrypted copy of the store may remain at {}",
+                working_path.display()
+            );
+        }
+    }
+}
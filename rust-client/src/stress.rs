@@ -0,0 +1,144 @@
+//! Throughput and error-rate statistics of the `stress_test` load generator.
+//!
+//! Every client of a stress test records one [`ClientStats`] entry per
+//! [`Operation`] it attempts, with the latency until the transaction was
+//! committed or the error that stopped it. [`LoadSummary::aggregate`] merges
+//! the clients into committed transactions per second, the share of failed
+//! attempts and latency percentiles per operation.
+
+use std::{fmt, time::Duration};
+
+/// Step of the mint → consume → send loop each client runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// The client's faucet mints a P2ID note to its wallet.
+    Mint,
+    /// The wallet consumes the minted note.
+    Consume,
+    /// The wallet sends part of the tokens to the next client's wallet.
+    Send,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 3] = [Operation::Mint, Operation::Consume, Operation::Send];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::Mint => "mint",
+            Operation::Consume => "consume",
+            Operation::Send => "send",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Outcomes recorded by one client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Latency of every committed transaction.
+    pub committed: Vec<(Operation, Duration)>,
+    /// Error of every failed attempt.
+    pub failed: Vec<(Operation, String)>,
+}
+
+impl ClientStats {
+    pub fn record(&mut self, operation: Operation, outcome: Result<Duration, String>) {
+        match outcome {
+            Ok(latency) => self.committed.push((operation, latency)),
+            Err(err) => self.failed.push((operation, err)),
+        }
+    }
+
+    pub fn attempts(&self) -> usize {
+        self.committed.len() + self.failed.len()
+    }
+}
+
+/// Counts and latencies of one operation across all clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationSummary {
+    pub operation: Operation,
+    pub committed: usize,
+    pub failed: usize,
+    pub p50: Option<Duration>,
+    pub p95: Option<Duration>,
+}
+
+/// Statistics of a whole stress test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadSummary {
+    pub clients: usize,
+    pub attempts: usize,
+    pub committed: usize,
+    pub failed: usize,
+    /// Wall-clock time of the load phase.
+    pub elapsed: Duration,
+    pub operations: Vec<OperationSummary>,
+}
+
+impl LoadSummary {
+    pub fn aggregate(stats: &[ClientStats], elapsed: Duration) -> Self {
+        let operations = Operation::ALL
+            .into_iter()
+            .map(|operation| {
+                let mut latencies: Vec<Duration> = stats
+                    .iter()
+                    .flat_map(|client| &client.committed)
+                    .filter(|(op, _)| *op == operation)
+                    .map(|(_, latency)| *latency)
+                    .collect();
+                latencies.sort();
+                let failed = stats
+                    .iter()
+                    .flat_map(|client| &client.failed)
+                    .filter(|(op, _)| *op == operation)
+                    .count();
+                OperationSummary {
+                    operation,
+                    committed: latencies.len(),
+                    failed,
+                    p50: percentile(&latencies, 50.0),
+                    p95: percentile(&latencies, 95.0),
+                }
+            })
+            .collect();
+        Self {
+            clients: stats.len(),
+            attempts: stats.iter().map(ClientStats::attempts).sum(),
+            committed: stats.iter().map(|client| client.committed.len()).sum(),
+            failed: stats.iter().map(|client| client.failed.len()).sum(),
+            elapsed,
+            operations,
+        }
+    }
+
+    /// Committed transactions per second of wall-clock time.
+    pub fn throughput(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.committed as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Share of attempts that failed, from 0 to 1.
+    pub fn error_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            return 0.0;
+        }
+        self.failed as f64 / self.attempts as f64
+    }
+}
+
+/// Nearest-rank percentile of an already sorted sample set.
+pub fn percentile(sorted: &[Duration], p: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
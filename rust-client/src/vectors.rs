@@ -0,0 +1,238 @@
+//! Reference vectors the web client checks itself against.
+//!
+//! The Rust and TypeScript SDKs hash accounts, notes and code independently, so
+//! a change on either side can make the same inputs produce different IDs.
+//! [`ReferenceVectors::generate`] computes a fixed set of them offline:
+//!
+//! - the ID and commitments of the counter contract deployed from given seeds,
+//! - the recipient digest and ID of a P2ID note with a given serial number,
+//! - the script roots of note scripts that compile without a library,
+//! - the MAST roots of the counter's procedures.
+//!
+//! `cargo run --bin reference_vectors` writes them to [`VECTORS_FILE`] as JSON,
+//! and a web tutorial rebuilding the same inputs must reach the same hex strings.
+
+use miden_client::{
+    account::{Account, AccountId},
+    assembly::CodeBuilder,
+    crypto::RpoRandomCoin,
+    note::{create_p2id_note, Note, NoteAssets, NoteAttachment, NoteRecipient, NoteType},
+    Felt, Word,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{client::network_id, contracts::Counter, masm::MasmFile};
+
+/// Default output, relative to `rust-client`.
+pub const VECTORS_FILE: &str = "reference-vectors.json";
+
+/// Seeds the counter contract is deployed from.
+pub const ACCOUNT_SEEDS: [[u8; 32]; 3] = [[0; 32], [7; 32], [0xab; 32]];
+
+/// Serial number of the P2ID vector, as `[1, 2, 3, 4]`.
+pub const P2ID_SERIAL: [u64; 4] = [1, 2, 3, 4];
+
+/// Note scripts that compile on their own, by name and source.
+const NOTE_SCRIPTS: [(&str, &str, &str); 2] = [
+    (
+        "iterative_output_note",
+        "masm/notes/iterative_output_note.masm",
+        include_str!("../../masm/notes/iterative_output_note.masm"),
+    ),
+    (
+        "hash_preimage_note",
+        "masm/notes/hash_preimage_note.masm",
+        include_str!("../../masm/notes/hash_preimage_note.masm"),
+    ),
+];
+
+/// Every vector, as written to [`VECTORS_FILE`]. Words and IDs are hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceVectors {
+    pub accounts: Vec<AccountVector>,
+    pub notes: Vec<NoteVector>,
+    pub note_scripts: Vec<ScriptVector>,
+    pub procedures: Vec<ProcedureVector>,
+}
+
+/// The counter contract deployed from `seed`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountVector {
+    pub contract: String,
+    pub seed: String,
+    pub account_id: String,
+    /// The same ID in bech32, on the default [`crate::client::network`].
+    pub bech32: String,
+    pub code_commitment: String,
+    pub commitment: String,
+}
+
+/// A P2ID note without assets from one counter to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteVector {
+    pub kind: String,
+    pub sender: String,
+    pub target: String,
+    pub serial_num: String,
+    pub script_root: String,
+    pub recipient: String,
+    pub note_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptVector {
+    pub name: String,
+    pub root: String,
+}
+
+/// The MAST roots of a contract's procedures, in the order its code lists them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcedureVector {
+    pub contract: String,
+    pub component_root: String,
+    pub procedure_roots: Vec<String>,
+}
+
+impl ReferenceVectors {
+    /// Computes every vector. Needs no node and gives the same result on every run.
+    pub fn generate() -> Result<Self, Box<dyn std::error::Error>> {
+        let counters = ACCOUNT_SEEDS
+            .iter()
+            .map(|seed| Counter::deployer()?.build(*seed))
+            .collect::<Result<Vec<Account>, _>>()?;
+        let accounts = ACCOUNT_SEEDS
+            .iter()
+            .zip(&counters)
+            .map(|(seed, account)| AccountVector {
+                contract: "counter".to_string(),
+                seed: hex(seed),
+                account_id: account.id().to_hex(),
+                bech32: account.id().to_bech32(network_id()),
+                code_commitment: account.code().commitment().to_hex(),
+                commitment: account.commitment().to_hex(),
+            })
+            .collect();
+
+        let serial_num = Word::from(P2ID_SERIAL.map(Felt::new));
+        let note = p2id_note(counters[0].id(), counters[1].id(), serial_num)?;
+        let notes = vec![NoteVector {
+            kind: "p2id".to_string(),
+            sender: counters[0].id().to_hex(),
+            target: counters[1].id().to_hex(),
+            serial_num: serial_num.to_hex(),
+            script_root: note.script().root().to_hex(),
+            recipient: note.recipient().digest().to_hex(),
+            note_id: note.id().to_hex(),
+        }];
+
+        let note_scripts = NOTE_SCRIPTS
+            .iter()
+            .map(|(name, path, code)| {
+                let file = MasmFile::embedded(path, code);
+                let script = file.check(CodeBuilder::new().compile_note_script(&file.source))?;
+                Ok(ScriptVector {
+                    name: name.to_string(),
+                    root: script.root().to_hex(),
+                })
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        let procedures = vec![ProcedureVector {
+            contract: "counter".to_string(),
+            component_root: Counter::library()?.digest().to_hex(),
+            procedure_roots: counters[0]
+                .code()
+                .procedure_roots()
+                .map(|root| root.to_hex())
+                .collect(),
+        }];
+
+        Ok(Self {
+            accounts,
+            notes,
+            note_scripts,
+            procedures,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("vectors serialize to JSON")
+    }
+
+    /// Lines naming every vector that differs from `expected`.
+    pub fn diff(&self, expected: &Self) -> Vec<String> {
+        let mut drift = Vec::new();
+        compare(&mut drift, "accounts", &self.accounts, &expected.accounts);
+        compare(&mut drift, "notes", &self.notes, &expected.notes);
+        compare(
+            &mut drift,
+            "note_scripts",
+            &self.note_scripts,
+            &expected.note_scripts,
+        );
+        compare(
+            &mut drift,
+            "procedures",
+            &self.procedures,
+            &expected.procedures,
+        );
+        drift
+    }
+}
+
+/// A P2ID note without assets whose serial number is `serial_num`.
+///
+/// `create_p2id_note` draws the serial number itself, so the script and inputs
+/// are taken from such a note and combined with the fixed serial number.
+fn p2id_note(
+    sender: AccountId,
+    target: AccountId,
+    serial_num: Word,
+) -> Result<Note, Box<dyn std::error::Error>> {
+    let template = create_p2id_note(
+        sender,
+        target,
+        vec![],
+        NoteType::Public,
+        NoteAttachment::default(),
+        &mut RpoRandomCoin::new(Word::default()),
+    )?;
+    let recipient = NoteRecipient::new(
+        serial_num,
+        template.script().clone(),
+        template.inputs().clone(),
+    );
+    Ok(Note::new(
+        NoteAssets::default(),
+        template.metadata().clone(),
+        recipient,
+    ))
+}
+
+fn compare<T: PartialEq + Serialize>(
+    drift: &mut Vec<String>,
+    section: &str,
+    actual: &[T],
+    expected: &[T],
+) {
+    if actual.len() != expected.len() {
+        drift.push(format!(
+            "{section}: {} vectors, expected {}",
+            actual.len(),
+            expected.len()
+        ));
+    }
+    for (index, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        if actual != expected {
+            drift.push(format!(
+                "{section}[{index}]: got {}, expected {}",
+                serde_json::to_string(actual).expect("vectors serialize to JSON"),
+                serde_json::to_string(expected).expect("vectors serialize to JSON")
+            ));
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,260 @@
+//! SDK-style wrapper around the vesting contract in `masm/accounts/vesting.masm`.
+//!
+//! One vesting account holds one grant from a funder to a beneficiary. The
+//! funder locks the whole grant with a note, and from the start block on every
+//! full period releases an equal share of it. The beneficiary claims vested
+//! tokens with a note naming the amount, which the contract pays out in a P2ID
+//! note as long as it does not exceed what vested and was not claimed yet.
+
+use miden_client::{
+    account::{AccountId, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::ContractDeployer,
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const PARTIES_SLOT: &str = "miden::tutorials::vesting::parties";
+pub const SCHEDULE_SLOT: &str = "miden::tutorials::vesting::schedule";
+pub const GRANT_SLOT: &str = "miden::tutorials::vesting::grant";
+pub const CLAIMED_SLOT: &str = "miden::tutorials::vesting::claimed";
+
+/// Library path the vesting procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::vesting_contract";
+
+const COMPONENT_FILE: &str = "masm/accounts/vesting.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/vesting.masm");
+const FUND_NOTE_FILE: &str = "masm/notes/vesting_fund_note.masm";
+const FUND_NOTE_CODE: &str = include_str!("../../masm/notes/vesting_fund_note.masm");
+const CLAIM_NOTE_FILE: &str = "masm/notes/vesting_claim_note.masm";
+const CLAIM_NOTE_CODE: &str = include_str!("../../masm/notes/vesting_claim_note.masm");
+
+/// When the tokens of a grant vest, mirroring `get_vested_amount` in the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VestingSchedule {
+    /// Block the first period starts at.
+    pub start: u32,
+    /// Blocks per period.
+    pub period: u32,
+    /// Periods until the whole grant has vested.
+    pub periods: u32,
+    /// Tokens released at the end of each period.
+    pub per_period: u64,
+}
+
+impl VestingSchedule {
+    /// The whole grant, which the funder locks at once.
+    pub fn total(&self) -> u64 {
+        self.per_period * self.periods as u64
+    }
+
+    /// Tokens vested as of `block`: one share per full period since the start.
+    pub fn vested_at(&self, block: u32) -> u64 {
+        if block < self.start {
+            return 0;
+        }
+        let full_periods = ((block - self.start) / self.period).min(self.periods);
+        self.per_period * full_periods as u64
+    }
+
+    /// First block at which `amount` tokens have vested, or `None` past the grant.
+    pub fn vesting_block(&self, amount: u64) -> Option<u32> {
+        if amount > self.total() {
+            return None;
+        }
+        if amount == 0 {
+            return Some(self.start);
+        }
+        let periods = amount.div_ceil(self.per_period) as u32;
+        Some(self.start + periods * self.period)
+    }
+}
+
+/// A deployed vesting grant.
+pub struct Vesting {
+    pub account_id: AccountId,
+    pub funder: AccountId,
+    pub beneficiary: AccountId,
+    /// The whole grant, in the token it vests in.
+    pub grant: FungibleAsset,
+    pub schedule: VestingSchedule,
+    library: Library,
+}
+
+impl Vesting {
+    /// Builds a new grant in the token of `faucet_id` and starts tracking it.
+    ///
+    /// The contract is deployed on-chain with the fund note it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        funder: AccountId,
+        beneficiary: AccountId,
+        faucet_id: AccountId,
+        schedule: VestingSchedule,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if schedule.period == 0 {
+            return Err("a vesting period lasts at least one block".into());
+        }
+        // Fails when the total is above the largest fungible amount, which
+        // also keeps the contract's `periods * per_period` from wrapping
+        let grant = FungibleAsset::new(faucet_id, schedule.total())?;
+
+        let parties = Word::from([
+            beneficiary.suffix(),
+            beneficiary.prefix().as_felt(),
+            funder.suffix(),
+            funder.prefix().as_felt(),
+        ]);
+        let schedule_word = Word::from([
+            Felt::new(schedule.per_period),
+            Felt::new(schedule.periods as u64),
+            Felt::new(schedule.period as u64),
+            Felt::new(schedule.start as u64),
+        ]);
+        let slots = vec![
+            StorageSlot::with_value(StorageSlotName::new(PARTIES_SLOT)?, parties),
+            StorageSlot::with_value(StorageSlotName::new(SCHEDULE_SLOT)?, schedule_word),
+            StorageSlot::with_value(StorageSlotName::new(GRANT_SLOT)?, Word::default()),
+            StorageSlot::with_value(StorageSlotName::new(CLAIMED_SLOT)?, Word::default()),
+        ];
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slots(slots);
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            funder,
+            beneficiary,
+            grant,
+            schedule,
+            library,
+        })
+    }
+
+    /// Moves the whole grant from the funder into the contract.
+    pub async fn fund(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let script =
+            self.note_script(client, MasmFile::embedded(FUND_NOTE_FILE, FUND_NOTE_CODE))?;
+        let note = NoteBuilder::new(self.funder, script)
+            .asset(self.grant)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+        self.send_and_consume(client, self.funder, note, None).await
+    }
+
+    /// Has the beneficiary claim `amount` vested tokens and returns the P2ID note paying them.
+    ///
+    /// The contract checks the amount against the schedule at the reference
+    /// block of its transaction, which is the block the client last synced to.
+    pub async fn claim(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        amount: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let payout = create_p2id_note(
+            self.account_id,
+            self.beneficiary,
+            vec![FungibleAsset::new(self.grant.faucet_id(), amount)?.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        inputs.push(Felt::new(amount));
+        let script =
+            self.note_script(client, MasmFile::embedded(CLAIM_NOTE_FILE, CLAIM_NOTE_CODE))?;
+        let note = NoteBuilder::new(self.beneficiary, script)
+            .inputs(inputs)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let report = self
+            .send_and_consume(client, self.beneficiary, note, Some(&payout))
+            .await?;
+        Ok((report, payout))
+    }
+
+    /// Returns the tokens claimed so far, as of the last sync.
+    pub async fn claimed(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<u64, ClientError> {
+        let vesting = get_full_account(client, self.account_id).await?;
+        let slot = StorageSlotName::new(CLAIMED_SLOT).expect("valid slot name");
+        let claimed = vesting
+            .storage()
+            .get_item(&slot)
+            .expect("vesting account is missing its claimed slot");
+        // The amount sits on top of the stack, which is the last element of the word
+        Ok(claimed[3].as_int())
+    }
+
+    /// Returns the tokens the beneficiary can claim at the block the client last synced to.
+    pub async fn claimable(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<u64, ClientError> {
+        let height = client.get_sync_height().await?.as_u32();
+        let claimed = self.claimed(client).await?;
+        Ok(self.schedule.vested_at(height) - claimed)
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the contract itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("vesting note script does not compile");
+        Ok(script)
+    }
+
+    /// Sends `note` from `sender` and has the vesting contract consume it right away.
+    async fn send_and_consume(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        sender: AccountId,
+        note: Note,
+        payout: Option<&Note>,
+    ) -> Result<TxReport, Box<dyn std::error::Error>> {
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, sender, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .expected_output_recipients(
+                payout
+                    .map(|payout| payout.recipient().clone())
+                    .into_iter()
+                    .collect(),
+            )
+            .build()?;
+        submit_and_wait(client, self.account_id, consume_request).await
+    }
+}
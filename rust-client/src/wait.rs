@@ -0,0 +1,558 @@
+use std::fmt;
+
+use tokio::time::{sleep, Duration, Instant};
+
+use miden_client::{
+    account::AccountId,
+    block::BlockNumber,
+    keystore::FilesystemKeyStore,
+    note::{NoteConsumability, NoteId, NoteTag},
+    store::{InputNoteRecord, TransactionFilter},
+    transaction::{TransactionId, TransactionRequest, TransactionStatus},
+    Client, ClientError,
+};
+
+use crate::{
+    client::{network, network_id},
+    middleware::submit_logged,
+    output::{emit, Event},
+    say, session,
+};
+
+/// Outcome of a transaction sent with [`submit_and_wait`].
+#[derive(Debug, Clone)]
+pub struct TxReport {
+    pub tx_id: TransactionId,
+    /// Final status, either committed or discarded.
+    pub status: TransactionStatus,
+    /// Time from submission until the final status was observed.
+    pub elapsed: Duration,
+}
+
+impl TxReport {
+    pub fn is_committed(&self) -> bool {
+        matches!(self.status, TransactionStatus::Committed { .. })
+    }
+
+    pub fn explorer_url(&self) -> Option<String> {
+        explorer_url(self.tx_id)
+    }
+}
+
+/// Link to a transaction on the MidenScan of the selected network, if it has one.
+pub fn explorer_url(tx_id: TransactionId) -> Option<String> {
+    network().explorer_tx_url(tx_id)
+}
+
+/// Prints the explorer link of a transaction, or its ID on a network without explorer.
+pub fn print_explorer_link(label: &str, tx_id: TransactionId) {
+    match explorer_url(tx_id) {
+        Some(url) => say!("{label} on MidenScan: {url}"),
+        None => say!("{label}: {}", tx_id.to_hex()),
+    }
+}
+
+/// Longest a transaction or note wait lasts before giving up, unless [`WaitOptions`] say otherwise.
+pub const DEFAULT_TX_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Why waiting for a transaction ended without it being committed.
+#[derive(Debug)]
+pub enum WaitError {
+    /// The transaction was still pending when the deadline passed.
+    Timeout {
+        tx_id: TransactionId,
+        waited: Duration,
+    },
+    /// The store discarded the transaction.
+    Discarded {
+        tx_id: TransactionId,
+        status: TransactionStatus,
+    },
+    /// The chain passed the expiration block while the transaction was still pending.
+    Expired {
+        tx_id: TransactionId,
+        expiration_block: BlockNumber,
+    },
+    /// The awaited notes had not arrived when the deadline passed.
+    NotesTimeout { waited: Duration },
+    /// The chain had not reached the awaited block when the deadline passed.
+    BlockTimeout { block: u32, waited: Duration },
+    /// Syncing or reading the store failed.
+    Client(ClientError),
+}
+
+impl fmt::Display for WaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitError::Timeout { tx_id, waited } => write!(
+                f,
+                "transaction {} still pending after {:.1?}",
+                tx_id.to_hex(),
+                waited
+            ),
+            WaitError::Discarded { tx_id, status } => write!(
+                f,
+                "transaction {} was discarded: {:?}",
+                tx_id.to_hex(),
+                status
+            ),
+            WaitError::Expired {
+                tx_id,
+                expiration_block,
+            } => write!(
+                f,
+                "transaction {} expired at block {} without being committed",
+                tx_id.to_hex(),
+                expiration_block
+            ),
+            WaitError::NotesTimeout { waited } => {
+                write!(f, "awaited notes did not arrive within {:.1?}", waited)
+            }
+            WaitError::BlockTimeout { block, waited } => {
+                write!(
+                    f,
+                    "the chain did not reach block {block} within {:.1?}",
+                    waited
+                )
+            }
+            WaitError::Client(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for WaitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WaitError::Client(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ClientError> for WaitError {
+    fn from(err: ClientError) -> Self {
+        WaitError::Client(err)
+    }
+}
+
+/// How long to wait for a transaction or notes and how often to check on them.
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    pub timeout: Duration,
+    pub poller: Poller,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TX_TIMEOUT,
+            poller: Poller::default(),
+        }
+    }
+}
+
+/// Time allowed per block by [`WaitOptions::for_blocks`], well above the
+/// usual block time so that a slow node still makes it.
+pub const BLOCK_TIME_ALLOWANCE: Duration = Duration::from_secs(10);
+
+impl WaitOptions {
+    /// Default options whose timeout also leaves room for `blocks` blocks to
+    /// be produced, for [`wait_for_block`].
+    pub fn for_blocks(blocks: u32) -> Self {
+        Self::default().with_timeout(DEFAULT_TX_TIMEOUT + BLOCK_TIME_ALLOWANCE * blocks)
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_poller(mut self, poller: Poller) -> Self {
+        self.poller = poller;
+        self
+    }
+}
+
+/// Submits a transaction, prints its explorer link and waits until it is committed or discarded.
+///
+/// A discarded transaction is still reported as `Ok`, so callers can inspect
+/// why; only a wait that times out or outlives the expiration block fails.
+pub async fn submit_and_wait(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    request: TransactionRequest,
+) -> Result<TxReport, WaitError> {
+    let start = Instant::now();
+    let tx_id = submit_logged(client, account_id, request).await?;
+    print_explorer_link("View transaction", tx_id);
+
+    let status = wait_for_final_status(client, tx_id).await?;
+    let report = TxReport {
+        tx_id,
+        status,
+        elapsed: start.elapsed(),
+    };
+    if !report.is_committed() {
+        say!(
+            "⚠️ transaction {} was discarded: {:?}",
+            tx_id.to_hex(),
+            report.status
+        );
+    }
+    Ok(report)
+}
+
+/// Waits for a specific transaction to be committed, failing if it is discarded.
+pub async fn wait_for_tx(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_id: TransactionId,
+) -> Result<(), WaitError> {
+    wait_for_tx_with(client, tx_id, WaitOptions::default()).await
+}
+
+/// [`wait_for_tx`] with a custom deadline and polling interval.
+pub async fn wait_for_tx_with(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_id: TransactionId,
+    options: WaitOptions,
+) -> Result<(), WaitError> {
+    match wait_for_final_status_with(client, tx_id, options).await? {
+        status @ TransactionStatus::Discarded(_) => Err(WaitError::Discarded { tx_id, status }),
+        _ => Ok(()),
+    }
+}
+
+/// Polls the store until the transaction leaves the pending state and returns its final status.
+pub async fn wait_for_final_status(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_id: TransactionId,
+) -> Result<TransactionStatus, WaitError> {
+    wait_for_final_status_with(client, tx_id, WaitOptions::default()).await
+}
+
+/// [`wait_for_final_status`] with a custom deadline and polling interval.
+///
+/// Fails early once the chain is past the expiration block of a transaction
+/// that is still pending, since it can no longer be committed.
+pub async fn wait_for_final_status_with(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_id: TransactionId,
+    options: WaitOptions,
+) -> Result<TransactionStatus, WaitError> {
+    let start = Instant::now();
+    let result = poll_final_status(client, tx_id, start, options).await;
+    session::record_wait(start.elapsed());
+    if let Ok(status) = &result {
+        session::record_final(tx_id, status);
+    }
+    result
+}
+
+async fn poll_final_status(
+    client: &mut Client<FilesystemKeyStore>,
+    tx_id: TransactionId,
+    start: Instant,
+    options: WaitOptions,
+) -> Result<TransactionStatus, WaitError> {
+    let deadline = start + options.timeout;
+    let mut poller = options.poller;
+    loop {
+        if !sync_with_backoff(client, &mut poller, deadline).await? {
+            return Err(WaitError::Timeout {
+                tx_id,
+                waited: start.elapsed(),
+            });
+        }
+
+        // Check transaction status
+        let txs = client
+            .get_transactions(TransactionFilter::Ids(vec![tx_id]))
+            .await?;
+        if let Some(tx) = txs.first() {
+            if let Some(event) = Event::transaction_final(tx_id, &tx.status) {
+                emit(event);
+            }
+            match &tx.status {
+                status @ TransactionStatus::Committed { .. } => {
+                    say!(
+                        "✅ transaction {} committed in {:.1?}",
+                        tx_id.to_hex(),
+                        start.elapsed()
+                    );
+                    return Ok(status.clone());
+                }
+                status @ TransactionStatus::Discarded(_) => return Ok(status.clone()),
+                TransactionStatus::Pending => {}
+            }
+
+            let expiration_block = tx.details.expiration_block_num;
+            if client.get_sync_height().await? > expiration_block {
+                return Err(WaitError::Expired {
+                    tx_id,
+                    expiration_block,
+                });
+            }
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(WaitError::Timeout {
+                tx_id,
+                waited: start.elapsed(),
+            });
+        }
+        let delay = poller.next_delay().min(deadline - now);
+        say!(
+            "Transaction {} not yet committed. Next check in {:.1?}...",
+            tx_id.to_hex(),
+            delay
+        );
+        sleep(delay).await;
+    }
+}
+
+/// Syncs until the chain reaches `block` and returns the height it reached.
+///
+/// Fails with [`WaitError::BlockTimeout`] once `options.timeout` passes, so a
+/// stalled node ends the run instead of keeping it waiting.
+pub async fn wait_for_block(
+    client: &mut Client<FilesystemKeyStore>,
+    block: u32,
+    options: &WaitOptions,
+) -> Result<u32, WaitError> {
+    let start = Instant::now();
+    let deadline = start + options.timeout;
+    let mut poller = options.poller.clone();
+    loop {
+        if !sync_with_backoff(client, &mut poller, deadline).await? {
+            return Err(WaitError::BlockTimeout {
+                block,
+                waited: start.elapsed(),
+            });
+        }
+        let height = client.get_sync_height().await?.as_u32();
+        if height >= block {
+            return Ok(height);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(WaitError::BlockTimeout {
+                block,
+                waited: start.elapsed(),
+            });
+        }
+        let delay = poller.next_delay().min(deadline - now);
+        say!(
+            "Block {height}, waiting for block {block}. Next check in {:.1?}...",
+            delay
+        );
+        sleep(delay).await;
+    }
+}
+
+/// A consumable note together with the accounts that can consume it.
+pub type ConsumableNote = (InputNoteRecord, Vec<NoteConsumability>);
+
+/// Waits until an account has at least `expected` consumable notes.
+pub async fn wait_for_notes(
+    client: &mut Client<FilesystemKeyStore>,
+    account_id: AccountId,
+    expected: usize,
+) -> Result<(), WaitError> {
+    wait_for(client, note_count(account_id, expected))
+        .await
+        .map(|_| ())
+}
+
+/// Polls the consumable notes of every tracked account until `ready` accepts them.
+///
+/// Returns the notes `ready` last saw, so the caller can pick the ones it waited
+/// for. Combine with [`note_count`], [`has_note`] or [`has_note_with_tag`], or
+/// pass a closure of your own.
+pub async fn wait_for<F>(
+    client: &mut Client<FilesystemKeyStore>,
+    ready: F,
+) -> Result<Vec<ConsumableNote>, WaitError>
+where
+    F: FnMut(&[ConsumableNote]) -> bool,
+{
+    wait_for_with(client, WaitOptions::default(), ready).await
+}
+
+/// [`wait_for`] with a custom deadline and polling interval.
+pub async fn wait_for_with<F>(
+    client: &mut Client<FilesystemKeyStore>,
+    options: WaitOptions,
+    mut ready: F,
+) -> Result<Vec<ConsumableNote>, WaitError>
+where
+    F: FnMut(&[ConsumableNote]) -> bool,
+{
+    let start = Instant::now();
+    let deadline = start + options.timeout;
+    let mut poller = options.poller;
+    loop {
+        if !sync_with_backoff(client, &mut poller, deadline).await? {
+            return Err(WaitError::NotesTimeout {
+                waited: start.elapsed(),
+            });
+        }
+        let notes = client.get_consumable_notes(None).await?;
+        if ready(&notes) {
+            return Ok(notes);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(WaitError::NotesTimeout {
+                waited: start.elapsed(),
+            });
+        }
+        let delay = poller.next_delay().min(deadline - now);
+        say!(
+            "{} consumable notes found, still waiting. Next check in {:.1?}...",
+            notes.len(),
+            delay
+        );
+        sleep(delay).await;
+    }
+}
+
+/// Ready once `account_id` can consume at least `expected` notes.
+pub fn note_count(account_id: AccountId, expected: usize) -> impl Fn(&[ConsumableNote]) -> bool {
+    move |notes| {
+        let count = notes
+            .iter()
+            .filter(|(_, consumers)| consumers.iter().any(|(id, _)| *id == account_id))
+            .count();
+        if count < expected {
+            say!(
+                "{count} of {expected} consumable notes found for account {}",
+                account_id.to_bech32(network_id())
+            );
+        }
+        count >= expected
+    }
+}
+
+/// Ready once the note with `note_id` is consumable.
+pub fn has_note(note_id: NoteId) -> impl Fn(&[ConsumableNote]) -> bool {
+    move |notes| notes.iter().any(|(note, _)| note.id() == note_id)
+}
+
+/// Ready once a note carrying `tag` is consumable.
+///
+/// The client only fetches notes with tags it tracks, so add the tag with
+/// `Client::add_note_tag` first unless it targets one of your accounts.
+pub fn has_note_with_tag(tag: NoteTag) -> impl Fn(&[ConsumableNote]) -> bool {
+    move |notes| {
+        notes.iter().any(|(note, _)| {
+            note.metadata()
+                .is_some_and(|metadata| metadata.tag() == tag)
+        })
+    }
+}
+
+/// Polling interval that starts short and slows down the longer a wait takes.
+///
+/// Public endpoints rate-limit clients that sync too often, and a transaction
+/// that is not committed after a few blocks rarely lands in the next second.
+#[derive(Debug, Clone)]
+pub struct Poller {
+    interval: Duration,
+    max_interval: Duration,
+}
+
+impl Default for Poller {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(2), Duration::from_secs(20))
+    }
+}
+
+impl Poller {
+    pub fn new(initial: Duration, max_interval: Duration) -> Self {
+        Self {
+            interval: initial,
+            max_interval,
+        }
+    }
+
+    /// Returns the delay before the next poll and grows the following one by half.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.interval;
+        self.interval = self.interval.mul_f64(1.5).min(self.max_interval);
+        delay
+    }
+
+    /// Slows down after the node rejected a request for exceeding its rate limit.
+    ///
+    /// A `Retry-After` hint from the node wins over the local backoff.
+    pub fn rate_limited(&mut self, retry_after: Option<Duration>) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| (self.interval * 2).min(RATE_LIMIT_MAX_DELAY));
+        self.interval = delay.max(self.interval);
+        delay
+    }
+}
+
+/// Upper bound for the backoff after a rate limit without a `Retry-After` hint.
+const RATE_LIMIT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Syncs the client, waiting out rate limits instead of failing the surrounding wait.
+///
+/// Never sleeps past `deadline`, and returns `false` once the node is still
+/// rate limiting when it passes, so the caller can time out as usual.
+async fn sync_with_backoff(
+    client: &mut Client<FilesystemKeyStore>,
+    poller: &mut Poller,
+    deadline: Instant,
+) -> Result<bool, ClientError> {
+    loop {
+        match client.sync_state().await {
+            Ok(_) => return Ok(true),
+            Err(err) => match rate_limit_hint(&err) {
+                Some(retry_after) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Ok(false);
+                    }
+                    let delay = poller.rate_limited(retry_after).min(deadline - now);
+                    say!(
+                        "Rate limited by the node. Retrying sync in {:.1?}...",
+                        delay
+                    );
+                    sleep(delay).await;
+                }
+                None => return Err(err),
+            },
+        }
+    }
+}
+
+/// Detects a rate-limit rejection, returning the `Retry-After` delay if the node sent one.
+///
+/// The client surfaces gRPC statuses only as text, so this matches on the message.
+fn rate_limit_hint(err: &ClientError) -> Option<Option<Duration>> {
+    let message = err.to_string().to_lowercase();
+    let rate_limited = [
+        "resource exhausted",
+        "resourceexhausted",
+        "too many requests",
+        "rate limit",
+    ]
+    .iter()
+    .any(|marker| message.contains(marker));
+    if !rate_limited {
+        return None;
+    }
+
+    let retry_after = message.find("retry-after").and_then(|start| {
+        let seconds: String = message[start + "retry-after".len()..]
+            .chars()
+            .skip_while(|c| !c.is_ascii_digit())
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        seconds.parse().ok().map(Duration::from_secs)
+    });
+    Some(retry_after)
+}
@@ -0,0 +1,205 @@
+//! SDK-style wrapper around the wrapped-token faucet in `masm/accounts/wrapped_token.masm`.
+//!
+//! The wrapper is one account built from two components: `BasicWallet`, which
+//! makes it a wallet holding the underlying token, and the wrapped-token
+//! component, which makes it a fungible faucet for its own token. A wrap note
+//! deposits underlying tokens and gets as many wrapped tokens minted back in a
+//! P2ID note. An unwrap note burns wrapped tokens and gets as many underlying
+//! tokens paid out of the vault, so every wrapped token stays backed 1:1.
+//!
+//! Unlike the escrow, the wrapper keeps `BasicWallet` and signs its transactions
+//! like any faucet. Only the holder of its key can run `move_asset_to_note`, so
+//! the reserves are as safe as that key.
+
+use std::sync::Arc;
+
+use miden_client::{
+    account::{component::BasicWallet, AccountId, AccountType, StorageSlot, StorageSlotName},
+    assembly::Library,
+    asset::FungibleAsset,
+    keystore::FilesystemKeyStore,
+    note::{create_p2id_note, Note, NoteAttachment, NoteScript, NoteType},
+    transaction::{OutputNote, TransactionRequestBuilder},
+    Client, ClientError, Felt, Word,
+};
+
+use crate::{
+    accounts::get_full_account,
+    deployer::{ContractAuth, ContractDeployer},
+    masm::MasmFile,
+    note_builder::{NoteBuilder, SerialNumbers},
+    wait::{submit_and_wait, TxReport},
+};
+
+pub const UNDERLYING_SLOT: &str = "miden::tutorials::wrapped_token::underlying";
+
+/// Library path the wrapped-token procedures are exported under.
+pub const LIBRARY_PATH: &str = "external_contract::wrapped_token";
+
+const COMPONENT_FILE: &str = "masm/accounts/wrapped_token.masm";
+const COMPONENT_CODE: &str = include_str!("../../masm/accounts/wrapped_token.masm");
+const WRAP_NOTE_FILE: &str = "masm/notes/wrap_note.masm";
+const WRAP_NOTE_CODE: &str = include_str!("../../masm/notes/wrap_note.masm");
+const UNWRAP_NOTE_FILE: &str = "masm/notes/unwrap_note.masm";
+const UNWRAP_NOTE_CODE: &str = include_str!("../../masm/notes/unwrap_note.masm");
+
+/// A deployed wrapper around the token of `underlying`.
+pub struct WrappedToken {
+    /// The wrapper's account, which is also the faucet of the wrapped token.
+    pub account_id: AccountId,
+    /// Faucet of the token the wrapper holds.
+    pub underlying: AccountId,
+    library: Library,
+}
+
+impl WrappedToken {
+    /// Builds a wrapper for the token of `underlying` and starts tracking it.
+    ///
+    /// Faucets sign their transactions, so the wrapper's key goes to `keystore`.
+    /// The account is deployed on-chain with the first note it consumes.
+    pub async fn deploy(
+        client: &mut Client<FilesystemKeyStore>,
+        keystore: &Arc<FilesystemKeyStore>,
+        underlying: AccountId,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let underlying_word = Word::from([
+            Felt::new(0),
+            Felt::new(0),
+            underlying.suffix(),
+            underlying.prefix().as_felt(),
+        ]);
+        let deployer = ContractDeployer::new(
+            MasmFile::embedded(COMPONENT_FILE, COMPONENT_CODE),
+            LIBRARY_PATH,
+        )
+        .with_slot(StorageSlot::with_value(
+            StorageSlotName::new(UNDERLYING_SLOT)?,
+            underlying_word,
+        ))
+        .with_component(BasicWallet)
+        .account_type(AccountType::FungibleFaucet)
+        .auth(ContractAuth::new_falcon512(keystore));
+        let library = deployer.library()?;
+        let account = deployer.deploy(client).await?;
+
+        Ok(Self {
+            account_id: account.id(),
+            underlying,
+            library,
+        })
+    }
+
+    /// `amount` of the wrapped token.
+    pub fn wrapped_asset(&self, amount: u64) -> Result<FungibleAsset, Box<dyn std::error::Error>> {
+        Ok(FungibleAsset::new(self.account_id, amount)?)
+    }
+
+    /// `amount` of the underlying token.
+    pub fn underlying_asset(
+        &self,
+        amount: u64,
+    ) -> Result<FungibleAsset, Box<dyn std::error::Error>> {
+        Ok(FungibleAsset::new(self.underlying, amount)?)
+    }
+
+    /// Deposits `amount` underlying tokens of `holder` and returns the P2ID note
+    /// paying the wrapped tokens minted for them.
+    pub async fn wrap(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        holder: AccountId,
+        amount: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        self.exchange(
+            client,
+            holder,
+            self.underlying_asset(amount)?,
+            self.wrapped_asset(amount)?,
+            MasmFile::embedded(WRAP_NOTE_FILE, WRAP_NOTE_CODE),
+        )
+        .await
+    }
+
+    /// Burns `amount` wrapped tokens of `holder` and returns the P2ID note
+    /// paying the underlying tokens released for them.
+    pub async fn unwrap(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        holder: AccountId,
+        amount: u64,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        self.exchange(
+            client,
+            holder,
+            self.wrapped_asset(amount)?,
+            self.underlying_asset(amount)?,
+            MasmFile::embedded(UNWRAP_NOTE_FILE, UNWRAP_NOTE_CODE),
+        )
+        .await
+    }
+
+    /// Returns the underlying tokens backing the wrapped ones, as of the last sync.
+    pub async fn reserves(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+    ) -> Result<u64, ClientError> {
+        let wrapper = get_full_account(client, self.account_id).await?;
+        Ok(wrapper.vault().get_balance(self.underlying).unwrap_or(0))
+    }
+
+    /// Sends `given` from `holder` in a note running `file`, has the wrapper
+    /// consume it and returns the P2ID note paying `received` back.
+    async fn exchange(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        holder: AccountId,
+        given: FungibleAsset,
+        received: FungibleAsset,
+        file: MasmFile,
+    ) -> Result<(TxReport, Note), Box<dyn std::error::Error>> {
+        let payout = create_p2id_note(
+            self.account_id,
+            holder,
+            vec![received.into()],
+            NoteType::Public,
+            NoteAttachment::default(),
+            client.rng(),
+        )?;
+
+        let mut inputs = payout.recipient().digest().to_vec();
+        inputs.push(payout.metadata().tag().into());
+        let script = self.note_script(client, file)?;
+        let note = NoteBuilder::new(holder, script)
+            .inputs(inputs)
+            .asset(given)
+            .target(self.account_id)
+            .build(&mut SerialNumbers::random(), client.rng())?;
+
+        let note_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![OutputNote::Full(note.clone())])
+            .build()?;
+        submit_and_wait(client, holder, note_request).await?;
+
+        let consume_request = TransactionRequestBuilder::new()
+            .input_notes([(note, None)])
+            .expected_output_recipients(vec![payout.recipient().clone()])
+            .build()?;
+        let report = submit_and_wait(client, self.account_id, consume_request).await?;
+        Ok((report, payout))
+    }
+
+    fn note_script(
+        &self,
+        client: &mut Client<FilesystemKeyStore>,
+        file: MasmFile,
+    ) -> Result<NoteScript, ClientError> {
+        let builder = client
+            .code_builder()
+            .with_dynamically_linked_library(&self.library)?;
+        // The scripts are embedded, so a failure here is a bug in the wrapper itself
+        let script = file
+            .check(builder.compile_note_script(&file.source))
+            .expect("wrapped-token note script does not compile");
+        Ok(script)
+    }
+}
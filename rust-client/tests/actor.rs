@@ -0,0 +1,56 @@
+//! Serializes access to shared state, see `rust_client::actor`.
+
+use rust_client::actor::{Actor, ActorStopped};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn jobs_from_many_tasks_all_apply() {
+    let (actor, handle) = Actor::new(Vec::<u32>::new());
+
+    let workers: Vec<_> = (0..8)
+        .map(|task| {
+            let handle = handle.clone();
+            tokio::spawn(async move {
+                for step in 0..25 {
+                    let value = task * 100 + step;
+                    let len = handle
+                        .call(move |log: &mut Vec<u32>| {
+                            Box::pin(async move {
+                                tokio::task::yield_now().await;
+                                log.push(value);
+                                log.len()
+                            })
+                        })
+                        .await
+                        .expect("the actor runs until every handle is dropped");
+                    assert!(len > step as usize);
+                }
+            })
+        })
+        .collect();
+    drop(handle);
+
+    let log = actor.run().await;
+    for worker in workers {
+        worker.await.unwrap();
+    }
+    assert_eq!(log.len(), 200);
+    // Each task's jobs run in the order it sent them
+    for task in 0..8 {
+        let own: Vec<u32> = log.iter().copied().filter(|v| v / 100 == task).collect();
+        assert_eq!(
+            own,
+            (0..25).map(|step| task * 100 + step).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[tokio::test]
+async fn calls_fail_once_the_actor_is_gone() {
+    let (actor, handle) = Actor::new(0u64);
+    drop(actor);
+
+    let result = handle
+        .call(|count: &mut u64| Box::pin(async move { *count }))
+        .await;
+    assert_eq!(result, Err(ActorStopped));
+}
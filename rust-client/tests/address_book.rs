@@ -0,0 +1,70 @@
+//! Records and looks up account auth with `rust_client::address_book`.
+
+mod common;
+
+use miden_client::auth::AuthSecretKey;
+use rust_client::{
+    address_book::{AccountAuth, AddressBook},
+    error::TutorialError,
+};
+
+use common::{account_id, scratch_path};
+
+#[test]
+fn keyed_accounts_keep_their_key() {
+    let path = scratch_path("keys.toml");
+    let key = AuthSecretKey::new_falcon512_rpo()
+        .public_key()
+        .to_commitment();
+
+    AddressBook::load(&path)
+        .unwrap()
+        .record_keys(account_id(1), key)
+        .unwrap();
+
+    let book = AddressBook::load(&path).unwrap();
+    assert_eq!(book.auth(account_id(1)), Some(AccountAuth::Keys));
+    assert_eq!(book.require_keys(account_id(1)).unwrap(), Some(key));
+}
+
+#[test]
+fn no_auth_contracts_have_no_key_to_look_up() {
+    let path = scratch_path("no_auth.toml");
+    AddressBook::load(&path)
+        .unwrap()
+        .record_no_auth(account_id(1))
+        .unwrap();
+
+    let book = AddressBook::load(&path).unwrap();
+    assert_eq!(book.auth(account_id(1)), Some(AccountAuth::NoAuth));
+    let err = book.require_keys(account_id(1)).unwrap_err();
+    assert_eq!(TutorialError::classify(&err), TutorialError::Config);
+}
+
+#[test]
+fn unknown_accounts_are_let_through() {
+    let book = AddressBook::load(scratch_path("unknown.toml")).unwrap();
+    assert_eq!(book.auth(account_id(1)), None);
+    assert_eq!(book.require_keys(account_id(1)).unwrap(), None);
+}
+
+#[test]
+fn recording_again_replaces_the_entry() {
+    let path = scratch_path("replace.toml");
+    let key = AuthSecretKey::new_falcon512_rpo()
+        .public_key()
+        .to_commitment();
+    let mut book = AddressBook::load(&path).unwrap();
+    book.record_keys(account_id(1), key).unwrap();
+    book.record_no_auth(account_id(1)).unwrap();
+
+    let book = AddressBook::load(&path).unwrap();
+    assert_eq!(book.auth(account_id(1)), Some(AccountAuth::NoAuth));
+    assert_eq!(
+        std::fs::read_to_string(&path)
+            .unwrap()
+            .matches("[[account]]")
+            .count(),
+        1
+    );
+}
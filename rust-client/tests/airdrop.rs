@@ -0,0 +1,99 @@
+//! Parses airdrop recipient files with `rust_client::airdrop`.
+
+mod common;
+
+use rust_client::{
+    airdrop::{batch_count, parse_recipients, total_amount, Recipient},
+    client::network_id,
+};
+
+use common::account_id;
+
+fn address(seed: u8) -> String {
+    account_id(seed).to_bech32(network_id())
+}
+
+#[test]
+fn reads_rows_with_and_without_amounts() {
+    let csv = format!(
+        "address,amount\n# early supporters\n{},25\n\n  {}  \n",
+        address(1),
+        address(2)
+    );
+
+    let recipients = parse_recipients(&csv, 10).unwrap();
+
+    assert_eq!(
+        recipients,
+        [
+            Recipient {
+                account_id: account_id(1),
+                amount: 25
+            },
+            Recipient {
+                account_id: account_id(2),
+                amount: 10
+            },
+        ]
+    );
+    assert_eq!(total_amount(&recipients), Some(35));
+}
+
+#[test]
+fn header_is_optional() {
+    let csv = format!("{},5\n", address(1));
+
+    assert_eq!(parse_recipients(&csv, 10).unwrap().len(), 1);
+}
+
+#[test]
+fn rejects_bad_rows_with_their_line() {
+    let bad = [
+        (format!("{},abc", address(1)), "invalid amount"),
+        (format!("{},0", address(1)), "positive"),
+        (format!("{},1,2", address(1)), "3 fields"),
+        ("not-an-address,5".to_string(), "invalid address"),
+    ];
+    for (row, reason) in bad {
+        let csv = format!("address,amount\n{},5\n{row}\n", address(2));
+
+        let err = parse_recipients(&csv, 10).unwrap_err();
+
+        assert_eq!(err.line, 3, "{row}");
+        assert!(err.reason.contains(reason), "{row}: {}", err.reason);
+    }
+}
+
+#[test]
+fn rejects_duplicate_recipients() {
+    let csv = format!("{}\n{}\n{},7\n", address(1), address(2), address(1));
+
+    let err = parse_recipients(&csv, 10).unwrap_err();
+
+    assert_eq!(err.line, 3);
+    assert!(err.reason.contains("listed twice"));
+}
+
+#[test]
+fn overflowing_total_is_none() {
+    let recipients = [
+        Recipient {
+            account_id: account_id(1),
+            amount: u64::MAX,
+        },
+        Recipient {
+            account_id: account_id(2),
+            amount: 1,
+        },
+    ];
+
+    assert_eq!(total_amount(&recipients), None);
+}
+
+#[test]
+fn batches_round_up() {
+    assert_eq!(batch_count(0, 4), 0);
+    assert_eq!(batch_count(4, 4), 1);
+    assert_eq!(batch_count(5, 4), 2);
+    assert_eq!(batch_count(10, 1024), 1);
+}
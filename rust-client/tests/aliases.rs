@@ -0,0 +1,70 @@
+//! Saves and reads account aliases with `rust_client::aliases`.
+
+mod common;
+
+use rust_client::{aliases::Aliases, network::Network};
+
+use common::{account_id, scratch_path};
+
+#[test]
+fn saved_aliases_survive_a_reload() {
+    let path = scratch_path("reload.toml");
+    let (alice, faucet) = (account_id(1), account_id(2));
+
+    let mut aliases = Aliases::load(&path).unwrap();
+    aliases.save(Network::Devnet, "Alice", alice).unwrap();
+    aliases.save(Network::Devnet, "faucet", faucet).unwrap();
+
+    let reloaded = Aliases::load(&path).unwrap();
+    assert_eq!(reloaded.get(Network::Devnet, "alice"), Ok(Some(alice)));
+    assert_eq!(reloaded.get(Network::Devnet, "ALICE"), Ok(Some(alice)));
+    assert_eq!(
+        reloaded.entries(Network::Devnet),
+        Ok(vec![
+            ("alice".to_string(), alice),
+            ("faucet".to_string(), faucet)
+        ])
+    );
+}
+
+#[test]
+fn saving_again_moves_the_alias() {
+    let path = scratch_path("move.toml");
+    let mut aliases = Aliases::load(&path).unwrap();
+    aliases
+        .save(Network::Devnet, "alice", account_id(1))
+        .unwrap();
+    aliases
+        .save(Network::Devnet, "alice", account_id(3))
+        .unwrap();
+
+    assert_eq!(
+        Aliases::load(&path).unwrap().get(Network::Devnet, "alice"),
+        Ok(Some(account_id(3)))
+    );
+}
+
+#[test]
+fn networks_are_kept_apart() {
+    let path = scratch_path("networks.toml");
+    let mut aliases = Aliases::load(&path).unwrap();
+    aliases
+        .save(Network::Devnet, "alice", account_id(1))
+        .unwrap();
+
+    assert_eq!(aliases.get(Network::Testnet, "alice"), Ok(None));
+    assert_eq!(aliases.entries(Network::Testnet), Ok(vec![]));
+}
+
+#[test]
+fn names_that_are_not_aliases_are_rejected() {
+    let path = scratch_path("invalid.toml");
+    let mut aliases = Aliases::load(&path).unwrap();
+    let id = account_id(1);
+    let bech32 = id.to_bech32(Network::Devnet.network_id());
+
+    assert!(aliases.save(Network::Devnet, "", id).is_err());
+    assert!(aliases.save(Network::Devnet, "bob's wallet", id).is_err());
+    assert!(aliases.save(Network::Devnet, &bech32, id).is_err());
+    assert!(!path.exists());
+}
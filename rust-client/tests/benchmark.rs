@@ -0,0 +1,103 @@
+//! Formats benchmark reports with `rust_client::benchmark`.
+
+use std::time::Duration;
+
+use rust_client::benchmark::{PhaseTimes, Report, Sample, Workload, CSV_HEADER};
+
+fn phases(execute: u64, prove: u64, submit: u64, commit: u64) -> PhaseTimes {
+    PhaseTimes {
+        execute: Duration::from_millis(execute),
+        prove: Duration::from_millis(prove),
+        submit: Duration::from_millis(submit),
+        commit: Duration::from_millis(commit),
+    }
+}
+
+fn sample(workload: Workload, iteration: usize, cycles: usize, phases: PhaseTimes) -> Sample {
+    Sample {
+        workload,
+        iteration,
+        tx_id: format!("0x{iteration:02x}"),
+        cycles,
+        phases,
+    }
+}
+
+fn report() -> Report {
+    let mut report = Report::new("testnet");
+    report.push(sample(
+        Workload::FpiRead,
+        1,
+        40_000,
+        phases(10, 900, 50, 3_000),
+    ));
+    report.push(sample(Workload::Mint, 1, 20_000, phases(4, 500, 30, 2_000)));
+    report.push(sample(
+        Workload::FpiRead,
+        2,
+        42_000,
+        phases(20, 1_100, 70, 5_000),
+    ));
+    report
+}
+
+#[test]
+fn summaries_average_each_workload_in_run_order() {
+    let summaries = report().summaries();
+
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].workload, Workload::FpiRead);
+    assert_eq!(summaries[0].samples, 2);
+    assert_eq!(summaries[0].cycles, 41_000);
+    assert_eq!(summaries[0].mean, phases(15, 1_000, 60, 4_000));
+    assert_eq!(summaries[0].mean.total(), Duration::from_millis(5_075));
+    assert_eq!(summaries[1].workload, Workload::Mint);
+    assert_eq!(summaries[1].mean, phases(4, 500, 30, 2_000));
+}
+
+#[test]
+fn mean_of_nothing_is_zero() {
+    assert_eq!(PhaseTimes::mean(&[]), PhaseTimes::default());
+    assert!(Report::new("devnet").summaries().is_empty());
+}
+
+#[test]
+fn csv_has_one_row_per_sample_in_milliseconds() {
+    let csv = report().to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], CSV_HEADER);
+    assert_eq!(
+        lines[1],
+        "fpi-read,1,0x01,40000,10.000,900.000,50.000,3000.000,3960.000"
+    );
+    assert_eq!(lines.len(), 4);
+}
+
+#[test]
+fn json_lists_summaries_and_samples() {
+    let json: serde_json::Value = serde_json::from_str(&report().to_json()).unwrap();
+
+    assert_eq!(json["network"], "testnet");
+    assert_eq!(json["summaries"][0]["workload"], "fpi-read");
+    assert_eq!(json["summaries"][0]["prove_ms"], 1000.0);
+    assert_eq!(json["samples"].as_array().unwrap().len(), 3);
+    assert_eq!(json["samples"][1]["workload"], "mint");
+    assert_eq!(json["samples"][1]["commit_ms"], 2000.0);
+}
+
+#[test]
+fn format_follows_the_extension() {
+    let dir = std::env::temp_dir().join(format!("benchmark-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let report = report();
+
+    report.write(&dir.join("bench.CSV")).unwrap();
+    report.write(&dir.join("bench.json")).unwrap();
+
+    let csv = std::fs::read_to_string(dir.join("bench.CSV")).unwrap();
+    let json = std::fs::read_to_string(dir.join("bench.json")).unwrap();
+    assert!(csv.starts_with(CSV_HEADER));
+    assert!(json.starts_with('{'));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,123 @@
+//! Runs the transfer flow of the tutorials against a mock node while
+//! `rust_client::chaos` fails 10% of RPC calls, relying on the retry layer.
+//!
+//! Run with `cargo test --features chaos --test chaos`.
+#![cfg(feature = "chaos")]
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use miden_client::{
+    asset::FungibleAsset,
+    builder::ClientBuilder,
+    keystore::FilesystemKeyStore,
+    note::NoteType,
+    rpc::NodeRpcClient,
+    testing::mock::MockRpcApi,
+    transaction::{PaymentNoteDescription, TransactionRequestBuilder},
+};
+use miden_client_sqlite_store::ClientBuilderSqliteExt;
+use miden_testing::MockChain;
+use rust_client::{
+    accounts::{create_basic_account, create_basic_faucet, get_full_account, top_up},
+    chaos::{ChaosConfig, ChaosRpcClient, CHAOS_ENV},
+    error::TutorialError,
+    middleware::RetryRpcClient,
+    notes::{consume_all, ConsumeOptions},
+    wait::{submit_and_wait, wait_for_notes, wait_for_tx},
+};
+
+use common::scratch_dir;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn transfer_completes_under_ten_percent_faults() {
+    // The moderate preset, sped up and with a fixed fault schedule
+    let config = ChaosConfig {
+        max_latency: Duration::from_millis(10),
+        timeout: Duration::from_millis(20),
+        seed: Some(1227),
+        ..ChaosConfig::moderate()
+    };
+    assert!((config.fault_rate() - 0.10).abs() < 1e-9);
+
+    // The mock node only makes blocks when asked to
+    let node = MockRpcApi::new(MockChain::new());
+    let producer = node.clone();
+    let blocks = tokio::spawn(async move {
+        loop {
+            producer.prove_block();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    });
+
+    let rpc: Arc<dyn NodeRpcClient> = Arc::new(
+        RetryRpcClient::new(Arc::new(ChaosRpcClient::new(Arc::new(node), config)), 5)
+            .with_base_delay(Duration::from_millis(10)),
+    );
+    let dir = scratch_dir("chaos");
+    let keystore = Arc::new(FilesystemKeyStore::new(dir.join("keystore")).unwrap());
+    let mut client = ClientBuilder::new()
+        .rpc(rpc)
+        .sqlite_store(dir.join("store.sqlite3"))
+        .authenticator(keystore.clone())
+        .in_debug_mode(true.into())
+        .build()
+        .await
+        .unwrap();
+    client.sync_state().await.unwrap();
+
+    let alice = create_basic_account(&mut client, &keystore).await.unwrap();
+    let bob = create_basic_account(&mut client, &keystore).await.unwrap();
+    let faucet = create_basic_faucet(&mut client, &keystore).await.unwrap();
+    top_up(&mut client, faucet.id(), alice.id(), 100)
+        .await
+        .unwrap();
+
+    let payment = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(
+                vec![FungibleAsset::new(faucet.id(), 40).unwrap().into()],
+                alice.id(),
+                bob.id(),
+            ),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    let report = submit_and_wait(&mut client, alice.id(), payment)
+        .await
+        .unwrap();
+    assert!(report.is_committed());
+
+    wait_for_notes(&mut client, bob.id(), 1).await.unwrap();
+    let options = ConsumeOptions::default().with_known_faucets([faucet.id()]);
+    let tx_id = consume_all(&mut client, bob.id(), &options)
+        .await
+        .unwrap()
+        .expect("Bob has a note to consume");
+    wait_for_tx(&mut client, tx_id).await.unwrap();
+    blocks.abort();
+
+    let balance = |account: miden_client::account::Account| {
+        account.vault().get_balance(faucet.id()).unwrap_or(0)
+    };
+    assert_eq!(
+        balance(get_full_account(&mut client, alice.id()).await.unwrap()),
+        60
+    );
+    assert_eq!(
+        balance(get_full_account(&mut client, bob.id()).await.unwrap()),
+        40
+    );
+}
+
+#[test]
+fn an_unknown_preset_is_a_config_error() {
+    // The only test here that reads the variable, so setting it cannot race
+    std::env::set_var(CHAOS_ENV, "extreme");
+    let err = ChaosConfig::from_env().unwrap_err();
+    std::env::remove_var(CHAOS_ENV);
+    assert_eq!(TutorialError::classify(&err), TutorialError::Config);
+    assert_eq!(TutorialError::Config.exit_code(), 2);
+}
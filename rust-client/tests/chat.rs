@@ -0,0 +1,82 @@
+//! Encodes chat messages into note inputs and reads them back, see `rust_client::chat`.
+
+mod common;
+
+use miden_client::Felt;
+use rust_client::chat::{
+    decode_text, encode_text, ChatError, ChatMessage, Conversation, MAX_MESSAGE_LEN,
+};
+
+use common::account_id;
+
+fn message(seq: u64, text: &str) -> ChatMessage {
+    ChatMessage {
+        from: account_id(1),
+        to: account_id(2),
+        seq,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn text_round_trips_through_felts() {
+    for text in ["", "hi", "exactly7", "Grüße aus Miden 👋"] {
+        let felts = encode_text(text);
+        assert_eq!(decode_text(&felts, text.len()).unwrap(), text);
+    }
+}
+
+#[test]
+fn message_round_trips_through_note_inputs() {
+    let sent = message(3, "A note is a message that may carry assets.");
+    let inputs = sent.to_inputs().unwrap();
+
+    let received = ChatMessage::from_inputs(sent.from, &inputs).unwrap();
+    assert_eq!(received, sent);
+}
+
+#[test]
+fn messages_over_the_limit_are_refused() {
+    let longest = message(1, &"a".repeat(MAX_MESSAGE_LEN));
+    assert!(longest.to_inputs().is_ok());
+
+    let too_long = message(1, &"a".repeat(MAX_MESSAGE_LEN + 1));
+    assert_eq!(
+        too_long.to_inputs(),
+        Err(ChatError::TooLong(MAX_MESSAGE_LEN + 1))
+    );
+}
+
+#[test]
+fn foreign_inputs_are_not_messages() {
+    let from = account_id(1);
+    assert!(matches!(
+        ChatMessage::from_inputs(from, &[Felt::new(1), Felt::new(2)]),
+        Err(ChatError::Malformed(_))
+    ));
+
+    // A length that does not match the number of text inputs
+    let mut inputs = message(1, "hello").to_inputs().unwrap();
+    inputs[3] = Felt::new(20);
+    assert!(matches!(
+        ChatMessage::from_inputs(from, &inputs),
+        Err(ChatError::Malformed(_))
+    ));
+}
+
+#[test]
+fn conversation_orders_by_seq_and_skips_duplicates() {
+    let mut conversation = Conversation::new(account_id(1));
+    assert_eq!(conversation.next_seq(), 1);
+
+    assert!(conversation.insert(message(2, "second")));
+    assert!(conversation.insert(message(1, "first")));
+    assert!(!conversation.insert(message(2, "second again")));
+
+    let texts: Vec<_> = conversation
+        .messages()
+        .map(|message| message.text.as_str())
+        .collect();
+    assert_eq!(texts, ["first", "second"]);
+    assert_eq!(conversation.next_seq(), 3);
+}
@@ -0,0 +1,36 @@
+//! Helpers shared by the integration tests, included with `mod common;`.
+
+// Every test crate compiles its own copy and uses only some of the helpers
+#![allow(dead_code)]
+
+use std::{fs, path::PathBuf};
+
+use miden_client::account::{AccountId, AccountStorageMode, AccountType};
+use miden_protocol::account::AccountIdVersion;
+
+/// A public wallet ID, distinct for every `seed`, without building an account.
+pub fn account_id(seed: u8) -> AccountId {
+    AccountId::dummy(
+        [seed; 15],
+        AccountIdVersion::Version0,
+        AccountType::RegularAccountUpdatableCode,
+        AccountStorageMode::Public,
+    )
+}
+
+/// An empty directory for the test, wiped if an earlier run left it behind.
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rust-client-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A path for a file the test creates, which does not exist yet.
+pub fn scratch_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rust-client-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    let _ = fs::remove_file(&path);
+    path
+}
@@ -0,0 +1,63 @@
+//! Loads `miden-tutorials.toml` files with `rust_client::config` and parses network presets.
+
+use std::{fs, path::PathBuf};
+
+use miden_client::address::NetworkId;
+use rust_client::{config::TutorialConfig, network::Network};
+
+fn scratch_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("config-file-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn missing_file_is_empty() {
+    let config = TutorialConfig::load("does-not-exist.toml").unwrap();
+    assert!(config.endpoint.is_none());
+    assert!(config.store_path.is_none());
+}
+
+#[test]
+fn reads_every_key() {
+    let path = scratch_file(
+        "full.toml",
+        r#"
+endpoint = "http://localhost:57291"
+network = "devnet"
+store_path = "./other.sqlite3"
+keystore_path = "./other-keystore"
+default_faucet_id = "mtst1qq"
+"#,
+    );
+    let config = TutorialConfig::load(&path).unwrap();
+    assert_eq!(config.endpoint.as_deref(), Some("http://localhost:57291"));
+    assert_eq!(config.network.as_deref(), Some("devnet"));
+    assert_eq!(config.store_path, Some(PathBuf::from("./other.sqlite3")));
+    assert_eq!(
+        config.keystore_path,
+        Some(PathBuf::from("./other-keystore"))
+    );
+    assert_eq!(config.default_faucet_id.as_deref(), Some("mtst1qq"));
+}
+
+#[test]
+fn rejects_unknown_keys() {
+    let path = scratch_file("typo.toml", "endpont = \"http://localhost:57291\"\n");
+    assert!(TutorialConfig::load(&path).is_err());
+}
+
+#[test]
+fn network_names_select_a_preset() {
+    assert_eq!(Network::from_name("devnet").unwrap(), Network::Devnet);
+    assert_eq!(Network::from_name("Localhost").unwrap(), Network::Localhost);
+    assert!(Network::from_name("mtst").is_err());
+    assert!(Network::from_name("mainnet").is_err());
+
+    assert_eq!(Network::Devnet.network_id(), NetworkId::Devnet);
+    assert_ne!(Network::Localhost.network_id(), NetworkId::Testnet);
+    assert!(Network::Localhost.explorer_base_url().is_none());
+    assert!(Network::Localhost.remote_prover_url().is_none());
+}
@@ -0,0 +1,119 @@
+//! Runs the contract fixtures from `rust_client::contracts` on a local mock chain.
+
+use miden_client::{
+    account::{AccountStorageMode, StorageSlotName},
+    Word,
+};
+use miden_testing::{Auth, MockChain};
+use rust_client::{
+    code_report::{CodeReport, MAX_CODE_SIZE, MAX_PROCEDURES},
+    contracts::{CountReader, Counter, Mapping, OracleReader},
+};
+
+#[tokio::test]
+async fn counter_fixture_increments() {
+    let mut builder = MockChain::builder();
+    let mut counter = builder
+        .add_existing_account_from_components(Auth::IncrNonce, [Counter::component().unwrap()])
+        .unwrap();
+    let chain = builder.build().unwrap();
+
+    let executed = chain
+        .build_tx_context(counter.id(), &[], &[])
+        .unwrap()
+        .tx_script(Counter::increment_script().unwrap())
+        .build()
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    counter.apply_delta(executed.account_delta()).unwrap();
+
+    let slot = StorageSlotName::new(Counter::COUNT_SLOT).unwrap();
+    let count = counter.storage().get_item(&slot).unwrap();
+    assert_eq!(count[3].as_int(), 1);
+}
+
+#[tokio::test]
+async fn mapping_fixture_writes_the_zero_key() {
+    let mut builder = MockChain::builder();
+    let mut mapping = builder
+        .add_existing_account_from_components(Auth::IncrNonce, [Mapping::component().unwrap()])
+        .unwrap();
+    let chain = builder.build().unwrap();
+
+    let executed = chain
+        .build_tx_context(mapping.id(), &[], &[])
+        .unwrap()
+        .tx_script(Mapping::write_script().unwrap())
+        .build()
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    mapping.apply_delta(executed.account_delta()).unwrap();
+
+    let slot = StorageSlotName::new(Mapping::MAP_SLOT).unwrap();
+    let value = mapping
+        .storage()
+        .get_map_item(&slot, Word::default())
+        .unwrap();
+    assert_ne!(value, Word::default());
+}
+
+#[test]
+fn oracle_reader_fixture_compiles() {
+    OracleReader::component().unwrap();
+    OracleReader::get_price_script().unwrap();
+}
+
+#[test]
+fn count_reader_fixture_compiles() {
+    let counter = Counter::deployer().unwrap().build([7; 32]).unwrap();
+
+    CountReader::component().unwrap();
+    CountReader::copy_count_script(counter.id()).unwrap();
+}
+
+#[test]
+fn deployer_builds_the_configured_account() {
+    let account = Counter::deployer()
+        .unwrap()
+        .storage_mode(AccountStorageMode::Network)
+        .build([7; 32])
+        .unwrap();
+
+    assert!(account.is_new());
+    assert_eq!(account.id().storage_mode(), AccountStorageMode::Network);
+    let slot = StorageSlotName::new(Counter::COUNT_SLOT).unwrap();
+    assert_eq!(account.storage().get_item(&slot).unwrap(), Word::default());
+}
+
+#[test]
+fn counter_code_is_well_within_the_limits() {
+    let account = Counter::deployer().unwrap().build([7; 32]).unwrap();
+    let report = CodeReport::of(account.code());
+
+    assert!(report.procedures >= 2, "the counter and its auth procedure");
+    assert!(report.warnings().is_empty(), "{:?}", report.warnings());
+    report.check("counter").unwrap();
+}
+
+#[test]
+fn code_reports_warn_before_failing() {
+    let report = CodeReport {
+        code_size: MAX_CODE_SIZE * 9 / 10,
+        procedures: 2,
+        commitment: Word::default(),
+    };
+    assert_eq!(report.warnings().len(), 1);
+    report.check("contract").unwrap();
+
+    let oversized = CodeReport {
+        procedures: MAX_PROCEDURES + 1,
+        ..report
+    };
+    let err = oversized.check("contract").unwrap_err();
+    assert_eq!(err.what, "procedures");
+    assert_eq!(err.used, MAX_PROCEDURES + 1);
+}
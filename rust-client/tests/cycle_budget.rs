@@ -0,0 +1,62 @@
+//! Keeps the counter increment within `Counter::INCREMENT_BUDGET` on a local mock chain.
+//!
+//! A failure here means a contract change made the increment more expensive.
+//! Raise the budget only if that was the intent.
+
+use miden_testing::{Auth, MockChain};
+use rust_client::{
+    contracts::Counter,
+    cycles::{CycleBudget, CycleCount},
+};
+
+#[tokio::test]
+async fn counter_increment_stays_within_its_cycle_budget() {
+    let mut builder = MockChain::builder();
+    let counter = builder
+        .add_existing_account_from_components(Auth::IncrNonce, [Counter::component().unwrap()])
+        .unwrap();
+    let chain = builder.build().unwrap();
+
+    let executed = chain
+        .build_tx_context(counter.id(), &[], &[])
+        .unwrap()
+        .tx_script(Counter::increment_script().unwrap())
+        .build()
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    let count = CycleCount::of(&executed);
+    assert!(count.tx_script > 0, "the script should have run");
+    if let Err(err) = Counter::INCREMENT_BUDGET.check(&count) {
+        panic!("{err}\n{count:#?}");
+    }
+}
+
+#[test]
+fn over_budget_names_the_phase_that_grew() {
+    let budget = CycleBudget {
+        name: "test",
+        tx_script: 100,
+        total: 1_000,
+    };
+    let count = CycleCount {
+        prologue: 300,
+        notes: 0,
+        tx_script: 150,
+        epilogue: 400,
+        auth: 50,
+        total: 850,
+        trace_length: 1_024,
+    };
+
+    let err = budget.check(&count).unwrap_err();
+    assert_eq!((err.section, err.used, err.budget), ("tx script", 150, 100));
+    assert!(budget
+        .check(&CycleCount {
+            tx_script: 100,
+            ..count
+        })
+        .is_ok());
+}
@@ -0,0 +1,47 @@
+//! Lays out and wipes per-binary data directories with `rust_client::data_dir`.
+
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use rust_client::{
+    client::ClientSetup,
+    data_dir::{binary_data_dir, DATA_DIR},
+};
+
+use common::scratch_dir;
+
+#[test]
+fn binaries_live_next_to_the_store() {
+    assert_eq!(
+        binary_data_dir(&PathBuf::from("./store.sqlite3"), "counter_contract_deploy"),
+        PathBuf::from("./data/counter_contract_deploy")
+    );
+    assert_eq!(
+        binary_data_dir(&PathBuf::from("/srv/miden/store.sqlite3"), "relayer"),
+        PathBuf::from("/srv/miden/data/relayer")
+    );
+}
+
+#[test]
+fn isolated_setup_keeps_state_until_fresh() {
+    let root = scratch_dir("fresh");
+    let setup = ClientSetup {
+        store_path: root.join("store.sqlite3"),
+        ..ClientSetup::default()
+    };
+
+    let isolated = setup.clone().with_data_dir("bank_contract", false).unwrap();
+    let dir = root.join(DATA_DIR).join("bank_contract");
+    assert_eq!(isolated.store_path, dir.join("store.sqlite3"));
+    assert_eq!(isolated.keystore_path, dir.join("keystore"));
+    fs::write(&isolated.store_path, b"earlier run").unwrap();
+
+    // Without --fresh a later run finds the same store
+    setup.clone().with_data_dir("bank_contract", false).unwrap();
+    assert!(isolated.store_path.exists());
+
+    setup.with_data_dir("bank_contract", true).unwrap();
+    assert!(dir.is_dir());
+    assert!(!isolated.store_path.exists());
+}
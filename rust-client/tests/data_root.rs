@@ -0,0 +1,40 @@
+//! Membership proofs against the root the data root contract stores.
+
+use rust_client::data_root::Dataset;
+
+fn dataset(names: &[&str]) -> Dataset {
+    Dataset::new(names.iter().map(|name| name.as_bytes().to_vec()).collect()).unwrap()
+}
+
+#[test]
+fn every_entry_proves_against_the_root() {
+    let dataset = dataset(&["alice", "bob", "carol", "dave", "erin"]);
+    for index in 0..dataset.entries().len() {
+        let proof = dataset.prove(index).unwrap();
+        assert!(proof.verify(dataset.root()), "entry {index} should verify");
+    }
+    assert!(dataset.prove(5).is_none(), "padding leaves are not entries");
+}
+
+#[test]
+fn forged_or_moved_entries_are_rejected() {
+    let dataset = dataset(&["alice", "bob", "carol"]);
+    let proof = dataset.prove(1).unwrap();
+
+    let mut forged = proof.clone();
+    forged.entry = b"mallory".to_vec();
+    assert!(!forged.verify(dataset.root()));
+
+    let mut moved = proof;
+    moved.index = 2;
+    assert!(!moved.verify(dataset.root()));
+}
+
+#[test]
+fn proofs_do_not_carry_over_to_a_new_root() {
+    let old = dataset(&["alice", "bob"]);
+    let new = dataset(&["alice", "carol"]);
+    let proof = old.prove(0).unwrap();
+    assert!(proof.verify(old.root()));
+    assert!(!proof.verify(new.root()));
+}
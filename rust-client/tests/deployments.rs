@@ -0,0 +1,55 @@
+//! Reads and writes the registry of `rust_client::deployments`.
+
+mod common;
+
+use std::fs;
+
+use rust_client::{deployments::Deployments, network::Network};
+
+use common::{account_id, scratch_path};
+
+#[test]
+fn missing_file_is_empty() {
+    let deployments = Deployments::load(scratch_path("missing.toml")).unwrap();
+    assert_eq!(deployments.get(Network::Devnet, "counter"), Ok(None));
+}
+
+#[test]
+fn recorded_ids_survive_a_reload() {
+    let path = scratch_path("reload.toml");
+    let counter = account_id(1);
+
+    let mut deployments = Deployments::load(&path).unwrap();
+    deployments
+        .record(Network::Devnet, "counter", counter)
+        .unwrap();
+
+    let reloaded = Deployments::load(&path).unwrap();
+    assert_eq!(reloaded.get(Network::Devnet, "counter"), Ok(Some(counter)));
+    assert!(fs::read_to_string(&path).unwrap().contains("[devnet]"));
+}
+
+#[test]
+fn networks_are_kept_apart() {
+    let path = scratch_path("networks.toml");
+    let (on_devnet, on_testnet) = (account_id(1), account_id(2));
+
+    let mut deployments = Deployments::load(&path).unwrap();
+    deployments
+        .record(Network::Devnet, "counter", on_devnet)
+        .unwrap();
+    deployments
+        .record(Network::Testnet, "counter", on_testnet)
+        .unwrap();
+
+    let reloaded = Deployments::load(&path).unwrap();
+    assert_eq!(
+        reloaded.get(Network::Devnet, "counter"),
+        Ok(Some(on_devnet))
+    );
+    assert_eq!(
+        reloaded.get(Network::Testnet, "counter"),
+        Ok(Some(on_testnet))
+    );
+    assert_eq!(reloaded.get(Network::Localhost, "counter"), Ok(None));
+}
@@ -0,0 +1,93 @@
+//! Checks that errors map to the exit codes documented in `rust_client::error`.
+
+use std::io;
+
+use miden_client::ClientError;
+use rust_client::{
+    airdrop::load_recipients,
+    code_report::CodeLimitExceeded,
+    error::{ConfigError, TutorialError},
+    masm::MasmFile,
+    wait::WaitError,
+};
+
+#[test]
+fn masm_errors_are_compile_failures() {
+    let err = MasmFile::embedded("broken.masm", "begin push.1 nope end")
+        .library("tests::broken")
+        .unwrap_err();
+    let boxed: Box<dyn std::error::Error> = err.into();
+    assert_eq!(
+        TutorialError::classify(boxed.as_ref()),
+        TutorialError::Compile
+    );
+    assert_eq!(TutorialError::Compile.exit_code(), 4);
+}
+
+#[test]
+fn oversized_code_is_a_compile_failure() {
+    let err = CodeLimitExceeded {
+        name: "tests::huge".to_string(),
+        what: "code bytes",
+        used: 40_000,
+        limit: 32_768,
+    };
+    assert_eq!(TutorialError::classify(&err), TutorialError::Compile);
+}
+
+#[test]
+fn io_errors_are_classified_by_kind() {
+    let missing = io::Error::new(io::ErrorKind::NotFound, "key.json");
+    assert_eq!(TutorialError::classify(&missing), TutorialError::Config);
+
+    let refused = io::Error::new(io::ErrorKind::ConnectionRefused, "rpc");
+    assert_eq!(
+        TutorialError::classify(&refused),
+        TutorialError::RpcUnreachable
+    );
+
+    let timed_out = io::Error::new(io::ErrorKind::TimedOut, "rpc");
+    assert_eq!(TutorialError::classify(&timed_out), TutorialError::Timeout);
+}
+
+#[test]
+fn bad_input_rows_are_config_errors() {
+    let path = std::env::temp_dir().join(format!("exit-codes-{}.csv", std::process::id()));
+    std::fs::write(&path, "address,amount\nnot-an-address,abc\n").unwrap();
+    let err = load_recipients(&path, 1).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(TutorialError::classify(err.as_ref()), TutorialError::Config);
+}
+
+#[test]
+fn marked_input_errors_are_config_errors() {
+    let err = ConfigError::new("alias `counter` is an account ID");
+    assert_eq!(TutorialError::classify(&err), TutorialError::Config);
+}
+
+#[test]
+fn client_errors_are_classified_by_variant() {
+    let err = ClientError::ClientInitializationError("bad store path".to_string());
+    assert_eq!(TutorialError::classify(&err), TutorialError::Config);
+}
+
+#[test]
+fn wait_errors_wrapping_the_client_use_its_class() {
+    let err = WaitError::from(ClientError::ClientInitializationError(
+        "bad store path".to_string(),
+    ));
+    assert_eq!(TutorialError::classify(&err), TutorialError::Config);
+}
+
+#[test]
+fn unknown_errors_fall_back_to_message() {
+    let err: Box<dyn std::error::Error> = "status: DeadlineExceeded".into();
+    assert_eq!(
+        TutorialError::classify(err.as_ref()),
+        TutorialError::Timeout
+    );
+
+    let err: Box<dyn std::error::Error> = "something else".into();
+    assert_eq!(TutorialError::classify(err.as_ref()), TutorialError::Other);
+    assert_eq!(TutorialError::Other.exit_code(), 1);
+}
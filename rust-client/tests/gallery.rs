@@ -0,0 +1,60 @@
+//! Keeps the MASM gallery in step with `masm/`, see `rust_client::gallery`.
+
+use std::{collections::BTreeSet, fs, path::Path};
+
+use rust_client::gallery::{self, Details, Kind, ENTRIES, MASM_DIR};
+
+fn masm_files(dir: &Path, prefix: &str, files: &mut BTreeSet<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        if path.is_dir() {
+            masm_files(&path, &format!("{prefix}{name}/"), files);
+        } else if name.ends_with(".masm") {
+            files.insert(format!("{prefix}{name}"));
+        }
+    }
+}
+
+#[test]
+fn every_masm_file_has_an_entry() {
+    let mut files = BTreeSet::new();
+    masm_files(Path::new(MASM_DIR), "", &mut files);
+    let listed: BTreeSet<String> = ENTRIES.iter().map(|e| e.path.to_string()).collect();
+
+    assert_eq!(listed.len(), ENTRIES.len(), "an entry is listed twice");
+    assert_eq!(files, listed, "add or remove entries in src/gallery.rs");
+}
+
+#[test]
+fn linked_tutorials_exist() {
+    for entry in ENTRIES {
+        for tutorial in entry.tutorials {
+            assert!(
+                Path::new("src/bin").join(format!("{tutorial}.rs")).exists(),
+                "{} links the missing tutorial {tutorial}",
+                entry.path
+            );
+        }
+    }
+}
+
+#[test]
+fn counter_details_come_from_the_source() {
+    let counter = gallery::find("counter").unwrap();
+    assert_eq!(counter.kind(), Kind::Account);
+
+    let details = counter.details(MASM_DIR).unwrap();
+    assert_eq!(details.exports, ["get_count", "increment_count"]);
+    assert_eq!(details.slots, ["miden::tutorials::counter"]);
+    assert!(details.uses("native_account"));
+    assert!(!details.uses("account"), "only whole path segments match");
+}
+
+#[test]
+fn imports_drop_their_alias() {
+    let details = Details::parse("use miden::standards::wallets::basic->wallet\nbegin\nend\n");
+    assert_eq!(details.imports, ["miden::standards::wallets::basic"]);
+    assert!(details.uses("basic"));
+    assert!(details.exports.is_empty());
+}
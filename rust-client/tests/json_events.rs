@@ -0,0 +1,176 @@
+//! Checks the shape of the JSON lines printed under `--json` and sent to `--report` sinks.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use miden_client::{Felt, Word};
+use rust_client::{
+    client::network_id,
+    contracts::Counter,
+    output::{Event, Reporter, Sink},
+};
+use serde_json::{json, Value};
+
+fn parse(event: &Event) -> Value {
+    serde_json::from_str(&event.to_json()).unwrap()
+}
+
+#[test]
+fn events_are_tagged_by_name() {
+    let account = Counter::deployer().unwrap().build([7; 32]).unwrap();
+
+    assert_eq!(
+        parse(&Event::account_created(&account)),
+        json!({
+            "event": "account_created",
+            "account_id": account.id().to_bech32(network_id()),
+            "account_type": "regular_immutable",
+        })
+    );
+}
+
+#[test]
+fn storage_values_carry_a_key_only_for_map_entries() {
+    let account = Counter::deployer().unwrap().build([7; 32]).unwrap();
+    let value = Word::from([Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(3)]);
+
+    let slot_value = parse(&Event::storage_value(
+        account.id(),
+        Counter::COUNT_SLOT,
+        value,
+    ));
+    assert_eq!(slot_value["event"], "storage_value");
+    assert_eq!(slot_value["slot"], Counter::COUNT_SLOT);
+    assert_eq!(slot_value["value"], value.to_hex());
+    assert!(slot_value.get("key").is_none());
+
+    let entry = parse(&Event::map_entry(
+        account.id(),
+        Counter::COUNT_SLOT,
+        Word::default(),
+        value,
+    ));
+    assert_eq!(entry["key"], Word::default().to_hex());
+}
+
+#[test]
+fn step_lines_become_step_events() {
+    assert_eq!(
+        parse(&Event::step("\n[STEP 2] Minting 60 tokens").unwrap()),
+        json!({"event": "step", "step": 2, "title": "Minting 60 tokens"})
+    );
+    assert_eq!(Event::step("[DRY RUN] Next transaction"), None);
+    assert_eq!(Event::step("Step 2 without brackets"), None);
+}
+
+#[test]
+fn sinks_are_told_apart_by_their_form() {
+    assert_eq!("-".parse::<Sink>(), Ok(Sink::Stdout));
+    assert_eq!(
+        "https://example.com/hook".parse::<Sink>(),
+        Ok(Sink::Webhook("https://example.com/hook".to_string()))
+    );
+    assert_eq!(
+        "reports/run.jsonl".parse::<Sink>(),
+        Ok(Sink::File(PathBuf::from("reports/run.jsonl")))
+    );
+    assert!("".parse::<Sink>().is_err());
+}
+
+#[test]
+fn file_sinks_append_one_line_per_event() {
+    let path = std::env::temp_dir().join(format!("report-sink-{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let reporter = Reporter::open([Sink::File(path.clone())]).unwrap();
+    let first = Event::step("[STEP 1] Creating accounts").unwrap();
+    let second = Event::step("[STEP 2] Sending notes").unwrap();
+    reporter.send(&first);
+    reporter.send(&second);
+
+    let contents = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines, [first.to_json(), second.to_json()]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_stalled_webhook_does_not_hold_up_the_run() {
+    // Accepts connections without ever answering, so every POST waits out its timeout
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+    let reporter = Reporter::open([Sink::Webhook(url)]).unwrap();
+    let event = Event::step("[STEP 1] Creating accounts").unwrap();
+    let start = Instant::now();
+    for _ in 0..200 {
+        reporter.send(&event);
+    }
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+/// Answers every POST with 200 and closes the connection, returning the bodies.
+fn serve_webhook(listener: TcpListener, requests: usize) -> thread::JoinHandle<Vec<String>> {
+    thread::spawn(move || {
+        let mut bodies = Vec::new();
+        for stream in listener.incoming().take(requests) {
+            let mut stream = BufReader::new(stream.unwrap());
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                stream.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            stream.read_exact(&mut body).unwrap();
+            bodies.push(String::from_utf8(body).unwrap());
+            stream
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .unwrap();
+        }
+        bodies
+    })
+}
+
+#[test]
+fn shutdown_posts_the_queued_events() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let server = serve_webhook(listener, 3);
+
+    let reporter = Reporter::open([Sink::Webhook(url)]).unwrap();
+    let events: Vec<Event> = (1..=3)
+        .map(|step| Event::step(&format!("[STEP {step}] Working")).unwrap())
+        .collect();
+    for event in &events {
+        reporter.send(event);
+    }
+    assert!(reporter.shutdown(Duration::from_secs(10)));
+
+    let expected: Vec<String> = events.iter().map(Event::to_json).collect();
+    assert_eq!(server.join().unwrap(), expected);
+}
+
+#[test]
+fn shutdown_gives_up_on_a_stalled_webhook() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+    let reporter = Reporter::open([Sink::Webhook(url)]).unwrap();
+    reporter.send(&Event::step("[STEP 1] Creating accounts").unwrap());
+    let start = Instant::now();
+    assert!(!reporter.shutdown(Duration::from_millis(200)));
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,44 @@
+//! Checks the pool arithmetic that `rust_client::pool` mirrors from the MASM.
+
+use rust_client::pool::{quote, PoolState};
+
+fn pool(reserve_a: u64, reserve_b: u64, total_shares: u64) -> PoolState {
+    PoolState {
+        reserve_a,
+        reserve_b,
+        total_shares,
+    }
+}
+
+#[test]
+fn swaps_never_decrease_the_product() {
+    let before = pool(10_000, 20_000, 10_000);
+    for amount_in in [1, 7, 999, 1_000, 12_345] {
+        let amount_out = quote(amount_in, before.reserve_a, before.reserve_b);
+        let after = pool(
+            before.reserve_a + amount_in,
+            before.reserve_b - amount_out,
+            before.total_shares,
+        );
+        assert!(after.k() >= before.k(), "swapping {amount_in} decreased k");
+    }
+    assert_eq!(quote(1_000, 10_000, 20_000), 1_818);
+}
+
+#[test]
+fn shares_follow_the_smaller_contribution() {
+    assert_eq!(PoolState::default().shares_for(10_000, 20_000), 10_000);
+
+    let state = pool(10_000, 20_000, 10_000);
+    assert_eq!(state.shares_for(1_000, 2_000), 1_000);
+    // Extra B beyond the pool's ratio earns nothing
+    assert_eq!(state.shares_for(1_000, 5_000), 1_000);
+    assert_eq!(state.shares_for(1_000, 1_000), 500);
+}
+
+#[test]
+fn withdrawals_round_down_in_favor_of_the_pool() {
+    let state = pool(11_000, 18_182, 10_000);
+    assert_eq!(state.withdrawal(10_000), (11_000, 18_182));
+    assert_eq!(state.withdrawal(3), (3, 5));
+}
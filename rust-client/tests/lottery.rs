@@ -0,0 +1,59 @@
+//! Checks the commitments and the draw that `rust_client::lottery` mirrors from the MASM.
+
+use miden_client::{Felt, Word};
+use miden_protocol::Hasher;
+use rust_client::lottery::{LotteryState, Secret};
+
+const SECRET: Secret = Secret {
+    number: 7,
+    salt: [Felt::new(1), Felt::new(2), Felt::new(3)],
+};
+
+#[test]
+fn commitments_hash_the_secret_word() {
+    let word = SECRET.word();
+    assert_eq!(word[3], Felt::new(7), "the number is the top of the stack");
+    assert_eq!(
+        SECRET.commitment(),
+        Hasher::hash_elements(&[Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(7)])
+    );
+
+    let resalted = Secret {
+        salt: [Felt::new(1), Felt::new(2), Felt::new(4)],
+        ..SECRET
+    };
+    assert_ne!(SECRET.commitment(), resalted.commitment());
+    let renumbered = Secret {
+        number: 8,
+        ..SECRET
+    };
+    assert_ne!(SECRET.commitment(), renumbered.commitment());
+    assert_ne!(SECRET.commitment(), Word::default());
+}
+
+#[test]
+fn seed_is_the_wrapping_sum_of_the_numbers() {
+    assert_eq!(LotteryState::seed_of([]), 0);
+    assert_eq!(LotteryState::seed_of([3, 4, 5]), 12);
+    assert_eq!(LotteryState::seed_of([u32::MAX, 2]), 1, "the sum wraps");
+    assert_eq!(
+        LotteryState::seed_of([5, 3, 4]),
+        LotteryState::seed_of([3, 4, 5]),
+        "the reveal order does not matter"
+    );
+}
+
+#[test]
+fn winner_is_the_seed_modulo_the_revealers() {
+    let state = |revealed, seed| LotteryState {
+        committed: 3,
+        revealed,
+        seed,
+        paid: false,
+    };
+    assert_eq!(state(0, 12).winner_index(), None, "nobody revealed");
+    assert_eq!(state(3, 12).winner_index(), Some(0));
+    assert_eq!(state(3, 13).winner_index(), Some(1));
+    assert_eq!(state(2, 13).winner_index(), Some(1));
+    assert_eq!(state(1, u32::MAX).winner_index(), Some(0));
+}
@@ -0,0 +1,35 @@
+//! Creates and removes throwaway store directories with `rust_client::memory_store`.
+
+use rust_client::{
+    client::ClientSetup,
+    error::ExitGuard,
+    memory_store::{create_scratch_dir, remove_scratch_dirs},
+};
+
+// One test, since the directories to remove are tracked process-wide
+#[test]
+fn scratch_dirs_hold_the_store_until_exit() {
+    let setup = ClientSetup::default().with_memory_store().unwrap();
+    let dir = setup.store_path.parent().unwrap().to_path_buf();
+    assert!(dir.is_dir());
+    assert!(dir.starts_with(std::env::temp_dir()));
+    assert_eq!(setup.keystore_path, dir.join("keystore"));
+    assert!(setup.store_passphrase.is_none());
+
+    let other = create_scratch_dir().unwrap();
+    assert_ne!(dir, other);
+
+    remove_scratch_dirs();
+    assert!(!dir.exists());
+    assert!(!other.exists());
+
+    // A run that panics never reaches `report`, its exit guard cleans up instead
+    let (sender, created) = std::sync::mpsc::channel();
+    let run = std::thread::spawn(move || {
+        let _guard = ExitGuard::new();
+        sender.send(create_scratch_dir().unwrap()).unwrap();
+        panic!("the tutorial failed an assertion");
+    });
+    assert!(run.join().is_err());
+    assert!(!created.recv().unwrap().exists());
+}
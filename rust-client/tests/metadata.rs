@@ -0,0 +1,58 @@
+//! Round-trips `rust_client::metadata::ContractMetadata` through account storage.
+
+use miden_client::{Felt, Word};
+use rust_client::{contracts::Counter, metadata::ContractMetadata};
+
+fn metadata(name_len: usize, url_len: usize) -> ContractMetadata {
+    ContractMetadata {
+        name: "n".repeat(name_len),
+        icon_hash: Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]),
+        url: "u".repeat(url_len),
+    }
+}
+
+fn round_trip(metadata: &ContractMetadata) -> ContractMetadata {
+    let account = Counter::deployer()
+        .unwrap()
+        .with_component(metadata.component().unwrap())
+        .build([7; 32])
+        .unwrap();
+    ContractMetadata::from_account(&account).unwrap()
+}
+
+#[test]
+fn metadata_survives_storage_at_boundary_lengths() {
+    // Empty, one felt, one felt and a byte, one word short, one word, one word and a byte
+    for (name_len, url_len) in [(0, 0), (1, 1), (7, 7), (8, 8), (27, 27), (28, 28), (28, 29)] {
+        let metadata = metadata(name_len, url_len);
+        assert_eq!(
+            round_trip(&metadata),
+            metadata,
+            "name {name_len}, url {url_len}"
+        );
+    }
+}
+
+#[test]
+fn long_urls_span_several_words() {
+    for url_len in [56, 57, 200] {
+        let metadata = metadata(4, url_len);
+        assert_eq!(round_trip(&metadata), metadata, "url {url_len}");
+    }
+}
+
+#[test]
+fn names_longer_than_a_word_are_refused() {
+    assert!(metadata(29, 0).component().is_err());
+}
+
+#[test]
+fn multibyte_text_is_kept_intact() {
+    let metadata = ContractMetadata {
+        // 7 bytes per felt splits the characters across felts
+        name: "Zähler 🧮".to_string(),
+        icon_hash: Word::default(),
+        url: "https://example.com/zähler/🧮".to_string(),
+    };
+    assert_eq!(round_trip(&metadata), metadata);
+}
@@ -0,0 +1,27 @@
+//! Checks which names `rust_client::name_registry` accepts and the keys it derives.
+
+use rust_client::name_registry::{name_hash, MAX_NAME_LEN};
+
+#[test]
+fn names_are_lowercase_letters_digits_and_dashes() {
+    for name in ["alice", "bob-2", "0", &"a".repeat(MAX_NAME_LEN)] {
+        assert!(name_hash(name).is_ok(), "'{name}' should be valid");
+    }
+    for name in [
+        "",
+        "Alice",
+        "al ice",
+        "alice.miden",
+        "ålice",
+        &"a".repeat(MAX_NAME_LEN + 1),
+    ] {
+        assert!(name_hash(name).is_err(), "'{name}' should be invalid");
+    }
+}
+
+#[test]
+fn each_name_has_its_own_key() {
+    assert_eq!(name_hash("alice").unwrap(), name_hash("alice").unwrap());
+    assert_ne!(name_hash("alice").unwrap(), name_hash("alice-").unwrap());
+    assert_ne!(name_hash("alice").unwrap(), name_hash("bob").unwrap());
+}
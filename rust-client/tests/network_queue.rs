@@ -0,0 +1,43 @@
+//! Orders pending network notes, see `rust_client::network_queue`.
+
+use miden_client::block::BlockNumber;
+use rust_client::network_queue::{queue_positions, NoteStatus};
+
+fn pending(block: u32) -> NoteStatus {
+    NoteStatus::Pending {
+        committed_at: BlockNumber::from(block),
+    }
+}
+
+#[test]
+fn pending_notes_queue_by_commit_block() {
+    let consumed = NoteStatus::Consumed {
+        committed_at: BlockNumber::from(3),
+        consumed_at: BlockNumber::from(5),
+    };
+    let statuses = [
+        pending(12),
+        NoteStatus::Submitted,
+        pending(10),
+        consumed,
+        pending(10),
+    ];
+
+    assert_eq!(
+        queue_positions(&statuses),
+        [Some(3), None, Some(1), None, Some(2)]
+    );
+}
+
+#[test]
+fn age_counts_blocks_waited() {
+    let tip = BlockNumber::from(20);
+    assert_eq!(NoteStatus::Submitted.age(tip), None);
+    assert_eq!(pending(14).age(tip), Some(6));
+
+    let consumed = NoteStatus::Consumed {
+        committed_at: BlockNumber::from(14),
+        consumed_at: BlockNumber::from(17),
+    };
+    assert_eq!(consumed.age(tip), Some(3));
+}
@@ -0,0 +1,382 @@
+//! Fuzz-style harness for the tutorial note scripts.
+//!
+//! Every case builds a note with randomized inputs or arguments, executes its
+//! consumption against a local mock chain and checks that it succeeds exactly
+//! when the inputs are valid. Copy a `*_cases` function to cover a new script.
+//!
+//! The run is reproducible: set `FUZZ_SEED` to replay a failure and `FUZZ_CASES`
+//! to change the number of cases per script.
+
+use std::collections::BTreeMap;
+
+use miden_client::{
+    account::{Account, AccountId},
+    assembly::CodeBuilder,
+    asset::FungibleAsset,
+    note::{
+        create_p2id_note, Note, NoteAssets, NoteAttachment, NoteInputs, NoteMetadata,
+        NoteRecipient, NoteScript, NoteTag, NoteType,
+    },
+    transaction::OutputNote,
+    Felt, Word,
+};
+use miden_protocol::Hasher;
+use miden_testing::{Auth, MockChain, MockChainBuilder};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rust_client::masm::MasmFile;
+
+const BURN_NOTE: &str = include_str!("../../masm/notes/burn_note.masm");
+const HASH_PREIMAGE_NOTE: &str = include_str!("../../masm/notes/hash_preimage_note.masm");
+const HTLC_NOTE: &str = include_str!("../../masm/notes/htlc_note.masm");
+
+/// One note consumption and whether it should succeed.
+struct Case {
+    description: String,
+    consumer: AccountId,
+    note: Note,
+    note_args: Option<Word>,
+    should_succeed: bool,
+}
+
+fn rng() -> ChaCha20Rng {
+    let seed = std::env::var("FUZZ_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(rand::random);
+    println!("FUZZ_SEED={seed}");
+    ChaCha20Rng::seed_from_u64(seed)
+}
+
+fn case_count() -> usize {
+    std::env::var("FUZZ_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(16)
+}
+
+fn random_word(rng: &mut impl Rng) -> Word {
+    Word::from([
+        Felt::new(rng.random_range(0..Felt::MODULUS)),
+        Felt::new(rng.random_range(0..Felt::MODULUS)),
+        Felt::new(rng.random_range(0..Felt::MODULUS)),
+        Felt::new(rng.random_range(0..Felt::MODULUS)),
+    ])
+}
+
+fn custom_note(
+    sender: AccountId,
+    script: NoteScript,
+    inputs: Vec<Felt>,
+    assets: NoteAssets,
+    serial_num: Word,
+) -> Note {
+    let recipient = NoteRecipient::new(serial_num, script, NoteInputs::new(inputs).unwrap());
+    let metadata = NoteMetadata::new(sender, NoteType::Public, NoteTag::new(0));
+    Note::new(assets, metadata, recipient)
+}
+
+/// Consumes hash-preimage notes with the right secret, a random secret or a
+/// secret with a single element changed.
+fn hash_preimage_cases(rng: &mut ChaCha20Rng, faucet: &Account, consumer: &Account) -> Vec<Case> {
+    let note_file = MasmFile::embedded("masm/notes/hash_preimage_note.masm", HASH_PREIMAGE_NOTE);
+    let script = note_file
+        .check(CodeBuilder::new().compile_note_script(&note_file.source))
+        .unwrap();
+    let asset = FungibleAsset::new(faucet.id(), 10).unwrap();
+
+    (0..case_count())
+        .map(|i| {
+            let secret = random_word(rng);
+            let digest = Hasher::hash_elements(secret.as_elements());
+            let note = custom_note(
+                faucet.id(),
+                script.clone(),
+                digest.to_vec(),
+                NoteAssets::new(vec![asset.into()]).unwrap(),
+                random_word(rng),
+            );
+
+            let (args, kind, should_succeed) = match rng.random_range(0..3) {
+                0 => (secret, "correct secret", true),
+                1 => (random_word(rng), "random secret", false),
+                _ => {
+                    let mut tampered: [Felt; 4] = secret.into();
+                    let idx = rng.random_range(0..4);
+                    tampered[idx] += Felt::new(rng.random_range(1..u32::MAX as u64));
+                    (Word::from(tampered), "tampered secret", false)
+                }
+            };
+            Case {
+                description: format!("hash preimage #{i}: {kind}"),
+                consumer: consumer.id(),
+                note,
+                note_args: Some(args),
+                should_succeed,
+            }
+        })
+        .collect()
+}
+
+/// Consumes HTLC notes as the recipient or the sender, before or after the
+/// deadline, with the right secret or a random one.
+fn htlc_cases(
+    rng: &mut ChaCha20Rng,
+    faucet: &Account,
+    sender: &Account,
+    recipient: &Account,
+) -> Vec<Case> {
+    let note_file = MasmFile::embedded("masm/notes/htlc_note.masm", HTLC_NOTE);
+    let script = note_file
+        .check(CodeBuilder::new().compile_note_script(&note_file.source))
+        .unwrap();
+    let asset = FungibleAsset::new(faucet.id(), 10).unwrap();
+
+    (0..case_count())
+        .map(|i| {
+            let secret = random_word(rng);
+            // The mock chain is at its first blocks, so a deadline of 0 has passed
+            let (deadline, expired) = if rng.random_bool(0.5) {
+                (u32::MAX as u64, false)
+            } else {
+                (0, true)
+            };
+            let mut inputs = Hasher::hash_elements(secret.as_elements()).to_vec();
+            inputs.extend([
+                recipient.id().prefix().as_felt(),
+                recipient.id().suffix(),
+                Felt::new(deadline),
+            ]);
+            let note = custom_note(
+                sender.id(),
+                script.clone(),
+                inputs,
+                NoteAssets::new(vec![asset.into()]).unwrap(),
+                random_word(rng),
+            );
+
+            let (consumer, args, kind, should_succeed) = match rng.random_range(0..3) {
+                0 => (recipient, secret, "recipient with the secret", !expired),
+                1 => (
+                    recipient,
+                    random_word(rng),
+                    "recipient with a random secret",
+                    false,
+                ),
+                _ => (sender, random_word(rng), "sender", expired),
+            };
+            let phase = if expired { "after" } else { "before" };
+            Case {
+                description: format!("htlc #{i}: {kind} {phase} the deadline"),
+                consumer: consumer.id(),
+                note,
+                note_args: Some(args),
+                should_succeed,
+            }
+        })
+        .collect()
+}
+
+/// Tokens the faucets of the burn cases have issued.
+const ISSUED: u64 = 500;
+
+/// Consumes burn notes as the issuing faucet, another faucet or a wallet, for
+/// amounts up to twice what the faucet has issued.
+fn burn_cases(
+    rng: &mut ChaCha20Rng,
+    faucet: &Account,
+    other_faucet: &Account,
+    wallet: &Account,
+) -> Vec<Case> {
+    let note_file = MasmFile::embedded("masm/notes/burn_note.masm", BURN_NOTE);
+    let script = note_file
+        .check(CodeBuilder::new().compile_note_script(&note_file.source))
+        .unwrap();
+
+    (0..case_count())
+        .map(|i| {
+            let amount = rng.random_range(1..=2 * ISSUED);
+            let note = custom_note(
+                wallet.id(),
+                script.clone(),
+                vec![],
+                NoteAssets::new(vec![FungibleAsset::new(faucet.id(), amount)
+                    .unwrap()
+                    .into()])
+                .unwrap(),
+                random_word(rng),
+            );
+
+            let (consumer, kind, should_succeed) = match rng.random_range(0..3) {
+                0 => (faucet, "issuing faucet", amount <= ISSUED),
+                1 => (other_faucet, "another faucet", false),
+                _ => (wallet, "wallet", false),
+            };
+            Case {
+                description: format!("burn #{i}: {kind} burns {amount} of {ISSUED} issued"),
+                consumer: consumer.id(),
+                note,
+                note_args: None,
+                should_succeed,
+            }
+        })
+        .collect()
+}
+
+/// Consumes P2ID notes whose target is the consumer, another account or random
+/// felts that do not form the consumer's ID.
+fn p2id_cases(
+    rng: &mut ChaCha20Rng,
+    faucet: &Account,
+    consumer: &Account,
+    other: &Account,
+) -> Vec<Case> {
+    let asset = FungibleAsset::new(faucet.id(), 10).unwrap();
+    let mut note_rng = miden_client::crypto::RpoRandomCoin::new(random_word(rng));
+    let script = create_p2id_note(
+        faucet.id(),
+        consumer.id(),
+        vec![],
+        NoteType::Public,
+        NoteAttachment::default(),
+        &mut note_rng,
+    )
+    .unwrap()
+    .script()
+    .clone();
+
+    (0..case_count())
+        .map(|i| {
+            let (inputs, kind, should_succeed) = match rng.random_range(0..3) {
+                0 => (
+                    vec![consumer.id().suffix(), consumer.id().prefix().as_felt()],
+                    "consumer is the target",
+                    true,
+                ),
+                1 => (
+                    vec![other.id().suffix(), other.id().prefix().as_felt()],
+                    "another account is the target",
+                    false,
+                ),
+                _ => (
+                    vec![
+                        Felt::new(rng.random_range(0..Felt::MODULUS)),
+                        Felt::new(rng.random_range(0..Felt::MODULUS)),
+                    ],
+                    "random target",
+                    false,
+                ),
+            };
+            let note = custom_note(
+                faucet.id(),
+                script.clone(),
+                inputs,
+                NoteAssets::new(vec![asset.into()]).unwrap(),
+                random_word(rng),
+            );
+            Case {
+                description: format!("p2id #{i}: {kind}"),
+                consumer: consumer.id(),
+                note,
+                note_args: None,
+                should_succeed,
+            }
+        })
+        .collect()
+}
+
+/// Executes every case against a mock chain holding all of their notes.
+async fn run(builder: MockChainBuilder, cases: Vec<Case>) {
+    let mut builder = builder;
+    for case in &cases {
+        builder.add_output_note(OutputNote::Full(case.note.clone()));
+    }
+    let chain: MockChain = builder.build().unwrap();
+
+    let mut failures = Vec::new();
+    for case in cases {
+        let note_args = case
+            .note_args
+            .map(|args| BTreeMap::from([(case.note.id(), args)]))
+            .unwrap_or_default();
+        let result = chain
+            .build_tx_context(case.consumer, &[case.note.id()], &[])
+            .unwrap()
+            .extend_note_args(note_args)
+            .build()
+            .unwrap()
+            .execute()
+            .await;
+
+        if result.is_ok() != case.should_succeed {
+            failures.push(format!(
+                "{}: expected {}, got {:?}",
+                case.description,
+                if case.should_succeed {
+                    "success"
+                } else {
+                    "failure"
+                },
+                result.err()
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[tokio::test]
+async fn hash_preimage_note_only_accepts_the_preimage() {
+    let mut rng = rng();
+    let mut builder = MockChain::builder();
+    let faucet = builder
+        .add_existing_basic_faucet(Auth::BasicAuth, "MID", 1_000_000, None)
+        .unwrap();
+    let consumer = builder.add_existing_wallet(Auth::BasicAuth).unwrap();
+
+    let cases = hash_preimage_cases(&mut rng, &faucet, &consumer);
+    run(builder, cases).await;
+}
+
+#[tokio::test]
+async fn htlc_note_pays_the_recipient_before_the_deadline_and_the_sender_after() {
+    let mut rng = rng();
+    let mut builder = MockChain::builder();
+    let faucet = builder
+        .add_existing_basic_faucet(Auth::BasicAuth, "MID", 1_000_000, None)
+        .unwrap();
+    let sender = builder.add_existing_wallet(Auth::BasicAuth).unwrap();
+    let recipient = builder.add_existing_wallet(Auth::BasicAuth).unwrap();
+
+    let cases = htlc_cases(&mut rng, &faucet, &sender, &recipient);
+    run(builder, cases).await;
+}
+
+#[tokio::test]
+async fn burn_note_only_burns_in_the_issuing_faucet() {
+    let mut rng = rng();
+    let mut builder = MockChain::builder();
+    let faucet = builder
+        .add_existing_basic_faucet(Auth::BasicAuth, "MID", 1_000_000, Some(ISSUED))
+        .unwrap();
+    let other_faucet = builder
+        .add_existing_basic_faucet(Auth::BasicAuth, "OTH", 1_000_000, Some(ISSUED))
+        .unwrap();
+    let wallet = builder.add_existing_wallet(Auth::BasicAuth).unwrap();
+
+    let cases = burn_cases(&mut rng, &faucet, &other_faucet, &wallet);
+    run(builder, cases).await;
+}
+
+#[tokio::test]
+async fn p2id_note_only_accepts_its_target() {
+    let mut rng = rng();
+    let mut builder = MockChain::builder();
+    let faucet = builder
+        .add_existing_basic_faucet(Auth::BasicAuth, "MID", 1_000_000, None)
+        .unwrap();
+    let consumer = builder.add_existing_wallet(Auth::BasicAuth).unwrap();
+    let other = builder.add_existing_wallet(Auth::BasicAuth).unwrap();
+
+    let cases = p2id_cases(&mut rng, &faucet, &consumer, &other);
+    run(builder, cases).await;
+}
@@ -0,0 +1,123 @@
+//! Reads orders back from their swap notes and matches against a `rust_client::order_book::OrderBook`.
+
+mod common;
+
+use miden_client::{
+    account::AccountId,
+    asset::FungibleAsset,
+    crypto::RpoRandomCoin,
+    note::{create_p2id_note, NoteAttachment, NoteType},
+    Word,
+};
+use miden_testing::{Auth, MockChain};
+use rust_client::order_book::{new_order, Order, OrderBook};
+
+use common::{account_id, scratch_path};
+
+/// Tokens A and B, the market sells A for B.
+struct Market {
+    token_a: AccountId,
+    token_b: AccountId,
+    rng: RpoRandomCoin,
+}
+
+impl Market {
+    fn new() -> Self {
+        let mut builder = MockChain::builder();
+        let mut faucet = |symbol| {
+            builder
+                .add_existing_basic_faucet(Auth::BasicAuth, symbol, 1_000_000, None)
+                .unwrap()
+                .id()
+        };
+        Self {
+            token_a: faucet("AAA"),
+            token_b: faucet("BBB"),
+            rng: RpoRandomCoin::new(Word::default()),
+        }
+    }
+
+    fn a(&self, amount: u64) -> FungibleAsset {
+        FungibleAsset::new(self.token_a, amount).unwrap()
+    }
+
+    fn b(&self, amount: u64) -> FungibleAsset {
+        FungibleAsset::new(self.token_b, amount).unwrap()
+    }
+
+    fn order(&mut self, maker_seed: u8, offered: u64, requested: u64) -> Order {
+        let (offered, requested) = (self.a(offered), self.b(requested));
+        new_order(account_id(maker_seed), offered, requested, &mut self.rng).unwrap()
+    }
+}
+
+#[test]
+fn orders_read_back_from_their_notes() {
+    let mut market = Market::new();
+    let posted = market.order(1, 40, 60);
+
+    let order = Order::from_note(posted.note.clone()).expect("a swap note is an order");
+    assert_eq!(order.maker(), account_id(1));
+    assert_eq!(order.offered, posted.offered);
+    assert_eq!(order.requested, posted.requested);
+    // The taker rebuilds the very payback the maker committed to in the inputs
+    let inputs = order.note.recipient().inputs().values();
+    assert_eq!(order.payback().recipient().digest().to_vec(), inputs[..4]);
+    assert_eq!(order.payback().assets().num_assets(), 1);
+
+    let p2id = create_p2id_note(
+        account_id(1),
+        account_id(2),
+        vec![posted.offered.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        &mut market.rng,
+    )
+    .unwrap();
+    assert!(Order::from_note(p2id).is_none(), "a P2ID note is no order");
+}
+
+#[test]
+fn best_match_takes_the_best_price_within_budget() {
+    let mut market = Market::new();
+    let mut book = OrderBook::load(scratch_path("match.json")).unwrap();
+    let expensive = market.order(1, 40, 60);
+    let cheap = market.order(2, 40, 50);
+    let small = market.order(2, 20, 40);
+    for order in [&expensive, &cheap, &small] {
+        assert!(book.insert(order.clone()));
+    }
+    assert!(!book.insert(cheap.clone()), "an order is only listed once");
+
+    let best = |budget| {
+        book.best_match(market.token_a, market.b(budget))
+            .map(Order::id)
+    };
+    assert_eq!(best(100), Some(cheap.id()));
+    assert_eq!(best(50), Some(cheap.id()));
+    assert_eq!(best(45), Some(small.id()), "only the small order fits");
+    assert_eq!(best(30), None);
+    assert!(
+        book.best_match(market.token_b, market.a(100)).is_none(),
+        "nobody sells B for A"
+    );
+}
+
+#[test]
+fn saved_books_load_back() {
+    let path = scratch_path("reload.json");
+    let mut market = Market::new();
+    let mut book = OrderBook::load(&path).unwrap();
+    assert!(book.orders().is_empty());
+    let first = market.order(1, 40, 60);
+    let second = market.order(2, 40, 50);
+    book.insert(first.clone());
+    book.insert(second.clone());
+    book.remove(first.id());
+    book.save().unwrap();
+
+    let reloaded = OrderBook::load(&path).unwrap();
+    let ids: Vec<_> = reloaded.orders().iter().map(Order::id).collect();
+    assert_eq!(ids, [second.id()]);
+    assert_eq!(reloaded.orders()[0].requested, second.requested);
+}
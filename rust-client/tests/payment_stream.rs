@@ -0,0 +1,40 @@
+//! Checks the stream arithmetic that `rust_client::payment_stream` mirrors from the MASM.
+
+use rust_client::payment_stream::StreamTerms;
+
+const TERMS: StreamTerms = StreamTerms {
+    start: 100,
+    rate: 3,
+    total: 100,
+};
+
+#[test]
+fn stream_ends_once_the_total_has_streamed() {
+    assert_eq!(
+        TERMS.end(),
+        134,
+        "33 blocks stream 99 tokens, the 34th the rest"
+    );
+    let exact = StreamTerms { rate: 4, ..TERMS };
+    assert_eq!(exact.end(), 125);
+}
+
+#[test]
+fn tokens_stream_at_the_rate_from_the_start() {
+    assert_eq!(TERMS.streamed_at(0), 0);
+    assert_eq!(TERMS.streamed_at(100), 0);
+    assert_eq!(TERMS.streamed_at(101), 3);
+    assert_eq!(TERMS.streamed_at(110), 30);
+    assert_eq!(TERMS.streamed_at(133), 99);
+    assert_eq!(TERMS.streamed_at(134), 100);
+    assert_eq!(
+        TERMS.streamed_at(u32::MAX),
+        100,
+        "streaming stops at the total"
+    );
+
+    for block in TERMS.start..TERMS.end() {
+        assert!(TERMS.streamed_at(block) < TERMS.total);
+        assert!(TERMS.streamed_at(block) <= TERMS.streamed_at(block + 1));
+    }
+}
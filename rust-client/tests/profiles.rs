@@ -0,0 +1,38 @@
+//! Lays out the profiles of `rust_client::profiles` on disk.
+
+use std::path::PathBuf;
+
+use rust_client::{client::ClientSetup, profiles::ProfileManager};
+
+#[test]
+fn profiles_live_next_to_the_configured_store() {
+    let setup = ClientSetup {
+        store_path: PathBuf::from("runs/one/store.sqlite3"),
+        ..ClientSetup::default()
+    };
+    let profiles = ProfileManager::new(setup);
+
+    assert_eq!(
+        profiles.store_path("Alice"),
+        PathBuf::from("runs/one/profiles/alice/store.sqlite3")
+    );
+    assert_eq!(
+        profiles.keystore_path("Alice"),
+        PathBuf::from("runs/one/profiles/alice/keystore")
+    );
+}
+
+#[test]
+fn each_persona_gets_its_own_directory() {
+    let profiles = ProfileManager::new(ClientSetup::default()).with_dir("/tmp/personas");
+
+    assert_eq!(
+        profiles.profile_dir("Bob"),
+        PathBuf::from("/tmp/personas/bob")
+    );
+    assert_ne!(profiles.store_path("Bob"), profiles.store_path("Carol"));
+    assert_ne!(
+        profiles.keystore_path("Bob"),
+        profiles.keystore_path("Carol")
+    );
+}
@@ -0,0 +1,44 @@
+//! Generates the web client's reference vectors, see `rust_client::vectors`.
+
+use rust_client::{
+    contracts::Counter,
+    vectors::{ReferenceVectors, ACCOUNT_SEEDS},
+};
+
+#[test]
+fn generation_is_deterministic() {
+    let first = ReferenceVectors::generate().unwrap();
+    let second = ReferenceVectors::generate().unwrap();
+
+    assert_eq!(first, second);
+    assert!(first.diff(&second).is_empty());
+}
+
+#[test]
+fn account_ids_match_the_counter_fixture() {
+    let vectors = ReferenceVectors::generate().unwrap();
+
+    assert_eq!(vectors.accounts.len(), ACCOUNT_SEEDS.len());
+    for (seed, vector) in ACCOUNT_SEEDS.iter().zip(&vectors.accounts) {
+        let account = Counter::deployer().unwrap().build(*seed).unwrap();
+        assert_eq!(vector.account_id, account.id().to_hex());
+    }
+    assert_eq!(vectors.notes[0].sender, vectors.accounts[0].account_id);
+    assert_eq!(vectors.notes[0].target, vectors.accounts[1].account_id);
+}
+
+#[test]
+fn json_round_trips_and_reports_drift() {
+    let vectors = ReferenceVectors::generate().unwrap();
+    let parsed: ReferenceVectors = serde_json::from_str(&vectors.to_json()).unwrap();
+    assert_eq!(parsed, vectors);
+
+    let mut drifted = parsed;
+    drifted.notes[0].note_id = "0x00".to_string();
+    drifted.procedures.clear();
+    let drift = vectors.diff(&drifted);
+
+    assert_eq!(drift.len(), 2, "{drift:?}");
+    assert!(drift[0].starts_with("notes[0]"));
+    assert!(drift[1].starts_with("procedures"));
+}
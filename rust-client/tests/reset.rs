@@ -0,0 +1,60 @@
+//! Finds and deletes a client's local state with `rust_client::reset`.
+
+mod common;
+
+use std::{fs, path::Path};
+
+use rust_client::{client::ClientSetup, reset::LocalState};
+
+use common::scratch_dir;
+
+fn setup_in(dir: &Path) -> ClientSetup {
+    ClientSetup {
+        store_path: dir.join("store.sqlite3"),
+        keystore_path: dir.join("keystore"),
+        ..ClientSetup::default()
+    }
+}
+
+#[test]
+fn removes_store_files_and_keystore_only() {
+    let dir = scratch_dir("remove");
+    let setup = setup_in(&dir);
+    fs::write(&setup.store_path, b"store").unwrap();
+    fs::write(dir.join("store.sqlite3-wal"), b"wal").unwrap();
+    fs::create_dir_all(&setup.keystore_path).unwrap();
+    fs::write(setup.keystore_path.join("key"), b"key").unwrap();
+    fs::write(dir.join("deployments.toml"), b"").unwrap();
+
+    let state = LocalState::of(&setup);
+    assert_eq!(
+        state.paths(),
+        [
+            setup.store_path.clone(),
+            dir.join("store.sqlite3-wal"),
+            setup.keystore_path.clone(),
+        ]
+    );
+
+    state.remove().unwrap();
+    assert!(LocalState::of(&setup).is_empty());
+    assert!(dir.join("deployments.toml").exists());
+}
+
+#[test]
+fn finds_a_sealed_store() {
+    let dir = scratch_dir("sealed");
+    let setup = setup_in(&dir);
+    fs::write(dir.join("store.sqlite3.enc"), b"sealed").unwrap();
+
+    assert_eq!(
+        LocalState::of(&setup).paths(),
+        [dir.join("store.sqlite3.enc")]
+    );
+}
+
+#[test]
+fn nothing_to_reset_in_an_empty_directory() {
+    let dir = scratch_dir("empty");
+    assert!(LocalState::of(&setup_in(&dir)).is_empty());
+}
@@ -0,0 +1,64 @@
+//! Records a run into a `rust_client::session::Session` and checks its summary.
+
+use std::time::Duration;
+
+use miden_client::{asset::FungibleAsset, transaction::TransactionId};
+use miden_testing::{Auth, MockChain};
+use rust_client::{client::network_id, contracts::Counter, session::Session};
+
+#[tokio::test]
+async fn summary_covers_the_recorded_run() {
+    let mut builder = MockChain::builder();
+    let faucet = builder
+        .add_existing_basic_faucet(Auth::BasicAuth, "MID", 1_000_000, None)
+        .unwrap();
+    let counter = builder
+        .add_existing_account_from_components(Auth::IncrNonce, [Counter::component().unwrap()])
+        .unwrap();
+    let chain = builder.build().unwrap();
+    let executed = chain
+        .build_tx_context(counter.id(), &[], &[])
+        .unwrap()
+        .tx_script(Counter::increment_script().unwrap())
+        .build()
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+    let tx_id: TransactionId = executed.id();
+
+    let mut session = Session::default();
+    assert!(session.is_empty());
+
+    session.account_created();
+    session.transaction_submitted(
+        counter.id(),
+        tx_id,
+        FungibleAsset::new(faucet.id(), 3).unwrap(),
+        2,
+    );
+    session.transaction_submitted(
+        counter.id(),
+        tx_id,
+        FungibleAsset::new(faucet.id(), 4).unwrap(),
+        0,
+    );
+    session.waited(Duration::from_secs(2));
+    session.waited(Duration::from_secs(3));
+    assert!(!session.is_empty());
+    assert_eq!(session.fees.get(&faucet.id()), Some(&7));
+    assert_eq!(session.notes_created, 2);
+    assert_eq!(session.waited, Duration::from_secs(5));
+
+    let lines = session.lines();
+    assert!(lines.contains(&"Accounts created: 1".to_string()));
+    assert!(lines.contains(&"Notes created: 2".to_string()));
+    assert!(lines.contains(&format!(
+        "Fees paid: 7 of {}",
+        faucet.id().to_bech32(network_id())
+    )));
+    assert!(lines.contains(&"Transactions (2):".to_string()));
+    assert!(lines
+        .iter()
+        .any(|line| line.contains(&tx_id.to_hex()) && line.ends_with("still pending")));
+}
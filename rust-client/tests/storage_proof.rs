@@ -0,0 +1,42 @@
+//! Checks `rust_client::storage_proof` against accounts committed on a local mock chain.
+
+use miden_client::{account::StorageSlotName, Felt, Word};
+use miden_testing::{Auth, MockChain};
+use rust_client::{
+    contracts::Mapping,
+    storage_proof::{MapEntryProof, ProofError},
+};
+
+fn genesis_proof() -> (MapEntryProof, miden_client::block::BlockHeader) {
+    let mut builder = MockChain::builder();
+    let mapping = builder
+        .add_existing_account_from_components(Auth::IncrNonce, [Mapping::component().unwrap()])
+        .unwrap();
+    let chain = builder.build().unwrap();
+
+    let header = chain.latest_block_header();
+    let account_witness = chain.account_tree().open(mapping.id());
+    let proof = MapEntryProof::prove(
+        &mapping,
+        StorageSlotName::new(Mapping::MAP_SLOT).unwrap(),
+        Word::default(),
+        header.block_num(),
+        account_witness,
+    )
+    .unwrap();
+    (proof, header)
+}
+
+#[test]
+fn map_entry_proof_verifies_against_its_block() {
+    let (proof, header) = genesis_proof();
+    assert_eq!(proof.value, Word::default());
+    proof.verify(&header).unwrap();
+}
+
+#[test]
+fn tampered_value_is_rejected() {
+    let (mut proof, header) = genesis_proof();
+    proof.value = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+    assert_eq!(proof.verify(&header), Err(ProofError::MapEntry));
+}
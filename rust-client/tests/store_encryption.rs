@@ -0,0 +1,112 @@
+//! Seals and reopens stores with `rust_client::store_encryption`.
+
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use rust_client::{
+    error::ExitGuard,
+    store_encryption::{
+        self, decrypt, encrypt, EncryptedStore, StoreEncryptionError, StorePassphrase,
+    },
+};
+
+use common::scratch_dir;
+
+#[test]
+fn only_the_right_passphrase_decrypts() {
+    let path = PathBuf::from("store.sqlite3.enc");
+    let sealed = encrypt(b"private notes", &StorePassphrase::new("correct horse"));
+
+    let plaintext = decrypt(&sealed, &StorePassphrase::new("correct horse"), &path).unwrap();
+    assert_eq!(plaintext, b"private notes");
+    assert!(matches!(
+        decrypt(&sealed, &StorePassphrase::new("battery staple"), &path),
+        Err(StoreEncryptionError::WrongPassphrase(_))
+    ));
+    assert!(matches!(
+        decrypt(
+            b"SQLite format 3",
+            &StorePassphrase::new("correct horse"),
+            &path
+        ),
+        Err(StoreEncryptionError::NotSealed(_))
+    ));
+}
+
+#[test]
+fn sealing_replaces_the_plaintext_store() {
+    let dir = scratch_dir("seal");
+    let store_path = dir.join("store.sqlite3");
+    let connection = rusqlite::Connection::open(&store_path).unwrap();
+    connection
+        .execute_batch("CREATE TABLE notes (id TEXT); INSERT INTO notes VALUES ('0xabc');")
+        .unwrap();
+    drop(connection);
+
+    // The first sealed run adopts the plaintext store
+    let passphrase = StorePassphrase::new("correct horse");
+    EncryptedStore::open(&store_path, passphrase.clone())
+        .unwrap()
+        .seal()
+        .unwrap();
+    assert!(
+        !store_path.exists(),
+        "the plaintext store should be removed"
+    );
+    let sealed = fs::read(EncryptedStore::sealed_path(&store_path)).unwrap();
+    assert!(!sealed.windows(5).any(|window| window == b"0xabc"));
+
+    // The next one decrypts it to a working copy with the same contents
+    let store = EncryptedStore::open(&store_path, passphrase).unwrap();
+    let connection = rusqlite::Connection::open(store.working_path()).unwrap();
+    let id: String = connection
+        .query_row("SELECT id FROM notes", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(id, "0xabc");
+    drop(connection);
+
+    let working_path = store.working_path().to_path_buf();
+    store.seal().unwrap();
+    assert!(!working_path.exists(), "the working copy should be removed");
+}
+
+#[cfg(unix)]
+#[test]
+fn new_stores_start_in_a_private_directory() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = scratch_dir("private");
+    let store =
+        EncryptedStore::open(&dir.join("store.sqlite3"), StorePassphrase::new("pw")).unwrap();
+    let working_path = store.working_path().to_path_buf();
+    let working_dir = working_path.parent().unwrap().to_path_buf();
+
+    let mode = |path: &std::path::Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode(&working_dir), 0o700);
+    assert_eq!(mode(&working_path), 0o600);
+
+    store.seal().unwrap();
+    assert!(
+        !working_dir.exists(),
+        "the working directory should be removed"
+    );
+}
+
+#[test]
+fn a_panicking_run_still_seals_its_store() {
+    let dir = scratch_dir("panic");
+    let store_path = dir.join("store.sqlite3");
+    let store = EncryptedStore::open(&store_path, StorePassphrase::new("pw")).unwrap();
+    let working_path = store.working_path().to_path_buf();
+
+    let run = std::thread::spawn(move || {
+        let _guard = ExitGuard::new();
+        store_encryption::install(store);
+        panic!("the tutorial failed an assertion");
+    });
+    assert!(run.join().is_err());
+
+    assert!(!working_path.exists(), "the working copy should be removed");
+    assert!(EncryptedStore::sealed_path(&store_path).exists());
+}
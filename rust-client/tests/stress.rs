@@ -0,0 +1,71 @@
+//! Aggregates stress-test outcomes with `rust_client::stress`.
+
+use std::time::Duration;
+
+use rust_client::stress::{percentile, ClientStats, LoadSummary, Operation};
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+fn stats(outcomes: &[(Operation, Result<u64, &str>)]) -> ClientStats {
+    let mut stats = ClientStats::default();
+    for (operation, outcome) in outcomes {
+        let outcome = outcome.map(secs).map_err(str::to_string);
+        stats.record(*operation, outcome);
+    }
+    stats
+}
+
+#[test]
+fn aggregates_clients_per_operation() {
+    let clients = [
+        stats(&[
+            (Operation::Mint, Ok(4)),
+            (Operation::Consume, Ok(6)),
+            (Operation::Send, Err("discarded")),
+        ]),
+        stats(&[
+            (Operation::Mint, Ok(8)),
+            (Operation::Consume, Ok(5)),
+            (Operation::Send, Ok(7)),
+            (Operation::Mint, Err("timed out")),
+        ]),
+    ];
+
+    let summary = LoadSummary::aggregate(&clients, secs(10));
+
+    assert_eq!(summary.clients, 2);
+    assert_eq!(summary.attempts, 7);
+    assert_eq!(summary.committed, 5);
+    assert_eq!(summary.failed, 2);
+    assert_eq!(summary.throughput(), 0.5);
+    assert!((summary.error_rate() - 2.0 / 7.0).abs() < 1e-9);
+
+    let mint = &summary.operations[0];
+    assert_eq!(mint.operation, Operation::Mint);
+    assert_eq!((mint.committed, mint.failed), (2, 1));
+    assert_eq!(mint.p50, Some(secs(4)));
+    assert_eq!(mint.p95, Some(secs(8)));
+    let send = &summary.operations[2];
+    assert_eq!((send.committed, send.failed), (1, 1));
+}
+
+#[test]
+fn empty_run_has_no_rates() {
+    let summary = LoadSummary::aggregate(&[ClientStats::default()], Duration::ZERO);
+
+    assert_eq!(summary.throughput(), 0.0);
+    assert_eq!(summary.error_rate(), 0.0);
+    assert!(summary.operations.iter().all(|op| op.p50.is_none()));
+}
+
+#[test]
+fn percentile_uses_nearest_rank() {
+    let sorted: Vec<Duration> = (1..=10).map(secs).collect();
+
+    assert_eq!(percentile(&sorted, 50.0), Some(secs(5)));
+    assert_eq!(percentile(&sorted, 95.0), Some(secs(10)));
+    assert_eq!(percentile(&sorted, 0.0), Some(secs(1)));
+    assert_eq!(percentile(&[], 50.0), None);
+}
@@ -0,0 +1,42 @@
+//! Checks the schedule arithmetic that `rust_client::vesting` mirrors from the MASM.
+
+use rust_client::vesting::VestingSchedule;
+
+const SCHEDULE: VestingSchedule = VestingSchedule {
+    start: 100,
+    period: 10,
+    periods: 4,
+    per_period: 25,
+};
+
+#[test]
+fn shares_vest_at_the_end_of_each_period() {
+    assert_eq!(SCHEDULE.total(), 100);
+    assert_eq!(SCHEDULE.vested_at(0), 0);
+    assert_eq!(SCHEDULE.vested_at(100), 0);
+    assert_eq!(SCHEDULE.vested_at(109), 0);
+    assert_eq!(SCHEDULE.vested_at(110), 25);
+    assert_eq!(SCHEDULE.vested_at(129), 50);
+    assert_eq!(SCHEDULE.vested_at(140), 100);
+    assert_eq!(
+        SCHEDULE.vested_at(u32::MAX),
+        100,
+        "vesting stops at the grant"
+    );
+}
+
+#[test]
+fn vesting_block_is_the_first_block_covering_the_amount() {
+    assert_eq!(SCHEDULE.vesting_block(0), Some(100));
+    assert_eq!(SCHEDULE.vesting_block(1), Some(110));
+    assert_eq!(SCHEDULE.vesting_block(25), Some(110));
+    assert_eq!(SCHEDULE.vesting_block(26), Some(120));
+    assert_eq!(SCHEDULE.vesting_block(100), Some(140));
+    assert_eq!(SCHEDULE.vesting_block(101), None);
+
+    for amount in [1, 25, 26, 60, 100] {
+        let block = SCHEDULE.vesting_block(amount).unwrap();
+        assert!(SCHEDULE.vested_at(block) >= amount);
+        assert!(SCHEDULE.vested_at(block - 1) < amount);
+    }
+}
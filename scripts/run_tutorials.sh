@@ -20,17 +20,70 @@ WEB_SKIPPED=(
 )
 
 RUST_EXAMPLES=(
+  account_retirement
+  amm_network_pool
+  auction_contract
+  bank_contract
+  batch_airdrop
+  commit_reveal_lottery
   counter_contract_deploy
   counter_contract_fpi
   counter_contract_increment
+  counter_cycle_budget
   create_mint_consume_send
+  data_root_commitment
   delegated_prover
+  discarded_transaction
+  duplicate_submission
+  emission_schedule_faucet
+  escrow_contract
+  expected_output_recipients
+  expiring_note
+  fungible_burn
   hash_preimage_note
+  htlc_swap
+  liquidity_pool
+  loyalty_points
+  many_output_notes
   mapping_example
+  mapping_struct_example
+  mixed_target_notes
+  multi_profile
+  multisig_wallet
+  name_registry
+  network_note_queue
   network_notes_counter_contract
+  nft_mint_transfer
+  note_cancellation
+  note_chat
+  note_consumption_order
   note_creation_in_masm
+  note_inputs_limits
+  note_tag_discovery
   oracle_data_query
+  order_book
+  owned_counter
+  p2ide_note
+  parallel_submission
+  partial_account_fpi
+  partial_fill_note
+  pay_per_use_credits
+  payment_stream
+  payment_with_acknowledgment
+  pipelined_proving
+  proving_options
+  relayer
+  replay_failed_transaction
+  router_contract
+  script_cycles
+  second_device_import
+  shared_client_tasks
+  storage_proof_owner
+  swap_note
+  token_allowance
+  token_vesting
   unauthenticated_note_transfer
+  wrapped_token
 )
 
 RUST_SKIPPED=(
@@ -41,16 +94,22 @@ RUST_SKIPPED=(
 
 usage() {
   cat <<'EOF'
-Usage: yarn tutorials [--web[=name]] [--rust[=name]] [--list]
+Usage: yarn tutorials [--web[=name]] [--rust[=name]] [--chaos[=preset]] [--list]
 
 Defaults to running all web and rust tutorials.
 
+--chaos builds the rust tutorials with the `chaos` feature and injects RPC
+latency, timeouts and transient errors to check that the tutorials survive
+them. Presets: light (6% of calls fail), moderate (10%, the default) and
+heavy (35%).
+
 Examples:
   yarn tutorials
   yarn tutorials --web
   yarn tutorials --rust
   yarn tutorials --web=createMintConsume
   yarn tutorials --rust=counter_contract_deploy
+  yarn tutorials --rust=unauthenticated_note_transfer --chaos=moderate
 EOF
 }
 
@@ -94,6 +153,7 @@ saw_selector=0
 web_names=()
 rust_names=()
 failures=()
+chaos_preset=""
 
 while [[ $# -gt 0 ]]; do
   case "$1" in
@@ -123,6 +183,12 @@ while [[ $# -gt 0 ]]; do
       run_rust=1
       add_rust_names "${1#--rust=}"
       ;;
+    --chaos)
+      chaos_preset="moderate"
+      ;;
+    --chaos=*)
+      chaos_preset="${1#--chaos=}"
+      ;;
     --list)
       echo "Web tutorials (default):"
       for name in "${WEB_EXAMPLES[@]}"; do
@@ -230,6 +296,16 @@ if [[ "$run_rust" -eq 1 ]]; then
 
   rust_retries="${TUTORIAL_RETRIES:-3}"
 
+  # Each run has its own directory, so the contracts one tutorial deploys for
+  # the next are recorded in a registry shared by all of them
+  deployments_file="$RUNS_DIR/deployments.toml"
+
+  cargo_features=()
+  if [[ -n "$chaos_preset" ]]; then
+    echo "Injecting RPC faults with the '$chaos_preset' chaos preset"
+    cargo_features=(--features chaos)
+  fi
+
   for name in "${rust_names[@]}"; do
     attempt=1
     while true; do
@@ -241,7 +317,9 @@ if [[ "$run_rust" -eq 1 ]]; then
       set +e
       (
         cd "$run_dir"
-        RUST_BACKTRACE=1 cargo run --manifest-path "$RUST_DIR/Cargo.toml" --bin "$name"
+        RUST_BACKTRACE=1 MIDEN_TUTORIAL_CHAOS="$chaos_preset" \
+          MIDEN_TUTORIAL_DEPLOYMENTS="$deployments_file" \
+          cargo run --manifest-path "$RUST_DIR/Cargo.toml" ${cargo_features[@]+"${cargo_features[@]}"} --bin "$name"
       ) 2>&1 | tee "$run_dir/output.log"
       status=${PIPESTATUS[0]}
       set -e
@@ -251,7 +329,9 @@ if [[ "$run_rust" -eq 1 ]]; then
         break
       fi
 
-      if [[ "$attempt" -ge "$rust_retries" ]]; then
+      # Config (2) and compile (4) errors fail the same way on every attempt,
+      # see rust_client::error for the exit codes
+      if [[ "$status" -eq 2 || "$status" -eq 4 || "$attempt" -ge "$rust_retries" ]]; then
         failures+=("rust:${name}")
         break
       fi